@@ -1,4 +1,6 @@
+use paw_app_config::validation::{ConfigReport, ConfigValidator};
 use paw_rdkafka::kafka_config::KafkaConfig;
+use paw_rust_base::env::runtime_env;
 use paw_sqlx::config::DatabaseConfig;
 use serde::Deserialize;
 use serde_env_field::env_field_wrap;
@@ -17,8 +19,7 @@ impl Config {
     }
 
     pub fn from_default_file() -> Result<Self, Box<dyn Error>> {
-        let file_content = include_str!("../config/config.toml");
-        Self::from_string(file_content)
+        Self::from_string(config_file())
     }
 
     pub fn topics_as_str_slice(&self) -> Vec<&str> {
@@ -27,19 +28,41 @@ impl Config {
 }
 
 pub fn read_kafka_config() -> Result<KafkaConfig, Box<dyn Error>> {
-    let file_content = match std::env::var("NAIS_CLUSTER_NAME") {
-        Ok(_) => include_str!("../config/nais/kafka_config.toml"),
-        Err(_) => include_str!("../config/local/kafka_config.toml"),
-    };
-    let config: KafkaConfig = toml::from_str(file_content)?;
+    let config: KafkaConfig = toml::from_str(kafka_config_file())?;
     Ok(config)
 }
 
 pub fn read_database_config() -> Result<DatabaseConfig, Box<dyn Error>> {
-    let file_content = match std::env::var("NAIS_CLUSTER_NAME") {
+    let config: DatabaseConfig = toml::from_str(database_config_file())?;
+    Ok(config)
+}
+
+/// Sjekker alle config-filer og miljøvariablene de refererer til.
+pub fn validate_config() -> ConfigReport {
+    ConfigValidator::new(runtime_env())
+        .require_placeholders(config_file())
+        .require_placeholders(kafka_config_file())
+        .require_placeholders(database_config_file())
+        .check_config("config.toml", Config::from_default_file())
+        .check_config("kafka_config.toml", read_kafka_config())
+        .check_config("database_config.toml", read_database_config())
+        .report()
+}
+
+fn config_file() -> &'static str {
+    include_str!("../config/config.toml")
+}
+
+fn kafka_config_file() -> &'static str {
+    match std::env::var("NAIS_CLUSTER_NAME") {
+        Ok(_) => include_str!("../config/nais/kafka_config.toml"),
+        Err(_) => include_str!("../config/local/kafka_config.toml"),
+    }
+}
+
+fn database_config_file() -> &'static str {
+    match std::env::var("NAIS_CLUSTER_NAME") {
         Ok(_) => include_str!("../config/nais/database_config.toml"),
         Err(_) => include_str!("../config/local/database_config.toml"),
-    };
-    let config: DatabaseConfig = toml::from_str(file_content)?;
-    Ok(config)
+    }
 }
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use health_and_monitoring::simple_app_state::AppState;
 use paw_rdkafka::kafka_config::KafkaConfig;
+use paw_rdkafka_hwm::rebalance::consumer_health::KafkaConsumerHealth;
 use paw_rdkafka_hwm::rebalance::hwm_rebalance_handler::HwmRebalanceHandler;
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use sqlx::PgPool;
@@ -9,11 +10,17 @@ use std::sync::Arc;
 pub fn create_kafka_consumer(
     app_state: Arc<AppState>,
     pg_pool: PgPool,
+    consumer_health: Arc<KafkaConsumerHealth>,
     kafka_config: KafkaConfig,
     topics: &[&str],
 ) -> Result<StreamConsumer<HwmRebalanceHandler>> {
     let config = kafka_config.rdkafka_client_config()?;
-    let context = HwmRebalanceHandler { pg_pool, app_state, version: *kafka_config.hwm_version };
+    let context = HwmRebalanceHandler {
+        pg_pool,
+        app_state,
+        version: *kafka_config.hwm_version,
+        consumer_health,
+    };
     let consumer: StreamConsumer<HwmRebalanceHandler> = config.create_with_context(context)?;
     consumer.subscribe(topics)?;
     Ok(consumer)
@@ -4,17 +4,21 @@ mod kafka;
 
 use crate::config::read_database_config;
 use crate::config::read_kafka_config;
+use crate::config::validate_config;
 use crate::kafka::consumer_task::spawn_kafka_consumer_task;
 use crate::kafka::kafka_connection::create_kafka_consumer;
 use crate::kafka::message_processor::BackupMessageProcessor;
 use axum_health::spawn_health_server;
+use health_and_monitoring::compound_health::CompoundHealth;
+use health_and_monitoring::dependency_health::DependencyHealth;
 use health_and_monitoring::nais_otel_setup::setup_nais_otel;
 use health_and_monitoring::simple_app_state::AppState;
+use paw_rdkafka_hwm::rebalance::consumer_health::{KafkaConsumerHealth, add_kafka_consumer_checks};
 use paw_rust_base::panic_logger::register_panic_logger;
+use paw_rust_base::task_supervisor::TaskSupervisor;
 use paw_sqlx::postgres::init_db;
 use std::error::Error;
 use std::sync::Arc;
-use tokio::signal::unix::{SignalKind, signal};
 use tracing::{error, info};
 
 #[tokio::main]
@@ -43,13 +47,29 @@ async fn main() {
 }
 
 async fn run_app() -> Result<(), Box<dyn Error>> {
+    let config_report = validate_config();
+    config_report.log();
+    config_report.into_result()?;
     let config = config::Config::from_default_file()?;
     info!("Konfigurasjon lastet: {:?}", config);
     let kafka_config = read_kafka_config()?;
     info!("Kafka konfigurasjon lastet: {:?}", kafka_config);
 
     let app_state = Arc::new(AppState::new());
-    let http_server_task = spawn_health_server(app_state.clone());
+    let mut supervisor = TaskSupervisor::default();
+    let shutdown_app_state = app_state.clone();
+    supervisor.on_shutdown(move || shutdown_app_state.set_is_ready(false));
+    let consumer_health = Arc::new(KafkaConsumerHealth::new());
+    let mut dependency_health = DependencyHealth::default();
+    add_kafka_consumer_checks(&mut dependency_health, consumer_health.clone());
+    let dependency_health = Arc::new(dependency_health);
+    let mut health = CompoundHealth::new();
+    health.add_health_check(app_state.clone());
+    health.add_health_check(dependency_health.clone());
+    let health_task = dependency_health.spawn_refresh_task(supervisor.cancellation_token());
+    supervisor.add_task("Helsesjekker", health_task);
+    let http_server_task = spawn_health_server(Arc::new(health), supervisor.cancellation_token());
+    supervisor.add_task("HTTP server", http_server_task);
     info!("HTTP server startet");
     let db_config = read_database_config()?;
     info!("Database config: {:?}", db_config);
@@ -59,6 +79,7 @@ async fn run_app() -> Result<(), Box<dyn Error>> {
     let consumer = create_kafka_consumer(
         app_state.clone(),
         pg_pool.clone(),
+        consumer_health,
         kafka_config,
         &config.topics_as_str_slice(),
     )?;
@@ -67,43 +88,14 @@ async fn run_app() -> Result<(), Box<dyn Error>> {
         hwm_version,
         pg_pool.clone(),
         BackupMessageProcessor,
+        supervisor.cancellation_token(),
     );
-    let signal = await_signal();
+    supervisor.add_task("Kafka consumer", kafka_task);
     app_state.set_has_started(true);
     info!("Alle tjenester startet, applikasjon kjører");
-    tokio::select! {
-        result = http_server_task => {
-            match result {
-                Ok(Ok(())) => info!("HTTP server stoppet."),
-                Ok(Err(e)) => return Err(e.into()),
-                Err(join_error) => return Err(Box::new(join_error)),
-            }
-        }
-        result = kafka_task => {
-            match result {
-                Ok(Ok(())) => info!("Kafka consumer stoppet."),
-                Ok(Err(e)) => return Err(e.into()),
-                Err(join_error) => return Err(Box::new(join_error)),
-            }
-        }
-        result = signal => {
-            match result {
-                Ok(signal) => info!("Signal '{}' mottatt, avslutter....", signal),
-                Err(e) => return Err(e),
-            }
-        }
-    }
+    let result = supervisor.run().await;
     app_state.set_is_alive(false);
     let _ = pg_pool.close().await;
     info!("Pg pool lukket");
-    Ok(())
-}
-
-async fn await_signal() -> Result<String, Box<dyn Error>> {
-    let mut term_signal = signal(SignalKind::terminate())?;
-    let mut interrupt_signal = signal(SignalKind::interrupt())?;
-    tokio::select! {
-        _ = term_signal.recv() => Ok("SIGTERM".to_string()),
-        _ = interrupt_signal.recv() => Ok("SIGINT".to_string())
-    }
+    Ok(result?)
 }
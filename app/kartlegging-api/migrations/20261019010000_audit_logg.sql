@@ -0,0 +1,15 @@
+CREATE TABLE audit_logg
+(
+    id                 BIGSERIAL PRIMARY KEY,
+    principal_type     VARCHAR(30)  NOT NULL,
+    principal_ident    VARCHAR(255),
+    query_type         VARCHAR(30)  NOT NULL,
+    identitetsnumre    VARCHAR[]    NOT NULL DEFAULT '{}',
+    trace_id           VARCHAR(32),
+    tidspunkt          TIMESTAMP(6) NOT NULL,
+    inserted_timestamp TIMESTAMP(6) NOT NULL
+);
+
+CREATE INDEX audit_logg_principal_ident_idx ON audit_logg (principal_ident);
+CREATE INDEX audit_logg_tidspunkt_idx ON audit_logg (tidspunkt);
+CREATE INDEX audit_logg_identitetsnumre_idx ON audit_logg USING GIN (identitetsnumre);
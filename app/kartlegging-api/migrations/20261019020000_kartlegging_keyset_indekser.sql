@@ -0,0 +1,5 @@
+CREATE INDEX kartlegging_arbeidssoeker_fra_periode_id_idx ON kartlegginger (arbeidssoeker_fra, periode_id);
+CREATE INDEX kartlegging_arbeidsledig_fra_periode_id_idx ON kartlegginger (arbeidsledig_fra, periode_id);
+
+CREATE INDEX profileringer_periode_id_tidspunkt_idx ON profileringer (periode_id, tidspunkt);
+CREATE INDEX egenvurderinger_periode_id_tidspunkt_idx ON egenvurderinger (periode_id, tidspunkt);
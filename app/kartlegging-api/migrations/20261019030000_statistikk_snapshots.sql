@@ -0,0 +1,16 @@
+-- Daglig snapshot av statistikk per kontor og profilering. Tom kontor_id/kontor_type betyr
+-- alle kontor/kontortyper, og profilert_til er 'INGEN' for perioder uten profilering.
+CREATE TABLE statistikk_snapshots
+(
+    dato               DATE         NOT NULL,
+    kontor_id          VARCHAR(30)  NOT NULL,
+    kontor_type        VARCHAR(30)  NOT NULL,
+    profilert_til      VARCHAR(30)  NOT NULL,
+    grenser            INTEGER[]    NOT NULL,
+    total              BIGINT       NOT NULL,
+    is_null            BIGINT       NOT NULL,
+    is_not_null        BIGINT       NOT NULL,
+    over_grenser       BIGINT[]     NOT NULL,
+    inserted_timestamp TIMESTAMP(6) NOT NULL,
+    PRIMARY KEY (dato, kontor_id, kontor_type, profilert_til)
+);
@@ -0,0 +1,12 @@
+-- Én rad per arbeidssøker hver gang Kafka-konsumenten endrer data for arbeidssøkeren.
+-- Id brukes som cursor i endringsfeeden.
+CREATE TABLE endringer
+(
+    id                 BIGSERIAL PRIMARY KEY,
+    arbeidssoeker_id   BIGINT       NOT NULL,
+    endring_type       VARCHAR(30)  NOT NULL,
+    tidspunkt          TIMESTAMP(6) NOT NULL
+);
+
+CREATE INDEX endringer_arbeidssoeker_id_idx ON endringer (arbeidssoeker_id);
+CREATE INDEX endringer_tidspunkt_idx ON endringer (tidspunkt);
@@ -0,0 +1,6 @@
+-- Adressebeskyttelse fra PDL og når navn og adressebeskyttelse sist ble hentet.
+-- NULL i pdl_oppdatert betyr at arbeidssøkeren aldri er oppdatert, og oppdateres først.
+ALTER TABLE arbeidssoekere ADD COLUMN adressebeskyttelse VARCHAR(30);
+ALTER TABLE arbeidssoekere ADD COLUMN pdl_oppdatert TIMESTAMP(6);
+
+CREATE INDEX arbeidssoekere_pdl_oppdatert_idx ON arbeidssoekere (pdl_oppdatert NULLS FIRST);
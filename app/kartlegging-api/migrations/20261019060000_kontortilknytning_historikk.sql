@@ -0,0 +1,33 @@
+-- Historikk for kontortilknytninger. kontortilknytninger har bare gjeldende kontor per
+-- oppfølgingsperiode, her lagres én rad for hver start, endring og avslutning.
+CREATE TABLE kontortilknytning_historikk
+(
+    id                   BIGSERIAL PRIMARY KEY,
+    kontortilknytning_id UUID         NOT NULL, -- oppfolgingsperiode_uuid
+    aktor_id             VARCHAR(20)  NOT NULL,
+    identitetsnummer     VARCHAR(20)  NOT NULL,
+    endring_type         VARCHAR(30)  NOT NULL,
+    kontor_id            VARCHAR(30)  NOT NULL,
+    kontor_navn          VARCHAR(255) NOT NULL,
+    kontor_type          VARCHAR(30)  NOT NULL,
+    tidspunkt            TIMESTAMP(6) NOT NULL,
+    inserted_timestamp   TIMESTAMP(6) NOT NULL,
+    updated_timestamp    TIMESTAMP(6)
+);
+
+CREATE INDEX kontortilknytning_historikk_aktor_id_idx ON kontortilknytning_historikk (aktor_id);
+CREATE INDEX kontortilknytning_historikk_identitetsnummer_idx ON kontortilknytning_historikk (identitetsnummer);
+
+-- Gjeldende kontortilknytninger blir starten på historikken
+INSERT INTO kontortilknytning_historikk (kontortilknytning_id, aktor_id, identitetsnummer, endring_type,
+                                         kontor_id, kontor_navn, kontor_type, tidspunkt, inserted_timestamp)
+SELECT id,
+       aktor_id,
+       identitetsnummer,
+       'STARTET',
+       kontor_id,
+       kontor_navn,
+       kontor_type,
+       tidspunkt,
+       now() AT TIME ZONE 'UTC'
+FROM kontortilknytninger;
@@ -0,0 +1,5 @@
+-- Transaksjonen som lagret endringen. Id tildeles før commit, så en transaksjon som committer
+-- sent kan gi en lavere id enn endringer som allerede er lest. Feeden leser derfor bare endringer
+-- fra transaksjoner eldre enn alle som fortsatt pågår (pg_snapshot_xmin).
+ALTER TABLE endringer
+    ADD COLUMN xid XID8 NOT NULL DEFAULT pg_current_xact_id();
@@ -0,0 +1,5 @@
+-- Feilede oppslag mot PDL per arbeidssøker. Arbeidssøkeren hoppes over til pdl_neste_forsok,
+-- og ventetiden dobles for hver feil.
+ALTER TABLE arbeidssoekere
+    ADD COLUMN pdl_antall_feil INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN pdl_neste_forsok TIMESTAMP;
@@ -1,13 +1,59 @@
+use crate::api::{eksport, endringer, graphql, kartlegging, statistics, tidslinje};
 use axum::http::header::CONTENT_TYPE;
 use axum::http::HeaderMap;
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{Json, Router};
+use paw_error_handling::problem_details::ProblemDetails;
 use std::sync::OnceLock;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::{Config, SwaggerUi};
 
-static SPEC_YAML: &str = include_str!("../../openapi/spec.yaml");
-static SPEC_JSON: OnceLock<String> = OnceLock::new();
+/// Spec generert fra handlere og DTO-er. `openapi/spec.yaml` er en sjekket inn kopi for klienter,
+/// og testen `spec_yaml_er_oppdatert` feiler hvis den avviker fra generert spec.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "PAW Arbeidssøkerregisteret Kartlegging API",
+        description = "API for å hente kartlegging av arbeidssøkere. Kalles av saksbehandlere \
+            via Azure AD.",
+        license(name = "Apache 2.0", identifier = "Apache-2.0"),
+        version = "1.0.0"
+    ),
+    paths(
+        kartlegging::finn_kartlegging,
+        tidslinje::finn_tidslinje,
+        eksport::eksporter,
+        graphql::graphql,
+        endringer::hent_endringer,
+        endringer::stroem_endringer,
+        statistics::finn_statistics,
+        statistics::finn_tidsserie
+    ),
+    components(schemas(ProblemDetails)),
+    modifiers(&BearerAuth),
+    security(("BearerAuth" = [])),
+    tags(
+        (name = "kartlegging", description = "Hente kartlegging av arbeidssøkere"),
+        (name = "statistikk", description = "Statistikk over arbeidssøkere")
+    )
+)]
+pub(crate) struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "BearerAuth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+static SPEC_YAML: OnceLock<String> = OnceLock::new();
 
 pub(crate) fn routes() -> Router {
     let docs_json_routes = Router::new().route("/api/docs.json", get(api_docs_json));
@@ -20,50 +66,60 @@ pub(crate) fn routes() -> Router {
 }
 
 async fn api_docs_json(_: HeaderMap) -> impl IntoResponse {
-    let value: serde_json::Value =
-        serde_json::from_str(spec_json()).expect("spec_json() er gyldig JSON");
-    Json(value).into_response()
+    Json(ApiDoc::openapi()).into_response()
 }
 
 async fn api_docs_yaml(_: HeaderMap) -> impl IntoResponse {
     (
         [(CONTENT_TYPE, "application/yaml; charset=utf-8")],
-        SPEC_YAML,
+        spec_yaml(),
     )
         .into_response()
 }
 
-fn spec_json() -> &'static str {
-    SPEC_JSON.get_or_init(|| {
-        let value: serde_json::Value =
-            serde_yaml::from_str(SPEC_YAML).expect("openapi/spec.yaml er gyldig YAML");
-        serde_json::to_string(&value).expect("spec kan serialiseres til JSON")
+pub(crate) fn spec_yaml() -> &'static str {
+    SPEC_YAML.get_or_init(|| {
+        serde_yaml::to_string(&ApiDoc::openapi()).expect("spec kan serialiseres til YAML")
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{spec_json, SPEC_YAML};
+    use super::{spec_yaml, ApiDoc};
+    use crate::api::eksport::API_EKSPORT_PATH;
+    use crate::api::endringer::{API_ENDRINGER_PATH, API_ENDRINGER_STREAM_PATH};
+    use crate::api::graphql::API_GRAPHQL_PATH;
     use crate::api::kartlegging::API_KARTLEGGING_PATH;
+    use crate::api::statistics::{API_STATISTICS_PATH, API_STATISTICS_TIDSSERIE_PATH};
+    use crate::api::tidslinje::API_TIDSLINJE_PATH;
     use crate::model::dto::arbeidssoeker::Arbeidssoeker;
     use crate::model::dto::bekreftelse::{Bekreftelse, Bekreftelsesloesning};
     use crate::model::dto::egenvurdering::Egenvurdering;
-    use crate::model::dto::kontortilknytning::{KontorType, Kontortilknytning};
+    use crate::model::dto::kontortilknytning::{
+        KontorType, Kontortilknytning, KontortilknytningEndringType,
+    };
     use crate::model::dto::ledighetsperiode::Ledighetsperiode;
     use crate::model::dto::opplysninger::{Jobbsituasjon, Opplysninger};
     use crate::model::dto::periode::Periode;
     use crate::model::dto::profilering::{Profilering, ProfilertTil};
     use crate::model::dto::request::{
-        IdentitetsnummerQueryRequest, PagingRequest, QueryRequest, TilknyttetKontorQueryRequest,
+        IdentitetsnummerQueryRequest, PagingRequest, PeriodeStatus, QueryRequest,
+        TidslinjeRequest, TilknyttetKontorQueryRequest,
     };
-    use crate::model::dto::response::{KartleggingResponse, PagingResponse};
-    use crate::model::sort::SortOrder;
-    use chrono::DateTime;
+    use crate::model::dto::response::{KartleggingResponse, PagingResponse, TidslinjeResponse};
+    use crate::model::dto::tidslinje::{ArbeidssoekerTidslinje, TidslinjeData, TidslinjeHendelse};
+    use crate::model::sort::{SortField, SortOrder};
+    use chrono::{DateTime, NaiveDate};
     use serde_json::json;
     use uuid::Uuid;
 
+    use utoipa::OpenApi;
+
+    const SPEC_FIL: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi/spec.yaml");
+    const OPPDATER_SPEC_ENV: &str = "OPPDATER_OPENAPI_SPEC";
+
     fn spec() -> serde_json::Value {
-        serde_json::from_str(spec_json()).expect("spec_json() er gyldig JSON")
+        serde_json::to_value(ApiDoc::openapi()).expect("spec kan serialiseres til JSON")
     }
 
     fn assert_conforms(spec: &serde_json::Value, schema_name: &str, instance: serde_json::Value) {
@@ -84,10 +140,20 @@ mod tests {
     }
 
     #[test]
-    fn spec_yaml_er_gyldig_yaml() {
-        let value: serde_json::Value =
-            serde_yaml::from_str(SPEC_YAML).expect("spec.yaml skal være gyldig YAML");
-        assert!(value.is_object());
+    fn spec_yaml_er_oppdatert() {
+        let generert = spec_yaml();
+        let sjekket_inn = std::fs::read_to_string(SPEC_FIL).unwrap_or_default();
+        if generert == sjekket_inn {
+            return;
+        }
+        if std::env::var(OPPDATER_SPEC_ENV).is_ok() {
+            std::fs::write(SPEC_FIL, generert).expect("Kunne ikke skrive openapi/spec.yaml");
+            return;
+        }
+        panic!(
+            "openapi/spec.yaml avviker fra spec generert fra koden. Oppdater med \
+            `{OPPDATER_SPEC_ENV}=true cargo test -p kartlegging-api spec_yaml_er_oppdatert`"
+        );
     }
 
     #[test]
@@ -100,7 +166,16 @@ mod tests {
             .cloned()
             .collect();
 
-        let registered = [API_KARTLEGGING_PATH];
+        let registered = [
+            API_KARTLEGGING_PATH,
+            API_TIDSLINJE_PATH,
+            API_EKSPORT_PATH,
+            API_GRAPHQL_PATH,
+            API_ENDRINGER_PATH,
+            API_ENDRINGER_STREAM_PATH,
+            API_STATISTICS_PATH,
+            API_STATISTICS_TIDSSERIE_PATH,
+        ];
 
         for path in &spec_paths {
             assert!(
@@ -125,6 +200,7 @@ mod tests {
                 page: 1,
                 page_size: 10,
                 sort_order: SortOrder::Ascending,
+                ..Default::default()
             }),
         });
         let instance = serde_json::to_value(&dto).unwrap();
@@ -137,11 +213,18 @@ mod tests {
         let dto = QueryRequest::TilknyttetKontor(TilknyttetKontorQueryRequest {
             kontor_id: "1337".to_string(),
             kontor_type: None,
-            ledig_siden: None,
+            ledig_siden: Some(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()),
+            ledig_siden_til: Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()),
+            periode_status: Some(PeriodeStatus::Aktiv),
+            profilert_til: Some(vec![ProfilertTil::AntattBehovForVeiledning]),
+            egenvurdering_avvik: Some(true),
             paging: Some(PagingRequest {
                 page: 1,
                 page_size: 10,
                 sort_order: SortOrder::Descending,
+                sort_field: Some(SortField::ArbeidsledigFra),
+                cursor: Some("bmVzdGUtc2lkZQ".to_string()),
+                include_total_count: Some(false),
             }),
         });
         let instance = serde_json::to_value(&dto).unwrap();
@@ -157,8 +240,9 @@ mod tests {
                 page: 1,
                 page_size: 100,
                 hit_size: 42,
-                total_count: 1000,
+                total_count: Some(1000),
                 sort_order: SortOrder::Ascending,
+                next_cursor: None,
             },
         };
         let instance = serde_json::to_value(&dto).unwrap();
@@ -176,6 +260,7 @@ mod tests {
             fornavn: Some("Kari".to_string()),
             mellomnavn: None,
             etternavn: Some("Nordmann".to_string()),
+            adressebeskyttelse: None,
             ledighetsperioder: vec![Ledighetsperiode {
                 ledig_siden: Some(
                     DateTime::parse_from_rfc3339("2021-01-01T12:00:00.000Z")
@@ -233,4 +318,71 @@ mod tests {
         let instance = serde_json::to_value(&dto).unwrap();
         assert_conforms(&spec, "Arbeidssoeker", instance);
     }
+
+    #[test]
+    fn tidslinje_request_konformerer() {
+        let spec = spec();
+        let dto = TidslinjeRequest {
+            identitetsnummer: "01017012345".to_string(),
+        };
+        let instance = serde_json::to_value(&dto).unwrap();
+        assert_conforms(&spec, "TidslinjeRequest", instance);
+    }
+
+    #[test]
+    fn tidslinje_response_konformerer() {
+        let spec = spec();
+        let periode_id = Uuid::parse_str("069f40c9-c47c-4ee2-9105-bc87bdb58af2").unwrap();
+        let tidspunkt = DateTime::parse_from_rfc3339("2021-01-01T12:00:00.000Z")
+            .unwrap()
+            .to_utc();
+        let dto = TidslinjeResponse {
+            arbeidssoekere: vec![ArbeidssoekerTidslinje {
+                id: 1337,
+                aktor_id: "101701234500".to_string(),
+                identitetsnummer: "01017012345".to_string(),
+                adressebeskyttelse: None,
+                hendelser: vec![
+                    TidslinjeHendelse {
+                        tidspunkt,
+                        periode_id: Some(periode_id),
+                        data: TidslinjeData::PeriodeStartet,
+                    },
+                    TidslinjeHendelse {
+                        tidspunkt,
+                        periode_id: Some(periode_id),
+                        data: TidslinjeData::Egenvurdering {
+                            id: Uuid::parse_str("7778ba2d-cbb5-4263-a49b-a4719821f0a5").unwrap(),
+                            profilering_id: Uuid::parse_str(
+                                "6d084994-d0b9-4466-9c1f-6126a3b3c2a8",
+                            )
+                            .unwrap(),
+                            profilert_til: ProfilertTil::AntattGodeMuligheter,
+                            egenvurdert_til: ProfilertTil::OppgittHindringer,
+                        },
+                    },
+                    TidslinjeHendelse {
+                        tidspunkt,
+                        periode_id: Some(periode_id),
+                        data: TidslinjeData::BekreftelsePaaVegneAv {
+                            bekreftelsesloesninger: vec![Bekreftelsesloesning::Dagpenger],
+                        },
+                    },
+                    TidslinjeHendelse {
+                        tidspunkt,
+                        periode_id: None,
+                        data: TidslinjeData::Kontortilknytning {
+                            id: Uuid::parse_str("47c4b16b-5d34-4658-9705-ab90e6d0db9b").unwrap(),
+                            endring_type: KontortilknytningEndringType::Startet,
+                            kontor_id: "1337".to_string(),
+                            kontor_navn: "NAV Bouvetøya".to_string(),
+                            kontor_type: KontorType::Arbeidsoppfolging,
+                        },
+                    },
+                ],
+            }],
+        };
+        let instance = serde_json::to_value(&dto).unwrap();
+        assert_conforms(&spec, "TidslinjeResponse", instance);
+    }
 }
@@ -0,0 +1,167 @@
+use crate::config::AppConfig;
+use crate::logic::eksport;
+use crate::logic::eksport::EksportKilde;
+use crate::logic::tilgang::{har_kontor_tilgang, AdressebeskyttelseTilgang};
+use crate::model::dto::eksport::{EksportParams, XlsxFil};
+use crate::model::dto::request::TilknyttetKontorQueryRequest;
+use crate::model::state::RouterState;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Extension, Router};
+use oauth2::principal::Principal;
+use paw_error_handling::problem_details::ProblemDetails;
+use paw_oauth2_resource_server::middleware::oauth2_middleware;
+use paw_oauth2_resource_server::state::AuthState;
+use paw_otel_tracing::otel_middleware::otel_middleware;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub const API_EKSPORT_PATH: &str = "/api/v1/kartlegging/eksport";
+
+pub(crate) fn routes(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    auth_state: Arc<AuthState>,
+) -> Router {
+    Router::new()
+        .route(API_EKSPORT_PATH, post(eksporter))
+        .route_layer(otel_middleware())
+        .route_layer(oauth2_middleware(auth_state.clone()))
+        .with_state(RouterState::new(app_config, pg_pool.clone()))
+}
+
+/// Strømmer alle arbeidssøkere tilknyttet kontoret som CSV eller XLSX. `type` og `paging` kan
+/// utelates, og `paging` brukes bare for sortering.
+#[utoipa::path(
+    post,
+    path = API_EKSPORT_PATH,
+    tag = "kartlegging",
+    params(EksportParams),
+    request_body = TilknyttetKontorQueryRequest,
+    responses(
+        (
+            status = 200,
+            description = "Fil med navn, ledig siden og siste profilering og egenvurdering",
+            content(
+                (String = "text/csv"),
+                (XlsxFil = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+            )
+        ),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 403,
+            description = "Mangler tilgang til kontoret",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 500,
+            description = "Intern feil",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, principal, params, request))]
+pub(crate) async fn eksporter(
+    State(state): State<RouterState>,
+    Extension(principal): Extension<Principal>,
+    params: Result<Query<EksportParams>, QueryRejection>,
+    request: String,
+) -> Result<Response, ProblemDetails> {
+    let Query(params) = params.map_err(|e| {
+        tracing::error!("Feil ved deserialisering av query-parametre: {}", e);
+        ProblemDetails::validation_error(API_EKSPORT_PATH, "Ugyldige query-parametre")
+    })?;
+    let eksport_request: TilknyttetKontorQueryRequest =
+        serde_json::from_str(&request).map_err(|e| {
+            tracing::error!("Feil ved deserialisering av request body: {}", e);
+            ProblemDetails::validation_error(API_EKSPORT_PATH, "Ugyldig request body")
+        })?;
+    eksport_request.validate(API_EKSPORT_PATH)?;
+
+    if !har_kontor_tilgang(
+        &principal,
+        &eksport_request.kontor_id,
+        &state.app_config.eksport_kontor_grupper,
+    ) {
+        tracing::warn!(
+            "Avviser eksport for kontor {} uten tilgang",
+            eksport_request.kontor_id
+        );
+        return Err(ProblemDetails::forbidden(
+            API_EKSPORT_PATH,
+            "Mangler tilgang til kontor",
+        ));
+    }
+
+    tracing::info!(
+        "Starter eksport for kontor {} som {:?}",
+        eksport_request.kontor_id,
+        params.format
+    );
+    let tilgang = AdressebeskyttelseTilgang::for_principal(&principal, &state.app_config);
+    let kilde = EksportKilde::new(
+        state.pg_pool.clone(),
+        principal,
+        tilgang,
+        &eksport_request,
+        state.app_config.audit_cef_logging,
+    );
+    let body = eksport::eksporter(kilde, params.format);
+    let content_disposition = format!(
+        "attachment; filename=\"{}\"",
+        params.format.filnavn(&eksport_request.kontor_id)
+    );
+    Ok((
+        [
+            (CONTENT_TYPE, params.format.content_type().to_string()),
+            (CONTENT_DISPOSITION, content_disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::dto::eksport::EksportFormat;
+    use axum::http::Uri;
+
+    #[test]
+    fn test_deserialize_eksport_params() {
+        let uri: Uri = "/api/v1/kartlegging/eksport?format=xlsx".parse().unwrap();
+        let Query(params) = Query::<EksportParams>::try_from_uri(&uri).unwrap();
+        assert_eq!(params.format, EksportFormat::Xlsx);
+
+        let uri: Uri = "/api/v1/kartlegging/eksport?format=pdf".parse().unwrap();
+        assert!(Query::<EksportParams>::try_from_uri(&uri).is_err());
+    }
+
+    #[test]
+    fn test_filnavn() {
+        assert_eq!(
+            EksportFormat::Csv.filnavn("1337"),
+            "arbeidssoekere-1337.csv"
+        );
+        assert_eq!(
+            EksportFormat::Xlsx.filnavn("13\"37\r\n"),
+            "arbeidssoekere-1337.xlsx"
+        );
+    }
+}
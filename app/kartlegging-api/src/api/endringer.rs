@@ -0,0 +1,254 @@
+use crate::config::AppConfig;
+use crate::logic::endringer;
+use crate::logic::tilgang::har_kontor_tilgang;
+use crate::model::dto::endring::{EndringerParams, EndringerResponse, EndringsstroemParams};
+use crate::model::state::RouterState;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use oauth2::principal::Principal;
+use paw_error_handling::problem_details::ProblemDetails;
+use paw_oauth2_resource_server::middleware::oauth2_middleware;
+use paw_oauth2_resource_server::state::AuthState;
+use paw_otel_tracing::otel_middleware::otel_middleware;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub const API_ENDRINGER_PATH: &str = "/api/v1/kartlegging/endringer";
+pub const API_ENDRINGER_STREAM_PATH: &str = "/api/v1/kartlegging/endringer/stream";
+const LAST_EVENT_ID: &str = "last-event-id";
+
+pub(crate) fn routes(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    auth_state: Arc<AuthState>,
+) -> Router {
+    Router::new()
+        .route(API_ENDRINGER_PATH, get(hent_endringer))
+        .route(API_ENDRINGER_STREAM_PATH, get(stroem_endringer))
+        .route_layer(otel_middleware())
+        .route_layer(oauth2_middleware(auth_state.clone()))
+        .with_state(RouterState::new(app_config, pg_pool.clone()))
+}
+
+/// Endringer for arbeidssøkere tilknyttet kontoret etter `cursor`, sortert på id. Neste kall
+/// sender `cursor` fra responsen.
+#[utoipa::path(
+    get,
+    path = API_ENDRINGER_PATH,
+    tag = "kartlegging",
+    params(EndringerParams),
+    responses(
+        (status = 200, description = "Endringer etter cursor", body = EndringerResponse),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 403,
+            description = "Mangler tilgang til kontoret",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 500,
+            description = "Intern feil",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, principal, params))]
+pub(crate) async fn hent_endringer(
+    State(state): State<RouterState>,
+    Extension(principal): Extension<Principal>,
+    params: Result<Query<EndringerParams>, QueryRejection>,
+) -> Result<Json<EndringerResponse>, ProblemDetails> {
+    let Query(params) = params.map_err(|e| {
+        tracing::error!("Feil ved deserialisering av query-parametre: {}", e);
+        ProblemDetails::validation_error(API_ENDRINGER_PATH, "Ugyldige query-parametre")
+    })?;
+    params.validate(API_ENDRINGER_PATH)?;
+    if !har_kontor_tilgang(
+        &principal,
+        &params.kontor_id,
+        &state.app_config.eksport_kontor_grupper,
+    ) {
+        tracing::warn!(
+            "Avviser endringer for kontor {} uten tilgang",
+            params.kontor_id
+        );
+        return Err(ProblemDetails::forbidden(
+            API_ENDRINGER_PATH,
+            "Mangler tilgang til kontor",
+        ));
+    }
+
+    let response = endringer::hent_endringer(
+        &state.pg_pool,
+        &principal,
+        &state.app_config,
+        &params.kontor_id,
+        params.cursor.unwrap_or_default(),
+        params.limit(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Henting av endringer feilet: {}", e);
+        ProblemDetails::database_error(API_ENDRINGER_PATH, "Henting av endringer feilet")
+    })?;
+    Ok(Json(response))
+}
+
+/// Server-sent events med én `endring`-event per endring for arbeidssøkere tilknyttet kontoret.
+/// Event-id er cursoren, slik at klienten fortsetter der den slapp ved ny tilkobling.
+#[utoipa::path(
+    get,
+    path = API_ENDRINGER_STREAM_PATH,
+    tag = "kartlegging",
+    params(
+        EndringsstroemParams,
+        (
+            "Last-Event-ID" = Option<i64>,
+            Header,
+            description = "Id for siste mottatte event, settes av EventSource ved ny tilkobling"
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Strøm av `endring`-events med Endring som JSON",
+            body = String,
+            content_type = "text/event-stream"
+        ),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 403,
+            description = "Mangler tilgang til kontoret",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 500,
+            description = "Intern feil",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, principal, params, headers))]
+pub(crate) async fn stroem_endringer(
+    State(state): State<RouterState>,
+    Extension(principal): Extension<Principal>,
+    params: Result<Query<EndringsstroemParams>, QueryRejection>,
+    headers: HeaderMap,
+) -> Result<Response, ProblemDetails> {
+    let Query(params) = params.map_err(|e| {
+        tracing::error!("Feil ved deserialisering av query-parametre: {}", e);
+        ProblemDetails::validation_error(API_ENDRINGER_STREAM_PATH, "Ugyldige query-parametre")
+    })?;
+    params.validate(API_ENDRINGER_STREAM_PATH)?;
+    if !har_kontor_tilgang(
+        &principal,
+        &params.kontor_id,
+        &state.app_config.eksport_kontor_grupper,
+    ) {
+        tracing::warn!(
+            "Avviser endringer for kontor {} uten tilgang",
+            params.kontor_id
+        );
+        return Err(ProblemDetails::forbidden(
+            API_ENDRINGER_STREAM_PATH,
+            "Mangler tilgang til kontor",
+        ));
+    }
+
+    let last_event_id = last_event_id(&headers).map_err(|e| {
+        tracing::error!("Feil ved lesing av Last-Event-ID: {}", e);
+        ProblemDetails::validation_error(API_ENDRINGER_STREAM_PATH, "Ugyldig Last-Event-ID")
+    })?;
+    let cursor = match last_event_id.or(params.cursor) {
+        Some(cursor) => cursor,
+        None => endringer::siste_cursor(&state.pg_pool).await.map_err(|e| {
+            tracing::error!("Henting av siste endring feilet: {}", e);
+            ProblemDetails::database_error(API_ENDRINGER_STREAM_PATH, "Henting av endringer feilet")
+        })?,
+    };
+    let receiver = endringer::endringsstroem(
+        state.app_config.clone(),
+        state.pg_pool.clone(),
+        principal,
+        params.kontor_id,
+        cursor,
+    );
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|event| (event, receiver))
+    });
+    Ok(Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+fn last_event_id(headers: &HeaderMap) -> anyhow::Result<Option<i64>> {
+    let Some(verdi) = headers.get(LAST_EVENT_ID) else {
+        return Ok(None);
+    };
+    let cursor = verdi.to_str()?.trim().parse::<i64>()?;
+    anyhow::ensure!(cursor >= 0, "Negativ Last-Event-ID: {}", cursor);
+    Ok(Some(cursor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderValue, Uri};
+
+    #[test]
+    fn test_deserialize_endringer_params() {
+        let uri: Uri = "/api/v1/kartlegging/endringer?kontorId=1337&cursor=42&limit=10"
+            .parse()
+            .unwrap();
+        let Query(params) = Query::<EndringerParams>::try_from_uri(&uri).unwrap();
+        assert_eq!(params.kontor_id, "1337");
+        assert_eq!(params.cursor, Some(42));
+        assert_eq!(params.limit(), 10);
+
+        let uri: Uri = "/api/v1/kartlegging/endringer?cursor=42".parse().unwrap();
+        assert!(Query::<EndringerParams>::try_from_uri(&uri).is_err());
+    }
+
+    #[test]
+    fn test_last_event_id() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(last_event_id(&headers).unwrap(), None);
+
+        headers.insert(LAST_EVENT_ID, HeaderValue::from_static("42"));
+        assert_eq!(last_event_id(&headers).unwrap(), Some(42));
+
+        headers.insert(LAST_EVENT_ID, HeaderValue::from_static("abc"));
+        assert!(last_event_id(&headers).is_err());
+    }
+}
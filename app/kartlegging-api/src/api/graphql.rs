@@ -0,0 +1,156 @@
+use crate::config::AppConfig;
+use crate::logic::graphql::loader::med_loadere;
+use crate::logic::graphql::{build_schema, KartleggingSchema};
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Extension, Json, Router};
+use oauth2::principal::Principal;
+use paw_error_handling::problem_details::ProblemDetails;
+use paw_oauth2_resource_server::middleware::oauth2_middleware;
+use paw_oauth2_resource_server::state::AuthState;
+use paw_otel_tracing::otel_middleware::otel_middleware;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub const API_GRAPHQL_PATH: &str = "/api/v1/kartlegging/graphql";
+
+#[derive(Clone)]
+pub(crate) struct GraphqlState {
+    schema: KartleggingSchema,
+    pg_pool: PgPool,
+}
+
+pub(crate) fn routes(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    auth_state: Arc<AuthState>,
+) -> Router {
+    let state = GraphqlState {
+        schema: build_schema(app_config, pg_pool.clone()),
+        pg_pool,
+    };
+    Router::new()
+        .route(API_GRAPHQL_PATH, post(graphql))
+        .route_layer(otel_middleware())
+        .route_layer(oauth2_middleware(auth_state.clone()))
+        .with_state(state)
+}
+
+/// GraphQL-spørring mot samme data som `/api/v1/kartlegging`. Feil i spørringen, også brudd på
+/// grensene for dybde og kompleksitet, returneres som `errors` med status 200.
+#[utoipa::path(
+    post,
+    path = API_GRAPHQL_PATH,
+    tag = "kartlegging",
+    request_body(
+        content = Object,
+        description = "GraphQL-request med `query`, og eventuelt `variables` og `operationName`"
+    ),
+    responses(
+        (status = 200, description = "GraphQL-response med `data` og/eller `errors`", body = Object),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, principal, request))]
+pub(crate) async fn graphql(
+    State(state): State<GraphqlState>,
+    Extension(principal): Extension<Principal>,
+    request: String,
+) -> Result<Json<async_graphql::Response>, ProblemDetails> {
+    let graphql_request: async_graphql::Request = serde_json::from_str(&request).map_err(|e| {
+        tracing::error!("Feil ved deserialisering av request body: {}", e);
+        ProblemDetails::validation_error(API_GRAPHQL_PATH, "Ugyldig request body")
+    })?;
+    let graphql_request = med_loadere(graphql_request.data(principal), &state.pg_pool);
+    let response = state.schema.execute(graphql_request).await;
+    if response.is_err() {
+        tracing::warn!("GraphQL-spørring feilet: {:?}", response.errors);
+    }
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::read_app_config;
+    use crate::logic::graphql::{build_schema, KartleggingSchema};
+    use sqlx::PgPool;
+    use std::sync::Arc;
+
+    fn schema() -> KartleggingSchema {
+        let app_config = read_app_config().expect("Kunne ikke lese app_config.toml");
+        let pg_pool = PgPool::connect_lazy("postgres://localhost/kartlegging_api")
+            .expect("Ugyldig database-url");
+        build_schema(Arc::new(app_config), pg_pool)
+    }
+
+    #[tokio::test]
+    async fn test_sdl_har_typene() {
+        let sdl = schema().sdl();
+        for forventet in [
+            "type Arbeidssoeker",
+            "type Ledighetsperiode",
+            "type Kontortilknytning",
+            "type Bekreftelse",
+            "input TilknyttetKontorQueryRequest",
+            "IKKE_VAERT_I_JOBB_SISTE_2_AAR",
+            "arbeidssoeker(identitetsnummer: String!): Arbeidssoeker",
+        ] {
+            assert!(sdl.contains(forventet), "SDL mangler '{forventet}'");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_for_dyp_spoerring_avvises() {
+        let response = schema()
+            .execute(
+                r#"{ __schema { types { fields { type {
+                    ofType { ofType { ofType { ofType { ofType { name } } } } }
+                } } } } }"#,
+            )
+            .await;
+        assert!(
+            response
+                .errors
+                .iter()
+                .any(|error| error.message.contains("nested too deep")),
+            "{:?}",
+            response.errors
+        );
+    }
+
+    #[tokio::test]
+    async fn test_for_kompleks_spoerring_avvises() {
+        let response = schema()
+            .execute(
+                r#"{ tilknyttetKontor(query: {
+                    kontorId: "1337",
+                    paging: { page: 1, pageSize: 1000, sortOrder: ASC }
+                }) {
+                    arbeidssoekere {
+                        id aktorId identitetsnummer fornavn mellomnavn etternavn
+                        kontortilknytninger { kontorId kontorNavn kontorType }
+                    }
+                } }"#,
+            )
+            .await;
+        assert!(
+            response
+                .errors
+                .iter()
+                .any(|error| error.message.contains("too complex")),
+            "{:?}",
+            response.errors
+        );
+    }
+}
@@ -1,10 +1,14 @@
+use crate::config::AppConfig;
+use crate::logic::audit::oppslag::logg_oppslag;
 use crate::logic::query;
-use crate::model::dto::request::QueryRequest;
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
+use crate::model::dto::request::{QueryRequest, QueryType};
 use crate::model::dto::response::KartleggingResponse;
 use crate::model::state::RouterState;
 use axum::extract::State;
 use axum::routing::post;
-use axum::{Json, Router};
+use axum::{Extension, Json, Router};
+use oauth2::principal::Principal;
 use paw_error_handling::problem_details::ProblemDetails;
 use paw_oauth2_resource_server::middleware::oauth2_middleware;
 use paw_oauth2_resource_server::state::AuthState;
@@ -14,17 +18,53 @@ use std::sync::Arc;
 
 pub const API_KARTLEGGING_PATH: &str = "/api/v1/kartlegging";
 
-pub(crate) fn routes(pg_pool: PgPool, auth_state: Arc<AuthState>) -> Router {
+pub(crate) fn routes(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    auth_state: Arc<AuthState>,
+) -> Router {
     Router::new()
         .route(API_KARTLEGGING_PATH, post(finn_kartlegging))
         .route_layer(otel_middleware())
         .route_layer(oauth2_middleware(auth_state.clone()))
-        .with_state(RouterState::new(pg_pool.clone()))
+        .with_state(RouterState::new(app_config, pg_pool.clone()))
 }
 
-#[tracing::instrument(skip(state, request), fields(arbeidssoekere_count))]
-async fn finn_kartlegging(
+#[utoipa::path(
+    post,
+    path = API_KARTLEGGING_PATH,
+    tag = "kartlegging",
+    request_body = QueryRequest,
+    responses(
+        (
+            status = 200,
+            description = "Oversikt over arbeidssøkere for gitt query",
+            body = KartleggingResponse
+        ),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 500,
+            description = "Intern feil",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, principal, request), fields(arbeidssoekere_count))]
+pub(crate) async fn finn_kartlegging(
     State(state): State<RouterState>,
+    Extension(principal): Extension<Principal>,
     request: String,
 ) -> Result<Json<KartleggingResponse>, ProblemDetails> {
     let query_request: QueryRequest = serde_json::from_str(&request).map_err(|e| {
@@ -37,27 +77,51 @@ async fn finn_kartlegging(
         ProblemDetails::database_error(API_KARTLEGGING_PATH, "Transaksjon feilet")
     })?;
 
-    let response = match query_request {
+    let tilgang = AdressebeskyttelseTilgang::for_principal(&principal, &state.app_config);
+    let (query_type, response) = match query_request {
         QueryRequest::Identitetsnummer(query) => {
             query.validate(API_KARTLEGGING_PATH)?;
-            query::arbeidssoeker_query::finn_for_identitetsnummer_query_request(&mut tx, &query)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Feil ved spørring: {}", e);
-                    ProblemDetails::database_error(API_KARTLEGGING_PATH, "Spørring feilet")
-                })?
+            let response = query::arbeidssoeker_query::finn_for_identitetsnummer_query_request(
+                &mut tx, &query, &tilgang,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("Feil ved spørring: {}", e);
+                ProblemDetails::database_error(API_KARTLEGGING_PATH, "Spørring feilet")
+            })?;
+            (QueryType::Identitetsnummer, response)
         }
         QueryRequest::TilknyttetKontor(query) => {
             query.validate(API_KARTLEGGING_PATH)?;
-            query::arbeidssoeker_query::finn_for_kontortilknytning_query_request(&mut tx, &query)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Feil ved spørring: {}", e);
-                    ProblemDetails::database_error(API_KARTLEGGING_PATH, "Spørring feilet")
-                })?
+            let response = query::arbeidssoeker_query::finn_for_kontortilknytning_query_request(
+                &mut tx, &query, &tilgang,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("Feil ved spørring: {}", e);
+                ProblemDetails::database_error(API_KARTLEGGING_PATH, "Spørring feilet")
+            })?;
+            (QueryType::TilknyttetKontor, response)
         }
     };
 
+    logg_oppslag(
+        &mut tx,
+        &principal,
+        &query_type,
+        response
+            .arbeidssoekere
+            .iter()
+            .map(|a| a.identitetsnummer.clone())
+            .collect(),
+        state.app_config.audit_cef_logging,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Kunne ikke audit-logge oppslag: {}", e);
+        ProblemDetails::database_error(API_KARTLEGGING_PATH, "Audit-logging feilet")
+    })?;
+
     tx.commit().await.map_err(|e| {
         tracing::error!("Kunne ikke commite transaksjon: {}", e);
         ProblemDetails::database_error(API_KARTLEGGING_PATH, "Transaksjon feilet")
@@ -1,25 +1,48 @@
 pub(crate) mod docs;
+pub(crate) mod eksport;
+pub(crate) mod endringer;
+pub(crate) mod graphql;
 pub(crate) mod kartlegging;
+pub(crate) mod reprojeksjon;
 pub(crate) mod statistics;
+pub(crate) mod tidslinje;
 
+use crate::config::AppConfig;
+use crate::logic::reprojeksjon::Reprojeksjon;
 use axum::Router;
-use health_and_monitoring::simple_app_state::AppState;
+use health_and_monitoring::HealthCheck;
 use paw_oauth2_resource_server::state::AuthState;
 use sqlx::PgPool;
 use std::sync::Arc;
 
 pub fn build_router(
-    app_state: Arc<AppState>,
+    app_config: Arc<AppConfig>,
+    health_check: Arc<dyn HealthCheck + Send + Sync>,
     pg_pool: PgPool,
     auth_state: Arc<AuthState>,
+    reprojeksjon: Arc<Reprojeksjon>,
 ) -> Router {
-    let health_routes = axum_health::routes(app_state);
+    let health_routes = axum_health::routes(health_check);
     let docs_routes = docs::routes();
-    let kartlegging_routes = kartlegging::routes(pg_pool.clone(), auth_state);
-    let statistics_routes = statistics::routes(pg_pool.clone());
+    let kartlegging_routes =
+        kartlegging::routes(app_config.clone(), pg_pool.clone(), auth_state.clone());
+    let tidslinje_routes =
+        tidslinje::routes(app_config.clone(), pg_pool.clone(), auth_state.clone());
+    let eksport_routes = eksport::routes(app_config.clone(), pg_pool.clone(), auth_state.clone());
+    let graphql_routes = graphql::routes(app_config.clone(), pg_pool.clone(), auth_state.clone());
+    let endringer_routes =
+        endringer::routes(app_config.clone(), pg_pool.clone(), auth_state.clone());
+    let statistics_routes =
+        statistics::routes(app_config.clone(), pg_pool.clone(), auth_state.clone());
+    let reprojeksjon_routes = reprojeksjon::routes(app_config, reprojeksjon, auth_state);
 
     health_routes
         .merge(docs_routes)
         .merge(kartlegging_routes)
+        .merge(tidslinje_routes)
+        .merge(eksport_routes)
+        .merge(graphql_routes)
+        .merge(endringer_routes)
         .merge(statistics_routes)
+        .merge(reprojeksjon_routes)
 }
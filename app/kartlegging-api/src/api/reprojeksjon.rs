@@ -0,0 +1,83 @@
+use crate::config::AppConfig;
+use crate::logic::reprojeksjon::Reprojeksjon;
+use crate::logic::tilgang::er_admin;
+use crate::model::dto::reprojeksjon::{ReprojeksjonRequest, ReprojeksjonStatus};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use oauth2::principal::Principal;
+use paw_error_handling::problem_details::ProblemDetails;
+use paw_oauth2_resource_server::middleware::oauth2_middleware;
+use paw_oauth2_resource_server::state::AuthState;
+use paw_otel_tracing::otel_middleware::otel_middleware;
+use std::sync::Arc;
+
+/// Intern kommando for å beregne kartlegginger på nytt etter endret logikk for ledighet.
+/// `POST` starter en reprojeksjon, `GET` gir status for pågående eller siste kjøring.
+/// Krever token for en NAV-ansatt i en av `admin_grupper`. Status holdes bare i minnet til
+/// podden som kjører reprojeksjonen: den forsvinner ved restart, og en kjøring som avbrytes av
+/// restart må startes på nytt. Med flere replikaer gir `GET` bare status fra podden som svarer.
+pub const INTERNAL_REPROJEKSJON_PATH: &str = "/internal/reprojeksjon";
+
+#[derive(Clone)]
+struct ReprojeksjonState {
+    app_config: Arc<AppConfig>,
+    reprojeksjon: Arc<Reprojeksjon>,
+}
+
+pub(crate) fn routes(
+    app_config: Arc<AppConfig>,
+    reprojeksjon: Arc<Reprojeksjon>,
+    auth_state: Arc<AuthState>,
+) -> Router {
+    Router::new()
+        .route(
+            INTERNAL_REPROJEKSJON_PATH,
+            get(hent_status).post(start_reprojeksjon),
+        )
+        .route_layer(otel_middleware())
+        .route_layer(oauth2_middleware(auth_state))
+        .with_state(ReprojeksjonState {
+            app_config,
+            reprojeksjon,
+        })
+}
+
+fn mangler_admin_tilgang() -> ProblemDetails {
+    tracing::warn!("Avviser reprojeksjon for principal uten admin-tilgang");
+    ProblemDetails::forbidden(INTERNAL_REPROJEKSJON_PATH, "Mangler admin-tilgang")
+}
+
+#[tracing::instrument(skip(state, principal))]
+async fn hent_status(
+    State(state): State<ReprojeksjonState>,
+    Extension(principal): Extension<Principal>,
+) -> Result<Json<Option<ReprojeksjonStatus>>, ProblemDetails> {
+    if !er_admin(&principal, &state.app_config.admin_grupper) {
+        return Err(mangler_admin_tilgang());
+    }
+    Ok(Json(state.reprojeksjon.status()))
+}
+
+#[tracing::instrument(skip(state, principal, request))]
+async fn start_reprojeksjon(
+    State(state): State<ReprojeksjonState>,
+    Extension(principal): Extension<Principal>,
+    request: String,
+) -> Result<(StatusCode, Json<ReprojeksjonStatus>), ProblemDetails> {
+    if !er_admin(&principal, &state.app_config.admin_grupper) {
+        return Err(mangler_admin_tilgang());
+    }
+    let request: ReprojeksjonRequest = serde_json::from_str(&request).map_err(|e| {
+        tracing::error!("Feil ved deserialisering av request body: {}", e);
+        ProblemDetails::validation_error(INTERNAL_REPROJEKSJON_PATH, "Ugyldig request body")
+    })?;
+    request.validate(INTERNAL_REPROJEKSJON_PATH)?;
+
+    let status = state
+        .reprojeksjon
+        .start(request.modus, request.batch_size())
+        .map_err(|e| ProblemDetails::conflict(INTERNAL_REPROJEKSJON_PATH, &e.to_string()))?;
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
@@ -1,39 +1,195 @@
+use crate::config::AppConfig;
 use crate::logic::query::statistics_query;
-use crate::model::dto::response::StatisticsResponse;
+use crate::model::dto::request::{StatisticsRequest, StatisticsTidsserieRequest};
+use crate::model::dto::response::{StatisticsResponse, StatisticsTidsserieResponse};
 use crate::model::state::RouterState;
-use axum::extract::State;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
 use axum::routing::get;
 use axum::{Json, Router};
+use chrono::Utc;
+use paw_date_time::virkedager::norsk_dato;
 use paw_error_handling::problem_details::ProblemDetails;
+use paw_oauth2_resource_server::middleware::oauth2_middleware;
+use paw_oauth2_resource_server::state::AuthState;
 use paw_otel_tracing::otel_middleware::otel_middleware;
 use sqlx::PgPool;
+use std::sync::Arc;
 
 pub const API_STATISTICS_PATH: &str = "/api/v1/statistics";
+pub const API_STATISTICS_TIDSSERIE_PATH: &str = "/api/v1/statistics/tidsserie";
 
-pub(crate) fn routes(pg_pool: PgPool) -> Router {
+pub(crate) fn routes(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    auth_state: Arc<AuthState>,
+) -> Router {
     Router::new()
         .route(API_STATISTICS_PATH, get(finn_statistics))
+        .route(API_STATISTICS_TIDSSERIE_PATH, get(finn_tidsserie))
         .route_layer(otel_middleware())
-        .with_state(RouterState::new(pg_pool.clone()))
+        .route_layer(oauth2_middleware(auth_state.clone()))
+        .with_state(RouterState::new(app_config, pg_pool.clone()))
 }
 
-#[tracing::instrument(skip(state))]
-async fn finn_statistics(
+#[utoipa::path(
+    get,
+    path = API_STATISTICS_PATH,
+    tag = "statistikk",
+    params(StatisticsRequest),
+    responses(
+        (status = 200, description = "Statistikk for arbeidssøkere", body = StatisticsResponse),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 500,
+            description = "Intern feil",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, request))]
+pub(crate) async fn finn_statistics(
     State(state): State<RouterState>,
+    request: Result<Query<StatisticsRequest>, QueryRejection>,
 ) -> Result<Json<StatisticsResponse>, ProblemDetails> {
+    let Query(request) = request.map_err(|e| {
+        tracing::error!("Feil ved deserialisering av query-parametre: {}", e);
+        ProblemDetails::validation_error(API_STATISTICS_PATH, "Ugyldige query-parametre")
+    })?;
+    request.validate(API_STATISTICS_PATH)?;
+
     let mut tx = state.pg_pool.begin().await.map_err(|e| {
         tracing::error!("Kunne ikke starte transaksjon: {}", e);
         ProblemDetails::database_error(API_STATISTICS_PATH, "Transaksjon feilet")
     })?;
 
-    let response = statistics_query::finn(&mut tx).await.map_err(|e| {
+    let response = statistics_query::finn(&mut tx, &request, &state.app_config.statistikk_grenser)
+        .await
+        .map_err(|e| {
+            tracing::error!("Feil ved spørring: {}", e);
+            ProblemDetails::database_error(API_STATISTICS_PATH, "Spørring feilet")
+        })?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Kunne ikke commite transaksjon: {}", e);
+        ProblemDetails::database_error(API_STATISTICS_PATH, "Transaksjon feilet")
+    })?;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = API_STATISTICS_TIDSSERIE_PATH,
+    tag = "statistikk",
+    params(
+        (
+            "fra" = Option<NaiveDate>,
+            Query,
+            description = "Første dato, standard er 30 dager før `til`"
+        ),
+        (
+            "til" = Option<NaiveDate>,
+            Query,
+            description = "Siste dato (inklusiv), standard er i dag"
+        ),
+        StatisticsRequest
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Daglig statistikk fra snapshots",
+            body = StatisticsTidsserieResponse
+        ),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 500,
+            description = "Intern feil",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, request))]
+pub(crate) async fn finn_tidsserie(
+    State(state): State<RouterState>,
+    request: Result<Query<StatisticsTidsserieRequest>, QueryRejection>,
+) -> Result<Json<StatisticsTidsserieResponse>, ProblemDetails> {
+    let Query(request) = request.map_err(|e| {
+        tracing::error!("Feil ved deserialisering av query-parametre: {}", e);
+        ProblemDetails::validation_error(API_STATISTICS_TIDSSERIE_PATH, "Ugyldige query-parametre")
+    })?;
+    // Samme dato som snapshotene lagres med
+    let idag = norsk_dato(Utc::now());
+    request.validate(API_STATISTICS_TIDSSERIE_PATH, idag)?;
+
+    let mut tx = state.pg_pool.begin().await.map_err(|e| {
+        tracing::error!("Kunne ikke starte transaksjon: {}", e);
+        ProblemDetails::database_error(API_STATISTICS_TIDSSERIE_PATH, "Transaksjon feilet")
+    })?;
+
+    let response = statistics_query::finn_tidsserie(
+        &mut tx,
+        &request,
+        &state.app_config.statistikk_grenser,
+        idag,
+    )
+    .await
+    .map_err(|e| {
         tracing::error!("Feil ved spørring: {}", e);
-        ProblemDetails::database_error(API_STATISTICS_PATH, "Spørring feilet")
+        ProblemDetails::database_error(API_STATISTICS_TIDSSERIE_PATH, "Spørring feilet")
     })?;
 
     tx.commit().await.map_err(|e| {
         tracing::error!("Kunne ikke commite transaksjon: {}", e);
-        ProblemDetails::database_error(API_STATISTICS_PATH, "Transaksjon feilet")
+        ProblemDetails::database_error(API_STATISTICS_TIDSSERIE_PATH, "Transaksjon feilet")
     })?;
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::dto::kontortilknytning::KontorType;
+    use axum::http::Uri;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_deserialize_tidsserie_query() {
+        let uri: Uri = "/api/v1/statistics/tidsserie?fra=2026-10-01&kontorId=1337\
+            &kontorType=ARBEIDSOPPFOLGING&profilertTil=OPPGITT_HINDRINGER&grenser=30,90"
+            .parse()
+            .unwrap();
+        let Query(request) = Query::<StatisticsTidsserieRequest>::try_from_uri(&uri).unwrap();
+        assert_eq!(request.fra, NaiveDate::from_ymd_opt(2026, 10, 1));
+        assert_eq!(request.til, None);
+        assert_eq!(request.filter.kontor_id, Some("1337".to_string()));
+        assert_eq!(
+            request.filter.kontor_type,
+            Some(KontorType::Arbeidsoppfolging)
+        );
+        assert_eq!(request.filter.grenser(), Some(vec![30, 90]));
+    }
+}
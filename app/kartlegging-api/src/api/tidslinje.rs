@@ -0,0 +1,121 @@
+use crate::config::AppConfig;
+use crate::logic::audit::oppslag::logg_oppslag;
+use crate::logic::query::tidslinje_query;
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
+use crate::model::dto::request::{QueryType, TidslinjeRequest};
+use crate::model::dto::response::TidslinjeResponse;
+use crate::model::state::RouterState;
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Extension, Json, Router};
+use oauth2::principal::Principal;
+use paw_error_handling::problem_details::ProblemDetails;
+use paw_oauth2_resource_server::middleware::oauth2_middleware;
+use paw_oauth2_resource_server::state::AuthState;
+use paw_otel_tracing::otel_middleware::otel_middleware;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub const API_TIDSLINJE_PATH: &str = "/api/v1/kartlegging/tidslinje";
+
+pub(crate) fn routes(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    auth_state: Arc<AuthState>,
+) -> Router {
+    Router::new()
+        .route(API_TIDSLINJE_PATH, post(finn_tidslinje))
+        .route_layer(otel_middleware())
+        .route_layer(oauth2_middleware(auth_state.clone()))
+        .with_state(RouterState::new(app_config, pg_pool.clone()))
+}
+
+#[utoipa::path(
+    post,
+    path = API_TIDSLINJE_PATH,
+    tag = "kartlegging",
+    request_body = TidslinjeRequest,
+    responses(
+        (
+            status = 200,
+            description = "Kronologisk tidslinje med alle hendelser per arbeidssøker",
+            body = TidslinjeResponse
+        ),
+        (
+            status = 400,
+            description = "Ugyldig request",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 401,
+            description = "Mangler eller ugyldig token",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+        (
+            status = 500,
+            description = "Intern feil",
+            body = ProblemDetails,
+            content_type = "application/problem+json"
+        ),
+    ),
+)]
+#[tracing::instrument(skip(state, principal, request), fields(hendelser_count))]
+pub(crate) async fn finn_tidslinje(
+    State(state): State<RouterState>,
+    Extension(principal): Extension<Principal>,
+    request: String,
+) -> Result<Json<TidslinjeResponse>, ProblemDetails> {
+    let tidslinje_request: TidslinjeRequest = serde_json::from_str(&request).map_err(|e| {
+        tracing::error!("Feil ved deserialisering av request body: {}", e);
+        ProblemDetails::validation_error(API_TIDSLINJE_PATH, "Ugyldig request body")
+    })?;
+    tidslinje_request.validate(API_TIDSLINJE_PATH)?;
+
+    let mut tx = state.pg_pool.begin().await.map_err(|e| {
+        tracing::error!("Kunne ikke starte transaksjon: {}", e);
+        ProblemDetails::database_error(API_TIDSLINJE_PATH, "Transaksjon feilet")
+    })?;
+
+    let tilgang = AdressebeskyttelseTilgang::for_principal(&principal, &state.app_config);
+    let response =
+        tidslinje_query::finn_for_identitetsnummer(&mut tx, &tidslinje_request, &tilgang)
+            .await
+            .map_err(|e| {
+                tracing::error!("Feil ved spørring: {}", e);
+                ProblemDetails::database_error(API_TIDSLINJE_PATH, "Spørring feilet")
+            })?;
+
+    logg_oppslag(
+        &mut tx,
+        &principal,
+        &QueryType::Tidslinje,
+        response
+            .arbeidssoekere
+            .iter()
+            .map(|a| a.identitetsnummer.clone())
+            .collect(),
+        state.app_config.audit_cef_logging,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Kunne ikke audit-logge oppslag: {}", e);
+        ProblemDetails::database_error(API_TIDSLINJE_PATH, "Audit-logging feilet")
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Kunne ikke commite transaksjon: {}", e);
+        ProblemDetails::database_error(API_TIDSLINJE_PATH, "Transaksjon feilet")
+    })?;
+
+    tracing::Span::current().record(
+        "hendelser_count",
+        response
+            .arbeidssoekere
+            .iter()
+            .map(|a| a.hendelser.len())
+            .sum::<usize>(),
+    );
+    Ok(Json(response))
+}
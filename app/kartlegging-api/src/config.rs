@@ -1,27 +1,94 @@
+use nais_schema_registry::config::require_schema_registry_env;
+use paw_app_config::layered::LayeredConfig;
+use paw_app_config::validation::{ConfigReport, ConfigValidator};
 use paw_app_config::{config::read_toml_config, read_config_file};
 use paw_date_time::duration;
 use paw_key_gen_client::config::PawKeyGenClientConfig;
 use paw_oauth2_resource_server::config::AuthConfig;
 use paw_otel_tracing::config::OtelTracingConfig;
 use paw_rdkafka::kafka_config::KafkaConfig;
+use paw_rust_base::env::runtime_env;
 use paw_sqlx::config::DatabaseConfig;
 use pdl_client::config::PDLClientConfig;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::time::Duration;
 use texas_client::config::TokenClientConfig;
 
 pub const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+const APP_CONFIG_ENV_PREFIX: &str = "KARTLEGGING_API_";
 
 #[derive(Deserialize)]
 pub struct AppConfig {
     #[serde(deserialize_with = "duration::iso8601::deserialize")]
     pub metrics_task_interval: Duration,
     pub periode_gap_grense_for_ledighet: i64,
+    pub audit_cef_logging: bool,
+    /// Standard grenser i antall dager ledig for statistikk og snapshots
+    pub statistikk_grenser: Vec<i32>,
+    #[serde(deserialize_with = "duration::iso8601::deserialize")]
+    pub statistikk_snapshot_interval: Duration,
+    /// Entra ID-grupper (objekt-id) og kontorene medlemmene kan eksportere arbeidssøkere for
+    #[serde(default)]
+    pub eksport_kontor_grupper: HashMap<String, Vec<String>>,
+    /// Maks dybde for GraphQL-spørringer
+    pub graphql_max_depth: usize,
+    /// Maks kompleksitet for GraphQL-spørringer, lister teller én gang per element på siden
+    pub graphql_max_complexity: usize,
+    /// Hvor ofte endringsfeeden (SSE) ser etter nye endringer
+    #[serde(deserialize_with = "duration::iso8601::deserialize")]
+    pub endringer_poll_interval: Duration,
+    /// Hvor lenge endringer beholdes før de slettes
+    #[serde(deserialize_with = "duration::iso8601::deserialize")]
+    pub endringer_oppbevaring: Duration,
+    /// Hvor ofte navn og adressebeskyttelse oppdateres fra PDL
+    #[serde(deserialize_with = "duration::iso8601::deserialize")]
+    pub pdl_oppdatering_interval: Duration,
+    /// Arbeidssøkere som ikke er oppdatert fra PDL på så lang tid oppdateres
+    #[serde(deserialize_with = "duration::iso8601::deserialize")]
+    pub pdl_oppdatering_alder: Duration,
+    /// Antall identer per bolk-oppslag mot PDL, maks 1000
+    pub pdl_oppdatering_batch_size: i32,
+    /// Entra ID-grupper (objekt-id) som gir tilgang til personer med fortrolig adresse
+    #[serde(default)]
+    pub fortrolig_adresse_grupper: Vec<String>,
+    /// Entra ID-grupper (objekt-id) som gir tilgang til personer med strengt fortrolig adresse
+    #[serde(default)]
+    pub strengt_fortrolig_adresse_grupper: Vec<String>,
+    /// Entra ID-grupper (objekt-id) som kan starte og følge med på reprojeksjon
+    #[serde(default)]
+    pub admin_grupper: Vec<String>,
+}
+
+/// Sjekker alle config-filer, miljøvariablene de refererer til og schema registry.
+pub fn validate_config() -> ConfigReport {
+    let validator = ConfigValidator::new(runtime_env())
+        .require_placeholders(read_config_file!("app_config.toml"))
+        .require_placeholders(read_config_file!("otel_tracing_config.toml"))
+        .require_placeholders(read_config_file!("database_config.toml"))
+        .require_placeholders(read_config_file!("auth_config.toml"))
+        .require_placeholders(read_config_file!("kafka_config.toml"))
+        .require_placeholders(read_config_file!("token_client_config.toml"))
+        .require_placeholders(read_config_file!("key_gen_client_config.toml"))
+        .require_placeholders(read_config_file!("pdl_client_config.toml"));
+    require_schema_registry_env(validator)
+        .check_config("app_config.toml", read_app_config())
+        .check_config("otel_tracing_config.toml", read_otel_tracing_config())
+        .check_config("database_config.toml", read_database_config())
+        .check_config("auth_config.toml", read_auth_config())
+        .check_config("kafka_config.toml", read_kafka_config())
+        .check_config("token_client_config.toml", read_token_client_config())
+        .check_config("key_gen_client_config.toml", read_paw_key_gen_client_config())
+        .check_config("pdl_client_config.toml", read_pdl_client_config())
+        .report()
 }
 
+/// Innebygd config, overstyrt av miljøvariabler med prefiks `APP_CONFIG_ENV_PREFIX`. Brukes for
+/// verdier som er ulike per miljø, som objekt-id for Entra ID-grupper.
 pub fn read_app_config() -> anyhow::Result<AppConfig> {
-    let content = read_config_file!("app_config.toml");
-    Ok(read_toml_config::<AppConfig>(content)?)
+    Ok(LayeredConfig::new(read_config_file!("app_config.toml"))
+        .with_env_prefix(APP_CONFIG_ENV_PREFIX)
+        .load()?)
 }
 
 pub fn read_otel_tracing_config() -> anyhow::Result<OtelTracingConfig> {
@@ -3,16 +3,19 @@ use crate::logic::process::message_process::KartleggingMessageProcessor;
 use health_and_monitoring::simple_app_state::AppState;
 use paw_rdkafka::kafka_config::KafkaConfig;
 use paw_rdkafka_hwm::hwm_message_processor::hwm_process_message;
+use paw_rdkafka_hwm::rebalance::consumer_health::KafkaConsumerHealth;
 use paw_rdkafka_hwm::rebalance::hwm_rebalance_handler::HwmRebalanceHandler;
 use paw_rust_base::error::ServerError;
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 pub fn create_kafka_consumer(
     app_state: Arc<AppState>,
     pg_pool: PgPool,
+    consumer_health: Arc<KafkaConsumerHealth>,
     kafka_config: KafkaConfig,
     topics: &[&str],
 ) -> anyhow::Result<StreamConsumer<HwmRebalanceHandler>> {
@@ -22,6 +25,7 @@ pub fn create_kafka_consumer(
         pg_pool,
         app_state,
         version: hwm_version,
+        consumer_health,
     };
     let consumer: StreamConsumer<HwmRebalanceHandler> = config.create_with_context(context)?;
     consumer.subscribe(topics)?;
@@ -33,10 +37,14 @@ pub fn kafka_consumer_task(
     hwm_version: i16,
     consumer: StreamConsumer<HwmRebalanceHandler>,
     processor: KartleggingMessageProcessor,
+    cancellation_token: CancellationToken,
 ) -> JoinHandle<anyhow::Result<()>> {
     tokio::spawn(async move {
         loop {
-            let message = consumer.recv().await?.detach();
+            let message = tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                message = consumer.recv() => message?.detach(),
+            };
             register_message_metrics(&message);
             hwm_process_message(hwm_version, pg_pool.clone(), &message, &processor)
                 .await
@@ -45,5 +53,8 @@ pub fn kafka_consumer_task(
                     message: e.to_string(),
                 })?;
         }
+        consumer.unsubscribe();
+        tracing::info!("Kafka consumer stoppet");
+        Ok(())
     })
 }
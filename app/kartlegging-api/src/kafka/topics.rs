@@ -5,8 +5,9 @@ use eksterne_hendelser::egenvurdering::PAW_EGENVURDERING_TOPIC;
 use eksterne_hendelser::opplysninger::PAW_OPPLYSNINGER_TOPIC;
 use eksterne_hendelser::periode::PAW_PERIODE_TOPIC;
 use eksterne_hendelser::profilering::PAW_PROFILERING_TOPIC;
+use interne_hendelser::identiteter::identitet_hendelse::PAW_IDENTITETER_TOPIC;
 
-pub static TOPICS: [&str; 7] = [
+pub static TOPICS: [&str; 8] = [
     PAW_PERIODE_TOPIC,
     PAW_OPPLYSNINGER_TOPIC,
     PAW_PROFILERING_TOPIC,
@@ -14,4 +15,5 @@ pub static TOPICS: [&str; 7] = [
     PAW_BEKREFTELSE_TOPIC,
     PAW_BEKREFTELSE_PAAVEGNEAV_TOPIC,
     POAO_SISTE_OPPFOLGINGSPERIODE_V3_TOPIC,
+    PAW_IDENTITETER_TOPIC,
 ];
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+const CEF_VERSION: &str = "CEF:0";
+const DEVICE_VENDOR: &str = "paw";
+const DEVICE_PRODUCT: &str = "kartlegging-api";
+const DEVICE_VERSION: &str = "1.0";
+const SIGNATURE_ID: &str = "audit:access";
+const NAME: &str = "Oppslag på arbeidssøker";
+const SEVERITY: &str = "INFO";
+
+/// Én CEF-linje per identitet som ble returnert, slik Arcsight forventer.
+pub struct CefAuditMelding<'a> {
+    pub principal_ident: Option<&'a str>,
+    pub identitetsnummer: &'a str,
+    pub query_type: &'a str,
+    pub trace_id: Option<&'a str>,
+    pub tidspunkt: DateTime<Utc>,
+}
+
+impl fmt::Display for CefAuditMelding<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|",
+            CEF_VERSION,
+            DEVICE_VENDOR,
+            DEVICE_PRODUCT,
+            DEVICE_VERSION,
+            SIGNATURE_ID,
+            NAME,
+            SEVERITY
+        )?;
+        write!(f, "end={}", self.tidspunkt.timestamp_millis())?;
+        write!(
+            f,
+            " suid={}",
+            escape_extension(self.principal_ident.unwrap_or("ukjent"))
+        )?;
+        write!(f, " duid={}", escape_extension(self.identitetsnummer))?;
+        if let Some(trace_id) = self.trace_id {
+            write!(f, " sproc={}", escape_extension(trace_id))?;
+        }
+        write!(f, " flexString1Label=Decision flexString1=Permit")?;
+        write!(
+            f,
+            " flexString2Label=QueryType flexString2={}",
+            escape_extension(self.query_type)
+        )
+    }
+}
+
+fn escape_extension(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cef_melding_format() {
+        let tidspunkt = DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+        let melding = CefAuditMelding {
+            principal_ident: Some("Z999999"),
+            identitetsnummer: "01017012345",
+            query_type: "IDENTITETSNUMMER",
+            trace_id: Some("0af7651916cd43dd8448eb211c80319c"),
+            tidspunkt,
+        };
+
+        assert_eq!(
+            melding.to_string(),
+            "CEF:0|paw|kartlegging-api|1.0|audit:access|Oppslag på arbeidssøker|INFO|\
+            end=1700000000000 suid=Z999999 duid=01017012345 \
+            sproc=0af7651916cd43dd8448eb211c80319c \
+            flexString1Label=Decision flexString1=Permit \
+            flexString2Label=QueryType flexString2=IDENTITETSNUMMER"
+        );
+    }
+
+    #[test]
+    fn test_cef_melding_escaper_extension_verdier() {
+        let tidspunkt = DateTime::from_timestamp_millis(0).unwrap();
+        let melding = CefAuditMelding {
+            principal_ident: Some("a=b\\c"),
+            identitetsnummer: "01017012345",
+            query_type: "TILKNYTTET_KONTOR",
+            trace_id: None,
+            tidspunkt,
+        };
+
+        let linje = melding.to_string();
+        assert!(linje.contains(" suid=a\\=b\\\\c "));
+        assert!(!linje.contains("sproc="));
+    }
+}
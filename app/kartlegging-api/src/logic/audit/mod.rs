@@ -0,0 +1,2 @@
+pub(crate) mod cef;
+pub(crate) mod oppslag;
@@ -0,0 +1,125 @@
+use crate::logic::audit::cef::CefAuditMelding;
+use crate::model::dao::audit_logg;
+use crate::model::dao::audit_logg::AuditLoggRow;
+use crate::model::dto::request::QueryType;
+use chrono::Utc;
+use oauth2::principal::Principal;
+use paw_otel_tracing::audit_log::AUDIT_LOG_TARGET;
+use paw_otel_tracing::trace_id::current_trace_id;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(skip(tx, principal, identitetsnumre))]
+pub async fn logg_oppslag(
+    tx: &mut Transaction<'_, Postgres>,
+    principal: &Principal,
+    query_type: &QueryType,
+    identitetsnumre: Vec<String>,
+    cef_enabled: bool,
+) -> anyhow::Result<()> {
+    let (principal_type, principal_ident) = principal_identitet(principal);
+    let row = AuditLoggRow::new(
+        principal_type.to_string(),
+        principal_ident,
+        query_type.as_ref().to_string(),
+        identitetsnumre,
+        current_trace_id(),
+        Utc::now(),
+    );
+    audit_logg::insert(tx, &row).await?;
+    tracing::debug!(
+        "Audit-logget oppslag på {} arbeidssøkere",
+        row.identitetsnumre.len()
+    );
+
+    if cef_enabled {
+        for identitetsnummer in &row.identitetsnumre {
+            let melding = CefAuditMelding {
+                principal_ident: row.principal_ident.as_deref(),
+                identitetsnummer,
+                query_type: &row.query_type,
+                trace_id: row.trace_id.as_deref(),
+                tidspunkt: row.tidspunkt,
+            };
+            tracing::info!(target: AUDIT_LOG_TARGET, "{}", melding);
+        }
+    }
+    Ok(())
+}
+
+fn principal_identitet(principal: &Principal) -> (&'static str, Option<String>) {
+    match principal {
+        Principal::NavAnsatt(nav_ansatt) => {
+            ("NAV_ANSATT", Some(nav_ansatt.ident.as_ref().to_string()))
+        }
+        Principal::NavSystem(nav_system) => ("NAV_SYSTEM", Some(nav_system.oid.clone())),
+        Principal::Borger(borger) => ("BORGER", Some(borger.ident.as_ref().to_string())),
+        Principal::EksterntSystem(eksternt_system) => {
+            ("EKSTERNT_SYSTEM", eksternt_system.sub.clone())
+        }
+        Principal::Anonym(_) => ("ANONYM", None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oauth2::principal::NavAnsatt;
+    use postgres_testcontainer::postgres::setup_postgres_container;
+    use types::nav_ident::NavIdent;
+
+    #[tokio::test]
+    async fn test_logg_oppslag() {
+        let postgres_guard = setup_postgres_container(5432)
+            .await
+            .expect("Failed to start Postgres container");
+        sqlx::migrate!("./migrations")
+            .run(&postgres_guard.pg_pool)
+            .await
+            .expect("Failed to run migrations");
+        let principal = Principal::NavAnsatt(NavAnsatt {
+            oid: "oid".to_string(),
+            ident: NavIdent::new("Z999999".to_string()).unwrap(),
+            name: None,
+            roles: vec![],
+            groups: vec![],
+        });
+
+        let mut tx = postgres_guard.pg_pool.begin().await.unwrap();
+        logg_oppslag(
+            &mut tx,
+            &principal,
+            &QueryType::TilknyttetKontor,
+            vec!["01017012345".to_string(), "02017012345".to_string()],
+            false,
+        )
+        .await
+        .expect("Kunne ikke audit-logge oppslag");
+        logg_oppslag(
+            &mut tx,
+            &principal,
+            &QueryType::Identitetsnummer,
+            vec!["02017012345".to_string()],
+            false,
+        )
+        .await
+        .expect("Kunne ikke audit-logge oppslag");
+
+        let rows = audit_logg::select_by_identitetsnummer(&mut tx, "01017012345")
+            .await
+            .expect("Kunne ikke hente audit_logg");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].principal_type, "NAV_ANSATT");
+        assert_eq!(rows[0].principal_ident.as_deref(), Some("Z999999"));
+        assert_eq!(rows[0].query_type, QueryType::TilknyttetKontor.as_ref());
+        assert_eq!(
+            rows[0].identitetsnumre,
+            vec!["01017012345".to_string(), "02017012345".to_string()]
+        );
+
+        let rows = audit_logg::select_by_identitetsnummer(&mut tx, "02017012345")
+            .await
+            .expect("Kunne ikke hente audit_logg");
+        assert_eq!(rows.len(), 2);
+        tx.rollback().await.unwrap();
+    }
+}
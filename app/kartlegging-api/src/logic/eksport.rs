@@ -0,0 +1,317 @@
+use crate::logic::audit::oppslag::logg_oppslag;
+use crate::logic::query::arbeidssoeker_query::map_kontortilknytning_filter;
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
+use crate::model::cursor::Cursor;
+use crate::model::dao::arbeidssoeker;
+use crate::model::dao::arbeidssoeker::KontortilknytningFilter;
+use crate::model::dto::eksport::{EksportFormat, EksportRad, EKSPORT_KOLONNER};
+use crate::model::dto::request::{QueryType, TilknyttetKontorQueryRequest};
+use crate::model::sort::{SortField, SortOrder};
+use axum::body::Body;
+use oauth2::principal::Principal;
+use rust_xlsxwriter::Workbook;
+use sqlx::PgPool;
+use std::fs::File;
+use std::io;
+use std::io::{Read, Seek, SeekFrom};
+use tokio::sync::mpsc;
+
+/// Antall arbeidssøkere som hentes, audit-logges og skrives om gangen
+pub const EKSPORT_BATCH_SIZE: i32 = 1000;
+const EKSPORT_BUFFER_SIZE: usize = 4;
+const XLSX_CHUNK_SIZE: usize = 64 * 1024;
+/// UTF-8 BOM slik at Excel leser æøå riktig i CSV
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const CSV_SEPARATOR: u8 = b';';
+
+/// Henter arbeidssøkere for eksport i batcher med keyset-paging. Hver batch leses og
+/// audit-logges i egen transaksjon.
+pub(crate) struct EksportKilde {
+    pg_pool: PgPool,
+    principal: Principal,
+    tilgang: AdressebeskyttelseTilgang,
+    filter: KontortilknytningFilter,
+    sort_field: SortField,
+    sort_order: SortOrder,
+    cef_enabled: bool,
+    cursor: Option<Cursor>,
+    ferdig: bool,
+}
+
+impl EksportKilde {
+    pub(crate) fn new(
+        pg_pool: PgPool,
+        principal: Principal,
+        tilgang: AdressebeskyttelseTilgang,
+        request: &TilknyttetKontorQueryRequest,
+        cef_enabled: bool,
+    ) -> Self {
+        let paging = request.paging.clone().unwrap_or_default();
+        Self {
+            pg_pool,
+            principal,
+            tilgang,
+            filter: map_kontortilknytning_filter(request, &tilgang),
+            sort_field: paging.sort_field.unwrap_or_default(),
+            sort_order: paging.sort_order,
+            cef_enabled,
+            cursor: None,
+            ferdig: false,
+        }
+    }
+
+    /// Neste batch, eller `None` når alle arbeidssøkere er hentet
+    async fn neste_batch(&mut self) -> anyhow::Result<Option<Vec<EksportRad>>> {
+        if self.ferdig {
+            return Ok(None);
+        }
+        let mut tx = self.pg_pool.begin().await?;
+        let rows = arbeidssoeker::select_eksport_by_kontortilknytning(
+            &mut tx,
+            &self.filter,
+            self.cursor.as_ref(),
+            EKSPORT_BATCH_SIZE,
+            &self.sort_field,
+            &self.sort_order,
+        )
+        .await?;
+        logg_oppslag(
+            &mut tx,
+            &self.principal,
+            &QueryType::Eksport,
+            rows.iter()
+                .map(|row| row.identitetsnummer.clone())
+                .collect(),
+            self.cef_enabled,
+        )
+        .await?;
+        tx.commit().await?;
+
+        self.ferdig = rows.len() < EKSPORT_BATCH_SIZE as usize;
+        self.cursor = rows.last().map(|row| Cursor {
+            sort_field: self.sort_field,
+            sort_order: self.sort_order,
+            sort_verdi: row.sort_verdi,
+            periode_id: row.periode_id,
+        });
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        tracing::debug!("Hentet {} arbeidssøkere for eksport", rows.len());
+        let rader = rows
+            .into_iter()
+            .map(|row| {
+                let skjermet = self
+                    .tilgang
+                    .skal_skjermes(row.adressebeskyttelse.as_deref());
+                let mut rad = EksportRad::from(row);
+                if skjermet {
+                    rad.skjerm();
+                }
+                rad
+            })
+            .collect();
+        Ok(Some(rader))
+    }
+}
+
+/// Starter eksporten i bakgrunnen og returnerer en body som strømmes mens batcher hentes.
+/// Feil underveis avbryter strømmen, slik at klienten ikke får en ufullstendig fil.
+pub(crate) fn eksporter(kilde: EksportKilde, format: EksportFormat) -> Body {
+    let (sender, receiver) = mpsc::channel::<io::Result<Vec<u8>>>(EKSPORT_BUFFER_SIZE);
+    match format {
+        EksportFormat::Csv => tokio::spawn(skriv_csv(kilde, sender)),
+        EksportFormat::Xlsx => tokio::spawn(skriv_xlsx(kilde, sender)),
+    };
+    Body::from_stream(futures::stream::unfold(
+        receiver,
+        |mut receiver| async move { receiver.recv().await.map(|chunk| (chunk, receiver)) },
+    ))
+}
+
+async fn skriv_csv(mut kilde: EksportKilde, sender: mpsc::Sender<io::Result<Vec<u8>>>) {
+    let resultat: anyhow::Result<()> = async {
+        let mut antall = 0;
+        let mut chunk = UTF8_BOM.to_vec();
+        chunk.extend(csv_header()?);
+        if sender.send(Ok(chunk)).await.is_err() {
+            return Ok(());
+        }
+        while let Some(rader) = kilde.neste_batch().await? {
+            antall += rader.len();
+            if sender.send(Ok(csv_rader(&rader)?)).await.is_err() {
+                tracing::warn!("Klienten koblet fra under eksport");
+                return Ok(());
+            }
+        }
+        tracing::info!("Eksporterte {} arbeidssøkere som CSV", antall);
+        Ok(())
+    }
+    .await;
+    if let Err(e) = resultat {
+        tracing::error!("Eksport til CSV feilet: {}", e);
+        let _ = sender.send(Err(io::Error::other(e.to_string()))).await;
+    }
+}
+
+fn csv_writer() -> csv::Writer<Vec<u8>> {
+    csv::WriterBuilder::new()
+        .delimiter(CSV_SEPARATOR)
+        .from_writer(Vec::new())
+}
+
+pub(crate) fn csv_header() -> anyhow::Result<Vec<u8>> {
+    let mut writer = csv_writer();
+    writer.write_record(EKSPORT_KOLONNER)?;
+    Ok(writer.into_inner()?)
+}
+
+pub(crate) fn csv_rader(rader: &[EksportRad]) -> anyhow::Result<Vec<u8>> {
+    let mut writer = csv_writer();
+    for rad in rader {
+        writer.write_record(rad.felter())?;
+    }
+    Ok(writer.into_inner()?)
+}
+
+/// XLSX må pakkes som zip til slutt, så radene skrives til en worksheet med konstant
+/// minnebruk og filen lagres til en midlertidig fil som strømmes når alle batcher er skrevet.
+async fn skriv_xlsx(mut kilde: EksportKilde, sender: mpsc::Sender<io::Result<Vec<u8>>>) {
+    let (rad_sender, rad_receiver) = mpsc::channel(EKSPORT_BUFFER_SIZE);
+    let skriver = tokio::task::spawn_blocking(move || skriv_xlsx_blocking(rad_receiver, sender));
+    loop {
+        match kilde.neste_batch().await {
+            Ok(Some(rader)) => {
+                if rad_sender.send(Ok(rader)).await.is_err() {
+                    tracing::warn!("XLSX-skriver er avsluttet");
+                    break;
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                let _ = rad_sender.send(Err(e)).await;
+                break;
+            }
+        }
+    }
+    drop(rad_sender);
+    if let Err(e) = skriver.await {
+        tracing::error!("XLSX-skriver feilet: {}", e);
+    }
+}
+
+fn skriv_xlsx_blocking(
+    mut rad_receiver: mpsc::Receiver<anyhow::Result<Vec<EksportRad>>>,
+    sender: mpsc::Sender<io::Result<Vec<u8>>>,
+) {
+    let resultat = skriv_xlsx_fil(&mut rad_receiver, &sender)
+        .and_then(|fil| fil.map(|fil| send_fil(fil, &sender)).transpose());
+    if let Err(e) = resultat {
+        tracing::error!("Eksport til XLSX feilet: {}", e);
+        let _ = sender.blocking_send(Err(io::Error::other(e.to_string())));
+    }
+}
+
+/// Skriver alle batcher til en midlertidig fil. Returnerer `None` hvis klienten har koblet fra.
+pub(crate) fn skriv_xlsx_fil(
+    rad_receiver: &mut mpsc::Receiver<anyhow::Result<Vec<EksportRad>>>,
+    sender: &mpsc::Sender<io::Result<Vec<u8>>>,
+) -> anyhow::Result<Option<File>> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    worksheet.set_name("Arbeidssøkere")?;
+    worksheet.write_row(0, 0, EKSPORT_KOLONNER)?;
+    let mut rad_nr = 1;
+    while let Some(batch) = rad_receiver.blocking_recv() {
+        if sender.is_closed() {
+            tracing::warn!("Klienten koblet fra under eksport");
+            return Ok(None);
+        }
+        for rad in batch? {
+            worksheet.write_row(rad_nr, 0, rad.felter())?;
+            rad_nr += 1;
+        }
+    }
+    let mut fil = tempfile::tempfile()?;
+    workbook.save_to_writer(&mut fil)?;
+    fil.seek(SeekFrom::Start(0))?;
+    tracing::info!("Eksporterte {} arbeidssøkere som XLSX", rad_nr - 1);
+    Ok(Some(fil))
+}
+
+fn send_fil(mut fil: File, sender: &mpsc::Sender<io::Result<Vec<u8>>>) -> anyhow::Result<()> {
+    loop {
+        let mut chunk = vec![0; XLSX_CHUNK_SIZE];
+        let lest = fil.read(&mut chunk)?;
+        if lest == 0 {
+            return Ok(());
+        }
+        chunk.truncate(lest);
+        if sender.blocking_send(Ok(chunk)).is_err() {
+            tracing::warn!("Klienten koblet fra under eksport");
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn rad(identitetsnummer: &str, etternavn: &str) -> EksportRad {
+        EksportRad {
+            identitetsnummer: identitetsnummer.to_string(),
+            etternavn: etternavn.to_string(),
+            fornavn: "Ola".to_string(),
+            mellomnavn: "".to_string(),
+            ledig_siden: "2026-10-01".to_string(),
+            siste_profilering: "ANTATT_GODE_MULIGHETER".to_string(),
+            siste_egenvurdering: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_csv_header_og_rader() {
+        let mut csv = csv_header().unwrap();
+        csv.extend(csv_rader(&[rad("12345678901", "Nordmann")]).unwrap());
+        csv.extend(csv_rader(&[rad("12345678902", "Nord;mann")]).unwrap());
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "Identitetsnummer;Etternavn;Fornavn;Mellomnavn;Ledig siden;Siste profilering;\
+            Siste egenvurdering\n\
+            12345678901;Nordmann;Ola;;2026-10-01;ANTATT_GODE_MULIGHETER;\n\
+            12345678902;\"Nord;mann\";Ola;;2026-10-01;ANTATT_GODE_MULIGHETER;\n"
+        );
+    }
+
+    #[test]
+    fn test_skriv_xlsx_fil() {
+        let (rad_sender, mut rad_receiver) = mpsc::channel(EKSPORT_BUFFER_SIZE);
+        let (sender, _receiver) = mpsc::channel(EKSPORT_BUFFER_SIZE);
+        rad_sender
+            .blocking_send(Ok(vec![rad("12345678901", "Nordmann")]))
+            .unwrap();
+        drop(rad_sender);
+
+        let mut fil = skriv_xlsx_fil(&mut rad_receiver, &sender)
+            .unwrap()
+            .expect("Klienten er tilkoblet");
+        let mut innhold = Vec::new();
+        fil.read_to_end(&mut innhold).unwrap();
+        // XLSX er en zip-fil
+        assert!(innhold.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn test_skriv_xlsx_fil_feiler_ved_feil_i_batch() {
+        let (rad_sender, mut rad_receiver) = mpsc::channel(EKSPORT_BUFFER_SIZE);
+        let (sender, _receiver) = mpsc::channel(EKSPORT_BUFFER_SIZE);
+        rad_sender
+            .blocking_send(Err(anyhow::anyhow!("Database utilgjengelig")))
+            .unwrap();
+        drop(rad_sender);
+
+        assert!(skriv_xlsx_fil(&mut rad_receiver, &sender).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::config::AppConfig;
+use crate::logic::audit::oppslag::logg_oppslag;
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
+use crate::model::dao::endring;
+use crate::model::dto::endring::{Endring, EndringerResponse, DEFAULT_ENDRINGER_LIMIT};
+use crate::model::dto::request::QueryType;
+use axum::response::sse::Event;
+use chrono::Utc;
+use oauth2::principal::Principal;
+use sqlx::PgPool;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+pub(crate) const ENDRING_EVENT: &str = "endring";
+const ENDRINGER_BUFFER_SIZE: usize = 4;
+const ENDRINGER_OPPRYDDING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Henter endringer etter `cursor` for kontoret og audit-logger dem i samme transaksjon.
+/// Personer med adressebeskyttelse principal ikke har tilgang til er ikke med.
+pub(crate) async fn hent_endringer(
+    pg_pool: &PgPool,
+    principal: &Principal,
+    app_config: &AppConfig,
+    kontor_id: &str,
+    cursor: i64,
+    limit: i32,
+) -> anyhow::Result<EndringerResponse> {
+    let synlige_graderinger =
+        AdressebeskyttelseTilgang::for_principal(principal, app_config).synlige_graderinger();
+    let mut tx = pg_pool.begin().await?;
+    let rows =
+        endring::select_by_kontor_id_after(&mut tx, kontor_id, &synlige_graderinger, cursor, limit)
+            .await?;
+    // Strømmen poller ofte, så tomme oppslag logges ikke
+    if !rows.is_empty() {
+        logg_oppslag(
+            &mut tx,
+            principal,
+            &QueryType::Endringer,
+            rows.iter()
+                .map(|row| row.identitetsnummer.clone())
+                .collect(),
+            app_config.audit_cef_logging,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+
+    let endringer = rows
+        .into_iter()
+        .map(Endring::try_from)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let cursor = endringer.last().map_or(cursor, |endring| endring.id);
+    Ok(EndringerResponse { endringer, cursor })
+}
+
+/// Cursor for siste registrerte endring, slik at en ny strøm bare får endringer fra nå
+pub(crate) async fn siste_cursor(pg_pool: &PgPool) -> anyhow::Result<i64> {
+    let mut tx = pg_pool.begin().await?;
+    let cursor = endring::select_max_id(&mut tx).await?;
+    tx.commit().await?;
+    Ok(cursor)
+}
+
+pub(crate) fn til_event(endring: &Endring) -> anyhow::Result<Event> {
+    Ok(Event::default()
+        .id(endring.id.to_string())
+        .event(ENDRING_EVENT)
+        .json_data(endring)?)
+}
+
+/// Poller etter nye endringer for kontoret i bakgrunnen og sender dem som SSE-events til
+/// klienten kobler fra. Feil avslutter strømmen, og klienten kan fortsette med `Last-Event-ID`.
+pub(crate) fn endringsstroem(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    principal: Principal,
+    kontor_id: String,
+    mut cursor: i64,
+) -> mpsc::Receiver<Result<Event, Infallible>> {
+    let (sender, receiver) = mpsc::channel(ENDRINGER_BUFFER_SIZE);
+    tokio::spawn(async move {
+        tracing::info!(
+            "Starter endringsstrøm for kontor {} fra {}",
+            kontor_id,
+            cursor
+        );
+        loop {
+            let response = match hent_endringer(
+                &pg_pool,
+                &principal,
+                &app_config,
+                &kontor_id,
+                cursor,
+                DEFAULT_ENDRINGER_LIMIT,
+            )
+            .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::error!("Henting av endringer for strøm feilet: {}", e);
+                    return;
+                }
+            };
+            cursor = response.cursor;
+            let full_side = response.endringer.len() == DEFAULT_ENDRINGER_LIMIT as usize;
+            for endring in &response.endringer {
+                let event = match til_event(endring) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        tracing::error!("Serialisering av endring feilet: {}", e);
+                        return;
+                    }
+                };
+                if sender.send(Ok(event)).await.is_err() {
+                    tracing::info!("Klienten koblet fra endringsstrømmen");
+                    return;
+                }
+            }
+            if full_side {
+                continue;
+            }
+            tokio::select! {
+                _ = sender.closed() => {
+                    tracing::info!("Klienten koblet fra endringsstrømmen");
+                    return;
+                }
+                _ = tokio::time::sleep(app_config.endringer_poll_interval) => {}
+            }
+        }
+    });
+    receiver
+}
+
+/// Sletter endringer eldre enn `endringer_oppbevaring`
+#[tracing::instrument(skip(app_config, pg_pool, cancellation_token))]
+pub fn endringer_opprydding_task(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<anyhow::Result<()>> {
+    tokio::spawn(async move {
+        while !cancellation_token.is_cancelled() {
+            tracing::debug!("Kjører task for opprydding av endringer");
+            if let Err(e) = slett_gamle_endringer(&pg_pool, app_config.endringer_oppbevaring).await
+            {
+                tracing::warn!(error = %e, "Kunne ikke slette gamle endringer");
+            }
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = tokio::time::sleep(ENDRINGER_OPPRYDDING_INTERVAL) => {}
+            }
+        }
+        Ok(())
+    })
+}
+
+async fn slett_gamle_endringer(pg_pool: &PgPool, oppbevaring: Duration) -> anyhow::Result<()> {
+    let grense = Utc::now() - chrono::Duration::from_std(oppbevaring)?;
+    let mut tx = pg_pool.begin().await?;
+    let antall = endring::delete_before(&mut tx, &grense).await?;
+    tx.commit().await?;
+    tracing::info!("Slettet {} endringer eldre enn {}", antall, grense);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::dto::endring::EndringType;
+    use axum::response::sse::Sse;
+    use axum::response::IntoResponse;
+
+    #[tokio::test]
+    async fn test_til_event() {
+        let endring = Endring {
+            id: 42,
+            arbeidssoeker_id: 1001,
+            identitetsnummer: "01017012345".to_string(),
+            endring_type: EndringType::Bekreftelse,
+            tidspunkt: "2026-10-19T04:00:00Z".parse().unwrap(),
+        };
+        let event = til_event(&endring).unwrap();
+        let response =
+            Sse::new(futures::stream::iter([Ok::<_, Infallible>(event)])).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let event = String::from_utf8(body.to_vec()).unwrap();
+        assert!(event.contains("id: 42\n"), "{event}");
+        assert!(event.contains("event: endring\n"), "{event}");
+        assert!(event.contains(r#""endringType":"BEKREFTELSE""#), "{event}");
+    }
+}
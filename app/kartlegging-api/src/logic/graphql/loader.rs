@@ -0,0 +1,386 @@
+use crate::logic::graphql::typer::Ledighetsperiode;
+use crate::model::dao::{
+    bekreftelse, bekreftelse_paavegneav, egenvurdering, kartlegging, kontortilknytning,
+    opplysninger, periode, profilering,
+};
+use crate::model::dto::bekreftelse::{Bekreftelse, Bekreftelsesloesning};
+use crate::model::dto::egenvurdering::Egenvurdering;
+use crate::model::dto::kontortilknytning::{KontorType, Kontortilknytning};
+use crate::model::dto::opplysninger::{Jobbsituasjon, Opplysninger};
+use crate::model::dto::periode::Periode;
+use crate::model::dto::profilering::{Profilering, ProfilertTil};
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::Request;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Feil må kunne klones fordi samme resultat deles av alle som ventet på batchen
+pub(crate) type LoaderFeil = Arc<anyhow::Error>;
+
+/// Legger til loadere i requesten. Loaderne cacher resultater, så de lages for hver request
+/// og deles ikke mellom principals.
+pub(crate) fn med_loadere(request: Request, pg_pool: &PgPool) -> Request {
+    request
+        .data(DataLoader::new(
+            LedighetsperiodeLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            KontortilknytningLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            PeriodeLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            OpplysningerLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            ProfileringLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            EgenvurderingLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            BekreftelseLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            BekreftelsePaaVegneAvLoader::new(pg_pool.clone()),
+            tokio::spawn,
+        ))
+}
+
+async fn begin(pg_pool: &PgPool) -> Result<Transaction<'static, Postgres>, LoaderFeil> {
+    pg_pool
+        .begin()
+        .await
+        .map_err(|e| Arc::new(anyhow::Error::from(e)))
+}
+
+async fn commit(tx: Transaction<'static, Postgres>) -> Result<(), LoaderFeil> {
+    tx.commit()
+        .await
+        .map_err(|e| Arc::new(anyhow::Error::from(e)))
+}
+
+/// Grupperer verdier på nøkkel og beholder rekkefølgen fra spørringen
+fn grupper<K: Hash + Eq, T>(
+    verdier: impl IntoIterator<Item = anyhow::Result<(K, T)>>,
+) -> Result<HashMap<K, Vec<T>>, LoaderFeil> {
+    let mut gruppert: HashMap<K, Vec<T>> = HashMap::new();
+    for verdi in verdier {
+        let (key, verdi) = verdi?;
+        gruppert.entry(key).or_default().push(verdi);
+    }
+    Ok(gruppert)
+}
+
+/// Ledighetsperioder per arbeidssøker-id, sortert på når arbeidssøkerperioden startet
+pub(crate) struct LedighetsperiodeLoader {
+    pg_pool: PgPool,
+}
+
+impl LedighetsperiodeLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<i64> for LedighetsperiodeLoader {
+    type Value = Vec<Ledighetsperiode>;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[i64]) -> Result<HashMap<i64, Self::Value>, Self::Error> {
+        tracing::debug!("Henter ledighetsperioder for {} arbeidssøkere", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = kartlegging::select_by_arbeidssoeker_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        grupper(rows.into_iter().map(|row| {
+            Ok((
+                row.arbeidssoeker_id,
+                Ledighetsperiode {
+                    periode_id: row.periode_id,
+                    ledig_siden: row.arbeidsledig_fra,
+                    arbeidssoeker_fra: row.arbeidssoeker_fra,
+                    arbeidssoeker_til: row.arbeidssoeker_til,
+                },
+            ))
+        }))
+    }
+}
+
+/// Kontortilknytninger per aktør-id
+pub(crate) struct KontortilknytningLoader {
+    pg_pool: PgPool,
+}
+
+impl KontortilknytningLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<String> for KontortilknytningLoader {
+    type Value = Vec<Kontortilknytning>;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        tracing::debug!("Henter kontortilknytninger for {} aktører", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = kontortilknytning::select_by_aktor_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        grupper(rows.into_iter().map(|row| {
+            Ok((
+                row.aktor_id,
+                Kontortilknytning {
+                    kontor_id: row.kontor_id,
+                    kontor_navn: row.kontor_navn,
+                    kontor_type: KontorType::from_str(row.kontor_type.as_str())?,
+                },
+            ))
+        }))
+    }
+}
+
+pub(crate) struct PeriodeLoader {
+    pg_pool: PgPool,
+}
+
+impl PeriodeLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<Uuid> for PeriodeLoader {
+    type Value = Periode;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        tracing::debug!("Henter {} perioder", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = periode::select_by_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.id,
+                    Periode {
+                        id: row.id,
+                        startet: row.startet_tidspunkt,
+                        avsluttet: row.avsluttet_tidspunkt,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+/// Opplysninger per periode-id, sortert på tidspunkt
+pub(crate) struct OpplysningerLoader {
+    pg_pool: PgPool,
+}
+
+impl OpplysningerLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<Uuid> for OpplysningerLoader {
+    type Value = Vec<Opplysninger>;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        tracing::debug!("Henter opplysninger for {} perioder", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = opplysninger::select_by_periode_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        grupper(rows.into_iter().map(|row| {
+            let mut jobbsituasjon = Vec::new();
+            for j in &row.jobbsituasjon {
+                jobbsituasjon.push(Jobbsituasjon::from_str(j.as_str())?);
+            }
+            Ok((
+                row.periode_id,
+                Opplysninger {
+                    id: row.id,
+                    jobbsituasjon,
+                    tidspunkt: row.tidspunkt,
+                },
+            ))
+        }))
+    }
+}
+
+/// Profileringer per periode-id, sortert på tidspunkt
+pub(crate) struct ProfileringLoader {
+    pg_pool: PgPool,
+}
+
+impl ProfileringLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<Uuid> for ProfileringLoader {
+    type Value = Vec<Profilering>;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        tracing::debug!("Henter profileringer for {} perioder", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = profilering::select_by_periode_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        grupper(rows.into_iter().map(|row| {
+            Ok((
+                row.periode_id,
+                Profilering {
+                    id: row.id,
+                    profilert_til: ProfilertTil::from_str(row.profilert_til.as_str())?,
+                    tidspunkt: row.tidspunkt,
+                },
+            ))
+        }))
+    }
+}
+
+/// Egenvurderinger per periode-id, sortert på tidspunkt
+pub(crate) struct EgenvurderingLoader {
+    pg_pool: PgPool,
+}
+
+impl EgenvurderingLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<Uuid> for EgenvurderingLoader {
+    type Value = Vec<Egenvurdering>;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        tracing::debug!("Henter egenvurderinger for {} perioder", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = egenvurdering::select_by_periode_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        grupper(rows.into_iter().map(|row| {
+            Ok((
+                row.periode_id,
+                Egenvurdering {
+                    id: row.id,
+                    egenvurdert_til: ProfilertTil::from_str(row.egenvurdert_til.as_str())?,
+                    tidspunkt: row.tidspunkt,
+                },
+            ))
+        }))
+    }
+}
+
+/// Bekreftelser per periode-id, sortert på hvilken periode de gjelder fra
+pub(crate) struct BekreftelseLoader {
+    pg_pool: PgPool,
+}
+
+impl BekreftelseLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<Uuid> for BekreftelseLoader {
+    type Value = Vec<Bekreftelse>;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        tracing::debug!("Henter bekreftelser for {} perioder", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = bekreftelse::select_by_periode_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        grupper(rows.into_iter().map(|row| {
+            Ok((
+                row.periode_id,
+                Bekreftelse {
+                    id: row.id,
+                    gjelder_fra: row.gjelder_fra,
+                    gjelder_til: row.gjelder_til,
+                    har_jobbet: row.har_jobbet,
+                    vil_fortsette: row.vil_fortsette,
+                    bekreftelsesloesning: Bekreftelsesloesning::from_str(
+                        row.bekreftelsesloesning.as_str(),
+                    )?,
+                },
+            ))
+        }))
+    }
+}
+
+/// Gjeldende bekreftelsesløsninger per periode-id
+pub(crate) struct BekreftelsePaaVegneAvLoader {
+    pg_pool: PgPool,
+}
+
+impl BekreftelsePaaVegneAvLoader {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+impl Loader<Uuid> for BekreftelsePaaVegneAvLoader {
+    type Value = Vec<Bekreftelsesloesning>;
+    type Error = LoaderFeil;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        tracing::debug!("Henter bekreftelse på vegne av for {} perioder", keys.len());
+        let mut tx = begin(&self.pg_pool).await?;
+        let rows = bekreftelse_paavegneav::select_endringer_by_periode_ids(&mut tx, keys).await?;
+        commit(tx).await?;
+        let mut loesninger = HashMap::new();
+        for row in rows {
+            let mut bekreftelsesloesninger = Vec::new();
+            for loesning in &row.bekreftelsesloesninger {
+                bekreftelsesloesninger.push(
+                    Bekreftelsesloesning::from_str(loesning.as_str())
+                        .map_err(|e| Arc::new(anyhow::Error::from(e)))?,
+                );
+            }
+            loesninger.insert(row.periode_id, bekreftelsesloesninger);
+        }
+        Ok(loesninger)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grupper_beholder_rekkefoelge() {
+        let gruppert = grupper([Ok((1, "a")), Ok((2, "b")), Ok((1, "c"))]).unwrap();
+        assert_eq!(gruppert.get(&1), Some(&vec!["a", "c"]));
+        assert_eq!(gruppert.get(&2), Some(&vec!["b"]));
+    }
+
+    #[test]
+    fn test_grupper_feiler_ved_ukjent_verdi() {
+        let resultat = grupper([
+            Ok((1, ProfilertTil::AntattGodeMuligheter)),
+            ProfilertTil::from_str("UKJENT")
+                .map(|profilert_til| (2, profilert_til))
+                .map_err(anyhow::Error::from),
+        ]);
+        assert!(resultat.is_err());
+    }
+}
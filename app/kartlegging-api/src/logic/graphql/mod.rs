@@ -0,0 +1,140 @@
+pub(crate) mod loader;
+pub(crate) mod typer;
+
+use crate::api::graphql::API_GRAPHQL_PATH;
+use crate::config::AppConfig;
+use crate::logic::audit::oppslag::logg_oppslag;
+use crate::logic::graphql::typer::{Arbeidssoeker, ArbeidssoekerSide};
+use crate::logic::query::arbeidssoeker_query::finn_side_for_kontortilknytning;
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
+use crate::model::dao::arbeidssoeker;
+use crate::model::dto::request::{
+    IdentitetsnummerQueryRequest, QueryType, TilknyttetKontorQueryRequest, DEFAULT_PAGE_SIZE,
+};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+use oauth2::principal::Principal;
+use paw_error_handling::problem_details::ProblemDetails;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub(crate) type KartleggingSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Schema med grenser for dybde og kompleksitet fra config. `PgPool` og `AppConfig` legges i
+/// schema-data, mens principal og loadere legges på hver request.
+pub(crate) fn build_schema(app_config: Arc<AppConfig>, pg_pool: PgPool) -> KartleggingSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(app_config.graphql_max_depth)
+        .limit_complexity(app_config.graphql_max_complexity)
+        .data(app_config)
+        .data(pg_pool)
+        .finish()
+}
+
+fn valideringsfeil(problem: ProblemDetails) -> async_graphql::Error {
+    async_graphql::Error::new(problem.detail.unwrap_or(problem.title))
+}
+
+fn spoerring_feilet(e: anyhow::Error) -> async_graphql::Error {
+    tracing::error!("Feil ved spørring: {}", e);
+    async_graphql::Error::new("Spørring feilet")
+}
+
+pub(crate) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Arbeidssøkeren med gitt identitetsnummer
+    async fn arbeidssoeker(
+        &self,
+        ctx: &Context<'_>,
+        identitetsnummer: String,
+    ) -> async_graphql::Result<Option<Arbeidssoeker>> {
+        let request = IdentitetsnummerQueryRequest {
+            identitetsnummer,
+            paging: None,
+        };
+        request
+            .validate(API_GRAPHQL_PATH)
+            .map_err(valideringsfeil)?;
+        let arbeidssoekere = finn_for_identitetsnummer(ctx, &request.identitetsnummer)
+            .await
+            .map_err(spoerring_feilet)?;
+        Ok(arbeidssoekere.into_iter().next())
+    }
+
+    /// Én side med arbeidssøkere tilknyttet kontoret. Kompleksiteten ganges med sidestørrelsen.
+    #[graphql(complexity = "query
+        .paging
+        .as_ref()
+        .map_or(DEFAULT_PAGE_SIZE, |paging| paging.page_size)
+        .max(1) as usize
+        * child_complexity")]
+    async fn tilknyttet_kontor(
+        &self,
+        ctx: &Context<'_>,
+        query: TilknyttetKontorQueryRequest,
+    ) -> async_graphql::Result<ArbeidssoekerSide> {
+        query.validate(API_GRAPHQL_PATH).map_err(valideringsfeil)?;
+        finn_for_tilknyttet_kontor(ctx, &query)
+            .await
+            .map_err(spoerring_feilet)
+    }
+}
+
+/// Henter arbeidssøkere og audit-logger oppslaget i samme transaksjon
+async fn finn_for_identitetsnummer(
+    ctx: &Context<'_>,
+    identitetsnummer: &str,
+) -> anyhow::Result<Vec<Arbeidssoeker>> {
+    let app_config = ctx.data_unchecked::<Arc<AppConfig>>();
+    let mut tx = ctx.data_unchecked::<PgPool>().begin().await?;
+    let rows = arbeidssoeker::select_by_identitetsnummer(&mut tx, identitetsnummer).await?;
+    logg_oppslag(
+        &mut tx,
+        ctx.data_unchecked::<Principal>(),
+        &QueryType::Graphql,
+        rows.iter()
+            .map(|row| row.identitetsnummer.clone())
+            .collect(),
+        app_config.audit_cef_logging,
+    )
+    .await?;
+    tx.commit().await?;
+    let tilgang = AdressebeskyttelseTilgang::for_principal(
+        ctx.data_unchecked::<Principal>(),
+        app_config,
+    );
+    Ok(rows
+        .into_iter()
+        .map(|row| Arbeidssoeker::fra_row(row, &tilgang))
+        .collect())
+}
+
+async fn finn_for_tilknyttet_kontor(
+    ctx: &Context<'_>,
+    query: &TilknyttetKontorQueryRequest,
+) -> anyhow::Result<ArbeidssoekerSide> {
+    let app_config = ctx.data_unchecked::<Arc<AppConfig>>();
+    let tilgang =
+        AdressebeskyttelseTilgang::for_principal(ctx.data_unchecked::<Principal>(), app_config);
+    let mut tx = ctx.data_unchecked::<PgPool>().begin().await?;
+    let (rows, paging) = finn_side_for_kontortilknytning(&mut tx, query, &tilgang).await?;
+    logg_oppslag(
+        &mut tx,
+        ctx.data_unchecked::<Principal>(),
+        &QueryType::Graphql,
+        rows.iter()
+            .map(|row| row.identitetsnummer.clone())
+            .collect(),
+        app_config.audit_cef_logging,
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(ArbeidssoekerSide {
+        arbeidssoekere: rows
+            .into_iter()
+            .map(|row| Arbeidssoeker::fra_row(row, &tilgang))
+            .collect(),
+        paging,
+    })
+}
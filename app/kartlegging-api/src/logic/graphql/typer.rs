@@ -0,0 +1,173 @@
+use crate::logic::graphql::loader::{
+    BekreftelseLoader, BekreftelsePaaVegneAvLoader, EgenvurderingLoader, KontortilknytningLoader,
+    LedighetsperiodeLoader, LoaderFeil, OpplysningerLoader, PeriodeLoader, ProfileringLoader,
+};
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
+use crate::model::dao::arbeidssoeker::ArbeidssoekerRow;
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
+use crate::model::dto::bekreftelse::{Bekreftelse, Bekreftelsesloesning};
+use crate::model::dto::egenvurdering::Egenvurdering;
+use crate::model::dto::kontortilknytning::Kontortilknytning;
+use crate::model::dto::opplysninger::Opplysninger;
+use crate::model::dto::periode::Periode;
+use crate::model::dto::profilering::Profilering;
+use crate::model::dto::response::PagingResponse;
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{ComplexObject, Context, SimpleObject};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+fn loader_feil(e: LoaderFeil) -> async_graphql::Error {
+    tracing::error!("Henting av data feilet: {}", e);
+    async_graphql::Error::new("Henting av data feilet")
+}
+
+#[derive(Debug, SimpleObject)]
+#[graphql(complex)]
+pub(crate) struct Arbeidssoeker {
+    pub id: i64,
+    pub aktor_id: String,
+    pub identitetsnummer: String,
+    pub fornavn: Option<String>,
+    pub mellomnavn: Option<String>,
+    pub etternavn: Option<String>,
+    pub adressebeskyttelse: Option<Adressebeskyttelse>,
+    /// Navn og kontortilknytninger skjules når kaller mangler tilgang til adressebeskyttelsen
+    #[graphql(skip)]
+    pub skjermet: bool,
+}
+
+impl Arbeidssoeker {
+    pub(crate) fn fra_row(row: ArbeidssoekerRow, tilgang: &AdressebeskyttelseTilgang) -> Self {
+        let skjermet = tilgang.skal_skjermes(row.adressebeskyttelse.as_deref());
+        let (fornavn, mellomnavn, etternavn) = if skjermet {
+            (None, None, None)
+        } else {
+            (row.fornavn, row.mellomnavn, row.etternavn)
+        };
+        Self {
+            id: row.id,
+            aktor_id: row.aktor_id,
+            identitetsnummer: row.identitetsnummer,
+            fornavn,
+            mellomnavn,
+            etternavn,
+            adressebeskyttelse: row
+                .adressebeskyttelse
+                .as_deref()
+                .map(Adressebeskyttelse::fra_lagret),
+            skjermet,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Arbeidssoeker {
+    /// Alle ledighetsperioder, sortert på når arbeidssøkerperioden startet
+    async fn ledighetsperioder(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<Ledighetsperiode>> {
+        let ledighetsperioder = ctx
+            .data_unchecked::<DataLoader<LedighetsperiodeLoader>>()
+            .load_one(self.id)
+            .await
+            .map_err(loader_feil)?;
+        Ok(ledighetsperioder.unwrap_or_default())
+    }
+
+    async fn kontortilknytninger(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<Kontortilknytning>> {
+        if self.skjermet {
+            return Ok(Vec::new());
+        }
+        let kontortilknytninger = ctx
+            .data_unchecked::<DataLoader<KontortilknytningLoader>>()
+            .load_one(self.aktor_id.clone())
+            .await
+            .map_err(loader_feil)?;
+        Ok(kontortilknytninger.unwrap_or_default())
+    }
+}
+
+/// Én arbeidssøkerperiode med når arbeidssøkeren ble ledig. Opplysninger, profilering og
+/// egenvurdering er de siste for perioden.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
+pub(crate) struct Ledighetsperiode {
+    #[graphql(skip)]
+    pub periode_id: Uuid,
+    pub ledig_siden: Option<DateTime<Utc>>,
+    pub arbeidssoeker_fra: DateTime<Utc>,
+    pub arbeidssoeker_til: Option<DateTime<Utc>>,
+}
+
+#[ComplexObject]
+impl Ledighetsperiode {
+    async fn periode(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Periode>> {
+        ctx.data_unchecked::<DataLoader<PeriodeLoader>>()
+            .load_one(self.periode_id)
+            .await
+            .map_err(loader_feil)
+    }
+
+    async fn opplysninger(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Opplysninger>> {
+        let opplysninger = ctx
+            .data_unchecked::<DataLoader<OpplysningerLoader>>()
+            .load_one(self.periode_id)
+            .await
+            .map_err(loader_feil)?;
+        Ok(opplysninger.and_then(|mut opplysninger| opplysninger.pop()))
+    }
+
+    async fn profilering(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Profilering>> {
+        let profileringer = ctx
+            .data_unchecked::<DataLoader<ProfileringLoader>>()
+            .load_one(self.periode_id)
+            .await
+            .map_err(loader_feil)?;
+        Ok(profileringer.and_then(|mut profileringer| profileringer.pop()))
+    }
+
+    async fn egenvurdering(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<Egenvurdering>> {
+        let egenvurderinger = ctx
+            .data_unchecked::<DataLoader<EgenvurderingLoader>>()
+            .load_one(self.periode_id)
+            .await
+            .map_err(loader_feil)?;
+        Ok(egenvurderinger.and_then(|mut egenvurderinger| egenvurderinger.pop()))
+    }
+
+    /// Alle bekreftelser for perioden, sortert på `gjelderFra`
+    async fn bekreftelser(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Bekreftelse>> {
+        let bekreftelser = ctx
+            .data_unchecked::<DataLoader<BekreftelseLoader>>()
+            .load_one(self.periode_id)
+            .await
+            .map_err(loader_feil)?;
+        Ok(bekreftelser.unwrap_or_default())
+    }
+
+    async fn bekreftelse_paa_vegne_av(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<Bekreftelsesloesning>> {
+        let loesninger = ctx
+            .data_unchecked::<DataLoader<BekreftelsePaaVegneAvLoader>>()
+            .load_one(self.periode_id)
+            .await
+            .map_err(loader_feil)?;
+        Ok(loesninger.unwrap_or_default())
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub(crate) struct ArbeidssoekerSide {
+    pub arbeidssoekere: Vec<Arbeidssoeker>,
+    pub paging: PagingResponse,
+}
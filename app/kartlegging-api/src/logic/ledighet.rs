@@ -0,0 +1,111 @@
+use crate::model::dao::bekreftelse::BekreftelseRow;
+use crate::model::dao::kartlegging::KartleggingRow;
+use chrono::{DateTime, Utc};
+
+/// Ledighet etter én bekreftelse. Nulles ut hvis arbeidssøker har jobbet, ellers beholdes
+/// eksisterende ledighet eller den settes til bekreftelsens gjelder_fra.
+pub(crate) fn arbeidsledig_fra_etter_bekreftelse(
+    arbeidsledig_fra: Option<DateTime<Utc>>,
+    har_jobbet: bool,
+    gjelder_fra: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if har_jobbet {
+        None
+    } else {
+        arbeidsledig_fra.or(Some(gjelder_fra))
+    }
+}
+
+/// Ledighet etter alle bekreftelser i rekkefølge, med `arbeidsledig_fra` som utgangspunkt.
+pub(crate) fn arbeidsledig_fra_etter_bekreftelser(
+    arbeidsledig_fra: Option<DateTime<Utc>>,
+    bekreftelser: &[BekreftelseRow],
+) -> Option<DateTime<Utc>> {
+    bekreftelser
+        .iter()
+        .fold(arbeidsledig_fra, |arbeidsledig_fra, bekreftelse| {
+            arbeidsledig_fra_etter_bekreftelse(
+                arbeidsledig_fra,
+                bekreftelse.har_jobbet,
+                bekreftelse.gjelder_fra,
+            )
+        })
+}
+
+/// Ledighet fra en tidligere, avsluttet kartlegging som videreføres til en ny periode dersom
+/// det er færre enn `periode_gap_grense` dager mellom periodene.
+pub(crate) fn viderefoert_arbeidsledig_fra(
+    tidligere_kartlegging: &KartleggingRow,
+    periode_startet: &DateTime<Utc>,
+    periode_gap_grense: i64,
+) -> Option<DateTime<Utc>> {
+    let arbeidsledig_fra = tidligere_kartlegging.arbeidsledig_fra?;
+    // Tidligere periode uten slutt er fortsatt aktiv, det er en feil
+    let arbeidssoeker_til = tidligere_kartlegging.arbeidssoeker_til?;
+    let periode_gap = *periode_startet - arbeidssoeker_til;
+    if periode_gap.num_days() < periode_gap_grense {
+        Some(arbeidsledig_fra)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use uuid::Uuid;
+
+    fn dato(dag: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, dag, 0, 0, 0).unwrap()
+    }
+
+    fn bekreftelse(gjelder_fra: DateTime<Utc>, har_jobbet: bool) -> BekreftelseRow {
+        BekreftelseRow::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            gjelder_fra,
+            gjelder_fra + Duration::days(14),
+            har_jobbet,
+            true,
+            "ARBEIDSSOEKERREGISTERET".to_string(),
+            gjelder_fra + Duration::days(14),
+        )
+    }
+
+    #[test]
+    fn ledighet_settes_fra_foerste_bekreftelse_og_nulles_ved_jobb() {
+        let bekreftelser = vec![bekreftelse(dato(1), false), bekreftelse(dato(15), false)];
+        assert_eq!(
+            arbeidsledig_fra_etter_bekreftelser(None, &bekreftelser),
+            Some(dato(1))
+        );
+
+        let bekreftelser = vec![
+            bekreftelse(dato(1), false),
+            bekreftelse(dato(15), true),
+            bekreftelse(dato(29), false),
+        ];
+        assert_eq!(
+            arbeidsledig_fra_etter_bekreftelser(Some(dato(1)), &bekreftelser),
+            Some(dato(29))
+        );
+    }
+
+    #[test]
+    fn ledighet_viderefoeres_bare_ved_kort_gap() {
+        let tidligere =
+            KartleggingRow::new(Uuid::new_v4(), 1, dato(1), Some(dato(10)), Some(dato(2)));
+        assert_eq!(
+            viderefoert_arbeidsledig_fra(&tidligere, &dato(20), 14),
+            Some(dato(2))
+        );
+        assert_eq!(
+            viderefoert_arbeidsledig_fra(&tidligere, &dato(25), 14),
+            None
+        );
+
+        let aktiv = KartleggingRow::new(Uuid::new_v4(), 1, dato(1), None, Some(dato(2)));
+        assert_eq!(viderefoert_arbeidsledig_fra(&aktiv, &dato(20), 14), None);
+    }
+}
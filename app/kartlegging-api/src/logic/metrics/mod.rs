@@ -1,5 +1,6 @@
 pub(crate) mod kafka_metrics;
 pub(crate) mod kartlegging_metrics;
+pub(crate) mod statistikk_snapshot;
 pub mod task;
 
 pub fn setup_metrics() {
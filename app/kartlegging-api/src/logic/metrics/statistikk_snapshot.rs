@@ -0,0 +1,22 @@
+use crate::model::dao::statistikk;
+use chrono::Utc;
+use paw_date_time::virkedager::norsk_dato;
+use sqlx::PgPool;
+
+/// Lagrer dagens snapshot av statistikk, med dato i norsk tid. Kjøres flere ganger om dagen, og
+/// siste kjøring for en dato erstatter tidligere.
+pub(crate) async fn lagre_statistikk_snapshot(
+    pg_pool: &PgPool,
+    grenser: &[i32],
+) -> anyhow::Result<()> {
+    let dato = norsk_dato(Utc::now());
+    let mut tx = pg_pool.begin().await?;
+    let rows = statistikk::replace_snapshot(&mut tx, dato, grenser).await?;
+    tx.commit().await?;
+    tracing::info!(
+        "Lagret snapshot av statistikk for {} med {} rader",
+        dato,
+        rows
+    );
+    Ok(())
+}
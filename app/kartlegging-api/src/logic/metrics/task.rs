@@ -1,18 +1,50 @@
 use crate::config::AppConfig;
 use crate::logic::metrics::kartlegging_metrics::register_kartlegging_metrics;
+use crate::logic::metrics::statistikk_snapshot::lagre_statistikk_snapshot;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
-#[tracing::instrument(skip(app_config, pg_pool))]
-pub fn metrics_task(app_config: Arc<AppConfig>, pg_pool: PgPool) -> JoinHandle<anyhow::Result<()>> {
+#[tracing::instrument(skip(app_config, pg_pool, cancellation_token))]
+pub fn metrics_task(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<anyhow::Result<()>> {
     tokio::spawn(async move {
-        loop {
+        while !cancellation_token.is_cancelled() {
             tracing::debug!("Kjører task for oppdatering av metrikker");
             if let Err(e) = register_kartlegging_metrics(&pg_pool).await {
                 tracing::warn!(error = %e, "Kunne ikke oppdatere metrikker");
             }
-            tokio::time::sleep(app_config.metrics_task_interval).await;
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = tokio::time::sleep(app_config.metrics_task_interval) => {}
+            }
+        }
+        Ok(())
+    })
+}
+
+#[tracing::instrument(skip(app_config, pg_pool, cancellation_token))]
+pub fn statistikk_snapshot_task(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<anyhow::Result<()>> {
+    tokio::spawn(async move {
+        while !cancellation_token.is_cancelled() {
+            tracing::debug!("Kjører task for snapshot av statistikk");
+            let grenser = &app_config.statistikk_grenser;
+            if let Err(e) = lagre_statistikk_snapshot(&pg_pool, grenser).await {
+                tracing::warn!(error = %e, "Kunne ikke lagre snapshot av statistikk");
+            }
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = tokio::time::sleep(app_config.statistikk_snapshot_interval) => {}
+            }
         }
+        Ok(())
     })
 }
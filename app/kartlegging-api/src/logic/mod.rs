@@ -1,3 +1,11 @@
+pub mod audit;
+pub(crate) mod eksport;
+pub mod endringer;
+pub(crate) mod graphql;
+pub(crate) mod ledighet;
 pub mod metrics;
+pub mod pdl_oppdatering;
 pub mod process;
 pub mod query;
+pub mod reprojeksjon;
+pub(crate) mod tilgang;
@@ -0,0 +1,380 @@
+use crate::config::AppConfig;
+use crate::model::dao::arbeidssoeker;
+use crate::model::dao::arbeidssoeker::{ArbeidssoekerRow, PdlOppdateringRow};
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
+use chrono::Utc;
+use pdl_client::client::PDLClient;
+use pdl_graphql::pdl::hent_person_navn_bolk::HentPersonNavnBolkHentPersonBolkPerson;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use types::identitetsnummer::Identitetsnummer;
+
+/// PDL tillater maks 1000 identer per bolk-oppslag
+const MAX_PDL_BOLK_SIZE: i32 = 1000;
+
+/// Oppdaterer navn og adressebeskyttelse fra PDL for arbeidssøkere som ikke er oppdatert på
+/// `pdl_oppdatering_alder`. Kjører i batcher til alle er oppdatert, og venter så
+/// `pdl_oppdatering_interval`. Arbeidssøkere PDL ikke svarer for ventes med, se
+/// [ventetid_etter_feil].
+#[tracing::instrument(skip(app_config, pg_pool, pdl_client, cancellation_token))]
+pub fn pdl_oppdatering_task(
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    pdl_client: Arc<PDLClient>,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<anyhow::Result<()>> {
+    tokio::spawn(async move {
+        let batch_size = app_config
+            .pdl_oppdatering_batch_size
+            .clamp(1, MAX_PDL_BOLK_SIZE);
+        while !cancellation_token.is_cancelled() {
+            tracing::debug!("Kjører task for oppdatering fra PDL");
+            loop {
+                match oppdater_batch(&pg_pool, &pdl_client, &app_config, batch_size).await {
+                    Ok(antall) if antall == batch_size as usize => {
+                        if cancellation_token.is_cancelled() {
+                            break;
+                        }
+                    }
+                    Ok(_) => break,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Kunne ikke oppdatere arbeidssøkere fra PDL");
+                        break;
+                    }
+                }
+            }
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = tokio::time::sleep(app_config.pdl_oppdatering_interval) => {}
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Oppdaterer én batch og returnerer antall arbeidssøkere som ble behandlet. Arbeidssøkere som
+/// ikke har feilet hentes med ett bolk-oppslag. Arbeidssøkere som har feilet før hentes én og én,
+/// så én ident PDL avviser ikke holder igjen resten av batchen. Feiler bolk-oppslaget, prøves
+/// ingen flere oppslag før neste kjøring.
+async fn oppdater_batch(
+    pg_pool: &PgPool,
+    pdl_client: &PDLClient,
+    app_config: &AppConfig,
+    batch_size: i32,
+) -> anyhow::Result<usize> {
+    let naa = Utc::now();
+    let grense = naa - chrono::Duration::from_std(app_config.pdl_oppdatering_alder)?;
+    let mut tx = pg_pool.begin().await?;
+    let rows =
+        arbeidssoeker::select_for_pdl_oppdatering(&mut tx, &grense, &naa, batch_size).await?;
+    tx.commit().await?;
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    let (feilet_foer, nye): (Vec<_>, Vec<_>) = rows.iter().partition(|row| row.pdl_antall_feil > 0);
+    let mut personer = HashMap::new();
+    let mut feilet = Vec::new();
+    let mut bolk_feil = None;
+    if !nye.is_empty() {
+        match hent_personer(pdl_client, &nye).await {
+            Ok(funnet) => personer.extend(funnet),
+            Err(e) => {
+                feilet.extend(nye);
+                bolk_feil = Some(e);
+            }
+        }
+    }
+    for row in feilet_foer {
+        if bolk_feil.is_some() {
+            feilet.push(row);
+            continue;
+        }
+        match hent_personer(pdl_client, &[row]).await {
+            Ok(funnet) => personer.extend(funnet),
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "Kunne ikke hente arbeidssøker {} fra PDL, {} feil på rad",
+                    row.arbeidssoeker.id,
+                    row.pdl_antall_feil + 1
+                );
+                feilet.push(row);
+            }
+        }
+    }
+
+    let mut tx = pg_pool.begin().await?;
+    let mut ikke_funnet = Vec::new();
+    let feilet_ider = feilet
+        .iter()
+        .map(|row| row.arbeidssoeker.id)
+        .collect::<HashSet<_>>();
+    for row in rows.iter().map(|row| &row.arbeidssoeker) {
+        if feilet_ider.contains(&row.id) {
+            continue;
+        }
+        match personer.remove(&row.identitetsnummer) {
+            Some(person) => {
+                arbeidssoeker::update_pdl_data(&mut tx, &oppdatert_row(row, &person)).await?;
+            }
+            None => ikke_funnet.push(row.id),
+        }
+    }
+    if !ikke_funnet.is_empty() {
+        tracing::warn!(
+            "Fant ikke {} arbeidssøkere i PDL, beholder lagrede data",
+            ikke_funnet.len()
+        );
+        arbeidssoeker::update_pdl_oppdatert(&mut tx, &ikke_funnet).await?;
+    }
+    for row in &feilet {
+        let ventetid = ventetid_etter_feil(
+            app_config.pdl_oppdatering_interval,
+            app_config.pdl_oppdatering_alder,
+            row.pdl_antall_feil + 1,
+        );
+        let neste_forsok = naa + chrono::Duration::from_std(ventetid)?;
+        arbeidssoeker::registrer_pdl_feil(&mut tx, &[row.arbeidssoeker.id], &neste_forsok).await?;
+    }
+    tx.commit().await?;
+    if let Some(e) = bolk_feil {
+        return Err(e.context(format!(
+            "Bolk-oppslag feilet, venter med {} arbeidssøkere",
+            feilet.len()
+        )));
+    }
+    tracing::info!(
+        "Oppdaterte {} arbeidssøkere fra PDL, {} feilet",
+        rows.len() - feilet.len(),
+        feilet.len()
+    );
+    Ok(rows.len())
+}
+
+/// Henter personene fra PDL, med identitetsnummer som nøkkel
+async fn hent_personer(
+    pdl_client: &PDLClient,
+    rows: &[&PdlOppdateringRow],
+) -> anyhow::Result<HashMap<String, HentPersonNavnBolkHentPersonBolkPerson>> {
+    let identer = rows
+        .iter()
+        .filter_map(|row| Identitetsnummer::new(row.arbeidssoeker.identitetsnummer.clone()))
+        .collect::<Vec<_>>();
+    if identer.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Ok(pdl_client
+        .hent_person_navn_bolk(identer)
+        .await?
+        .into_iter()
+        .filter_map(|resultat| resultat.person.map(|person| (resultat.ident, person)))
+        .collect())
+}
+
+/// Ventetid etter `antall_feil` feilede oppslag. Starter på `interval` og dobles for hvert forsøk,
+/// men blir aldri lengre enn `alder`, så arbeidssøkeren prøves minst like ofte som en vanlig
+/// oppdatering.
+fn ventetid_etter_feil(interval: Duration, alder: Duration, antall_feil: i32) -> Duration {
+    let eksponent = antall_feil.saturating_sub(1).clamp(0, 31) as u32;
+    interval.saturating_mul(2u32.pow(eksponent)).min(alder)
+}
+
+/// Arbeidssøkeren med navn og strengeste adressebeskyttelse fra PDL
+fn oppdatert_row(
+    row: &ArbeidssoekerRow,
+    person: &HentPersonNavnBolkHentPersonBolkPerson,
+) -> ArbeidssoekerRow {
+    let navn = person.navn.first();
+    let adressebeskyttelse = Adressebeskyttelse::strengeste(
+        person
+            .adressebeskyttelse
+            .iter()
+            .map(|beskyttelse| Adressebeskyttelse::fra_pdl(&beskyttelse.gradering)),
+    );
+    ArbeidssoekerRow::new(
+        row.id,
+        row.aktor_id.clone(),
+        row.identitetsnummer.clone(),
+        navn.map(|navn| navn.fornavn.clone()),
+        navn.and_then(|navn| navn.mellomnavn.clone()),
+        navn.map(|navn| navn.etternavn.clone()),
+        adressebeskyttelse.map(|gradering| gradering.as_ref().to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pdl_graphql::pdl::hent_person_navn_bolk::{
+        AdressebeskyttelseGradering, HentPersonNavnBolkHentPersonBolkPersonAdressebeskyttelse,
+        HentPersonNavnBolkHentPersonBolkPersonNavn,
+    };
+    use postgres_testcontainer::postgres::setup_postgres_container;
+    use tokio::sync::OnceCell;
+
+    fn arbeidssoeker_row() -> ArbeidssoekerRow {
+        ArbeidssoekerRow::new(
+            1001,
+            "2001701234500".to_string(),
+            "01017012345".to_string(),
+            Some("Kari".to_string()),
+            None,
+            Some("Nordmann".to_string()),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_oppdatert_row() {
+        let person = HentPersonNavnBolkHentPersonBolkPerson {
+            navn: vec![HentPersonNavnBolkHentPersonBolkPersonNavn {
+                fornavn: "Kari".to_string(),
+                mellomnavn: Some("Olsen".to_string()),
+                etternavn: "Hansen".to_string(),
+            }],
+            adressebeskyttelse: vec![
+                HentPersonNavnBolkHentPersonBolkPersonAdressebeskyttelse {
+                    gradering: AdressebeskyttelseGradering::FORTROLIG,
+                },
+                HentPersonNavnBolkHentPersonBolkPersonAdressebeskyttelse {
+                    gradering: AdressebeskyttelseGradering::STRENGT_FORTROLIG,
+                },
+            ],
+        };
+        let row = oppdatert_row(&arbeidssoeker_row(), &person);
+        assert_eq!(row.id, 1001);
+        assert_eq!(row.fornavn.as_deref(), Some("Kari"));
+        assert_eq!(row.mellomnavn.as_deref(), Some("Olsen"));
+        assert_eq!(row.etternavn.as_deref(), Some("Hansen"));
+        assert_eq!(row.adressebeskyttelse.as_deref(), Some("STRENGT_FORTROLIG"));
+    }
+
+    #[test]
+    fn test_oppdatert_row_uten_navn_og_gradering() {
+        let person = HentPersonNavnBolkHentPersonBolkPerson {
+            navn: vec![],
+            adressebeskyttelse: vec![HentPersonNavnBolkHentPersonBolkPersonAdressebeskyttelse {
+                gradering: AdressebeskyttelseGradering::UGRADERT,
+            }],
+        };
+        let row = oppdatert_row(&arbeidssoeker_row(), &person);
+        assert_eq!(row.fornavn, None);
+        assert_eq!(row.etternavn, None);
+        assert_eq!(row.adressebeskyttelse, None);
+    }
+
+    #[test]
+    fn test_ventetid_etter_feil_dobles_opp_til_alder() {
+        let interval = Duration::from_hours(1);
+        let alder = Duration::from_hours(7 * 24);
+        assert_eq!(
+            ventetid_etter_feil(interval, alder, 1),
+            Duration::from_hours(1)
+        );
+        assert_eq!(
+            ventetid_etter_feil(interval, alder, 2),
+            Duration::from_hours(2)
+        );
+        assert_eq!(
+            ventetid_etter_feil(interval, alder, 3),
+            Duration::from_hours(4)
+        );
+        assert_eq!(
+            ventetid_etter_feil(interval, alder, 8),
+            Duration::from_hours(128)
+        );
+        assert_eq!(ventetid_etter_feil(interval, alder, 9), alder);
+        assert_eq!(ventetid_etter_feil(interval, alder, i32::MAX), alder);
+    }
+
+    #[tokio::test]
+    async fn test_feilet_arbeidssoeker_venter_til_neste_forsok() {
+        let pg_pool = init().await;
+        let naa = Utc::now();
+        let grense = naa - chrono::Duration::days(7);
+        let row = ArbeidssoekerRow::new(
+            4101,
+            "2001704101000".to_string(),
+            "01017041010".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        let mut tx = pg_pool
+            .begin()
+            .await
+            .expect("Kunne ikke starte transaksjon");
+        arbeidssoeker::insert(&mut tx, &row)
+            .await
+            .expect("Kunne ikke lagre arbeidssøker");
+        arbeidssoeker::clear_pdl_oppdatert(&mut tx, &[row.id])
+            .await
+            .expect("Kunne ikke nullstille pdl_oppdatert");
+        arbeidssoeker::registrer_pdl_feil(&mut tx, &[row.id], &(naa + chrono::Duration::hours(1)))
+            .await
+            .expect("Kunne ikke registrere feil");
+        let foer_neste_forsok = pdl_oppdatering_ider(&mut tx, &grense, &naa).await;
+        let ved_neste_forsok = arbeidssoeker::select_for_pdl_oppdatering(
+            &mut tx,
+            &grense,
+            &(naa + chrono::Duration::hours(1)),
+            1000,
+        )
+        .await
+        .expect("Kunne ikke hente arbeidssøkere for PDL-oppdatering")
+        .into_iter()
+        .find(|rad| rad.arbeidssoeker.id == row.id);
+        arbeidssoeker::update_pdl_oppdatert(&mut tx, &[row.id])
+            .await
+            .expect("Kunne ikke oppdatere pdl_oppdatert");
+        arbeidssoeker::clear_pdl_oppdatert(&mut tx, &[row.id])
+            .await
+            .expect("Kunne ikke nullstille pdl_oppdatert");
+        let etter_oppdatering = pdl_oppdatering_ider(&mut tx, &grense, &naa).await;
+        tx.rollback()
+            .await
+            .expect("Kunne ikke rulle tilbake transaksjon");
+
+        assert!(!foer_neste_forsok.contains(&row.id));
+        assert_eq!(ved_neste_forsok.map(|rad| rad.pdl_antall_feil), Some(1));
+        assert!(
+            etter_oppdatering.contains(&row.id),
+            "Vellykket oppdatering skal nullstille feilene"
+        );
+    }
+
+    async fn pdl_oppdatering_ider(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        grense: &chrono::DateTime<Utc>,
+        naa: &chrono::DateTime<Utc>,
+    ) -> Vec<i64> {
+        arbeidssoeker::select_for_pdl_oppdatering(tx, grense, naa, 1000)
+            .await
+            .expect("Kunne ikke hente arbeidssøkere for PDL-oppdatering")
+            .iter()
+            .map(|rad| rad.arbeidssoeker.id)
+            .collect()
+    }
+
+    static INIT: OnceCell<PgPool> = OnceCell::const_new();
+
+    async fn init() -> &'static PgPool {
+        INIT.get_or_init(|| async {
+            let postgres_guard = setup_postgres_container(5432)
+                .await
+                .expect("Failed to start Postgres container");
+            sqlx::migrate!("./migrations")
+                .run(&postgres_guard.pg_pool)
+                .await
+                .expect("Failed to run migrations");
+            postgres_guard.pg_pool
+        })
+        .await
+    }
+}
@@ -1,6 +1,7 @@
-use crate::logic::process::PayloadProcessor;
+use crate::logic::process::{Endret, PayloadProcessor};
 use crate::model::dao::bekreftelse_paavegneav;
 use crate::model::dao::bekreftelse_paavegneav::BekreftelsePaaVegneAvRow;
+use crate::model::dto::endring::EndringType;
 use crate::model::error::{DaoError, PayloadProcessorError};
 use eksterne_hendelser::bekreftelse::paa_vegne_av::{Handling, PaaVegneAv};
 use eksterne_hendelser::serde::AvroDeserializer;
@@ -27,7 +28,7 @@ impl PayloadProcessor for BekreftelsePaaVegneAvProcessor {
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError> {
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
         match message.payload() {
             None => Err(PayloadProcessorError::no_payload_error(message).into()),
             Some(payload) => {
@@ -73,7 +74,7 @@ impl PayloadProcessor for BekreftelsePaaVegneAvProcessor {
                         }
                     }
 
-                    Ok(())
+                    Ok(Some(Endret::Periode(EndringType::BekreftelsePaaVegneAv, periode_id)))
                 } else {
                     match hendelse.handling {
                         Handling::Start(_) => {
@@ -89,7 +90,7 @@ impl PayloadProcessor for BekreftelsePaaVegneAvProcessor {
                         }
                     }
 
-                    Ok(())
+                    Ok(Some(Endret::Periode(EndringType::BekreftelsePaaVegneAv, periode_id)))
                 }
             }
         }
@@ -1,7 +1,9 @@
-use crate::logic::process::PayloadProcessor;
+use crate::logic::ledighet::arbeidsledig_fra_etter_bekreftelse;
+use crate::logic::process::{Endret, PayloadProcessor};
 use crate::model::dao::bekreftelse::BekreftelseRow;
 use crate::model::dao::kartlegging::KartleggingRow;
 use crate::model::dao::{bekreftelse, kartlegging};
+use crate::model::dto::endring::EndringType;
 use crate::model::error::{DaoError, PayloadProcessorError};
 use chrono::{DateTime, Utc};
 use eksterne_hendelser::bekreftelse::bekreftelse::Bekreftelse;
@@ -54,17 +56,11 @@ impl BekreftelseProcessor {
         hendelse: &Bekreftelse,
         kartlegging_row: &KartleggingRow,
     ) -> Option<DateTime<Utc>> {
-        if hendelse.svar.har_jobbet_i_denne_perioden {
-            // Nuller ut ledighet hvis arbeidssøker har jobbet
-            None
-        } else {
-            match kartlegging_row.arbeidsledig_fra {
-                // Har ikke jobbet og ledighet er ikke satt, så benytt bekreftelse gjelder_fra
-                None => Some(hendelse.svar.gjelder_fra),
-                // Har ikke jobbet og ledighet er satt, så behold eksisterende ledighet
-                Some(arbeidsledig_fra) => Some(arbeidsledig_fra),
-            }
-        }
+        arbeidsledig_fra_etter_bekreftelse(
+            kartlegging_row.arbeidsledig_fra,
+            hendelse.svar.har_jobbet_i_denne_perioden,
+            hendelse.svar.gjelder_fra,
+        )
     }
 }
 
@@ -73,7 +69,7 @@ impl PayloadProcessor for BekreftelseProcessor {
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError> {
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
         match message.payload() {
             None => Err(PayloadProcessorError::no_payload_error(message).into()),
             Some(payload) => {
@@ -108,10 +104,16 @@ impl PayloadProcessor for BekreftelseProcessor {
                     )
                     .await?;
 
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Bekreftelse,
+                        hendelse.periode_id,
+                    )))
                 } else {
                     tracing::warn!("Fant ingen kartlegginger for periode-id");
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Bekreftelse,
+                        hendelse.periode_id,
+                    )))
                 }
             }
         }
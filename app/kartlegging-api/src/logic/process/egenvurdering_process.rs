@@ -1,6 +1,7 @@
-use crate::logic::process::PayloadProcessor;
+use crate::logic::process::{Endret, PayloadProcessor};
 use crate::model::dao::egenvurdering;
 use crate::model::dao::egenvurdering::EgenvurderingRow;
+use crate::model::dto::endring::EndringType;
 use crate::model::error::{DaoError, PayloadProcessorError};
 use eksterne_hendelser::egenvurdering::Egenvurdering;
 use eksterne_hendelser::serde::AvroDeserializer;
@@ -27,7 +28,7 @@ impl PayloadProcessor for EgenvurderingProcessor {
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError> {
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
         match message.payload() {
             None => Err(PayloadProcessorError::no_payload_error(message).into()),
             Some(payload) => {
@@ -52,10 +53,16 @@ impl PayloadProcessor for EgenvurderingProcessor {
                     Err(DaoError::multiple_rows(message, "egenvurderinger", count as usize).into())
                 } else if count == 1 {
                     egenvurdering::update(tx, &row).await?;
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Egenvurdering,
+                        hendelse.periode_id,
+                    )))
                 } else {
                     egenvurdering::insert(tx, &row).await?;
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Egenvurdering,
+                        hendelse.periode_id,
+                    )))
                 }
             }
         }
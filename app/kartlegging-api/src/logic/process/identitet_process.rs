@@ -0,0 +1,688 @@
+use crate::logic::process::{Endret, PayloadProcessor};
+use crate::model::dao::arbeidssoeker::ArbeidssoekerRow;
+use crate::model::dao::{
+    arbeidssoeker, bekreftelse, bekreftelse_paavegneav, egenvurdering, kartlegging,
+    kontortilknytning, kontortilknytning_historikk, opplysninger, periode, profilering,
+};
+use crate::model::dto::endring::EndringType;
+use crate::model::error::PayloadProcessorError;
+use interne_hendelser::identiteter::identitet::Identitet;
+use interne_hendelser::identiteter::identitet_hendelse::IdentitetHendelse;
+use interne_hendelser::identiteter::identitet_hendelse_serde::deserialize_identitet_hendelse;
+use interne_hendelser::identiteter::identitet_type::IdentitetType;
+use paw_rdkafka_hwm::hwm_message_processor::ProcessorError;
+use rdkafka::message::OwnedMessage;
+use rdkafka::Message;
+use sqlx::{Postgres, Transaction};
+
+/// Identitetene i en hendelse, gruppert slik de lagres i databasen.
+struct Identiteter {
+    arbeidssoeker_ider: Vec<i64>,
+    aktor_ider: Vec<String>,
+    identitetsnumre: Vec<String>,
+}
+
+impl Identiteter {
+    fn from_hendelse(message: &OwnedMessage, identiteter: &[Identitet]) -> anyhow::Result<Self> {
+        let arbeidssoeker_ider = identiteter
+            .iter()
+            .filter(|i| i.identitet_type == IdentitetType::Arbeidssoekerid)
+            .map(|i| parse_arbeidssoeker_id(message, i))
+            .collect::<anyhow::Result<Vec<i64>>>()?;
+        Ok(Self {
+            arbeidssoeker_ider,
+            aktor_ider: filter_by_type(identiteter, &[IdentitetType::Aktorid]),
+            identitetsnumre: filter_by_type(
+                identiteter,
+                &[IdentitetType::Folkeregisterident, IdentitetType::Npid],
+            ),
+        })
+    }
+}
+
+/// De gjeldende identitetene til personen etter en endring.
+struct GjeldendeIdentiteter {
+    arbeidssoeker_id: i64,
+    aktor_id: String,
+    identitetsnummer: String,
+}
+
+impl GjeldendeIdentiteter {
+    fn from_hendelse(message: &OwnedMessage, identiteter: &[Identitet]) -> anyhow::Result<Self> {
+        let gjeldende = |identitet_type: IdentitetType| {
+            identiteter
+                .iter()
+                .find(|i| i.gjeldende && i.identitet_type == identitet_type)
+        };
+        let arbeidssoeker_id = gjeldende(IdentitetType::Arbeidssoekerid).ok_or_else(|| {
+            PayloadProcessorError::processing_error(message, "Fant ingen gjeldende arbeidssøker-id")
+        })?;
+        let aktor_id = gjeldende(IdentitetType::Aktorid).ok_or_else(|| {
+            PayloadProcessorError::processing_error(message, "Fant ingen gjeldende aktør-id")
+        })?;
+        let identitetsnummer = gjeldende(IdentitetType::Folkeregisterident)
+            .or_else(|| gjeldende(IdentitetType::Npid))
+            .ok_or_else(|| {
+                PayloadProcessorError::processing_error(
+                    message,
+                    "Fant ingen gjeldende folkeregisterident",
+                )
+            })?;
+        Ok(Self {
+            arbeidssoeker_id: parse_arbeidssoeker_id(message, arbeidssoeker_id)?,
+            aktor_id: aktor_id.identitet.clone(),
+            identitetsnummer: identitetsnummer.identitet.clone(),
+        })
+    }
+}
+
+fn parse_arbeidssoeker_id(message: &OwnedMessage, identitet: &Identitet) -> anyhow::Result<i64> {
+    identitet.identitet.parse::<i64>().map_err(|_| {
+        PayloadProcessorError::processing_error(message, "Ugyldig arbeidssøker-id").into()
+    })
+}
+
+fn filter_by_type(identiteter: &[Identitet], typer: &[IdentitetType]) -> Vec<String> {
+    identiteter
+        .iter()
+        .filter(|i| typer.contains(&i.identitet_type))
+        .map(|i| i.identitet.clone())
+        .collect()
+}
+
+pub struct IdentitetProcessor;
+
+impl IdentitetProcessor {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Samler personens arbeidssøkere under gjeldende arbeidssøker-id med gjeldende identiteter.
+    /// Ved splitt flyttes bare kartlegginger for perioder startet med en av personens
+    /// identiteter, resten tilhører den andre personen og flyttes når den hendelsen kommer. En ny
+    /// arbeidssøker etter splitt får navn og adressebeskyttelse ved neste PDL-oppdatering.
+    async fn oppdater_identiteter<'a>(
+        &'a self,
+        tx: &mut Transaction<'_, Postgres>,
+        message: &'a OwnedMessage,
+        identiteter: &'a [Identitet],
+        splittet: bool,
+    ) -> anyhow::Result<Option<i64>> {
+        let gjeldende = GjeldendeIdentiteter::from_hendelse(message, identiteter)?;
+        let alle = Identiteter::from_hendelse(message, identiteter)?;
+
+        kontortilknytning::update_identiteter(
+            tx,
+            &alle.aktor_ider,
+            &alle.identitetsnumre,
+            &gjeldende.aktor_id,
+            &gjeldende.identitetsnummer,
+        )
+        .await?;
+        kontortilknytning_historikk::update_identiteter(
+            tx,
+            &alle.aktor_ider,
+            &alle.identitetsnumre,
+            &gjeldende.aktor_id,
+            &gjeldende.identitetsnummer,
+        )
+        .await?;
+
+        let arbeidssoeker_rows = arbeidssoeker::select_by_identiteter(
+            tx,
+            &alle.arbeidssoeker_ider,
+            &alle.aktor_ider,
+            &alle.identitetsnumre,
+        )
+        .await?;
+
+        let Some(kilde_row) = arbeidssoeker_rows.first() else {
+            tracing::debug!("Fant ingen arbeidssøkere for identitetene, ingenting å oppdatere");
+            return Ok(None);
+        };
+
+        if arbeidssoeker_rows
+            .iter()
+            .any(|row| row.id == gjeldende.arbeidssoeker_id)
+        {
+            arbeidssoeker::update_identiteter(
+                tx,
+                &gjeldende.arbeidssoeker_id,
+                &gjeldende.aktor_id,
+                &gjeldende.identitetsnummer,
+            )
+            .await?;
+        } else if splittet {
+            // Navn og adressebeskyttelse tilhører den andre personen og hentes fra PDL i stedet
+            let row = ArbeidssoekerRow::new(
+                gjeldende.arbeidssoeker_id,
+                gjeldende.aktor_id.clone(),
+                gjeldende.identitetsnummer.clone(),
+                None,
+                None,
+                None,
+                None,
+            );
+            arbeidssoeker::insert(tx, &row).await?;
+            arbeidssoeker::clear_pdl_oppdatert(tx, &[gjeldende.arbeidssoeker_id]).await?;
+        } else {
+            let row = ArbeidssoekerRow::new(
+                gjeldende.arbeidssoeker_id,
+                gjeldende.aktor_id.clone(),
+                gjeldende.identitetsnummer.clone(),
+                kilde_row.fornavn.clone(),
+                kilde_row.mellomnavn.clone(),
+                kilde_row.etternavn.clone(),
+                kilde_row.adressebeskyttelse.clone(),
+            );
+            arbeidssoeker::insert(tx, &row).await?;
+        }
+
+        let andre_arbeidssoeker_ider = arbeidssoeker_rows
+            .iter()
+            .map(|row| row.id)
+            .filter(|id| *id != gjeldende.arbeidssoeker_id)
+            .collect::<Vec<i64>>();
+
+        if !splittet {
+            kartlegging::update_arbeidssoeker_id(
+                tx,
+                &andre_arbeidssoeker_ider,
+                &gjeldende.arbeidssoeker_id,
+            )
+            .await?;
+        }
+        kartlegging::update_arbeidssoeker_id_by_identitetsnumre(
+            tx,
+            &alle.identitetsnumre,
+            &gjeldende.arbeidssoeker_id,
+        )
+        .await?;
+
+        // Arbeidssøkere uten kartlegginger er flettet inn i gjeldende arbeidssøker
+        let mut tomme_arbeidssoeker_ider = Vec::new();
+        for arbeidssoeker_id in andre_arbeidssoeker_ider {
+            if kartlegging::count_by_arbeidssoeker_id(tx, &arbeidssoeker_id).await? == 0 {
+                tomme_arbeidssoeker_ider.push(arbeidssoeker_id);
+            } else {
+                tracing::info!(
+                    "Arbeidssøker har fortsatt kartlegginger etter splitt, beholdes til neste identitetshendelse"
+                );
+            }
+        }
+        arbeidssoeker::delete_by_ids(tx, &tomme_arbeidssoeker_ider).await?;
+
+        tracing::info!(
+            "Oppdaterte identiteter for {} arbeidssøkere, slettet {}",
+            arbeidssoeker_rows.len(),
+            tomme_arbeidssoeker_ider.len()
+        );
+        Ok(Some(gjeldende.arbeidssoeker_id))
+    }
+
+    /// Sletter alle data lagret for de slettede identitetene.
+    async fn slett_identiteter<'a>(
+        &'a self,
+        tx: &mut Transaction<'_, Postgres>,
+        message: &'a OwnedMessage,
+        tidligere_identiteter: &'a [Identitet],
+    ) -> anyhow::Result<()> {
+        let alle = Identiteter::from_hendelse(message, tidligere_identiteter)?;
+
+        let arbeidssoeker_ider = arbeidssoeker::select_by_identiteter(
+            tx,
+            &alle.arbeidssoeker_ider,
+            &alle.aktor_ider,
+            &alle.identitetsnumre,
+        )
+        .await?
+        .into_iter()
+        .map(|row| row.id)
+        .collect::<Vec<i64>>();
+
+        let mut periode_ids =
+            kartlegging::select_periode_ids_by_arbeidssoeker_ids(tx, &arbeidssoeker_ider).await?;
+        periode_ids
+            .extend(periode::select_ids_by_identitetsnumre(tx, &alle.identitetsnumre).await?);
+        periode_ids.sort();
+        periode_ids.dedup();
+
+        opplysninger::delete_by_periode_ids(tx, &periode_ids).await?;
+        profilering::delete_by_periode_ids(tx, &periode_ids).await?;
+        egenvurdering::delete_by_periode_ids(tx, &periode_ids).await?;
+        bekreftelse::delete_by_periode_ids(tx, &periode_ids).await?;
+        bekreftelse_paavegneav::delete_by_periode_ids(tx, &periode_ids).await?;
+        kartlegging::delete_by_periode_ids(tx, &periode_ids).await?;
+        periode::delete_by_ids(tx, &periode_ids).await?;
+        arbeidssoeker::delete_by_ids(tx, &arbeidssoeker_ider).await?;
+        kontortilknytning::delete_by_identiteter(tx, &alle.aktor_ider, &alle.identitetsnumre)
+            .await?;
+        kontortilknytning_historikk::delete_by_identiteter(
+            tx,
+            &alle.aktor_ider,
+            &alle.identitetsnumre,
+        )
+        .await?;
+
+        tracing::info!(
+            "Slettet {} arbeidssøkere og {} perioder for slettede identiteter",
+            arbeidssoeker_ider.len(),
+            periode_ids.len()
+        );
+        Ok(())
+    }
+}
+
+impl PayloadProcessor for IdentitetProcessor {
+    async fn process_payload<'a>(
+        &'a self,
+        tx: &mut Transaction<'_, Postgres>,
+        message: &'a OwnedMessage,
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
+        match message.payload() {
+            None => Err(PayloadProcessorError::no_payload_error(message).into()),
+            Some(payload) => {
+                let hendelse = deserialize_identitet_hendelse(payload)
+                    .map_err(|e| PayloadProcessorError::deserialization_error(message, &*e))?;
+
+                tracing::debug!("Mottok Identitet-hendelse");
+
+                let arbeidssoeker_id = match hendelse {
+                    IdentitetHendelse::IdentiteterEndret(data) => {
+                        self.oppdater_identiteter(tx, message, &data.identiteter, false)
+                            .await?
+                    }
+                    IdentitetHendelse::IdentiteterMerget(data) => {
+                        self.oppdater_identiteter(tx, message, &data.identiteter, false)
+                            .await?
+                    }
+                    IdentitetHendelse::IdentiteterSplittet(data) => {
+                        self.oppdater_identiteter(tx, message, &data.identiteter, true)
+                            .await?
+                    }
+                    IdentitetHendelse::IdentiteterSlettet(data) => {
+                        self.slett_identiteter(tx, message, &data.tidligere_identiteter)
+                            .await?;
+                        None
+                    }
+                };
+
+                Ok(arbeidssoeker_id.map(|id| Endret::Arbeidssoeker(EndringType::Identitet, id)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::process::identitet_process::IdentitetProcessor;
+    use crate::logic::process::PayloadProcessor;
+    use crate::model::dao::arbeidssoeker::ArbeidssoekerRow;
+    use crate::model::dao::kartlegging::KartleggingRow;
+    use crate::model::dao::kontortilknytning::KontortilknytningRow;
+    use crate::model::dao::opplysninger::OpplysningerRow;
+    use crate::model::dao::periode::PeriodeRow;
+    use crate::model::dao::{arbeidssoeker, kartlegging, kontortilknytning, opplysninger, periode};
+    use crate::model::dto::kontortilknytning::KontorType;
+    use chrono::{Duration, Utc};
+    use interne_hendelser::identiteter::identitet_hendelse::PAW_IDENTITETER_TOPIC;
+    use interne_hendelser::identiteter::identitet_type::IdentitetType;
+    use postgres_testcontainer::postgres::setup_postgres_container;
+    use sqlx::{PgPool, Postgres, Transaction};
+    use test_data_generator::identiteter::{
+        create_dummy_identitet, create_dummy_identiteter_endret, create_dummy_identiteter_merget,
+        create_dummy_identiteter_slettet, create_dummy_identiteter_splittet,
+    };
+    use test_data_generator::json::JsonGenerator;
+    use tokio::sync::OnceCell;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_process_messages() {
+        let context = init().await;
+
+        test_process_identiteter_endret(context).await;
+        test_process_identiteter_merget(context).await;
+        test_process_identiteter_splittet(context).await;
+        test_process_identiteter_slettet(context).await;
+    }
+
+    async fn test_process_identiteter_endret(context: &TestContext) {
+        let periode_id = Uuid::new_v4();
+        let kontortilknytning_id = Uuid::new_v4();
+        let mut tx = context.start_tx().await;
+        lagre_arbeidssoeker(&mut tx, 1001, "1000000000101", "01017000101").await;
+        lagre_periode(&mut tx, periode_id, 1001, "01017000101").await;
+        lagre_kontortilknytning(
+            &mut tx,
+            kontortilknytning_id,
+            "1000000000101",
+            "01017000101",
+        )
+        .await;
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        let hendelse = create_dummy_identiteter_endret(
+            vec![
+                create_dummy_identitet("1001", IdentitetType::Arbeidssoekerid, true),
+                create_dummy_identitet("1000000000101", IdentitetType::Aktorid, false),
+                create_dummy_identitet("1000000000102", IdentitetType::Aktorid, true),
+                create_dummy_identitet("01017000101", IdentitetType::Folkeregisterident, false),
+                create_dummy_identitet("01017000102", IdentitetType::Folkeregisterident, true),
+            ],
+            vec![],
+        );
+        let message = context
+            .json_generator
+            .create_json_message(PAW_IDENTITETER_TOPIC, &hendelse);
+
+        let mut tx = context.start_tx().await;
+        let result = context.processor.process_payload(&mut tx, &message).await;
+        assert!(result.is_ok());
+        let arbeidssoeker_rows = arbeidssoeker::select_by_arbeidssoeker_id(&mut tx, &1001)
+            .await
+            .expect("Kunne ikke hente arbeidssøker");
+        let kontortilknytning_row = kontortilknytning::select_by_id(&mut tx, &kontortilknytning_id)
+            .await
+            .expect("Kunne ikke hente kontortilknytning")
+            .expect("Ingen kontortilknytning funnet");
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        assert_eq!(arbeidssoeker_rows.len(), 1);
+        let arbeidssoeker_row = arbeidssoeker_rows
+            .first()
+            .expect("Ingen arbeidssøker funnet");
+        assert_eq!(arbeidssoeker_row.aktor_id, "1000000000102");
+        assert_eq!(arbeidssoeker_row.identitetsnummer, "01017000102");
+        assert_eq!(kontortilknytning_row.aktor_id, "1000000000102");
+        assert_eq!(kontortilknytning_row.identitetsnummer, "01017000102");
+    }
+
+    async fn test_process_identiteter_merget(context: &TestContext) {
+        let periode_id_1 = Uuid::new_v4();
+        let periode_id_2 = Uuid::new_v4();
+        let mut tx = context.start_tx().await;
+        lagre_arbeidssoeker(&mut tx, 2001, "1000000000201", "01017000201").await;
+        lagre_arbeidssoeker(&mut tx, 2002, "1000000000202", "01017000202").await;
+        lagre_periode(&mut tx, periode_id_1, 2001, "01017000201").await;
+        lagre_periode(&mut tx, periode_id_2, 2002, "01017000202").await;
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        let hendelse = create_dummy_identiteter_merget(
+            vec![
+                create_dummy_identitet("2001", IdentitetType::Arbeidssoekerid, true),
+                create_dummy_identitet("2002", IdentitetType::Arbeidssoekerid, false),
+                create_dummy_identitet("1000000000201", IdentitetType::Aktorid, true),
+                create_dummy_identitet("1000000000202", IdentitetType::Aktorid, false),
+                create_dummy_identitet("01017000201", IdentitetType::Folkeregisterident, true),
+                create_dummy_identitet("01017000202", IdentitetType::Folkeregisterident, false),
+            ],
+            vec![],
+        );
+        let message = context
+            .json_generator
+            .create_json_message(PAW_IDENTITETER_TOPIC, &hendelse);
+
+        let mut tx = context.start_tx().await;
+        let result = context.processor.process_payload(&mut tx, &message).await;
+        assert!(result.is_ok());
+        let arbeidssoeker_rows_1 = arbeidssoeker::select_by_arbeidssoeker_id(&mut tx, &2001)
+            .await
+            .expect("Kunne ikke hente arbeidssøker");
+        let arbeidssoeker_rows_2 = arbeidssoeker::select_by_arbeidssoeker_id(&mut tx, &2002)
+            .await
+            .expect("Kunne ikke hente arbeidssøker");
+        let kartlegging_rows_1 = kartlegging::select_by_periode_id(&mut tx, &periode_id_1)
+            .await
+            .expect("Kunne ikke hente kartlegging");
+        let kartlegging_rows_2 = kartlegging::select_by_periode_id(&mut tx, &periode_id_2)
+            .await
+            .expect("Kunne ikke hente kartlegging");
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        assert_eq!(arbeidssoeker_rows_1.len(), 1);
+        assert!(arbeidssoeker_rows_2.is_empty());
+        assert_eq!(kartlegging_rows_1.len(), 1);
+        assert_eq!(kartlegging_rows_2.len(), 1);
+        assert_eq!(kartlegging_rows_1[0].arbeidssoeker_id, 2001);
+        assert_eq!(kartlegging_rows_2[0].arbeidssoeker_id, 2001);
+    }
+
+    async fn test_process_identiteter_splittet(context: &TestContext) {
+        let periode_id_1 = Uuid::new_v4();
+        let periode_id_2 = Uuid::new_v4();
+        let mut tx = context.start_tx().await;
+        let kilde_row = ArbeidssoekerRow::new(
+            3001,
+            "1000000000301".to_string(),
+            "01017000301".to_string(),
+            Some("Test".to_string()),
+            Some("Mellom".to_string()),
+            Some("Testesen".to_string()),
+            Some("FORTROLIG".to_string()),
+        );
+        arbeidssoeker::insert(&mut tx, &kilde_row)
+            .await
+            .expect("Kunne ikke lagre arbeidssøker");
+        lagre_periode(&mut tx, periode_id_1, 3001, "01017000301").await;
+        lagre_periode(&mut tx, periode_id_2, 3001, "01017000302").await;
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        let hendelse = create_dummy_identiteter_splittet(
+            vec![
+                create_dummy_identitet("3002", IdentitetType::Arbeidssoekerid, true),
+                create_dummy_identitet("1000000000302", IdentitetType::Aktorid, true),
+                create_dummy_identitet("01017000302", IdentitetType::Folkeregisterident, true),
+            ],
+            vec![
+                create_dummy_identitet("3001", IdentitetType::Arbeidssoekerid, true),
+                create_dummy_identitet("1000000000301", IdentitetType::Aktorid, true),
+                create_dummy_identitet("01017000301", IdentitetType::Folkeregisterident, true),
+                create_dummy_identitet("01017000302", IdentitetType::Folkeregisterident, false),
+            ],
+        );
+        let message = context
+            .json_generator
+            .create_json_message(PAW_IDENTITETER_TOPIC, &hendelse);
+
+        let mut tx = context.start_tx().await;
+        let result = context.processor.process_payload(&mut tx, &message).await;
+        assert!(result.is_ok());
+        let arbeidssoeker_rows_1 = arbeidssoeker::select_by_arbeidssoeker_id(&mut tx, &3001)
+            .await
+            .expect("Kunne ikke hente arbeidssøker");
+        let arbeidssoeker_rows_2 = arbeidssoeker::select_by_arbeidssoeker_id(&mut tx, &3002)
+            .await
+            .expect("Kunne ikke hente arbeidssøker");
+        let kartlegging_rows_1 = kartlegging::select_by_periode_id(&mut tx, &periode_id_1)
+            .await
+            .expect("Kunne ikke hente kartlegging");
+        let kartlegging_rows_2 = kartlegging::select_by_periode_id(&mut tx, &periode_id_2)
+            .await
+            .expect("Kunne ikke hente kartlegging");
+        let pdl_oppdatering_ids = arbeidssoeker::select_for_pdl_oppdatering(
+            &mut tx,
+            &(Utc::now() - Duration::hours(1)),
+            &Utc::now(),
+            1000,
+        )
+        .await
+        .expect("Kunne ikke hente arbeidssøkere for PDL-oppdatering")
+        .iter()
+        .map(|row| row.arbeidssoeker.id)
+        .collect::<Vec<i64>>();
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        assert_eq!(arbeidssoeker_rows_1.len(), 1);
+        assert_eq!(arbeidssoeker_rows_1[0].identitetsnummer, "01017000301");
+        assert_eq!(arbeidssoeker_rows_2.len(), 1);
+        assert_eq!(arbeidssoeker_rows_2[0].aktor_id, "1000000000302");
+        assert_eq!(arbeidssoeker_rows_2[0].identitetsnummer, "01017000302");
+        assert_eq!(arbeidssoeker_rows_1[0].fornavn.as_deref(), Some("Test"));
+        // Ingenting kopieres fra den andre personen, alt hentes fra PDL
+        assert_eq!(arbeidssoeker_rows_2[0].fornavn, None);
+        assert_eq!(arbeidssoeker_rows_2[0].mellomnavn, None);
+        assert_eq!(arbeidssoeker_rows_2[0].etternavn, None);
+        assert_eq!(arbeidssoeker_rows_2[0].adressebeskyttelse, None);
+        assert!(
+            pdl_oppdatering_ids.contains(&3002),
+            "Ny arbeidssøker etter splitt skal oppdateres fra PDL først"
+        );
+        assert_eq!(kartlegging_rows_1[0].arbeidssoeker_id, 3001);
+        assert_eq!(kartlegging_rows_2[0].arbeidssoeker_id, 3002);
+    }
+
+    async fn test_process_identiteter_slettet(context: &TestContext) {
+        let periode_id = Uuid::new_v4();
+        let opplysninger_id = Uuid::new_v4();
+        let kontortilknytning_id = Uuid::new_v4();
+        let mut tx = context.start_tx().await;
+        lagre_arbeidssoeker(&mut tx, 4001, "1000000000401", "01017000401").await;
+        lagre_periode(&mut tx, periode_id, 4001, "01017000401").await;
+        lagre_kontortilknytning(
+            &mut tx,
+            kontortilknytning_id,
+            "1000000000401",
+            "01017000401",
+        )
+        .await;
+        opplysninger::insert(
+            &mut tx,
+            &OpplysningerRow::new(opplysninger_id, periode_id, vec![], Utc::now()),
+        )
+        .await
+        .expect("Kunne ikke lagre opplysninger");
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        let hendelse = create_dummy_identiteter_slettet(vec![
+            create_dummy_identitet("4001", IdentitetType::Arbeidssoekerid, true),
+            create_dummy_identitet("1000000000401", IdentitetType::Aktorid, true),
+            create_dummy_identitet("01017000401", IdentitetType::Folkeregisterident, true),
+        ]);
+        let message = context
+            .json_generator
+            .create_json_message(PAW_IDENTITETER_TOPIC, &hendelse);
+
+        let mut tx = context.start_tx().await;
+        let result = context.processor.process_payload(&mut tx, &message).await;
+        assert!(result.is_ok());
+        let arbeidssoeker_rows = arbeidssoeker::select_by_arbeidssoeker_id(&mut tx, &4001)
+            .await
+            .expect("Kunne ikke hente arbeidssøker");
+        let kartlegging_rows = kartlegging::select_by_periode_id(&mut tx, &periode_id)
+            .await
+            .expect("Kunne ikke hente kartlegging");
+        let periode_row = periode::select_by_id(&mut tx, &periode_id)
+            .await
+            .expect("Kunne ikke hente periode");
+        let opplysninger_row = opplysninger::select_by_id(&mut tx, &opplysninger_id)
+            .await
+            .expect("Kunne ikke hente opplysninger");
+        let kontortilknytning_row = kontortilknytning::select_by_id(&mut tx, &kontortilknytning_id)
+            .await
+            .expect("Kunne ikke hente kontortilknytning");
+        tx.commit().await.expect("Kunne ikke commit transaksjon");
+
+        assert!(arbeidssoeker_rows.is_empty());
+        assert!(kartlegging_rows.is_empty());
+        assert!(periode_row.is_none());
+        assert!(opplysninger_row.is_none());
+        assert!(kontortilknytning_row.is_none());
+    }
+
+    async fn lagre_arbeidssoeker(
+        tx: &mut Transaction<'_, Postgres>,
+        arbeidssoeker_id: i64,
+        aktor_id: &str,
+        identitetsnummer: &str,
+    ) {
+        let row = ArbeidssoekerRow::new(
+            arbeidssoeker_id,
+            aktor_id.to_string(),
+            identitetsnummer.to_string(),
+            Some("Test".to_string()),
+            None,
+            Some("Testesen".to_string()),
+            None,
+        );
+        arbeidssoeker::insert(tx, &row)
+            .await
+            .expect("Kunne ikke lagre arbeidssøker");
+    }
+
+    async fn lagre_periode(
+        tx: &mut Transaction<'_, Postgres>,
+        periode_id: Uuid,
+        arbeidssoeker_id: i64,
+        identitetsnummer: &str,
+    ) {
+        let startet = Utc::now();
+        periode::insert(
+            tx,
+            &PeriodeRow::new(periode_id, identitetsnummer.to_string(), startet, None),
+        )
+        .await
+        .expect("Kunne ikke lagre periode");
+        kartlegging::insert(
+            tx,
+            &KartleggingRow::new(periode_id, arbeidssoeker_id, startet, None, None),
+        )
+        .await
+        .expect("Kunne ikke lagre kartlegging");
+    }
+
+    async fn lagre_kontortilknytning(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        aktor_id: &str,
+        identitetsnummer: &str,
+    ) {
+        let row = KontortilknytningRow::new(
+            id,
+            aktor_id.to_string(),
+            identitetsnummer.to_string(),
+            "1234".to_string(),
+            "NAV Test".to_string(),
+            KontorType::Arbeidsoppfolging.as_ref().to_string(),
+            Utc::now(),
+        );
+        kontortilknytning::insert(tx, &row)
+            .await
+            .expect("Kunne ikke lagre kontortilknytning");
+    }
+
+    static INIT: OnceCell<TestContext> = OnceCell::const_new();
+
+    async fn init() -> &'static TestContext {
+        INIT.get_or_init(|| async {
+            let postgres_guard = setup_postgres_container(5432)
+                .await
+                .expect("Failed to start Postgres container");
+            sqlx::migrate!("./migrations")
+                .run(&postgres_guard.pg_pool)
+                .await
+                .expect("Failed to run migrations");
+            TestContext {
+                pg_pool: postgres_guard.pg_pool,
+                json_generator: JsonGenerator,
+                processor: IdentitetProcessor::new(),
+            }
+        })
+        .await
+    }
+
+    struct TestContext {
+        pg_pool: PgPool,
+        json_generator: JsonGenerator,
+        processor: IdentitetProcessor,
+    }
+
+    impl TestContext {
+        async fn start_tx(&self) -> Transaction<'_, Postgres> {
+            self.pg_pool
+                .begin()
+                .await
+                .expect("Kunne ikke starte transaksjon")
+        }
+    }
+}
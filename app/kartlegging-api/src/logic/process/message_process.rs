@@ -2,11 +2,14 @@ use crate::config::AppConfig;
 use crate::logic::process::bekreftelse_paavegneav_process::BekreftelsePaaVegneAvProcessor;
 use crate::logic::process::bekreftelse_process::BekreftelseProcessor;
 use crate::logic::process::egenvurdering_process::EgenvurderingProcessor;
+use crate::logic::process::identitet_process::IdentitetProcessor;
 use crate::logic::process::oppfolgingsperiode_process::OppfolgingsperiodeProcessor;
 use crate::logic::process::opplysninger_process::OpplysningerProcessor;
 use crate::logic::process::periode_process::PeriodeProcessor;
 use crate::logic::process::profilering_process::ProfileringProcessor;
-use crate::logic::process::PayloadProcessor;
+use crate::logic::process::{Endret, PayloadProcessor};
+use crate::model::dao::endring;
+use chrono::Utc;
 use dab_oppfolgingperioder::oppfolgingsperiode::POAO_SISTE_OPPFOLGINGSPERIODE_V3_TOPIC;
 use eksterne_hendelser::bekreftelse::bekreftelse::PAW_BEKREFTELSE_TOPIC;
 use eksterne_hendelser::bekreftelse::paa_vegne_av::PAW_BEKREFTELSE_PAAVEGNEAV_TOPIC;
@@ -14,6 +17,7 @@ use eksterne_hendelser::egenvurdering::PAW_EGENVURDERING_TOPIC;
 use eksterne_hendelser::opplysninger::PAW_OPPLYSNINGER_TOPIC;
 use eksterne_hendelser::periode::PAW_PERIODE_TOPIC;
 use eksterne_hendelser::profilering::PAW_PROFILERING_TOPIC;
+use interne_hendelser::identiteter::identitet_hendelse::PAW_IDENTITETER_TOPIC;
 use paw_key_gen_client::client::PawKeyGenClient;
 use paw_rdkafka_hwm::hwm_message_processor::{MessageProcessor, ProcessorError};
 use pdl_client::client::PDLClient;
@@ -33,6 +37,7 @@ pub struct KartleggingMessageProcessor {
     bekreftelse_processor: Arc<BekreftelseProcessor>,
     bekreftelse_paavegneav_processor: Arc<BekreftelsePaaVegneAvProcessor>,
     oppfolgingsperiode_processor: Arc<OppfolgingsperiodeProcessor>,
+    identitet_processor: Arc<IdentitetProcessor>,
 }
 
 impl KartleggingMessageProcessor {
@@ -65,6 +70,7 @@ impl KartleggingMessageProcessor {
                 schema_registry_settings.clone(),
             )),
             oppfolgingsperiode_processor: Arc::new(OppfolgingsperiodeProcessor::new()),
+            identitet_processor: Arc::new(IdentitetProcessor::new()),
         })
     }
 }
@@ -118,12 +124,15 @@ impl MessageProcessor for KartleggingMessageProcessor {
                             .process_payload(tx, message)
                             .await
                     }
+                    topic if topic == PAW_IDENTITETER_TOPIC => {
+                        self.identitet_processor.process_payload(tx, message).await
+                    }
                     topic => {
                         panic!("Mottok melding på ukjent topic: {}", topic);
                     }
                 };
 
-                result.map_err(|e| {
+                let endret = result.map_err(|e| {
                     tracing::error!(
                         error = e,
                         "Prosessering av melding på topic: {}, partition: {}, offset: {} feilet",
@@ -132,13 +141,46 @@ impl MessageProcessor for KartleggingMessageProcessor {
                         message.offset()
                     );
                     e
-                })
+                })?;
+
+                if let Some(endret) = endret {
+                    registrer_endring(tx, &endret).await?;
+                }
+                Ok(())
             }
             .instrument(tracing::Span::current()),
         )
     }
 }
 
+/// Registrerer endringen for endringsfeeden i samme transaksjon som selve endringen
+async fn registrer_endring(
+    tx: &mut Transaction<'_, Postgres>,
+    endret: &Endret,
+) -> anyhow::Result<()> {
+    let tidspunkt = Utc::now();
+    let antall = match endret {
+        Endret::Periode(endring_type, periode_id) => {
+            endring::insert_for_periode_id(tx, periode_id, endring_type.as_ref(), &tidspunkt)
+                .await?
+        }
+        Endret::AktorId(endring_type, aktor_id) => {
+            endring::insert_for_aktor_id(tx, aktor_id, endring_type.as_ref(), &tidspunkt).await?
+        }
+        Endret::Arbeidssoeker(endring_type, arbeidssoeker_id) => {
+            endring::insert_for_arbeidssoeker_id(
+                tx,
+                arbeidssoeker_id,
+                endring_type.as_ref(),
+                &tidspunkt,
+            )
+            .await?
+        }
+    };
+    tracing::debug!("Registrerte {} endringer", antall);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config::read_app_config;
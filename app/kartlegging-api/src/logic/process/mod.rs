@@ -1,21 +1,35 @@
+use crate::model::dto::endring::EndringType;
 use paw_rdkafka_hwm::hwm_message_processor::ProcessorError;
 use rdkafka::message::OwnedMessage;
 use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
 
 pub(crate) mod bekreftelse_paavegneav_process;
 pub(crate) mod bekreftelse_process;
 pub(crate) mod egenvurdering_process;
+pub(crate) mod identitet_process;
 pub mod message_process;
 pub(crate) mod oppfolgingsperiode_process;
 pub(crate) mod opplysninger_process;
 pub(crate) mod periode_process;
 pub(crate) mod profilering_process;
 
+/// Hvilken arbeidssøker en prosessert melding endret data for, identifisert med nøkkelen
+/// prosessoren har tilgjengelig
+#[derive(Debug, Clone, PartialEq)]
+pub enum Endret {
+    Periode(EndringType, Uuid),
+    AktorId(EndringType, String),
+    Arbeidssoeker(EndringType, i64),
+}
+
 #[allow(async_fn_in_trait)]
 pub trait PayloadProcessor {
+    /// Returnerer hva som ble endret, eller `None` om meldingen ikke endret data for noen
+    /// arbeidssøker
     async fn process_payload<'a>(
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError>;
+    ) -> anyhow::Result<Option<Endret>, ProcessorError>;
 }
@@ -1,8 +1,11 @@
-use crate::logic::process::PayloadProcessor;
-use crate::model::dao::kontortilknytning;
+use crate::logic::process::{Endret, PayloadProcessor};
 use crate::model::dao::kontortilknytning::KontortilknytningRow;
-use crate::model::dto::kontortilknytning::KontorType;
+use crate::model::dao::kontortilknytning_historikk::KontortilknytningHistorikkRow;
+use crate::model::dao::{kontortilknytning, kontortilknytning_historikk};
+use crate::model::dto::endring::EndringType;
+use crate::model::dto::kontortilknytning::{KontorType, KontortilknytningEndringType};
 use crate::model::error::{DaoError, PayloadProcessorError};
+use chrono::{DateTime, Utc};
 use dab_oppfolgingperioder::oppfolgingsperiode::{
     Oppfolgingsperiode, OppfolgingsperiodeAvsluttet, OppfolgingsperiodeEndret,
 };
@@ -23,6 +26,7 @@ impl OppfolgingsperiodeProcessor {
         tx: &mut Transaction<'_, Postgres>,
         message: &OwnedMessage,
         data: &'a OppfolgingsperiodeEndret,
+        endring_type: KontortilknytningEndringType,
     ) -> anyhow::Result<u64> {
         let row = KontortilknytningRow::new(
             data.id.clone(),
@@ -33,6 +37,16 @@ impl OppfolgingsperiodeProcessor {
             KontorType::Arbeidsoppfolging.as_ref().to_string(), // Akkurat nå vil alle kontortilknytninger være av type Arbeidsoppfolging. Feltet åpner for å kunne ta imot andre typer tilknytninger i fremtiden
             data.start_tidspunkt.clone(),
         );
+        // Endringer har ikke eget tidspunkt i hendelsen, så da brukes tidspunktet på meldingen
+        let tidspunkt = match endring_type {
+            KontortilknytningEndringType::Startet => data.start_tidspunkt,
+            _ => message
+                .timestamp()
+                .to_millis()
+                .and_then(DateTime::from_timestamp_millis)
+                .unwrap_or_else(Utc::now),
+        };
+        self.insert_historikk(tx, &row, endring_type, tidspunkt).await?;
         let count = kontortilknytning::count_by_id(tx, &data.id).await?;
         if count > 1 {
             Err(DaoError::multiple_rows(message, "bekreftelse_paavegneav", count as usize).into())
@@ -48,8 +62,37 @@ impl OppfolgingsperiodeProcessor {
         tx: &mut Transaction<'_, Postgres>,
         data: &'a OppfolgingsperiodeAvsluttet,
     ) -> anyhow::Result<u64> {
+        if let Some(row) = kontortilknytning::select_by_id(tx, &data.id).await? {
+            self.insert_historikk(
+                tx,
+                &row,
+                KontortilknytningEndringType::Avsluttet,
+                data.slutt_tidspunkt,
+            )
+            .await?;
+        }
         kontortilknytning::delete(tx, &data.id).await
     }
+
+    async fn insert_historikk<'a>(
+        &'a self,
+        tx: &mut Transaction<'_, Postgres>,
+        row: &'a KontortilknytningRow,
+        endring_type: KontortilknytningEndringType,
+        tidspunkt: DateTime<Utc>,
+    ) -> anyhow::Result<u64> {
+        let historikk_row = KontortilknytningHistorikkRow {
+            kontortilknytning_id: row.id,
+            aktor_id: row.aktor_id.clone(),
+            identitetsnummer: row.identitetsnummer.clone(),
+            endring_type: endring_type.as_ref().to_string(),
+            kontor_id: row.kontor_id.clone(),
+            kontor_navn: row.kontor_navn.clone(),
+            kontor_type: row.kontor_type.clone(),
+            tidspunkt,
+        };
+        kontortilknytning_historikk::insert(tx, &historikk_row).await
+    }
 }
 
 impl PayloadProcessor for OppfolgingsperiodeProcessor {
@@ -57,7 +100,7 @@ impl PayloadProcessor for OppfolgingsperiodeProcessor {
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError> {
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
         match message.payload() {
             None => Err(PayloadProcessorError::no_payload_error(message).into()),
             Some(payload) => {
@@ -66,19 +109,37 @@ impl PayloadProcessor for OppfolgingsperiodeProcessor {
 
                 tracing::debug!("Mottok Oppfolgingsperiode-hendelse");
 
-                match hendelse {
+                let aktor_id = match hendelse {
                     Oppfolgingsperiode::Startet(data) => {
-                        self.upsert_kontortilknytning(tx, &message, &data).await?
+                        self.upsert_kontortilknytning(
+                            tx,
+                            &message,
+                            &data,
+                            KontortilknytningEndringType::Startet,
+                        )
+                        .await?;
+                        data.aktor_id
                     }
                     Oppfolgingsperiode::Endret(data) => {
-                        self.upsert_kontortilknytning(tx, &message, &data).await?
+                        self.upsert_kontortilknytning(
+                            tx,
+                            &message,
+                            &data,
+                            KontortilknytningEndringType::Endret,
+                        )
+                        .await?;
+                        data.aktor_id
                     }
                     Oppfolgingsperiode::Avsluttet(data) => {
-                        self.delete_kontortilknytning(tx, &data).await?
+                        self.delete_kontortilknytning(tx, &data).await?;
+                        data.aktor_id
                     }
                 };
 
-                Ok(())
+                Ok(Some(Endret::AktorId(
+                    EndringType::Kontortilknytning,
+                    aktor_id,
+                )))
             }
         }
     }
@@ -88,7 +149,7 @@ impl PayloadProcessor for OppfolgingsperiodeProcessor {
 mod tests {
     use crate::logic::process::oppfolgingsperiode_process::OppfolgingsperiodeProcessor;
     use crate::logic::process::PayloadProcessor;
-    use crate::model::dao::kontortilknytning;
+    use crate::model::dao::{kontortilknytning, kontortilknytning_historikk};
     use crate::model::dto::kontortilknytning::KontorType;
     use dab_oppfolgingperioder::oppfolgingsperiode::{
         Oppfolgingsperiode, POAO_SISTE_OPPFOLGINGSPERIODE_V3_TOPIC,
@@ -135,9 +196,14 @@ mod tests {
             kontortilknytning::select_by_id(&mut tx, &oppfolgingsperiode_id)
                 .await
                 .expect("Kunne ikke hente kontortilknytning");
+        let historikk = hent_historikk(&mut tx, context).await;
         tx.commit().await.expect("Kunne ikke commit transaksjon");
 
         assert!(optional_kontortilknytning_row.is_some());
+        assert_eq!(
+            historikk,
+            vec![("STARTET".to_string(), kontor_id_1.to_string())]
+        );
         match oppfolgingsperiode {
             Oppfolgingsperiode::Startet(hendelse) => {
                 let kontortilknytning_row =
@@ -186,9 +252,17 @@ mod tests {
             kontortilknytning::select_by_id(&mut tx, &oppfolgingsperiode_id)
                 .await
                 .expect("Kunne ikke hente kontortilknytning");
+        let historikk = hent_historikk(&mut tx, context).await;
         tx.commit().await.expect("Kunne ikke commit transaksjon");
 
         assert!(optional_kontortilknytning_row.is_some());
+        assert_eq!(
+            historikk,
+            vec![
+                ("STARTET".to_string(), context.kontor_id_1.to_string()),
+                ("ENDRET".to_string(), kontor_id_2.to_string()),
+            ]
+        );
         match oppfolgingsperiode {
             Oppfolgingsperiode::Startet(_) => {
                 panic!("Uventet hendelsetype")
@@ -235,9 +309,31 @@ mod tests {
             kontortilknytning::select_by_id(&mut tx, &oppfolgingsperiode_id)
                 .await
                 .expect("Kunne ikke hente oppfolgingsperiode");
+        let historikk = hent_historikk(&mut tx, context).await;
         tx.commit().await.expect("Kunne ikke commit transaksjon");
 
         assert!(optional_kontortilknytning_row.is_none());
+        assert_eq!(
+            historikk,
+            vec![
+                ("STARTET".to_string(), context.kontor_id_1.to_string()),
+                ("ENDRET".to_string(), context.kontor_id_2.to_string()),
+                ("AVSLUTTET".to_string(), context.kontor_id_2.to_string()),
+            ]
+        );
+    }
+
+    async fn hent_historikk(
+        tx: &mut Transaction<'_, Postgres>,
+        context: &TestContext,
+    ) -> Vec<(String, String)> {
+        kontortilknytning_historikk::select_by_aktor_id(tx, context.aktor_id)
+            .await
+            .expect("Kunne ikke hente kontortilknytning-historikk")
+            .into_iter()
+            .filter(|row| row.kontortilknytning_id == context.oppfolgingsperiode_id)
+            .map(|row| (row.endring_type, row.kontor_id))
+            .collect()
     }
 
     static INIT: OnceCell<TestContext> = OnceCell::const_new();
@@ -1,6 +1,7 @@
-use crate::logic::process::PayloadProcessor;
+use crate::logic::process::{Endret, PayloadProcessor};
 use crate::model::dao::opplysninger;
 use crate::model::dao::opplysninger::OpplysningerRow;
+use crate::model::dto::endring::EndringType;
 use crate::model::error::{DaoError, PayloadProcessorError};
 use eksterne_hendelser::opplysninger::Opplysninger;
 use eksterne_hendelser::serde::AvroDeserializer;
@@ -27,7 +28,7 @@ impl PayloadProcessor for OpplysningerProcessor {
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError> {
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
         match message.payload() {
             None => Err(PayloadProcessorError::no_payload_error(message).into()),
             Some(payload) => {
@@ -55,10 +56,16 @@ impl PayloadProcessor for OpplysningerProcessor {
                     Err(DaoError::multiple_rows(message, "opplysninger", count as usize).into())
                 } else if count == 1 {
                     opplysninger::update(tx, &row).await?;
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Opplysninger,
+                        hendelse.periode_id,
+                    )))
                 } else {
                     opplysninger::insert(tx, &row).await?;
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Opplysninger,
+                        hendelse.periode_id,
+                    )))
                 }
             }
         }
@@ -1,10 +1,15 @@
 use crate::config::AppConfig;
-use crate::logic::process::PayloadProcessor;
+use crate::logic::ledighet::{
+    arbeidsledig_fra_etter_bekreftelser, viderefoert_arbeidsledig_fra,
+};
+use crate::logic::process::{Endret, PayloadProcessor};
 use crate::model::dao::arbeidssoeker::ArbeidssoekerRow;
 use crate::model::dao::kartlegging::KartleggingRow;
 use crate::model::dao::periode::PeriodeRow;
 use crate::model::dao::{arbeidssoeker, bekreftelse, kartlegging, periode};
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
 use crate::model::dto::arbeidssoeker::Arbeidssoeker;
+use crate::model::dto::endring::EndringType;
 use crate::model::dto::navn::Navn;
 use crate::model::error::{DaoError, IdentityError, PayloadProcessorError};
 use chrono::{DateTime, Utc};
@@ -96,11 +101,12 @@ impl PeriodeProcessor {
         ))
     }
 
+    /// Henter navn og strengeste adressebeskyttelse fra PDL
     async fn hent_navn<'a>(
         &'a self,
         message: &'a OwnedMessage,
         identitetsnummer: &String,
-    ) -> anyhow::Result<Navn> {
+    ) -> anyhow::Result<(Navn, Option<Adressebeskyttelse>)> {
         let identitetsnummer_struct =
             Identitetsnummer::new(identitetsnummer.clone()).ok_or_else(|| {
                 PayloadProcessorError::processing_error(
@@ -116,19 +122,26 @@ impl PeriodeProcessor {
         let pdl_navn = pdl_navn_response.ok_or_else(|| {
             PayloadProcessorError::processing_error(message, "Fant ingen person i PDL")
         })?;
+        let adressebeskyttelse = Adressebeskyttelse::strengeste(
+            pdl_navn
+                .adressebeskyttelse
+                .iter()
+                .map(|beskyttelse| Adressebeskyttelse::fra_pdl(&beskyttelse.gradering)),
+        );
 
         if pdl_navn.navn.is_empty() {
             tracing::warn!("Fant ingen navn for person i PDL, setter alle navn til null");
-            Ok(Navn::default())
+            Ok((Navn::default(), adressebeskyttelse))
         } else {
             let pdl_navn_entry = pdl_navn.navn.first().ok_or_else(|| {
                 PayloadProcessorError::processing_error(message, "Fant ingen navn for person i PDL")
             })?;
-            Ok(Navn::new(
+            let navn = Navn::new(
                 pdl_navn_entry.fornavn.clone(),
                 pdl_navn_entry.mellomnavn.clone(),
                 pdl_navn_entry.etternavn.clone(),
-            ))
+            );
+            Ok((navn, adressebeskyttelse))
         }
     }
 
@@ -140,17 +153,8 @@ impl PeriodeProcessor {
         // Hent bekreftelser for periode-id
         let bekreftelse_rows = bekreftelse::select_by_periode_id(tx, periode_id).await?;
 
-        let mut arbeidsledighet: Option<DateTime<Utc>> = None;
-        // Loop igjennom bekreftelser og oppsummer ledighet
-        for bekreftelse_row in bekreftelse_rows {
-            if bekreftelse_row.har_jobbet {
-                arbeidsledighet = None
-            } else if !bekreftelse_row.har_jobbet && arbeidsledighet.is_none() {
-                arbeidsledighet = Some(bekreftelse_row.gjelder_fra);
-            }
-        }
-
-        Ok(arbeidsledighet)
+        // Oppsummer ledighet fra bekreftelsene
+        Ok(arbeidsledig_fra_etter_bekreftelser(None, &bekreftelse_rows))
     }
 
     async fn utled_arbeidsledighet_fra_eksisterende_kartlegging<'a>(
@@ -195,31 +199,14 @@ impl PeriodeProcessor {
                 let tidligere_kartlegging_row =
                     kartlegging::select_latest_by_arbeidssoeker_id(tx, &arbeidssoeker_id).await?;
 
-                match tidligere_kartlegging_row {
-                    // Ingen tidligere kartlegging for arbeidssøker-id
-                    None => None,
-                    // Har en tidligere kartlegging for arbeidssøker-id, så hent eventuell ledighet fra den
-                    Some(kartlegging_row) => {
-                        match kartlegging_row.arbeidsledig_fra {
-                            // Ingen ledighet satt for tidligere kartlegging
-                            None => None,
-                            Some(arbeidsledig_fra) => match kartlegging_row.arbeidssoeker_til {
-                                // Tidligere periode er fortsatt aktiv. Dette er en feil!
-                                None => None,
-                                Some(arbeidssoeker_til) => {
-                                    let periode_gap = periode_startet.clone() - arbeidssoeker_til;
-
-                                    // Om det er mindre enn 14 dager siden tidligere periode ble avsluttet, bruk ledighet fra den
-                                    if periode_gap.num_days() < periode_gap_grense {
-                                        Some(arbeidsledig_fra)
-                                    } else {
-                                        None
-                                    }
-                                }
-                            },
-                        }
-                    }
-                }
+                // Har en tidligere kartlegging for arbeidssøker-id, så hent eventuell ledighet fra den
+                tidligere_kartlegging_row.and_then(|kartlegging_row| {
+                    viderefoert_arbeidsledig_fra(
+                        &kartlegging_row,
+                        periode_startet,
+                        periode_gap_grense,
+                    )
+                })
             }
         };
 
@@ -232,7 +219,7 @@ impl PayloadProcessor for PeriodeProcessor {
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError> {
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
         match message.payload() {
             None => Err(PayloadProcessorError::no_payload_error(message).into()),
             Some(payload) => {
@@ -313,7 +300,7 @@ impl PayloadProcessor for PeriodeProcessor {
                         )
                         .await?;
 
-                        Ok(())
+                        Ok(Some(Endret::Periode(EndringType::Periode, hendelse.id)))
                     } else {
                         // Kartlegging finnes ikke fra før
 
@@ -341,13 +328,13 @@ impl PayloadProcessor for PeriodeProcessor {
                         );
                         kartlegging::insert(tx, &kartlegging_row).await?;
 
-                        Ok(())
+                        Ok(Some(Endret::Periode(EndringType::Periode, hendelse.id)))
                     }
                 } else {
                     // Arbeidssøker finnes ikke fra før
 
-                    // Hent navn fra PDL
-                    let navn = self
+                    // Hent navn og adressebeskyttelse fra PDL
+                    let (navn, adressebeskyttelse) = self
                         .hent_navn(message, &arbeidssoeker.identitetsnummer)
                         .await?;
 
@@ -359,6 +346,7 @@ impl PayloadProcessor for PeriodeProcessor {
                         navn.fornavn.clone(),
                         navn.mellomnavn.clone(),
                         navn.etternavn.clone(),
+                        adressebeskyttelse.map(|gradering| gradering.as_ref().to_string()),
                     );
                     arbeidssoeker::insert(tx, &arbeidssoeker_row).await?;
 
@@ -381,7 +369,7 @@ impl PayloadProcessor for PeriodeProcessor {
                     );
                     kartlegging::insert(tx, &kartlegging_row).await?;
 
-                    Ok(())
+                    Ok(Some(Endret::Periode(EndringType::Periode, hendelse.id)))
                 }
             }
         }
@@ -751,6 +739,7 @@ mod tests {
             fornavn: None,
             mellomnavn: None,
             etternavn: None,
+            adressebeskyttelse: None,
         };
 
         let tidligere_kartlegging_row = KartleggingRow {
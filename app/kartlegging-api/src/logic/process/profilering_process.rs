@@ -1,6 +1,7 @@
-use crate::logic::process::PayloadProcessor;
+use crate::logic::process::{Endret, PayloadProcessor};
 use crate::model::dao::profilering;
 use crate::model::dao::profilering::ProfileringRow;
+use crate::model::dto::endring::EndringType;
 use crate::model::error::{DaoError, PayloadProcessorError};
 use eksterne_hendelser::profilering::Profilering;
 use eksterne_hendelser::serde::AvroDeserializer;
@@ -27,7 +28,7 @@ impl PayloadProcessor for ProfileringProcessor {
         &'a self,
         tx: &mut Transaction<'_, Postgres>,
         message: &'a OwnedMessage,
-    ) -> anyhow::Result<(), ProcessorError> {
+    ) -> anyhow::Result<Option<Endret>, ProcessorError> {
         match message.payload() {
             None => Err(PayloadProcessorError::no_payload_error(message).into()),
             Some(payload) => {
@@ -51,10 +52,16 @@ impl PayloadProcessor for ProfileringProcessor {
                     Err(DaoError::multiple_rows(message, "profileringer", count as usize).into())
                 } else if count == 1 {
                     profilering::update(tx, &row).await?;
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Profilering,
+                        hendelse.periode_id,
+                    )))
                 } else {
                     profilering::insert(tx, &row).await?;
-                    Ok(())
+                    Ok(Some(Endret::Periode(
+                        EndringType::Profilering,
+                        hendelse.periode_id,
+                    )))
                 }
             }
         }
@@ -1,13 +1,15 @@
 use crate::logic::query::{kontortilknytning_query, ledighetsperioder_query};
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
 use crate::model::dao::arbeidssoeker;
-use crate::model::dao::arbeidssoeker::ArbeidssoekerRow;
+use crate::model::dao::arbeidssoeker::{ArbeidssoekerRow, KontortilknytningFilter};
+use crate::model::cursor::Cursor;
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
 use crate::model::dto::arbeidssoeker::Arbeidssoeker;
 use crate::model::dto::kontortilknytning::KontorType;
 use crate::model::dto::request::{
-    IdentitetsnummerQueryRequest, PagingRequest, TilknyttetKontorQueryRequest,
+    IdentitetsnummerQueryRequest, PagingRequest, PeriodeStatus, TilknyttetKontorQueryRequest,
 };
 use crate::model::dto::response::{KartleggingResponse, PagingResponse};
-use crate::model::sort::SortOrder;
 use chrono::NaiveDate;
 use sqlx::{Postgres, Transaction};
 
@@ -15,13 +17,10 @@ use sqlx::{Postgres, Transaction};
 pub async fn finn_for_identitetsnummer_query_request(
     tx: &mut Transaction<'_, Postgres>,
     request: &IdentitetsnummerQueryRequest,
+    tilgang: &AdressebeskyttelseTilgang,
 ) -> anyhow::Result<KartleggingResponse> {
     let identitetsnummer = &request.identitetsnummer;
-    let paging = request.paging.clone().unwrap_or_else(|| PagingRequest {
-        page: 1,
-        page_size: 1000,
-        sort_order: SortOrder::Ascending,
-    });
+    let paging = request.paging.clone().unwrap_or_default();
     tracing::info!(
         "Finner arbeidssøkere for identitetsnummer, offset {}, limit {}, sort_order {}",
         paging.offset(),
@@ -30,13 +29,14 @@ pub async fn finn_for_identitetsnummer_query_request(
     );
     let arbeidssoeker_rows =
         arbeidssoeker::select_by_identitetsnummer(tx, &identitetsnummer).await?;
-    let arbeidssoekere = map_rows(tx, &paging, &arbeidssoeker_rows).await?;
+    let arbeidssoekere = map_rows(tx, &paging, &arbeidssoeker_rows, tilgang).await?;
     let paging_response = PagingResponse {
         page: paging.page,
         page_size: paging.page_size,
         hit_size: arbeidssoekere.len() as i32,
-        total_count: arbeidssoeker_rows.len() as i64,
+        total_count: Some(arbeidssoeker_rows.len() as i64),
         sort_order: paging.sort_order,
+        next_cursor: None,
     };
     Ok(KartleggingResponse {
         arbeidssoekere,
@@ -48,80 +48,140 @@ pub async fn finn_for_identitetsnummer_query_request(
 pub async fn finn_for_kontortilknytning_query_request(
     tx: &mut Transaction<'_, Postgres>,
     request: &TilknyttetKontorQueryRequest,
+    tilgang: &AdressebeskyttelseTilgang,
 ) -> anyhow::Result<KartleggingResponse> {
-    let kontor_id = &request.kontor_id;
-    let kontor_typer = request
-        .kontor_type
-        .clone()
-        .map(|kt| vec![kt])
-        .unwrap_or(vec![
-            KontorType::Arbeidsoppfolging,
-            KontorType::Arena,
-            KontorType::GeografiskTilknytning,
-        ])
-        .iter()
-        .map(|kt| kt.as_ref().to_string())
-        .collect::<Vec<String>>();
-    let ledig_siden = request
-        .ledig_siden
-        .unwrap_or(NaiveDate::from_epoch_days(0).unwrap());
-    let paging = request.paging.clone().unwrap_or_else(|| PagingRequest {
-        page: 1,
-        page_size: 1000,
-        sort_order: SortOrder::Ascending,
-    });
-
-    let total_count =
-        arbeidssoeker::count_by_kontortilknytning(tx, &kontor_id, &kontor_typer, &ledig_siden)
-            .await?;
-    let kontor_join = kontor_typer
-        .iter()
-        .map(|k| k.to_string())
-        .collect::<Vec<String>>()
-        .join(", ");
+    let paging = request.paging.clone().unwrap_or_default();
+    let (arbeidssoeker_rows, paging_response) =
+        finn_side_for_kontortilknytning(tx, request, tilgang).await?;
+    let arbeidssoekere = map_rows(tx, &paging, &arbeidssoeker_rows, tilgang).await?;
+    Ok(KartleggingResponse {
+        arbeidssoekere,
+        paging: paging_response,
+    })
+}
+
+/// Henter én side med arbeidssøkere tilknyttet kontoret, uten ledighetsperioder og
+/// kontortilknytninger. Personer med adressebeskyttelse kaller ikke har tilgang til er ikke med.
+#[tracing::instrument(skip(tx, request))]
+pub(crate) async fn finn_side_for_kontortilknytning(
+    tx: &mut Transaction<'_, Postgres>,
+    request: &TilknyttetKontorQueryRequest,
+    tilgang: &AdressebeskyttelseTilgang,
+) -> anyhow::Result<(Vec<ArbeidssoekerRow>, PagingResponse)> {
+    let filter = map_kontortilknytning_filter(request, tilgang);
+    let paging = request.paging.clone().unwrap_or_default();
+    let sort_field = paging.sort_field.unwrap_or_default();
+    let cursor = paging.decoded_cursor();
+    // Med cursor starter siden rett etter cursor, uavhengig av page
+    let offset = if cursor.is_some() { 0 } else { paging.offset() };
+
+    let total_count = if paging.include_total_count() {
+        Some(arbeidssoeker::count_by_kontortilknytning(tx, &filter).await?)
+    } else {
+        None
+    };
     tracing::info!(
-        "Finner arbeidssøkere for tilknyttet kontor av typer {}, offset {}, limit {}, sort_order {}",
-        kontor_join,
-        paging.offset(),
+        "Finner arbeidssøkere for tilknyttet kontor av typer {}, cursor {}, offset {}, limit {}, sort {} {}",
+        filter.kontor_typer.join(", "),
+        cursor.is_some(),
+        offset,
         paging.limit(),
+        sort_field.as_ref(),
         paging.sort_order.to_string()
     );
-    let arbeidssoeker_rows = arbeidssoeker::select_by_kontortilknytning(
+    // Henter én rad ekstra for å vite om det finnes en neste side
+    let mut rows = arbeidssoeker::select_by_kontortilknytning(
         tx,
-        &kontor_id,
-        &kontor_typer,
-        &ledig_siden,
-        paging.offset(),
-        paging.limit(),
+        &filter,
+        cursor.as_ref(),
+        offset,
+        paging.limit() + 1,
+        &sort_field,
         &paging.sort_order,
     )
     .await?;
-    let arbeidssoekere = map_rows(tx, &paging, &arbeidssoeker_rows).await?;
+    let next_cursor = if rows.len() > paging.limit() as usize {
+        rows.truncate(paging.limit() as usize);
+        rows.last().map(|row| {
+            Cursor {
+                sort_field,
+                sort_order: paging.sort_order,
+                sort_verdi: row.sort_verdi,
+                periode_id: row.periode_id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+    let arbeidssoeker_rows = rows
+        .into_iter()
+        .map(|row| row.arbeidssoeker)
+        .collect::<Vec<ArbeidssoekerRow>>();
     let paging_response = PagingResponse {
         page: paging.page,
         page_size: paging.page_size,
-        hit_size: arbeidssoekere.len() as i32,
+        hit_size: arbeidssoeker_rows.len() as i32,
         total_count,
         sort_order: paging.sort_order,
+        next_cursor,
     };
-    Ok(KartleggingResponse {
-        arbeidssoekere,
-        paging: paging_response,
-    })
+    Ok((arbeidssoeker_rows, paging_response))
+}
+
+pub(crate) fn map_kontortilknytning_filter(
+    request: &TilknyttetKontorQueryRequest,
+    tilgang: &AdressebeskyttelseTilgang,
+) -> KontortilknytningFilter {
+    let kontor_typer = request
+        .kontor_type
+        .map(|kt| vec![kt])
+        .unwrap_or(vec![
+            KontorType::Arbeidsoppfolging,
+            KontorType::Arena,
+            KontorType::GeografiskTilknytning,
+        ])
+        .iter()
+        .map(|kt| kt.as_ref().to_string())
+        .collect::<Vec<String>>();
+    KontortilknytningFilter {
+        kontor_id: request.kontor_id.clone(),
+        kontor_typer,
+        ledig_siden: request
+            .ledig_siden
+            .unwrap_or(NaiveDate::from_epoch_days(0).unwrap()),
+        ledig_siden_til: request.ledig_siden_til,
+        periode_aktiv: request
+            .periode_status
+            .map(|status| status == PeriodeStatus::Aktiv),
+        profilert_til: request.profilert_til.as_ref().map(|profilert_til| {
+            profilert_til
+                .iter()
+                .map(|p| p.as_ref().to_string())
+                .collect()
+        }),
+        egenvurdering_avvik: request.egenvurdering_avvik,
+        synlige_graderinger: tilgang.synlige_graderinger(),
+    }
 }
 
 async fn map_rows(
     tx: &mut Transaction<'_, Postgres>,
     paging: &PagingRequest,
     arbeidssoeker_rows: &Vec<ArbeidssoekerRow>,
+    tilgang: &AdressebeskyttelseTilgang,
 ) -> anyhow::Result<Vec<Arbeidssoeker>> {
     let mut arbeidssoekere = Vec::new();
     for row in arbeidssoeker_rows {
+        let skjermet = tilgang.skal_skjermes(row.adressebeskyttelse.as_deref());
         let ledighetsperioder =
             ledighetsperioder_query::finn_for_arbeidssoeker_id(tx, row.id, paging.clone()).await?;
-        let kontortilknytninger =
-            kontortilknytning_query::finn_for_aktor_id(tx, &*row.aktor_id).await?;
-        arbeidssoekere.push(Arbeidssoeker::new(
+        let kontortilknytninger = if skjermet {
+            Vec::new()
+        } else {
+            kontortilknytning_query::finn_for_aktor_id(tx, &*row.aktor_id).await?
+        };
+        let mut arbeidssoeker = Arbeidssoeker::new(
             row.id.clone(),
             row.aktor_id.clone(),
             row.identitetsnummer.clone(),
@@ -130,10 +190,412 @@ async fn map_rows(
             row.etternavn.clone(),
             ledighetsperioder,
             kontortilknytninger,
-        ))
+        );
+        arbeidssoeker.adressebeskyttelse = row
+            .adressebeskyttelse
+            .as_deref()
+            .map(Adressebeskyttelse::fra_lagret);
+        if skjermet {
+            arbeidssoeker.skjerm();
+        }
+        arbeidssoekere.push(arbeidssoeker);
     }
     Ok(arbeidssoekere)
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::config::read_app_config;
+    use crate::model::dao::egenvurdering::EgenvurderingRow;
+    use crate::model::dao::kartlegging::KartleggingRow;
+    use crate::model::dao::kontortilknytning::KontortilknytningRow;
+    use crate::model::dao::profilering::ProfileringRow;
+    use crate::model::dao::{egenvurdering, endring, kartlegging, kontortilknytning, profilering};
+    use crate::model::dto::profilering::ProfilertTil;
+    use crate::model::sort::{SortField, SortOrder};
+    use chrono::{Duration, Utc};
+    use oauth2::principal::{NavAnsatt, Principal};
+    use postgres_testcontainer::postgres::setup_postgres_container;
+    use types::nav_ident::NavIdent;
+    use uuid::Uuid;
+
+    const KONTOR_ID: &str = "1337";
+
+    #[tokio::test]
+    async fn test_kontorsoek_utelater_adressebeskyttede_uten_tilgang() {
+        let postgres_guard = setup_postgres_container(5432)
+            .await
+            .expect("Failed to start Postgres container");
+        sqlx::migrate!("./migrations")
+            .run(&postgres_guard.pg_pool)
+            .await
+            .expect("Failed to run migrations");
+        let mut tx = postgres_guard.pg_pool.begin().await.unwrap();
+        for (id, adressebeskyttelse) in [
+            (1001, None),
+            (1002, Some("FORTROLIG")),
+            (1003, Some("STRENGT_FORTROLIG")),
+        ] {
+            insert_arbeidssoeker_tilknyttet_kontor(&mut tx, id, adressebeskyttelse).await;
+        }
+        // Endringsfeeden leser bare endringer fra committede transaksjoner
+        tx.commit().await.unwrap();
+        let mut tx = postgres_guard.pg_pool.begin().await.unwrap();
+
+        let mut app_config = read_app_config().expect("Kunne ikke lese app_config.toml");
+        app_config.fortrolig_adresse_grupper = vec!["fortrolig".to_string()];
+        let uten_tilgang = AdressebeskyttelseTilgang::default();
+        let fortrolig_tilgang =
+            AdressebeskyttelseTilgang::for_principal(&nav_ansatt(vec!["fortrolig"]), &app_config);
+        let request = TilknyttetKontorQueryRequest {
+            kontor_id: KONTOR_ID.to_string(),
+            kontor_type: None,
+            ledig_siden: None,
+            ledig_siden_til: None,
+            periode_status: None,
+            profilert_til: None,
+            egenvurdering_avvik: None,
+            paging: None,
+        };
+
+        let (rows, paging) = finn_side_for_kontortilknytning(&mut tx, &request, &uten_tilgang)
+            .await
+            .unwrap();
+        assert_eq!(ids(&rows), vec![1001]);
+        assert_eq!(paging.total_count, Some(1));
+
+        let (rows, paging) = finn_side_for_kontortilknytning(&mut tx, &request, &fortrolig_tilgang)
+            .await
+            .unwrap();
+        assert_eq!(ids(&rows), vec![1001, 1002]);
+        assert_eq!(paging.total_count, Some(2));
+
+        let eksport = arbeidssoeker::select_eksport_by_kontortilknytning(
+            &mut tx,
+            &map_kontortilknytning_filter(&request, &uten_tilgang),
+            None,
+            10,
+            &SortField::default(),
+            &SortOrder::Ascending,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            eksport
+                .iter()
+                .map(|row| row.identitetsnummer.as_str())
+                .collect::<Vec<_>>(),
+            vec!["01017001001"]
+        );
+
+        let endringer = endring::select_by_kontor_id_after(
+            &mut tx,
+            KONTOR_ID,
+            &uten_tilgang.synlige_graderinger(),
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            endringer
+                .iter()
+                .map(|row| row.arbeidssoeker_id)
+                .collect::<Vec<_>>(),
+            vec![1001]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_kontorsoek_filtre_og_cursor() {
+        let postgres_guard = setup_postgres_container(5432)
+            .await
+            .expect("Failed to start Postgres container");
+        sqlx::migrate!("./migrations")
+            .run(&postgres_guard.pg_pool)
+            .await
+            .expect("Failed to run migrations");
+        let mut tx = postgres_guard.pg_pool.begin().await.unwrap();
+        let mut periode_ider = Vec::new();
+        for id in 1001..=1004 {
+            periode_ider.push(insert_arbeidssoeker_tilknyttet_kontor(&mut tx, id, None).await);
+        }
+        sqlx::query(
+            "UPDATE kartlegginger SET arbeidssoeker_til = $1 WHERE arbeidssoeker_id = 1002",
+        )
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        let naa = Utc::now();
+        // Siste profilering gjelder, så 1002 er ANTATT_GODE_MULIGHETER
+        for (periode_id, profilert_til, tidspunkt) in [
+            (periode_ider[0], ProfilertTil::AntattGodeMuligheter, naa),
+            (
+                periode_ider[1],
+                ProfilertTil::OppgittHindringer,
+                naa - Duration::days(1),
+            ),
+            (periode_ider[1], ProfilertTil::AntattGodeMuligheter, naa),
+            (periode_ider[2], ProfilertTil::OppgittHindringer, naa),
+        ] {
+            profilering::insert(
+                &mut tx,
+                &ProfileringRow::new(
+                    Uuid::new_v4(),
+                    periode_id,
+                    Uuid::new_v4(),
+                    profilert_til.as_ref().to_string(),
+                    tidspunkt,
+                ),
+            )
+            .await
+            .unwrap();
+        }
+        for (periode_id, egenvurdert_til) in [
+            (periode_ider[0], ProfilertTil::AntattBehovForVeiledning),
+            (periode_ider[1], ProfilertTil::AntattGodeMuligheter),
+        ] {
+            egenvurdering::insert(
+                &mut tx,
+                &EgenvurderingRow::new(
+                    Uuid::new_v4(),
+                    periode_id,
+                    Uuid::new_v4(),
+                    ProfilertTil::AntattGodeMuligheter.as_ref().to_string(),
+                    egenvurdert_til.as_ref().to_string(),
+                    naa,
+                ),
+            )
+            .await
+            .unwrap();
+        }
+        let alle = TilknyttetKontorQueryRequest {
+            kontor_id: KONTOR_ID.to_string(),
+            kontor_type: None,
+            ledig_siden: None,
+            ledig_siden_til: None,
+            periode_status: None,
+            profilert_til: None,
+            egenvurdering_avvik: None,
+            paging: None,
+        };
+
+        let aktive = TilknyttetKontorQueryRequest {
+            periode_status: Some(PeriodeStatus::Aktiv),
+            ..alle.clone()
+        };
+        assert_eq!(finn_ids(&mut tx, &aktive).await, vec![1001, 1003, 1004]);
+        let avsluttede = TilknyttetKontorQueryRequest {
+            periode_status: Some(PeriodeStatus::Avsluttet),
+            ..alle.clone()
+        };
+        assert_eq!(finn_ids(&mut tx, &avsluttede).await, vec![1002]);
+
+        let antatt_gode_muligheter = TilknyttetKontorQueryRequest {
+            profilert_til: Some(vec![ProfilertTil::AntattGodeMuligheter]),
+            ..alle.clone()
+        };
+        assert_eq!(
+            finn_ids(&mut tx, &antatt_gode_muligheter).await,
+            vec![1001, 1002]
+        );
+
+        let med_avvik = TilknyttetKontorQueryRequest {
+            egenvurdering_avvik: Some(true),
+            ..alle.clone()
+        };
+        assert_eq!(finn_ids(&mut tx, &med_avvik).await, vec![1001]);
+        let uten_avvik = TilknyttetKontorQueryRequest {
+            egenvurdering_avvik: Some(false),
+            ..alle.clone()
+        };
+        assert_eq!(finn_ids(&mut tx, &uten_avvik).await, vec![1002]);
+
+        let ledig_intervall = TilknyttetKontorQueryRequest {
+            ledig_siden: Some((naa - Duration::days(13)).date_naive()),
+            ledig_siden_til: Some((naa - Duration::days(11)).date_naive()),
+            ..alle.clone()
+        };
+        assert_eq!(finn_ids(&mut tx, &ledig_intervall).await, vec![1002, 1003]);
+
+        let mut paging = PagingRequest {
+            page_size: 2,
+            sort_order: SortOrder::Descending,
+            sort_field: Some(SortField::ArbeidsledigFra),
+            ..Default::default()
+        };
+        let foerste_side = TilknyttetKontorQueryRequest {
+            paging: Some(paging.clone()),
+            ..alle.clone()
+        };
+        let (rows, paging_response) = finn_side_for_kontortilknytning(
+            &mut tx,
+            &foerste_side,
+            &AdressebeskyttelseTilgang::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            rows.iter().map(|row| row.id).collect::<Vec<_>>(),
+            vec![1001, 1002]
+        );
+        assert_eq!(paging_response.total_count, Some(4));
+        let next_cursor = paging_response.next_cursor.expect("Mangler cursor");
+
+        // En ny arbeidssøker foran cursor forskyver ikke neste side
+        insert_arbeidssoeker_tilknyttet_kontor(&mut tx, 1000, None).await;
+        paging.cursor = Some(next_cursor);
+        let andre_side = TilknyttetKontorQueryRequest {
+            paging: Some(paging),
+            ..alle
+        };
+        assert!(andre_side.validate("/test").is_ok());
+        let (rows, paging_response) = finn_side_for_kontortilknytning(
+            &mut tx,
+            &andre_side,
+            &AdressebeskyttelseTilgang::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            rows.iter().map(|row| row.id).collect::<Vec<_>>(),
+            vec![1003, 1004]
+        );
+        assert_eq!(paging_response.total_count, None);
+        assert_eq!(paging_response.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_endringer_fra_transaksjon_som_committer_sent_hoppes_ikke_over() {
+        let postgres_guard = setup_postgres_container(5432)
+            .await
+            .expect("Failed to start Postgres container");
+        let pg_pool = &postgres_guard.pg_pool;
+        sqlx::migrate!("./migrations")
+            .run(pg_pool)
+            .await
+            .expect("Failed to run migrations");
+        let mut tx = pg_pool.begin().await.unwrap();
+        for id in [1001, 1002] {
+            insert_arbeidssoeker_tilknyttet_kontor(&mut tx, id, None).await;
+        }
+        tx.commit().await.unwrap();
+        let mut tx = pg_pool.begin().await.unwrap();
+        let cursor = endring::select_max_id(&mut tx).await.unwrap();
+        tx.commit().await.unwrap();
+        let synlige_graderinger = AdressebeskyttelseTilgang::default().synlige_graderinger();
+        let endringer_etter_cursor = || async {
+            let mut tx = pg_pool.begin().await.unwrap();
+            let rows = endring::select_by_kontor_id_after(
+                &mut tx,
+                KONTOR_ID,
+                &synlige_graderinger,
+                cursor,
+                10,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            rows.iter()
+                .map(|row| row.arbeidssoeker_id)
+                .collect::<Vec<_>>()
+        };
+
+        // Første transaksjon får lavest id, men committer etter den andre
+        let mut sen_tx = pg_pool.begin().await.unwrap();
+        endring::insert_for_arbeidssoeker_id(&mut sen_tx, &1001, "BEKREFTELSE", &Utc::now())
+            .await
+            .unwrap();
+        let mut tx = pg_pool.begin().await.unwrap();
+        endring::insert_for_arbeidssoeker_id(&mut tx, &1002, "BEKREFTELSE", &Utc::now())
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(endringer_etter_cursor().await, Vec::<i64>::new());
+        let mut tx = pg_pool.begin().await.unwrap();
+        assert_eq!(endring::select_max_id(&mut tx).await.unwrap(), cursor);
+        tx.commit().await.unwrap();
+
+        sen_tx.commit().await.unwrap();
+        assert_eq!(endringer_etter_cursor().await, vec![1001, 1002]);
+    }
+
+    async fn finn_ids(
+        tx: &mut Transaction<'_, Postgres>,
+        request: &TilknyttetKontorQueryRequest,
+    ) -> Vec<i64> {
+        let (rows, _) =
+            finn_side_for_kontortilknytning(tx, request, &AdressebeskyttelseTilgang::default())
+                .await
+                .unwrap();
+        ids(&rows)
+    }
+
+    async fn insert_arbeidssoeker_tilknyttet_kontor(
+        tx: &mut Transaction<'_, Postgres>,
+        id: i64,
+        adressebeskyttelse: Option<&str>,
+    ) -> Uuid {
+        let periode_id = Uuid::new_v4();
+        let aktor_id = format!("20017010{id}");
+        let identitetsnummer = format!("0101700{id}");
+        let ledig_fra = Utc::now() - Duration::days(id - 990);
+        arbeidssoeker::insert(
+            tx,
+            &ArbeidssoekerRow::new(
+                id,
+                aktor_id.clone(),
+                identitetsnummer.clone(),
+                Some("Kari".to_string()),
+                None,
+                Some("Nordmann".to_string()),
+                adressebeskyttelse.map(String::from),
+            ),
+        )
+        .await
+        .unwrap();
+        kartlegging::insert(
+            tx,
+            &KartleggingRow::new(periode_id, id, ledig_fra, None, Some(ledig_fra)),
+        )
+        .await
+        .unwrap();
+        kontortilknytning::insert(
+            tx,
+            &KontortilknytningRow::new(
+                Uuid::new_v4(),
+                aktor_id,
+                identitetsnummer,
+                KONTOR_ID.to_string(),
+                "NAV Test".to_string(),
+                KontorType::Arbeidsoppfolging.as_ref().to_string(),
+                ledig_fra,
+            ),
+        )
+        .await
+        .unwrap();
+        endring::insert_for_arbeidssoeker_id(tx, &id, "BEKREFTELSE", &Utc::now())
+            .await
+            .unwrap();
+        periode_id
+    }
+
+    fn ids(rows: &[ArbeidssoekerRow]) -> Vec<i64> {
+        let mut ids = rows.iter().map(|row| row.id).collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    fn nav_ansatt(groups: Vec<&str>) -> Principal {
+        Principal::NavAnsatt(NavAnsatt {
+            oid: "oid".to_string(),
+            ident: NavIdent::new("A123456".to_string()).unwrap(),
+            name: None,
+            roles: vec![],
+            groups: groups.into_iter().map(String::from).collect(),
+        })
+    }
+}
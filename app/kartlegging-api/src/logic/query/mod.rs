@@ -2,3 +2,4 @@ pub(crate) mod arbeidssoeker_query;
 pub(crate) mod kontortilknytning_query;
 pub(crate) mod ledighetsperioder_query;
 pub(crate) mod statistics_query;
+pub(crate) mod tidslinje_query;
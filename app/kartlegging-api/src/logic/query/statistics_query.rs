@@ -1,22 +1,175 @@
-use crate::model::dao::kartlegging;
-use crate::model::dto::response::StatisticsResponse;
+use crate::model::dao::statistikk;
+use crate::model::dao::statistikk::{StatistikkFilter, StatistikkSnapshotRow};
+use crate::model::dto::request::{StatisticsRequest, StatisticsTidsserieRequest};
+use crate::model::dto::response::{
+    LedigOver, StatisticsPunkt, StatisticsResponse, StatisticsTidsserieResponse,
+};
+use chrono::NaiveDate;
 use sqlx::{Postgres, Transaction};
 
-#[tracing::instrument(skip(tx))]
-pub async fn finn(tx: &mut Transaction<'_, Postgres>) -> anyhow::Result<StatisticsResponse> {
+#[tracing::instrument(skip(tx, request))]
+pub async fn finn(
+    tx: &mut Transaction<'_, Postgres>,
+    request: &StatisticsRequest,
+    standard_grenser: &[i32],
+) -> anyhow::Result<StatisticsResponse> {
     tracing::info!("Finner statistikk for arbeidssøkere",);
-    let rows = kartlegging::count_metrics(tx).await?;
+    let filter = map_filter(request);
+    let grenser = request
+        .grenser()
+        .unwrap_or_else(|| standard_grenser.to_vec());
+    let row = statistikk::count_by_filter(tx, &filter, &grenser).await?;
     Ok(StatisticsResponse {
-        total: rows.total,
-        is_null: rows.is_null,
-        is_not_null: rows.is_not_null,
-        over_30_days: rows.over_30_days,
-        over_60_days: rows.over_60_days,
-        over_90_days: rows.over_90_days,
-        over_180_days: rows.over_180_days,
-        over_365_days: rows.over_365_days,
+        total: row.total,
+        is_null: row.is_null,
+        is_not_null: row.is_not_null,
+        ledig_over: grenser
+            .iter()
+            .zip(row.over_grenser)
+            .map(|(dager, antall)| LedigOver {
+                dager: *dager,
+                antall,
+            })
+            .collect(),
     })
 }
 
+#[tracing::instrument(skip(tx, request))]
+pub async fn finn_tidsserie(
+    tx: &mut Transaction<'_, Postgres>,
+    request: &StatisticsTidsserieRequest,
+    standard_grenser: &[i32],
+    idag: NaiveDate,
+) -> anyhow::Result<StatisticsTidsserieResponse> {
+    let (fra, til) = request.periode(idag);
+    tracing::info!("Finner tidsserie for statistikk fra {} til {}", fra, til);
+    let filter = map_filter(&request.filter);
+    let grenser = request
+        .filter
+        .grenser()
+        .unwrap_or_else(|| standard_grenser.to_vec());
+    let rows = statistikk::select_snapshots(tx, &filter, fra, til).await?;
+    Ok(StatisticsTidsserieResponse {
+        tidsserie: til_tidsserie(&rows, &grenser),
+    })
+}
+
+fn map_filter(request: &StatisticsRequest) -> StatistikkFilter {
+    StatistikkFilter {
+        kontor_id: request.kontor_id.clone(),
+        kontor_type: request
+            .kontor_type
+            .as_ref()
+            .map(|kontor_type| kontor_type.as_ref().to_string()),
+        profilert_til: request.profilert_til().map(|profilert_til| {
+            profilert_til
+                .iter()
+                .map(|p| p.as_ref().to_string())
+                .collect()
+        }),
+    }
+}
+
+/// Summerer snapshot-rader (én per profilering) til ett punkt per dato. Rader må være sortert
+/// på dato.
+pub(crate) fn til_tidsserie(
+    rows: &[StatistikkSnapshotRow],
+    grenser: &[i32],
+) -> Vec<StatisticsPunkt> {
+    let mut tidsserie: Vec<StatisticsPunkt> = Vec::new();
+    for row in rows {
+        let punkt = match tidsserie.last_mut() {
+            Some(punkt) if punkt.dato == row.dato => punkt,
+            _ => {
+                tidsserie.push(StatisticsPunkt {
+                    dato: row.dato,
+                    total: 0,
+                    is_null: 0,
+                    is_not_null: 0,
+                    ledig_over: grenser
+                        .iter()
+                        .filter(|grense| row.grenser.contains(grense))
+                        .map(|grense| LedigOver {
+                            dager: *grense,
+                            antall: 0,
+                        })
+                        .collect(),
+                });
+                tidsserie.last_mut().expect("Punkt er nettopp lagt til")
+            }
+        };
+        punkt.total += row.total;
+        punkt.is_null += row.is_null;
+        punkt.is_not_null += row.is_not_null;
+        for ledig_over in punkt.ledig_over.iter_mut() {
+            if let Some(index) = row.grenser.iter().position(|g| *g == ledig_over.dager) {
+                ledig_over.antall += row.over_grenser.get(index).copied().unwrap_or_default();
+            }
+        }
+    }
+    tidsserie
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    fn dato(dag: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, dag).unwrap()
+    }
+
+    fn snapshot(dag: u32, grenser: Vec<i32>) -> StatistikkSnapshotRow {
+        let over_grenser = grenser.iter().map(|g| (*g / 30) as i64).collect();
+        StatistikkSnapshotRow {
+            dato: dato(dag),
+            grenser,
+            total: 10,
+            is_null: 4,
+            is_not_null: 6,
+            over_grenser,
+        }
+    }
+
+    #[test]
+    fn test_til_tidsserie() {
+        let rows = vec![
+            // To profileringer for første dag
+            snapshot(1, vec![30, 90]),
+            snapshot(1, vec![30, 90]),
+            // Grenser endret i config
+            snapshot(2, vec![30, 60]),
+        ];
+        let tidsserie = til_tidsserie(&rows, &[30, 90]);
+        assert_eq!(
+            tidsserie,
+            vec![
+                StatisticsPunkt {
+                    dato: dato(1),
+                    total: 20,
+                    is_null: 8,
+                    is_not_null: 12,
+                    ledig_over: vec![
+                        LedigOver {
+                            dager: 30,
+                            antall: 2,
+                        },
+                        LedigOver {
+                            dager: 90,
+                            antall: 6,
+                        },
+                    ],
+                },
+                StatisticsPunkt {
+                    dato: dato(2),
+                    total: 10,
+                    is_null: 4,
+                    is_not_null: 6,
+                    ledig_over: vec![LedigOver {
+                        dager: 30,
+                        antall: 1,
+                    }],
+                },
+            ]
+        );
+    }
+}
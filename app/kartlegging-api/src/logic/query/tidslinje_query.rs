@@ -0,0 +1,363 @@
+use crate::logic::tilgang::AdressebeskyttelseTilgang;
+use crate::model::dao::arbeidssoeker::ArbeidssoekerRow;
+use crate::model::dao::bekreftelse::BekreftelseRow;
+use crate::model::dao::bekreftelse_paavegneav::BekreftelsePaaVegneAvEndringRow;
+use crate::model::dao::egenvurdering::EgenvurderingRow;
+use crate::model::dao::kontortilknytning_historikk::KontortilknytningHistorikkRow;
+use crate::model::dao::opplysninger::OpplysningerRow;
+use crate::model::dao::periode::PeriodeRow;
+use crate::model::dao::profilering::ProfileringRow;
+use crate::model::dao::{
+    arbeidssoeker, bekreftelse, bekreftelse_paavegneav, egenvurdering, kartlegging,
+    kontortilknytning_historikk, opplysninger, periode, profilering,
+};
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
+use crate::model::dto::bekreftelse::Bekreftelsesloesning;
+use crate::model::dto::kontortilknytning::{KontorType, KontortilknytningEndringType};
+use crate::model::dto::opplysninger::Jobbsituasjon;
+use crate::model::dto::profilering::ProfilertTil;
+use crate::model::dto::request::TidslinjeRequest;
+use crate::model::dto::response::TidslinjeResponse;
+use crate::model::dto::tidslinje::{ArbeidssoekerTidslinje, TidslinjeData, TidslinjeHendelse};
+use sqlx::{Postgres, Transaction};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Lagrede rader for én arbeidssøker som tidslinjen bygges fra
+pub(crate) struct TidslinjeRows {
+    pub perioder: Vec<PeriodeRow>,
+    pub opplysninger: Vec<OpplysningerRow>,
+    pub profileringer: Vec<ProfileringRow>,
+    pub egenvurderinger: Vec<EgenvurderingRow>,
+    pub bekreftelser: Vec<BekreftelseRow>,
+    pub bekreftelse_paavegneav: Vec<BekreftelsePaaVegneAvEndringRow>,
+    pub kontortilknytninger: Vec<KontortilknytningHistorikkRow>,
+}
+
+#[tracing::instrument(skip(tx, request))]
+pub async fn finn_for_identitetsnummer(
+    tx: &mut Transaction<'_, Postgres>,
+    request: &TidslinjeRequest,
+    tilgang: &AdressebeskyttelseTilgang,
+) -> anyhow::Result<TidslinjeResponse> {
+    tracing::info!("Finner tidslinje for identitetsnummer");
+    let arbeidssoeker_rows =
+        arbeidssoeker::select_by_identitetsnummer(tx, &request.identitetsnummer).await?;
+    let mut arbeidssoekere = Vec::new();
+    for row in &arbeidssoeker_rows {
+        let mut rows = hent_rows(tx, row).await?;
+        if tilgang.skal_skjermes(row.adressebeskyttelse.as_deref()) {
+            rows.kontortilknytninger.clear();
+        }
+        arbeidssoekere.push(ArbeidssoekerTidslinje {
+            id: row.id,
+            aktor_id: row.aktor_id.clone(),
+            identitetsnummer: row.identitetsnummer.clone(),
+            adressebeskyttelse: row
+                .adressebeskyttelse
+                .as_deref()
+                .map(Adressebeskyttelse::fra_lagret),
+            hendelser: bygg_tidslinje(rows)?,
+        });
+    }
+    Ok(TidslinjeResponse { arbeidssoekere })
+}
+
+async fn hent_rows(
+    tx: &mut Transaction<'_, Postgres>,
+    arbeidssoeker_row: &ArbeidssoekerRow,
+) -> anyhow::Result<TidslinjeRows> {
+    let periode_ids = kartlegging::select_by_arbeidssoeker_ids(tx, &[arbeidssoeker_row.id])
+        .await?
+        .into_iter()
+        .map(|row| row.periode_id)
+        .collect::<Vec<Uuid>>();
+    Ok(TidslinjeRows {
+        perioder: periode::select_by_ids(tx, &periode_ids).await?,
+        opplysninger: opplysninger::select_by_periode_ids(tx, &periode_ids).await?,
+        profileringer: profilering::select_by_periode_ids(tx, &periode_ids).await?,
+        egenvurderinger: egenvurdering::select_by_periode_ids(tx, &periode_ids).await?,
+        bekreftelser: bekreftelse::select_by_periode_ids(tx, &periode_ids).await?,
+        bekreftelse_paavegneav: bekreftelse_paavegneav::select_endringer_by_periode_ids(
+            tx,
+            &periode_ids,
+        )
+        .await?,
+        kontortilknytninger: kontortilknytning_historikk::select_by_aktor_id(
+            tx,
+            &arbeidssoeker_row.aktor_id,
+        )
+        .await?,
+    })
+}
+
+/// Slår sammen alle rader til én kronologisk tidslinje. Hendelser med samme tidspunkt beholder
+/// rekkefølgen de legges til i, slik at periodestart kommer før alt annet i perioden.
+pub(crate) fn bygg_tidslinje(rows: TidslinjeRows) -> anyhow::Result<Vec<TidslinjeHendelse>> {
+    let mut hendelser = Vec::new();
+    for row in &rows.perioder {
+        hendelser.push(TidslinjeHendelse {
+            tidspunkt: row.startet_tidspunkt,
+            periode_id: Some(row.id),
+            data: TidslinjeData::PeriodeStartet,
+        });
+    }
+    for row in rows.opplysninger {
+        let mut jobbsituasjon = Vec::new();
+        for j in &row.jobbsituasjon {
+            jobbsituasjon.push(Jobbsituasjon::from_str(j.as_str())?);
+        }
+        hendelser.push(TidslinjeHendelse {
+            tidspunkt: row.tidspunkt,
+            periode_id: Some(row.periode_id),
+            data: TidslinjeData::Opplysninger {
+                id: row.id,
+                jobbsituasjon,
+            },
+        });
+    }
+    for row in rows.profileringer {
+        hendelser.push(TidslinjeHendelse {
+            tidspunkt: row.tidspunkt,
+            periode_id: Some(row.periode_id),
+            data: TidslinjeData::Profilering {
+                id: row.id,
+                opplysninger_id: row.opplysninger_id,
+                profilert_til: ProfilertTil::from_str(row.profilert_til.as_str())?,
+            },
+        });
+    }
+    for row in rows.egenvurderinger {
+        hendelser.push(TidslinjeHendelse {
+            tidspunkt: row.tidspunkt,
+            periode_id: Some(row.periode_id),
+            data: TidslinjeData::Egenvurdering {
+                id: row.id,
+                profilering_id: row.profilering_id,
+                profilert_til: ProfilertTil::from_str(row.profilert_til.as_str())?,
+                egenvurdert_til: ProfilertTil::from_str(row.egenvurdert_til.as_str())?,
+            },
+        });
+    }
+    for row in rows.bekreftelser {
+        hendelser.push(TidslinjeHendelse {
+            tidspunkt: row.tidspunkt,
+            periode_id: Some(row.periode_id),
+            data: TidslinjeData::Bekreftelse {
+                id: row.id,
+                gjelder_fra: row.gjelder_fra,
+                gjelder_til: row.gjelder_til,
+                har_jobbet: row.har_jobbet,
+                vil_fortsette: row.vil_fortsette,
+                bekreftelsesloesning: Bekreftelsesloesning::from_str(
+                    row.bekreftelsesloesning.as_str(),
+                )?,
+            },
+        });
+    }
+    for row in rows.bekreftelse_paavegneav {
+        let mut bekreftelsesloesninger = Vec::new();
+        for loesning in &row.bekreftelsesloesninger {
+            bekreftelsesloesninger.push(Bekreftelsesloesning::from_str(loesning.as_str())?);
+        }
+        hendelser.push(TidslinjeHendelse {
+            tidspunkt: row.endret_tidspunkt,
+            periode_id: Some(row.periode_id),
+            data: TidslinjeData::BekreftelsePaaVegneAv {
+                bekreftelsesloesninger,
+            },
+        });
+    }
+    for row in &rows.perioder {
+        if let Some(avsluttet_tidspunkt) = row.avsluttet_tidspunkt {
+            hendelser.push(TidslinjeHendelse {
+                tidspunkt: avsluttet_tidspunkt,
+                periode_id: Some(row.id),
+                data: TidslinjeData::PeriodeAvsluttet,
+            });
+        }
+    }
+    for row in rows.kontortilknytninger {
+        hendelser.push(TidslinjeHendelse {
+            tidspunkt: row.tidspunkt,
+            periode_id: None,
+            data: TidslinjeData::Kontortilknytning {
+                id: row.kontortilknytning_id,
+                endring_type: KontortilknytningEndringType::from_str(row.endring_type.as_str())?,
+                kontor_id: row.kontor_id,
+                kontor_navn: row.kontor_navn,
+                kontor_type: KontorType::from_str(row.kontor_type.as_str())?,
+            },
+        });
+    }
+    // Stabil sortering, se over
+    hendelser.sort_by_key(|hendelse| hendelse.tidspunkt);
+    Ok(hendelser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    fn dato(dag: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, dag, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_bygg_tidslinje() {
+        let periode_id = Uuid::new_v4();
+        let opplysninger_id = Uuid::new_v4();
+        let profilering_id = Uuid::new_v4();
+        let rows = TidslinjeRows {
+            perioder: vec![PeriodeRow::new(
+                periode_id,
+                "01017012345".to_string(),
+                dato(1),
+                Some(dato(20)),
+            )],
+            opplysninger: vec![OpplysningerRow::new(
+                opplysninger_id,
+                periode_id,
+                vec!["HAR_SAGT_OPP".to_string()],
+                dato(1),
+            )],
+            profileringer: vec![ProfileringRow::new(
+                profilering_id,
+                periode_id,
+                opplysninger_id,
+                "ANTATT_GODE_MULIGHETER".to_string(),
+                dato(1) + Duration::minutes(1),
+            )],
+            egenvurderinger: vec![EgenvurderingRow::new(
+                Uuid::new_v4(),
+                periode_id,
+                profilering_id,
+                "ANTATT_GODE_MULIGHETER".to_string(),
+                "OPPGITT_HINDRINGER".to_string(),
+                dato(2),
+            )],
+            bekreftelser: vec![
+                BekreftelseRow::new(
+                    Uuid::new_v4(),
+                    periode_id,
+                    dato(1),
+                    dato(15),
+                    false,
+                    true,
+                    "ARBEIDSSOEKERREGISTERET".to_string(),
+                    dato(15),
+                ),
+                BekreftelseRow::new(
+                    Uuid::new_v4(),
+                    periode_id,
+                    dato(15),
+                    dato(29),
+                    true,
+                    false,
+                    "DAGPENGER".to_string(),
+                    dato(19),
+                ),
+            ],
+            bekreftelse_paavegneav: vec![BekreftelsePaaVegneAvEndringRow {
+                periode_id,
+                bekreftelsesloesninger: vec!["DAGPENGER".to_string()],
+                endret_tidspunkt: dato(10),
+            }],
+            kontortilknytninger: vec![
+                kontortilknytning_historikk_row("STARTET", "1337", dato(3)),
+                kontortilknytning_historikk_row("ENDRET", "1234", dato(12)),
+                kontortilknytning_historikk_row("AVSLUTTET", "1234", dato(21)),
+            ],
+        };
+
+        let hendelser = bygg_tidslinje(rows).unwrap();
+        let typer = hendelser
+            .iter()
+            .map(|hendelse| match hendelse.data {
+                TidslinjeData::PeriodeStartet => "PERIODE_STARTET",
+                TidslinjeData::PeriodeAvsluttet => "PERIODE_AVSLUTTET",
+                TidslinjeData::Opplysninger { .. } => "OPPLYSNINGER",
+                TidslinjeData::Profilering { .. } => "PROFILERING",
+                TidslinjeData::Egenvurdering { .. } => "EGENVURDERING",
+                TidslinjeData::Bekreftelse { .. } => "BEKREFTELSE",
+                TidslinjeData::BekreftelsePaaVegneAv { .. } => "BEKREFTELSE_PAA_VEGNE_AV",
+                TidslinjeData::Kontortilknytning { .. } => "KONTORTILKNYTNING",
+            })
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            typer,
+            vec![
+                "PERIODE_STARTET",
+                "OPPLYSNINGER",
+                "PROFILERING",
+                "EGENVURDERING",
+                "KONTORTILKNYTNING",
+                "BEKREFTELSE_PAA_VEGNE_AV",
+                "KONTORTILKNYTNING",
+                "BEKREFTELSE",
+                "BEKREFTELSE",
+                "PERIODE_AVSLUTTET",
+                "KONTORTILKNYTNING",
+            ]
+        );
+        assert!(hendelser.is_sorted_by_key(|hendelse| hendelse.tidspunkt));
+        assert_eq!(hendelser[4].periode_id, None);
+        let kontorendringer = hendelser
+            .iter()
+            .filter_map(|hendelse| match &hendelse.data {
+                TidslinjeData::Kontortilknytning {
+                    endring_type,
+                    kontor_id,
+                    ..
+                } => Some((*endring_type, kontor_id.as_str())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kontorendringer,
+            vec![
+                (KontortilknytningEndringType::Startet, "1337"),
+                (KontortilknytningEndringType::Endret, "1234"),
+                (KontortilknytningEndringType::Avsluttet, "1234"),
+            ]
+        );
+    }
+
+    fn kontortilknytning_historikk_row(
+        endring_type: &str,
+        kontor_id: &str,
+        tidspunkt: DateTime<Utc>,
+    ) -> KontortilknytningHistorikkRow {
+        KontortilknytningHistorikkRow {
+            kontortilknytning_id: Uuid::nil(),
+            aktor_id: "101701234500".to_string(),
+            identitetsnummer: "01017012345".to_string(),
+            endring_type: endring_type.to_string(),
+            kontor_id: kontor_id.to_string(),
+            kontor_navn: "NAV Bouvetøya".to_string(),
+            kontor_type: "ARBEIDSOPPFOLGING".to_string(),
+            tidspunkt,
+        }
+    }
+
+    #[test]
+    fn test_bygg_tidslinje_med_ukjent_verdi_feiler() {
+        let rows = TidslinjeRows {
+            perioder: vec![],
+            opplysninger: vec![],
+            profileringer: vec![ProfileringRow::new(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                "IKKE_EN_PROFILERING".to_string(),
+                dato(1),
+            )],
+            egenvurderinger: vec![],
+            bekreftelser: vec![],
+            bekreftelse_paavegneav: vec![],
+            kontortilknytninger: vec![],
+        };
+        assert!(bygg_tidslinje(rows).is_err());
+    }
+}
@@ -0,0 +1,381 @@
+use crate::config::AppConfig;
+use crate::logic::ledighet::{arbeidsledig_fra_etter_bekreftelser, viderefoert_arbeidsledig_fra};
+use crate::model::dao::bekreftelse::BekreftelseRow;
+use crate::model::dao::kartlegging::KartleggingRow;
+use crate::model::dao::periode::PeriodeRow;
+use crate::model::dao::{arbeidssoeker, bekreftelse, kartlegging, periode};
+use crate::model::dto::reprojeksjon::{
+    KartleggingAvvik, KartleggingVerdier, ReprojeksjonModus, ReprojeksjonStatus,
+    ReprojeksjonTilstand, MAX_AVVIK_EKSEMPLER,
+};
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+#[derive(thiserror::Error, Debug)]
+#[error("En reprojeksjon kjører allerede")]
+pub struct ReprojeksjonKjoererError;
+
+/// Beregner kartlegginger på nytt fra lagrede perioder og bekreftelser, i batcher med én
+/// transaksjon per batch slik at API og Kafka-konsument kan kjøre som normalt imens.
+/// Bare én reprojeksjon kan kjøre om gangen per pod, og status for siste kjøring beholdes i
+/// minnet til appen restartes.
+pub struct Reprojeksjon {
+    app_config: Arc<AppConfig>,
+    pg_pool: PgPool,
+    cancellation_token: CancellationToken,
+    status: Arc<Mutex<Option<ReprojeksjonStatus>>>,
+}
+
+impl Reprojeksjon {
+    pub fn new(
+        app_config: Arc<AppConfig>,
+        pg_pool: PgPool,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        Self {
+            app_config,
+            pg_pool,
+            cancellation_token,
+            status: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn status(&self) -> Option<ReprojeksjonStatus> {
+        self.status.lock().expect("Status-lås er forgiftet").clone()
+    }
+
+    pub fn start(
+        &self,
+        modus: ReprojeksjonModus,
+        batch_size: i64,
+    ) -> Result<ReprojeksjonStatus, ReprojeksjonKjoererError> {
+        let status = {
+            let mut current = self.status.lock().expect("Status-lås er forgiftet");
+            if let Some(status) = current.as_ref()
+                && status.tilstand == ReprojeksjonTilstand::Kjoerer
+            {
+                return Err(ReprojeksjonKjoererError);
+            }
+            let status = ReprojeksjonStatus::new(modus, batch_size);
+            *current = Some(status.clone());
+            status
+        };
+
+        tracing::info!(?modus, batch_size, "Starter reprojeksjon av kartlegginger");
+        let app_config = self.app_config.clone();
+        let pg_pool = self.pg_pool.clone();
+        let cancellation_token = self.cancellation_token.clone();
+        let shared_status = self.status.clone();
+        tokio::spawn(async move {
+            let result = kjoer(
+                &app_config,
+                &pg_pool,
+                &cancellation_token,
+                &shared_status,
+                modus,
+                batch_size,
+            )
+            .await;
+            oppdater_status(&shared_status, |status| {
+                status.avsluttet = Some(Utc::now());
+                status.tilstand = match &result {
+                    Ok(()) if cancellation_token.is_cancelled() => ReprojeksjonTilstand::Avbrutt,
+                    Ok(()) => ReprojeksjonTilstand::Fullfoert,
+                    Err(e) => {
+                        status.feil = Some(e.to_string());
+                        ReprojeksjonTilstand::Feilet
+                    }
+                };
+                tracing::info!(
+                    tilstand = ?status.tilstand,
+                    behandlet = status.arbeidssoekere_behandlet,
+                    avvik = status.avvik,
+                    oppdatert = status.oppdatert,
+                    "Reprojeksjon av kartlegginger er ferdig"
+                );
+            });
+            if let Err(e) = result {
+                tracing::error!(error = %e, "Reprojeksjon av kartlegginger feilet");
+            }
+        });
+        Ok(status)
+    }
+}
+
+fn oppdater_status(
+    status: &Mutex<Option<ReprojeksjonStatus>>,
+    oppdatering: impl FnOnce(&mut ReprojeksjonStatus),
+) {
+    if let Some(status) = status.lock().expect("Status-lås er forgiftet").as_mut() {
+        oppdatering(status);
+    }
+}
+
+async fn kjoer(
+    app_config: &AppConfig,
+    pg_pool: &PgPool,
+    cancellation_token: &CancellationToken,
+    status: &Mutex<Option<ReprojeksjonStatus>>,
+    modus: ReprojeksjonModus,
+    batch_size: i64,
+) -> anyhow::Result<()> {
+    let mut tx = pg_pool.begin().await?;
+    let totalt = arbeidssoeker::count(&mut tx).await?;
+    tx.commit().await?;
+    oppdater_status(status, |status| status.arbeidssoekere_totalt = totalt);
+
+    let mut etter_id = i64::MIN;
+    while !cancellation_token.is_cancelled() {
+        let mut tx = pg_pool.begin().await?;
+        let arbeidssoeker_ider =
+            arbeidssoeker::select_ids_after(&mut tx, etter_id, batch_size).await?;
+        let Some(siste_id) = arbeidssoeker_ider.last() else {
+            break;
+        };
+        etter_id = *siste_id;
+
+        let resultat = reprojiser_batch(
+            &mut tx,
+            &arbeidssoeker_ider,
+            modus,
+            app_config.periode_gap_grense_for_ledighet,
+        )
+        .await?;
+        tx.commit().await?;
+
+        oppdater_status(status, |status| {
+            status.arbeidssoekere_behandlet += arbeidssoeker_ider.len() as i64;
+            status.kartlegginger_kontrollert += resultat.kontrollert;
+            status.avvik += resultat.avvik.len() as i64;
+            status.oppdatert += resultat.oppdatert;
+            status.endret_underveis += resultat.endret_underveis;
+            let ledig_plass = MAX_AVVIK_EKSEMPLER.saturating_sub(status.avvik_eksempler.len());
+            status
+                .avvik_eksempler
+                .extend(resultat.avvik.into_iter().take(ledig_plass));
+            tracing::info!(
+                "Reprojeksjon: {}/{} arbeidssøkere behandlet, {} avvik",
+                status.arbeidssoekere_behandlet,
+                status.arbeidssoekere_totalt,
+                status.avvik
+            );
+        });
+    }
+    Ok(())
+}
+
+struct BatchResultat {
+    kontrollert: i64,
+    avvik: Vec<KartleggingAvvik>,
+    oppdatert: i64,
+    endret_underveis: i64,
+}
+
+async fn reprojiser_batch(
+    tx: &mut Transaction<'_, Postgres>,
+    arbeidssoeker_ider: &[i64],
+    modus: ReprojeksjonModus,
+    periode_gap_grense: i64,
+) -> anyhow::Result<BatchResultat> {
+    let kartlegging_rows = kartlegging::select_by_arbeidssoeker_ids(tx, arbeidssoeker_ider).await?;
+    let periode_ids = kartlegging_rows
+        .iter()
+        .map(|row| row.periode_id)
+        .collect::<Vec<Uuid>>();
+    let perioder = periode::select_by_ids(tx, &periode_ids)
+        .await?
+        .into_iter()
+        .map(|row| (row.id, row))
+        .collect::<HashMap<Uuid, PeriodeRow>>();
+    let mut bekreftelser: HashMap<Uuid, Vec<BekreftelseRow>> = HashMap::new();
+    for row in bekreftelse::select_by_periode_ids(tx, &periode_ids).await? {
+        bekreftelser.entry(row.periode_id).or_default().push(row);
+    }
+
+    let mut per_arbeidssoeker: HashMap<i64, Vec<KartleggingRow>> = HashMap::new();
+    for row in kartlegging_rows {
+        per_arbeidssoeker
+            .entry(row.arbeidssoeker_id)
+            .or_default()
+            .push(row);
+    }
+
+    let mut resultat = BatchResultat {
+        kontrollert: 0,
+        avvik: Vec::new(),
+        oppdatert: 0,
+        endret_underveis: 0,
+    };
+    for lagrede in per_arbeidssoeker.values() {
+        let beregnede = beregn_kartlegginger(lagrede, &perioder, &bekreftelser, periode_gap_grense);
+        for (lagret, beregnet) in lagrede.iter().zip(beregnede.iter()) {
+            resultat.kontrollert += 1;
+            if lagret == beregnet {
+                continue;
+            }
+            if modus == ReprojeksjonModus::Skriv {
+                if kartlegging::update_if_unchanged(tx, lagret, beregnet).await? == 1 {
+                    resultat.oppdatert += 1;
+                } else {
+                    resultat.endret_underveis += 1;
+                }
+            }
+            resultat.avvik.push(KartleggingAvvik {
+                periode_id: lagret.periode_id,
+                lagret: verdier(lagret),
+                beregnet: verdier(beregnet),
+            });
+        }
+    }
+    Ok(resultat)
+}
+
+fn verdier(row: &KartleggingRow) -> KartleggingVerdier {
+    KartleggingVerdier {
+        arbeidssoeker_fra: row.arbeidssoeker_fra,
+        arbeidssoeker_til: row.arbeidssoeker_til,
+        arbeidsledig_fra: row.arbeidsledig_fra,
+    }
+}
+
+/// Beregner kartleggingene til én arbeidssøker slik de ville blitt om periodene og
+/// bekreftelsene ble behandlet i rekkefølge. `lagrede` må være sortert på start, og
+/// resultatet har samme rekkefølge. Kartlegginger uten lagret periode beholdes som de er, siden
+/// det ikke finnes noe å beregne dem fra.
+pub(crate) fn beregn_kartlegginger(
+    lagrede: &[KartleggingRow],
+    perioder: &HashMap<Uuid, PeriodeRow>,
+    bekreftelser: &HashMap<Uuid, Vec<BekreftelseRow>>,
+    periode_gap_grense: i64,
+) -> Vec<KartleggingRow> {
+    let mut beregnede: Vec<KartleggingRow> = Vec::with_capacity(lagrede.len());
+    for lagret in lagrede {
+        let Some(periode) = perioder.get(&lagret.periode_id) else {
+            beregnede.push(lagret.clone());
+            continue;
+        };
+        let arbeidssoeker_fra = periode.startet_tidspunkt;
+        let arbeidssoeker_til = periode.avsluttet_tidspunkt;
+        let viderefoert = beregnede.last().and_then(|tidligere| {
+            viderefoert_arbeidsledig_fra(tidligere, &arbeidssoeker_fra, periode_gap_grense)
+        });
+        let arbeidsledig_fra = arbeidsledig_fra_etter_bekreftelser(
+            viderefoert,
+            bekreftelser
+                .get(&lagret.periode_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        );
+        beregnede.push(KartleggingRow::new(
+            lagret.periode_id,
+            lagret.arbeidssoeker_id,
+            arbeidssoeker_fra,
+            arbeidssoeker_til,
+            arbeidsledig_fra,
+        ));
+    }
+    beregnede
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, TimeZone};
+
+    fn dato(dag: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, dag, 0, 0, 0).unwrap()
+    }
+
+    fn bekreftelse(
+        periode_id: Uuid,
+        gjelder_fra: DateTime<Utc>,
+        har_jobbet: bool,
+    ) -> BekreftelseRow {
+        BekreftelseRow::new(
+            Uuid::new_v4(),
+            periode_id,
+            gjelder_fra,
+            gjelder_fra + Duration::days(14),
+            har_jobbet,
+            true,
+            "ARBEIDSSOEKERREGISTERET".to_string(),
+            gjelder_fra + Duration::days(14),
+        )
+    }
+
+    #[test]
+    fn beregner_ledighet_fra_bekreftelser_og_tidligere_periode() {
+        let periode_id_1 = Uuid::new_v4();
+        let periode_id_2 = Uuid::new_v4();
+        let perioder = HashMap::from([
+            (
+                periode_id_1,
+                PeriodeRow::new(
+                    periode_id_1,
+                    "01017012345".to_string(),
+                    dato(1),
+                    Some(dato(10)),
+                ),
+            ),
+            (
+                periode_id_2,
+                PeriodeRow::new(periode_id_2, "01017012345".to_string(), dato(15), None),
+            ),
+        ]);
+        let bekreftelser = HashMap::from([(
+            periode_id_1,
+            vec![
+                bekreftelse(periode_id_1, dato(1), false),
+                bekreftelse(periode_id_1, dato(5), false),
+            ],
+        )]);
+        // Lagret med utdatert logikk: ingen ledighet og feil slutt på første periode
+        let lagrede = vec![
+            KartleggingRow::new(periode_id_1, 1, dato(1), None, None),
+            KartleggingRow::new(periode_id_2, 1, dato(15), None, None),
+        ];
+
+        let beregnede = beregn_kartlegginger(&lagrede, &perioder, &bekreftelser, 14);
+
+        assert_eq!(
+            beregnede,
+            vec![
+                KartleggingRow::new(periode_id_1, 1, dato(1), Some(dato(10)), Some(dato(1))),
+                KartleggingRow::new(periode_id_2, 1, dato(15), None, Some(dato(1))),
+            ]
+        );
+
+        // Med kortere grense videreføres ikke ledigheten
+        let beregnede = beregn_kartlegginger(&lagrede, &perioder, &bekreftelser, 3);
+        assert_eq!(beregnede[1].arbeidsledig_fra, None);
+    }
+
+    #[test]
+    fn kartlegging_uten_periode_beholder_lagrede_verdier() {
+        let periode_id_1 = Uuid::new_v4();
+        let periode_id_2 = Uuid::new_v4();
+        let perioder = HashMap::from([(
+            periode_id_2,
+            PeriodeRow::new(periode_id_2, "01017012345".to_string(), dato(5), None),
+        )]);
+        let lagrede = vec![
+            KartleggingRow::new(periode_id_1, 1, dato(1), Some(dato(2)), Some(dato(1))),
+            KartleggingRow::new(periode_id_2, 1, dato(5), None, None),
+        ];
+
+        let beregnede = beregn_kartlegginger(&lagrede, &perioder, &HashMap::new(), 14);
+
+        assert_eq!(
+            beregnede,
+            vec![
+                KartleggingRow::new(periode_id_1, 1, dato(1), Some(dato(2)), Some(dato(1))),
+                // Ledigheten fra kartleggingen uten periode videreføres som før
+                KartleggingRow::new(periode_id_2, 1, dato(5), None, Some(dato(1))),
+            ]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::config::AppConfig;
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
+use oauth2::principal::Principal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Om principal er NAV-ansatt og medlem av en Entra ID-gruppe som gir tilgang til `kontor_id`.
+/// Andre principal-typer har aldri tilgang.
+pub(crate) fn har_kontor_tilgang(
+    principal: &Principal,
+    kontor_id: &str,
+    kontor_grupper: &HashMap<String, Vec<String>>,
+) -> bool {
+    match principal {
+        Principal::NavAnsatt(nav_ansatt) => nav_ansatt.groups.iter().any(|gruppe| {
+            kontor_grupper
+                .get(gruppe)
+                .is_some_and(|kontorer| kontorer.iter().any(|kontor| kontor == kontor_id))
+        }),
+        _ => false,
+    }
+}
+
+/// Om principal er NAV-ansatt og medlem av en av `admin_grupper`
+pub(crate) fn er_admin(principal: &Principal, admin_grupper: &[String]) -> bool {
+    match principal {
+        Principal::NavAnsatt(nav_ansatt) => nav_ansatt
+            .groups
+            .iter()
+            .any(|gruppe| admin_grupper.contains(gruppe)),
+        _ => false,
+    }
+}
+
+/// Hvilke graderinger av adressebeskyttelse principal har tilgang til. Bare NAV-ansatte i en av
+/// gruppene fra config har tilgang, andre principal-typer får alltid skjermet graderte personer.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct AdressebeskyttelseTilgang {
+    fortrolig: bool,
+    strengt_fortrolig: bool,
+}
+
+impl AdressebeskyttelseTilgang {
+    pub(crate) fn for_principal(principal: &Principal, app_config: &AppConfig) -> Self {
+        match principal {
+            Principal::NavAnsatt(nav_ansatt) => {
+                let er_medlem = |grupper: &[String]| {
+                    nav_ansatt
+                        .groups
+                        .iter()
+                        .any(|gruppe| grupper.contains(gruppe))
+                };
+                Self {
+                    fortrolig: er_medlem(&app_config.fortrolig_adresse_grupper),
+                    strengt_fortrolig: er_medlem(&app_config.strengt_fortrolig_adresse_grupper),
+                }
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Om navn og kontor skal skjules for en person med lagret `adressebeskyttelse`. Ukjente
+    /// graderinger skjermes.
+    pub(crate) fn skal_skjermes(&self, adressebeskyttelse: Option<&str>) -> bool {
+        let Some(adressebeskyttelse) = adressebeskyttelse else {
+            return false;
+        };
+        match Adressebeskyttelse::from_str(adressebeskyttelse) {
+            Ok(Adressebeskyttelse::Ugradert) => false,
+            Ok(Adressebeskyttelse::Fortrolig) => !self.fortrolig,
+            Ok(Adressebeskyttelse::StrengtFortrolig)
+            | Ok(Adressebeskyttelse::StrengtFortroligUtland) => !self.strengt_fortrolig,
+            Ok(Adressebeskyttelse::UkjentVerdi) | Err(_) => true,
+        }
+    }
+
+    /// Lagrede graderinger principal kan se i søk på kontor. Personer uten gradering er alltid
+    /// med, personer med andre graderinger tas ut av søket.
+    pub(crate) fn synlige_graderinger(&self) -> Vec<String> {
+        let mut graderinger = vec![Adressebeskyttelse::Ugradert];
+        if self.fortrolig {
+            graderinger.push(Adressebeskyttelse::Fortrolig);
+        }
+        if self.strengt_fortrolig {
+            graderinger.push(Adressebeskyttelse::StrengtFortrolig);
+            graderinger.push(Adressebeskyttelse::StrengtFortroligUtland);
+        }
+        graderinger
+            .into_iter()
+            .map(|gradering| gradering.as_ref().to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::read_app_config;
+    use oauth2::principal::{NavAnsatt, NavSystem};
+    use types::nav_ident::NavIdent;
+
+    fn nav_ansatt(groups: Vec<&str>) -> Principal {
+        Principal::NavAnsatt(NavAnsatt {
+            oid: "oid".to_string(),
+            ident: NavIdent::new("A123456".to_string()).unwrap(),
+            name: None,
+            roles: vec![],
+            groups: groups.into_iter().map(String::from).collect(),
+        })
+    }
+
+    fn kontor_grupper() -> HashMap<String, Vec<String>> {
+        HashMap::from([
+            ("gruppe-1".to_string(), vec!["1337".to_string()]),
+            (
+                "gruppe-2".to_string(),
+                vec!["4242".to_string(), "0101".to_string()],
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_nav_ansatt_med_kontor_gruppe_har_tilgang() {
+        let grupper = kontor_grupper();
+        assert!(har_kontor_tilgang(
+            &nav_ansatt(vec!["gruppe-1"]),
+            "1337",
+            &grupper
+        ));
+        assert!(har_kontor_tilgang(
+            &nav_ansatt(vec!["annen", "gruppe-2"]),
+            "0101",
+            &grupper
+        ));
+    }
+
+    #[test]
+    fn test_nav_ansatt_uten_kontor_gruppe_har_ikke_tilgang() {
+        let grupper = kontor_grupper();
+        assert!(!har_kontor_tilgang(
+            &nav_ansatt(vec!["gruppe-1"]),
+            "4242",
+            &grupper
+        ));
+        assert!(!har_kontor_tilgang(&nav_ansatt(vec![]), "1337", &grupper));
+    }
+
+    #[test]
+    fn test_adressebeskyttelse_tilgang() {
+        let tilgang = AdressebeskyttelseTilgang {
+            fortrolig: true,
+            strengt_fortrolig: false,
+        };
+        assert!(!tilgang.skal_skjermes(None));
+        assert!(!tilgang.skal_skjermes(Some("UGRADERT")));
+        assert!(!tilgang.skal_skjermes(Some("FORTROLIG")));
+        assert!(tilgang.skal_skjermes(Some("STRENGT_FORTROLIG")));
+        assert!(tilgang.skal_skjermes(Some("STRENGT_FORTROLIG_UTLAND")));
+        assert!(tilgang.skal_skjermes(Some("HEMMELIG")));
+        assert_eq!(tilgang.synlige_graderinger(), vec!["UGRADERT", "FORTROLIG"]);
+
+        let ingen_tilgang = AdressebeskyttelseTilgang::default();
+        assert!(ingen_tilgang.skal_skjermes(Some("FORTROLIG")));
+        assert!(!ingen_tilgang.skal_skjermes(None));
+        assert_eq!(ingen_tilgang.synlige_graderinger(), vec!["UGRADERT"]);
+    }
+
+    #[test]
+    fn test_adressebeskyttelse_tilgang_fra_grupper() {
+        let mut app_config = read_app_config().expect("Kunne ikke lese app_config.toml");
+        app_config.fortrolig_adresse_grupper = vec!["fortrolig".to_string()];
+        app_config.strengt_fortrolig_adresse_grupper = vec!["strengt".to_string()];
+
+        let tilgang =
+            AdressebeskyttelseTilgang::for_principal(&nav_ansatt(vec!["strengt"]), &app_config);
+        assert!(!tilgang.skal_skjermes(Some("STRENGT_FORTROLIG")));
+        assert!(tilgang.skal_skjermes(Some("FORTROLIG")));
+
+        let principal = Principal::NavSystem(NavSystem {
+            oid: "oid".to_string(),
+            roles: vec![],
+        });
+        assert_eq!(
+            AdressebeskyttelseTilgang::for_principal(&principal, &app_config),
+            AdressebeskyttelseTilgang::default()
+        );
+    }
+
+    #[test]
+    fn test_nav_system_har_ikke_tilgang() {
+        let principal = Principal::NavSystem(NavSystem {
+            oid: "oid".to_string(),
+            roles: vec![],
+        });
+        assert!(!har_kontor_tilgang(&principal, "1337", &kontor_grupper()));
+    }
+
+    #[test]
+    fn test_er_admin() {
+        let admin_grupper = vec!["admin".to_string()];
+        assert!(er_admin(
+            &nav_ansatt(vec!["annen", "admin"]),
+            &admin_grupper
+        ));
+        assert!(!er_admin(&nav_ansatt(vec!["annen"]), &admin_grupper));
+        assert!(!er_admin(&nav_ansatt(vec!["admin"]), &[]));
+
+        let principal = Principal::NavSystem(NavSystem {
+            oid: "oid".to_string(),
+            roles: vec!["admin".to_string()],
+        });
+        assert!(!er_admin(&principal, &admin_grupper));
+    }
+}
@@ -1,38 +1,54 @@
 use errors::app::AppError;
 use errors::database::DatabaseError;
+use health_and_monitoring::compound_health::CompoundHealth;
+use health_and_monitoring::dependency_health::DependencyHealth;
 use health_and_monitoring::simple_app_state;
+use health_and_monitoring::CheckType;
 use kartlegging_api::api::build_router;
 use kartlegging_api::config::{
     read_app_config, read_auth_config, read_database_config, read_kafka_config, read_otel_tracing_config,
     read_paw_key_gen_client_config, read_pdl_client_config, read_token_client_config,
-    HTTP_TIMEOUT,
+    validate_config, HTTP_TIMEOUT,
 };
 use kartlegging_api::kafka::consumer::{create_kafka_consumer, kafka_consumer_task};
 use kartlegging_api::kafka::topics::TOPICS;
+use kartlegging_api::logic::endringer::endringer_opprydding_task;
 use kartlegging_api::logic::metrics::setup_metrics;
-use kartlegging_api::logic::metrics::task::metrics_task;
+use kartlegging_api::logic::metrics::task::{metrics_task, statistikk_snapshot_task};
+use kartlegging_api::logic::pdl_oppdatering::pdl_oppdatering_task;
 use kartlegging_api::logic::process::message_process::KartleggingMessageProcessor;
-use kartlegging_api::server::{
-    async_task_handler, shutdown_handler, shutdown_signal_task, web_server_task,
-};
+use kartlegging_api::logic::reprojeksjon::Reprojeksjon;
+use kartlegging_api::server::web_server_task;
 use nais_schema_registry::config::create_schema_registry_settings;
+use nais_schema_registry::health::SchemaRegistryCheck;
 use paw_key_gen_client::client::PawKeyGenClient;
 use paw_oauth2_resource_server::state::AuthState;
 use paw_otel_tracing::otel_setup::setup_otel;
 use paw_rdkafka::error::KafkaError;
+use paw_rdkafka_hwm::rebalance::consumer_health::{add_kafka_consumer_checks, KafkaConsumerHealth};
 use paw_rust_base::panic_logger::register_panic_logger;
+use paw_rust_base::task_supervisor::TaskSupervisor;
+use paw_sqlx::health::PgPoolCheck;
 use paw_sqlx::postgres::{clear_db, init_db};
 use pdl_client::client::PDLClient;
 use reqwest::Client;
 use std::sync::Arc;
+use texas_client::health::TokenEndpointCheck;
 use texas_client::token_client::create_token_client;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     register_panic_logger();
 
-    let app_config = Arc::new(read_app_config()?);
     let otel_tracing_config = read_otel_tracing_config()?;
+    setup_otel(otel_tracing_config)?;
+    setup_metrics();
+
+    let config_report = validate_config();
+    config_report.log();
+    config_report.into_result()?;
+
+    let app_config = Arc::new(read_app_config()?);
     let database_config = read_database_config()?;
     let auth_config = read_auth_config()?;
     let kafka_config = read_kafka_config()?;
@@ -40,9 +56,6 @@ async fn main() -> anyhow::Result<()> {
     let key_gen_client_config = read_paw_key_gen_client_config()?;
     let pdl_client_config = read_pdl_client_config()?;
 
-    setup_otel(otel_tracing_config)?;
-    setup_metrics();
-
     let hwm_version = *kafka_config.hwm_version;
 
     let http_client = Client::builder()
@@ -51,6 +64,9 @@ async fn main() -> anyhow::Result<()> {
         .map_err(|_| AppError::AppInitFailed("Kunne ikke opprette HTTP-klient".to_string()))?;
 
     let app_state = Arc::new(simple_app_state::AppState::new());
+    let mut supervisor = TaskSupervisor::default();
+    let shutdown_app_state = app_state.clone();
+    supervisor.on_shutdown(move || shutdown_app_state.set_is_ready(false));
     let auth_state = AuthState::new(auth_config, http_client.clone())
         .await
         .map_err(|e| anyhow::anyhow!(e.to_string()))?;
@@ -76,6 +92,7 @@ async fn main() -> anyhow::Result<()> {
         http_client.clone(),
         token_client.clone(),
     ));
+    let pdl_target_scope = pdl_client_config.target_scope.to_string();
     let pdl_client = Arc::new(PDLClient::from_config(
         pdl_client_config,
         http_client.clone(),
@@ -83,32 +100,101 @@ async fn main() -> anyhow::Result<()> {
     ));
 
     let schema_registry_settings = create_schema_registry_settings()?;
-    let consumer = create_kafka_consumer(app_state.clone(), pg_pool.clone(), kafka_config, &TOPICS)
-        .map_err(|e| KafkaError::CreateConsumer(e.to_string()))?;
+    let consumer_health = Arc::new(KafkaConsumerHealth::new());
+
+    let mut dependency_health = DependencyHealth::default();
+    dependency_health.add_check(PgPoolCheck::new(pg_pool.clone()), CheckType::IsReady);
+    add_kafka_consumer_checks(&mut dependency_health, consumer_health.clone());
+    // Schema registry og token-endepunktet er delt av alle pods, så det hjelper ikke å ta
+    // podden ut av rotasjon når de er nede. De vises bare i /internal/health.
+    dependency_health.add_check(
+        SchemaRegistryCheck::new(schema_registry_settings.clone()),
+        CheckType::Informational,
+    );
+    dependency_health.add_check(
+        TokenEndpointCheck::new(token_client.clone(), pdl_target_scope),
+        CheckType::Informational,
+    );
+    let dependency_health = Arc::new(dependency_health);
+    let mut health = CompoundHealth::new();
+    health.add_health_check(app_state.clone());
+    health.add_health_check(dependency_health.clone());
+
+    let consumer = create_kafka_consumer(
+        app_state.clone(),
+        pg_pool.clone(),
+        consumer_health,
+        kafka_config,
+        &TOPICS,
+    )
+    .map_err(|e| KafkaError::CreateConsumer(e.to_string()))?;
     let message_processor = KartleggingMessageProcessor::new(
         app_config.clone(),
         schema_registry_settings,
         key_gen_client.clone(),
         pdl_client.clone(),
     )?;
-    let consumer_task =
-        kafka_consumer_task(pg_pool.clone(), hwm_version, consumer, message_processor);
+    let consumer_task = kafka_consumer_task(
+        pg_pool.clone(),
+        hwm_version,
+        consumer,
+        message_processor,
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("KafkaConsumer", consumer_task);
+
+    let reprojeksjon = Arc::new(Reprojeksjon::new(
+        app_config.clone(),
+        pg_pool.clone(),
+        supervisor.cancellation_token(),
+    ));
+    let router = build_router(
+        app_config.clone(),
+        Arc::new(health),
+        pg_pool.clone(),
+        auth_state,
+        reprojeksjon,
+    );
+    let server_task = web_server_task(router, supervisor.cancellation_token()).await;
+    supervisor.add_task("Webserver", server_task);
+
+    let metrics_task = metrics_task(
+        app_config.clone(),
+        pg_pool.clone(),
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("Metrics", metrics_task);
+
+    let statistikk_snapshot_task = statistikk_snapshot_task(
+        app_config.clone(),
+        pg_pool.clone(),
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("StatistikkSnapshot", statistikk_snapshot_task);
 
-    let router = build_router(app_state.clone(), pg_pool.clone(), auth_state);
-    let server_task = web_server_task(router).await;
+    let endringer_opprydding_task = endringer_opprydding_task(
+        app_config.clone(),
+        pg_pool.clone(),
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("EndringerOpprydding", endringer_opprydding_task);
 
-    let metrics_task = metrics_task(app_config.clone(), pg_pool.clone());
+    let pdl_oppdatering_task = pdl_oppdatering_task(
+        app_config.clone(),
+        pg_pool.clone(),
+        pdl_client.clone(),
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("PdlOppdatering", pdl_oppdatering_task);
 
-    let signal_task = shutdown_signal_task();
+    let health_task = dependency_health.spawn_refresh_task(supervisor.cancellation_token());
+    supervisor.add_task("Helsesjekker", health_task);
 
     app_state.set_has_started(true);
 
-    tokio::select! {
-        result = server_task => async_task_handler("Webserver", result),
-        result = consumer_task => async_task_handler("KafkaConsumer", result),
-        result = metrics_task => async_task_handler("Metrics", result),
-        signal = signal_task => shutdown_handler(signal),
-    }?;
-
-    Ok(())
+    let result = supervisor.run().await;
+    app_state.set_is_alive(false);
+    pg_pool.close().await;
+    tracing::info!("Pg pool lukket");
+    result
 }
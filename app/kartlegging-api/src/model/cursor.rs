@@ -0,0 +1,102 @@
+use crate::model::sort::{SortField, SortOrder};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::NaiveDateTime;
+use std::str::FromStr;
+use uuid::Uuid;
+
+const CURSOR_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+/// Posisjonen til siste rad i en side, brukt for keyset-paging. Sorteringsverdien og
+/// periode_id er sammen unike, slik at nye rader ikke forskyver sidene slik OFFSET gjør.
+/// Sorteringen er med slik at en cursor ikke kan brukes med en annen sortering enn den ble
+/// laget for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    pub sort_field: SortField,
+    pub sort_order: SortOrder,
+    pub sort_verdi: NaiveDateTime,
+    pub periode_id: Uuid,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[error("Ugyldig cursor")]
+pub struct InvalidCursorError;
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let raw = format!(
+            "{}|{}|{}|{}",
+            self.sort_field.as_ref(),
+            self.sort_order.as_ref(),
+            self.sort_verdi.format(CURSOR_TIMESTAMP_FORMAT),
+            self.periode_id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, InvalidCursorError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|_| InvalidCursorError)?;
+        let raw = String::from_utf8(bytes).map_err(|_| InvalidCursorError)?;
+        let mut deler = raw.splitn(4, '|');
+        let mut neste = || deler.next().ok_or(InvalidCursorError);
+        let (sort_field, sort_order, sort_verdi, periode_id) =
+            (neste()?, neste()?, neste()?, neste()?);
+        Ok(Cursor {
+            sort_field: SortField::from_str(sort_field).map_err(|_| InvalidCursorError)?,
+            sort_order: SortOrder::from_str(sort_order).map_err(|_| InvalidCursorError)?,
+            sort_verdi: NaiveDateTime::parse_from_str(sort_verdi, CURSOR_TIMESTAMP_FORMAT)
+                .map_err(|_| InvalidCursorError)?,
+            periode_id: Uuid::parse_str(periode_id).map_err(|_| InvalidCursorError)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_kan_kodes_og_dekodes() {
+        let cursor = Cursor {
+            sort_field: SortField::ArbeidsledigFra,
+            sort_order: SortOrder::Descending,
+            sort_verdi: NaiveDateTime::parse_from_str(
+                "2026-01-01T12:34:56.123456",
+                CURSOR_TIMESTAMP_FORMAT,
+            )
+            .unwrap(),
+            periode_id: Uuid::parse_str("069f40c9-c47c-4ee2-9105-bc87bdb58af2").unwrap(),
+        };
+        let encoded = cursor.encode();
+        assert_eq!(
+            String::from_utf8(URL_SAFE_NO_PAD.decode(&encoded).unwrap()).unwrap(),
+            "ARBEIDSLEDIG_FRA|DESC|2026-01-01T12:34:56.123456|069f40c9-c47c-4ee2-9105-bc87bdb58af2"
+        );
+        assert_eq!(Cursor::decode(&encoded), Ok(cursor));
+    }
+
+    #[test]
+    fn ugyldig_cursor_gir_feil() {
+        assert_eq!(Cursor::decode("ikke-en-cursor"), Err(InvalidCursorError));
+        assert_eq!(
+            Cursor::decode(&URL_SAFE_NO_PAD.encode("2026-01-01|ikke-uuid")),
+            Err(InvalidCursorError)
+        );
+        assert_eq!(
+            Cursor::decode(
+                &URL_SAFE_NO_PAD
+                    .encode("2026-01-01T12:34:56.123456|069f40c9-c47c-4ee2-9105-bc87bdb58af2")
+            ),
+            Err(InvalidCursorError)
+        );
+        assert_eq!(
+            Cursor::decode(&URL_SAFE_NO_PAD.encode(
+                "ARBEIDSSOEKER_FRA|OPP|2026-01-01T12:34:56.123456|069f40c9-c47c-4ee2-9105-bc87bdb58af2"
+            )),
+            Err(InvalidCursorError)
+        );
+    }
+}
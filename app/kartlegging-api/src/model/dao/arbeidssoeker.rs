@@ -1,6 +1,8 @@
-use crate::model::sort::SortOrder;
-use chrono::{NaiveDate, Utc};
+use crate::model::cursor::Cursor;
+use crate::model::sort::{SortField, SortOrder};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use sqlx::{FromRow, Postgres, Transaction};
+use uuid::Uuid;
 
 #[derive(Debug, FromRow)]
 pub(crate) struct ArbeidssoekerRow {
@@ -10,6 +12,7 @@ pub(crate) struct ArbeidssoekerRow {
     pub fornavn: Option<String>,
     pub mellomnavn: Option<String>,
     pub etternavn: Option<String>,
+    pub adressebeskyttelse: Option<String>,
 }
 
 impl ArbeidssoekerRow {
@@ -20,6 +23,7 @@ impl ArbeidssoekerRow {
         fornavn: Option<String>,
         mellomnavn: Option<String>,
         etternavn: Option<String>,
+        adressebeskyttelse: Option<String>,
     ) -> Self {
         Self {
             id,
@@ -28,32 +32,89 @@ impl ArbeidssoekerRow {
             fornavn,
             mellomnavn,
             etternavn,
+            adressebeskyttelse,
         }
     }
 }
 
+/// Filtre for søk på arbeidssøkere tilknyttet et kontor. `None` betyr ingen filtrering.
+#[derive(Debug)]
+pub(crate) struct KontortilknytningFilter {
+    pub kontor_id: String,
+    pub kontor_typer: Vec<String>,
+    pub ledig_siden: NaiveDate,
+    pub ledig_siden_til: Option<NaiveDate>,
+    pub periode_aktiv: Option<bool>,
+    pub profilert_til: Option<Vec<String>>,
+    pub egenvurdering_avvik: Option<bool>,
+    /// Graderinger av adressebeskyttelse kaller har tilgang til. Personer uten gradering er alltid med.
+    pub synlige_graderinger: Vec<String>,
+}
+
+/// Felles WHERE for telling og søk på kontortilknytning, parametre $1-$8 fra `KontortilknytningFilter`.
+/// Kontortilknytning sjekkes med EXISTS slik at hver kartlegging bare gir én rad.
+// language=SQL
+const KONTORTILKNYTNING_WHERE: &str = r#"
+        WHERE EXISTS (
+            SELECT 1 FROM kontortilknytninger kt
+            WHERE kt.aktor_id = a.aktor_id AND kt.kontor_id = $1 AND kt.kontor_type = ANY($2)
+        )
+        AND k.arbeidsledig_fra NOTNULL
+        AND k.arbeidsledig_fra > $3
+        AND ($4::date IS NULL OR k.arbeidsledig_fra < $4)
+        AND ($5::boolean IS NULL OR (k.arbeidssoeker_til IS NULL) = $5)
+        AND ($6::varchar[] IS NULL OR (
+            SELECT pr.profilert_til FROM profileringer pr
+            WHERE pr.periode_id = k.periode_id
+            ORDER BY pr.tidspunkt DESC
+            LIMIT 1
+        ) = ANY($6))
+        AND ($7::boolean IS NULL OR EXISTS (
+            SELECT 1 FROM (
+                SELECT e.profilert_til, e.egenvurdert_til FROM egenvurderinger e
+                WHERE e.periode_id = k.periode_id
+                ORDER BY e.tidspunkt DESC
+                LIMIT 1
+            ) siste
+            WHERE (siste.profilert_til <> siste.egenvurdert_til) = $7
+        ))
+        AND (a.adressebeskyttelse IS NULL OR a.adressebeskyttelse = ANY($8))
+"#;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct KontortilknytningRow {
+    #[sqlx(flatten)]
+    pub arbeidssoeker: ArbeidssoekerRow,
+    pub periode_id: Uuid,
+    pub sort_verdi: NaiveDateTime,
+}
+
 #[tracing::instrument(skip(tx))]
 pub async fn count_by_kontortilknytning(
     tx: &mut Transaction<'_, Postgres>,
-    kontor_id: &str,
-    kontor_typer: &Vec<String>,
-    ledig_siden: &NaiveDate,
+    filter: &KontortilknytningFilter,
 ) -> anyhow::Result<i64> {
     tracing::debug!("Count arbeidssøkere by kontortilknytning");
-    let count = sqlx::query_scalar(
+    // language=SQL
+    let sql = format!(
         r#"
         SELECT COUNT(*) AS count
         FROM arbeidssoekere a
-        LEFT JOIN kartlegginger k on a.id = k.arbeidssoeker_id
-        LEFT JOIN kontortilknytninger kt on a.aktor_id = kt.aktor_id
-        WHERE kt.kontor_id = $1 AND kt.kontor_type = ANY($2) AND k.arbeidsledig_fra NOTNULL AND k.arbeidsledig_fra > $3
-        "#,
-    )
-    .bind(kontor_id)
-    .bind(&kontor_typer[..])
-    .bind(ledig_siden)
-    .fetch_one(&mut **tx)
-    .await?;
+        JOIN kartlegginger k on a.id = k.arbeidssoeker_id
+        {KONTORTILKNYTNING_WHERE}
+        "#
+    );
+    let count = sqlx::query_scalar(sqlx::AssertSqlSafe(sql))
+        .bind(&filter.kontor_id)
+        .bind(&filter.kontor_typer[..])
+        .bind(filter.ledig_siden)
+        .bind(filter.ledig_siden_til)
+        .bind(filter.periode_aktiv)
+        .bind(filter.profilert_til.as_deref())
+        .bind(filter.egenvurdering_avvik)
+        .bind(&filter.synlige_graderinger[..])
+        .fetch_one(&mut **tx)
+        .await?;
     Ok(count)
 }
 
@@ -71,7 +132,8 @@ pub async fn select_by_arbeidssoeker_id(
             identitetsnummer,
             fornavn,
             mellomnavn,
-            etternavn
+            etternavn,
+            adressebeskyttelse
         FROM arbeidssoekere
         WHERE id = $1
         "#,
@@ -96,7 +158,8 @@ pub async fn select_by_identitetsnummer(
             identitetsnummer,
             fornavn,
             mellomnavn,
-            etternavn
+            etternavn,
+            adressebeskyttelse
         FROM arbeidssoekere
         WHERE identitetsnummer = $1
         "#,
@@ -107,18 +170,25 @@ pub async fn select_by_identitetsnummer(
     Ok(rows)
 }
 
+/// Henter en side sortert på `sort_field` og periode_id. Med `cursor` hentes radene etter
+/// cursor (keyset), ellers brukes `offset`.
 #[tracing::instrument(skip(tx))]
 pub async fn select_by_kontortilknytning(
     tx: &mut Transaction<'_, Postgres>,
-    kontor_id: &str,
-    kontor_typer: &Vec<String>,
-    ledig_siden: &NaiveDate,
+    filter: &KontortilknytningFilter,
+    cursor: Option<&Cursor>,
     offset: i32,
     limit: i32,
+    sort_field: &SortField,
     sort_order: &SortOrder,
-) -> anyhow::Result<Vec<ArbeidssoekerRow>> {
+) -> anyhow::Result<Vec<KontortilknytningRow>> {
     tracing::debug!("Select arbeidssøkere by kontortilknytning");
     let dir = sort_order.as_ref();
+    let sort_column = sort_field.column();
+    let cursor_cmp = match sort_order {
+        SortOrder::Ascending => ">",
+        SortOrder::Descending => "<",
+    };
     // language=SQL
     let sql = format!(
         r#"
@@ -128,21 +198,30 @@ pub async fn select_by_kontortilknytning(
             a.identitetsnummer,
             a.fornavn,
             a.mellomnavn,
-            a.etternavn
+            a.etternavn,
+            a.adressebeskyttelse,
+            k.periode_id,
+            {sort_column} AS sort_verdi
         FROM arbeidssoekere a
-        LEFT JOIN kartlegginger k on a.id = k.arbeidssoeker_id
-        LEFT JOIN kontortilknytninger kt on a.aktor_id = kt.aktor_id
-        WHERE kt.kontor_id = $1 AND kt.kontor_type = ANY($2) AND k.arbeidsledig_fra NOTNULL AND k.arbeidsledig_fra > $3
-        ORDER BY k.arbeidssoeker_fra {}
-        OFFSET $4
-        LIMIT $5
-        "#,
-        dir
+        JOIN kartlegginger k on a.id = k.arbeidssoeker_id
+        {KONTORTILKNYTNING_WHERE}
+        AND ($9::timestamp IS NULL OR ({sort_column}, k.periode_id) {cursor_cmp} ($9, $10::uuid))
+        ORDER BY {sort_column} {dir}, k.periode_id {dir}
+        OFFSET $11
+        LIMIT $12
+        "#
     );
-    let rows = sqlx::query_as::<_, ArbeidssoekerRow>(sqlx::AssertSqlSafe(sql))
-        .bind(kontor_id)
-        .bind(kontor_typer)
-        .bind(ledig_siden)
+    let rows = sqlx::query_as::<_, KontortilknytningRow>(sqlx::AssertSqlSafe(sql))
+        .bind(&filter.kontor_id)
+        .bind(&filter.kontor_typer[..])
+        .bind(filter.ledig_siden)
+        .bind(filter.ledig_siden_til)
+        .bind(filter.periode_aktiv)
+        .bind(filter.profilert_til.as_deref())
+        .bind(filter.egenvurdering_avvik)
+        .bind(&filter.synlige_graderinger[..])
+        .bind(cursor.map(|c| c.sort_verdi))
+        .bind(cursor.map(|c| c.periode_id))
         .bind(offset)
         .bind(limit)
         .fetch_all(&mut **tx)
@@ -150,6 +229,87 @@ pub async fn select_by_kontortilknytning(
     Ok(rows)
 }
 
+#[derive(Debug, FromRow)]
+pub(crate) struct EksportRow {
+    pub identitetsnummer: String,
+    pub fornavn: Option<String>,
+    pub mellomnavn: Option<String>,
+    pub etternavn: Option<String>,
+    pub adressebeskyttelse: Option<String>,
+    pub periode_id: Uuid,
+    pub arbeidsledig_fra: Option<NaiveDateTime>,
+    pub profilert_til: Option<String>,
+    pub egenvurdert_til: Option<String>,
+    pub sort_verdi: NaiveDateTime,
+}
+
+/// Henter neste batch for eksport med siste profilering og egenvurdering per periode. Radene
+/// etter `cursor` hentes med keyset, slik at hele utvalget kan leses uten OFFSET.
+#[tracing::instrument(skip(tx))]
+pub async fn select_eksport_by_kontortilknytning(
+    tx: &mut Transaction<'_, Postgres>,
+    filter: &KontortilknytningFilter,
+    cursor: Option<&Cursor>,
+    limit: i32,
+    sort_field: &SortField,
+    sort_order: &SortOrder,
+) -> anyhow::Result<Vec<EksportRow>> {
+    tracing::debug!("Select eksport av arbeidssøkere by kontortilknytning");
+    let dir = sort_order.as_ref();
+    let sort_column = sort_field.column();
+    let cursor_cmp = match sort_order {
+        SortOrder::Ascending => ">",
+        SortOrder::Descending => "<",
+    };
+    // language=SQL
+    let sql = format!(
+        r#"
+        SELECT
+            a.identitetsnummer,
+            a.fornavn,
+            a.mellomnavn,
+            a.etternavn,
+            a.adressebeskyttelse,
+            k.periode_id,
+            k.arbeidsledig_fra,
+            (
+                SELECT pr.profilert_til FROM profileringer pr
+                WHERE pr.periode_id = k.periode_id
+                ORDER BY pr.tidspunkt DESC
+                LIMIT 1
+            ) AS profilert_til,
+            (
+                SELECT e.egenvurdert_til FROM egenvurderinger e
+                WHERE e.periode_id = k.periode_id
+                ORDER BY e.tidspunkt DESC
+                LIMIT 1
+            ) AS egenvurdert_til,
+            {sort_column} AS sort_verdi
+        FROM arbeidssoekere a
+        JOIN kartlegginger k on a.id = k.arbeidssoeker_id
+        {KONTORTILKNYTNING_WHERE}
+        AND ($9::timestamp IS NULL OR ({sort_column}, k.periode_id) {cursor_cmp} ($9, $10::uuid))
+        ORDER BY {sort_column} {dir}, k.periode_id {dir}
+        LIMIT $11
+        "#
+    );
+    let rows = sqlx::query_as::<_, EksportRow>(sqlx::AssertSqlSafe(sql))
+        .bind(&filter.kontor_id)
+        .bind(&filter.kontor_typer[..])
+        .bind(filter.ledig_siden)
+        .bind(filter.ledig_siden_til)
+        .bind(filter.periode_aktiv)
+        .bind(filter.profilert_til.as_deref())
+        .bind(filter.egenvurdering_avvik)
+        .bind(&filter.synlige_graderinger[..])
+        .bind(cursor.map(|c| c.sort_verdi))
+        .bind(cursor.map(|c| c.periode_id))
+        .bind(limit)
+        .fetch_all(&mut **tx)
+        .await?;
+    Ok(rows)
+}
+
 #[tracing::instrument(skip(tx, row))]
 pub async fn insert<'a>(
     tx: &mut Transaction<'_, Postgres>,
@@ -165,8 +325,10 @@ pub async fn insert<'a>(
             fornavn,
             mellomnavn,
             etternavn,
+            adressebeskyttelse,
+            pdl_oppdatert,
             inserted_timestamp
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)
         "#,
     )
     .bind(&row.id)
@@ -175,6 +337,7 @@ pub async fn insert<'a>(
     .bind(&row.fornavn)
     .bind(&row.mellomnavn)
     .bind(&row.etternavn)
+    .bind(&row.adressebeskyttelse)
     .bind(Utc::now())
     .execute(&mut **tx)
     .await?;
@@ -195,8 +358,9 @@ pub async fn update<'a>(
             fornavn,
             mellomnavn,
             etternavn,
+            adressebeskyttelse,
             updated_timestamp
-        ) = ($2, $3, $4, $5, $6) WHERE id = $1
+        ) = ($2, $3, $4, $5, $6, $7) WHERE id = $1
         "#,
     )
     .bind(row.id)
@@ -204,8 +368,263 @@ pub async fn update<'a>(
     .bind(&row.fornavn)
     .bind(&row.mellomnavn)
     .bind(&row.etternavn)
+    .bind(&row.adressebeskyttelse)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct PdlOppdateringRow {
+    #[sqlx(flatten)]
+    pub arbeidssoeker: ArbeidssoekerRow,
+    pub pdl_antall_feil: i32,
+}
+
+/// Arbeidssøkere som ikke er oppdatert fra PDL siden `oppdatert_foer`, de eldste først. Arbeidssøkere
+/// der oppslaget har feilet hoppes over til neste forsøk.
+#[tracing::instrument(skip(tx))]
+pub async fn select_for_pdl_oppdatering(
+    tx: &mut Transaction<'_, Postgres>,
+    oppdatert_foer: &DateTime<Utc>,
+    naa: &DateTime<Utc>,
+    limit: i32,
+) -> anyhow::Result<Vec<PdlOppdateringRow>> {
+    tracing::debug!("Select arbeidssøkere for PDL-oppdatering");
+    let rows = sqlx::query_as::<_, PdlOppdateringRow>(
+        r#"
+        SELECT
+            id,
+            aktor_id,
+            identitetsnummer,
+            fornavn,
+            mellomnavn,
+            etternavn,
+            adressebeskyttelse,
+            pdl_antall_feil
+        FROM arbeidssoekere
+        WHERE (pdl_oppdatert IS NULL OR pdl_oppdatert < $1)
+        AND (pdl_neste_forsok IS NULL OR pdl_neste_forsok <= $2)
+        ORDER BY pdl_oppdatert NULLS FIRST, id
+        LIMIT $3
+        "#,
+    )
+    .bind(oppdatert_foer)
+    .bind(naa)
+    .bind(limit)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
+
+/// Lagrer navn og adressebeskyttelse fra PDL og markerer arbeidssøkeren som oppdatert
+#[tracing::instrument(skip(tx, row))]
+pub async fn update_pdl_data(
+    tx: &mut Transaction<'_, Postgres>,
+    row: &ArbeidssoekerRow,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update navn og adressebeskyttelse for arbeidssøker");
+    let result = sqlx::query(
+        r#"
+        UPDATE arbeidssoekere SET (
+            fornavn,
+            mellomnavn,
+            etternavn,
+            adressebeskyttelse,
+            pdl_oppdatert,
+            pdl_antall_feil,
+            pdl_neste_forsok,
+            updated_timestamp
+        ) = ($2, $3, $4, $5, $6, 0, NULL, $6) WHERE id = $1
+        "#,
+    )
+    .bind(row.id)
+    .bind(&row.fornavn)
+    .bind(&row.mellomnavn)
+    .bind(&row.etternavn)
+    .bind(&row.adressebeskyttelse)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Markerer arbeidssøkere som oppdatert uten å endre data, f.eks. når PDL ikke fant personen
+#[tracing::instrument(skip(tx))]
+pub async fn update_pdl_oppdatert(
+    tx: &mut Transaction<'_, Postgres>,
+    ids: &[i64],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update pdl_oppdatert for arbeidssøkere");
+    let result = sqlx::query(
+        r#"
+        UPDATE arbeidssoekere SET (
+            pdl_oppdatert,
+            pdl_antall_feil,
+            pdl_neste_forsok
+        ) = ($2, 0, NULL) WHERE id = ANY($1)
+        "#,
+    )
+    .bind(ids)
     .bind(Utc::now())
     .execute(&mut **tx)
     .await?;
     Ok(result.rows_affected())
 }
+
+/// Teller opp feilede oppslag mot PDL og venter med arbeidssøkerne til `neste_forsok`
+#[tracing::instrument(skip(tx))]
+pub async fn registrer_pdl_feil(
+    tx: &mut Transaction<'_, Postgres>,
+    ids: &[i64],
+    neste_forsok: &DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Registrer feilet PDL-oppslag for arbeidssøkere");
+    let result = sqlx::query(
+        r#"
+        UPDATE arbeidssoekere SET (
+            pdl_antall_feil,
+            pdl_neste_forsok
+        ) = (pdl_antall_feil + 1, $2) WHERE id = ANY($1)
+        "#,
+    )
+    .bind(ids)
+    .bind(neste_forsok)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Gjør at arbeidssøkerne hentes fra PDL ved neste oppdatering, før alle andre
+#[tracing::instrument(skip(tx))]
+pub async fn clear_pdl_oppdatert(
+    tx: &mut Transaction<'_, Postgres>,
+    ids: &[i64],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Clear pdl_oppdatert for arbeidssøkere");
+    let result = sqlx::query("UPDATE arbeidssoekere SET pdl_oppdatert = NULL WHERE id = ANY($1)")
+        .bind(ids)
+        .execute(&mut **tx)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Henter arbeidssøkere med en av identitetene, eller med en periode startet med et av
+/// identitetsnumrene.
+#[tracing::instrument(skip(tx, arbeidssoeker_ider, aktor_ider, identitetsnumre))]
+pub async fn select_by_identiteter(
+    tx: &mut Transaction<'_, Postgres>,
+    arbeidssoeker_ider: &[i64],
+    aktor_ider: &[String],
+    identitetsnumre: &[String],
+) -> anyhow::Result<Vec<ArbeidssoekerRow>> {
+    tracing::debug!("Select arbeidssøkere by identiteter");
+    let rows = sqlx::query_as::<_, ArbeidssoekerRow>(
+        r#"
+        SELECT
+            id,
+            aktor_id,
+            identitetsnummer,
+            fornavn,
+            mellomnavn,
+            etternavn,
+            adressebeskyttelse
+        FROM arbeidssoekere
+        WHERE id = ANY($1) OR aktor_id = ANY($2) OR identitetsnummer = ANY($3)
+        OR id IN (
+            SELECT k.arbeidssoeker_id FROM kartlegginger k
+            JOIN perioder p ON p.id = k.periode_id
+            WHERE p.identitetsnummer = ANY($3)
+        )
+        ORDER BY id
+        "#,
+    )
+    .bind(arbeidssoeker_ider)
+    .bind(aktor_ider)
+    .bind(identitetsnumre)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
+
+#[tracing::instrument(skip(tx, aktor_id, identitetsnummer))]
+pub async fn update_identiteter(
+    tx: &mut Transaction<'_, Postgres>,
+    id: &i64,
+    aktor_id: &str,
+    identitetsnummer: &str,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update identiteter for arbeidssøker");
+    let result = sqlx::query(
+        r#"
+        UPDATE arbeidssoekere SET (
+            aktor_id,
+            identitetsnummer,
+            updated_timestamp
+        ) = ($2, $3, $4) WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(aktor_id)
+    .bind(identitetsnummer)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    ids: &[i64],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete arbeidssøkere by ids");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM arbeidssoekere WHERE id = ANY($1)
+        "#,
+    )
+    .bind(ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn count(tx: &mut Transaction<'_, Postgres>) -> anyhow::Result<i64> {
+    tracing::debug!("Count arbeidssøkere");
+    let count = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM arbeidssoekere
+        "#,
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(count)
+}
+
+/// Henter neste side med arbeidssøker-ider etter `etter_id`, sortert på id.
+#[tracing::instrument(skip(tx))]
+pub async fn select_ids_after(
+    tx: &mut Transaction<'_, Postgres>,
+    etter_id: i64,
+    limit: i64,
+) -> anyhow::Result<Vec<i64>> {
+    tracing::debug!("Select arbeidssøker-ider");
+    let ids = sqlx::query_scalar(
+        r#"
+        SELECT id
+        FROM arbeidssoekere
+        WHERE id > $1
+        ORDER BY id
+        LIMIT $2
+        "#,
+    )
+    .bind(etter_id)
+    .bind(limit)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(ids)
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Postgres, Transaction};
+
+#[derive(Debug, FromRow)]
+pub(crate) struct AuditLoggRow {
+    pub principal_type: String,
+    pub principal_ident: Option<String>,
+    pub query_type: String,
+    pub identitetsnumre: Vec<String>,
+    pub trace_id: Option<String>,
+    pub tidspunkt: DateTime<Utc>,
+}
+
+impl AuditLoggRow {
+    pub fn new(
+        principal_type: String,
+        principal_ident: Option<String>,
+        query_type: String,
+        identitetsnumre: Vec<String>,
+        trace_id: Option<String>,
+        tidspunkt: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            principal_type,
+            principal_ident,
+            query_type,
+            identitetsnumre,
+            trace_id,
+            tidspunkt,
+        }
+    }
+}
+
+#[cfg(test)]
+#[tracing::instrument(skip(tx, identitetsnummer))]
+pub async fn select_by_identitetsnummer(
+    tx: &mut Transaction<'_, Postgres>,
+    identitetsnummer: &str,
+) -> anyhow::Result<Vec<AuditLoggRow>> {
+    tracing::debug!("Select audit_logg by identitetsnummer");
+    let rows = sqlx::query_as::<_, AuditLoggRow>(
+        r#"
+        SELECT
+            principal_type,
+            principal_ident,
+            query_type,
+            identitetsnumre,
+            trace_id,
+            tidspunkt AT TIME ZONE 'UTC' AS tidspunkt
+        FROM audit_logg
+        WHERE $1 = ANY(identitetsnumre)
+        ORDER BY tidspunkt DESC
+        "#,
+    )
+    .bind(identitetsnummer)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
+
+#[tracing::instrument(skip(tx, row))]
+pub async fn insert(tx: &mut Transaction<'_, Postgres>, row: &AuditLoggRow) -> anyhow::Result<u64> {
+    tracing::debug!("Insert audit_logg");
+    let result = sqlx::query(
+        r#"
+        INSERT INTO audit_logg (
+            principal_type,
+            principal_ident,
+            query_type,
+            identitetsnumre,
+            trace_id,
+            tidspunkt,
+            inserted_timestamp
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(&row.principal_type)
+    .bind(&row.principal_ident)
+    .bind(&row.query_type)
+    .bind(&row.identitetsnumre)
+    .bind(&row.trace_id)
+    .bind(row.tidspunkt)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
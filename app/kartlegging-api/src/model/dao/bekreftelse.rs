@@ -181,3 +181,48 @@ pub async fn update<'a>(
     .await?;
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete bekreftelser by periode_id");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM bekreftelser WHERE periode_id = ANY($1)
+        "#,
+    )
+    .bind(periode_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<Vec<BekreftelseRow>> {
+    tracing::debug!("Select bekreftelser by periode_ids");
+    let rows = sqlx::query_as::<_, BekreftelseRow>(
+        r#"
+        SELECT
+            id,
+            periode_id,
+            gjelder_fra AT TIME ZONE 'UTC' AS gjelder_fra,
+            gjelder_til AT TIME ZONE 'UTC' AS gjelder_til,
+            har_jobbet,
+            vil_fortsette,
+            bekreftelsesloesning,
+            tidspunkt   AT TIME ZONE 'UTC' AS tidspunkt
+        FROM bekreftelser
+        WHERE periode_id = ANY($1)
+        ORDER BY periode_id, gjelder_fra
+        "#,
+    )
+    .bind(periode_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
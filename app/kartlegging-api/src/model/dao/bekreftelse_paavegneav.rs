@@ -1,4 +1,4 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::{FromRow, Postgres, Transaction};
 use uuid::Uuid;
 
@@ -82,3 +82,51 @@ pub async fn update<'a>(
     .await?;
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete bekreftelse_paavegneav by periode_id");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM bekreftelse_paavegneav WHERE periode_id = ANY($1)
+        "#,
+    )
+    .bind(periode_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Gjeldende bekreftelsesløsninger for en periode med tidspunkt for siste endring. Tabellen
+/// har bare siste tilstand, så tidligere endringer finnes ikke.
+#[derive(Debug, FromRow)]
+pub(crate) struct BekreftelsePaaVegneAvEndringRow {
+    pub periode_id: Uuid,
+    pub bekreftelsesloesninger: Vec<String>,
+    pub endret_tidspunkt: DateTime<Utc>,
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_endringer_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<Vec<BekreftelsePaaVegneAvEndringRow>> {
+    tracing::debug!("Select bekreftelse_paavegneav by periode_ids");
+    let rows = sqlx::query_as::<_, BekreftelsePaaVegneAvEndringRow>(
+        r#"
+        SELECT
+            periode_id,
+            bekreftelsesloesninger,
+            COALESCE(updated_timestamp, inserted_timestamp) AT TIME ZONE 'UTC' AS endret_tidspunkt
+        FROM bekreftelse_paavegneav
+        WHERE periode_id = ANY($1)
+        "#,
+    )
+    .bind(periode_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
@@ -137,3 +137,46 @@ pub async fn update<'a>(
     .await?;
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete egenvurderinger by periode_id");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM egenvurderinger WHERE periode_id = ANY($1)
+        "#,
+    )
+    .bind(periode_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<Vec<EgenvurderingRow>> {
+    tracing::debug!("Select egenvurderinger by periode_ids");
+    let rows = sqlx::query_as::<_, EgenvurderingRow>(
+        r#"
+        SELECT
+            id,
+            periode_id,
+            profilering_id,
+            profilert_til,
+            egenvurdert_til,
+            tidspunkt AT TIME ZONE 'UTC' AS tidspunkt
+        FROM egenvurderinger
+        WHERE periode_id = ANY($1)
+        ORDER BY periode_id, tidspunkt
+        "#,
+    )
+    .bind(periode_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
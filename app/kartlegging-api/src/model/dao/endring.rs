@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct EndringRow {
+    pub id: i64,
+    pub arbeidssoeker_id: i64,
+    pub identitetsnummer: String,
+    pub endring_type: String,
+    pub tidspunkt: DateTime<Utc>,
+}
+
+/// Registrerer endringen for arbeidssøkeren med kartlegging for perioden
+#[tracing::instrument(skip(tx))]
+pub async fn insert_for_periode_id(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_id: &Uuid,
+    endring_type: &str,
+    tidspunkt: &DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Insert endringer by periode_id");
+    let result = sqlx::query(
+        r#"
+        INSERT INTO endringer (arbeidssoeker_id, endring_type, tidspunkt)
+        SELECT arbeidssoeker_id, $2, $3
+        FROM kartlegginger
+        WHERE periode_id = $1
+        "#,
+    )
+    .bind(periode_id)
+    .bind(endring_type)
+    .bind(tidspunkt)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn insert_for_aktor_id(
+    tx: &mut Transaction<'_, Postgres>,
+    aktor_id: &str,
+    endring_type: &str,
+    tidspunkt: &DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Insert endringer by aktor_id");
+    let result = sqlx::query(
+        r#"
+        INSERT INTO endringer (arbeidssoeker_id, endring_type, tidspunkt)
+        SELECT id, $2, $3
+        FROM arbeidssoekere
+        WHERE aktor_id = $1
+        "#,
+    )
+    .bind(aktor_id)
+    .bind(endring_type)
+    .bind(tidspunkt)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn insert_for_arbeidssoeker_id(
+    tx: &mut Transaction<'_, Postgres>,
+    arbeidssoeker_id: &i64,
+    endring_type: &str,
+    tidspunkt: &DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Insert endring by arbeidssoeker_id");
+    let result = sqlx::query(
+        r#"
+        INSERT INTO endringer (arbeidssoeker_id, endring_type, tidspunkt)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(arbeidssoeker_id)
+    .bind(endring_type)
+    .bind(tidspunkt)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Endringer etter `cursor` for arbeidssøkere tilknyttet kontoret, sortert på id. Arbeidssøkere med
+/// en gradering av adressebeskyttelse som ikke er i `synlige_graderinger` tas ikke med.
+/// Endringer fra transaksjoner som ikke er eldre enn alle pågående tas ikke med før de er det, så
+/// en lavere id som committes sent ikke havner bak cursoren.
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_kontor_id_after(
+    tx: &mut Transaction<'_, Postgres>,
+    kontor_id: &str,
+    synlige_graderinger: &[String],
+    cursor: i64,
+    limit: i32,
+) -> anyhow::Result<Vec<EndringRow>> {
+    tracing::debug!("Select endringer by kontor_id");
+    let rows = sqlx::query_as::<_, EndringRow>(
+        r#"
+        SELECT
+            e.id,
+            e.arbeidssoeker_id,
+            a.identitetsnummer,
+            e.endring_type,
+            e.tidspunkt AT TIME ZONE 'UTC' AS tidspunkt
+        FROM endringer e
+        JOIN arbeidssoekere a ON a.id = e.arbeidssoeker_id
+        WHERE e.id > $2
+        AND e.xid < pg_snapshot_xmin(pg_current_snapshot())
+        AND EXISTS (
+            SELECT 1 FROM kontortilknytninger kt
+            WHERE kt.aktor_id = a.aktor_id AND kt.kontor_id = $1
+        )
+        AND (a.adressebeskyttelse IS NULL OR a.adressebeskyttelse = ANY($4))
+        ORDER BY e.id
+        LIMIT $3
+        "#,
+    )
+    .bind(kontor_id)
+    .bind(cursor)
+    .bind(limit)
+    .bind(synlige_graderinger)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
+
+/// Id for siste endring som kan leses av `select_by_kontor_id_after`, eller 0 hvis det ikke finnes
+/// endringer
+#[tracing::instrument(skip(tx))]
+pub async fn select_max_id(tx: &mut Transaction<'_, Postgres>) -> anyhow::Result<i64> {
+    tracing::debug!("Select max id fra endringer");
+    let id = sqlx::query_scalar(
+        r#"
+        SELECT COALESCE(MAX(id), 0)
+        FROM endringer
+        WHERE xid < pg_snapshot_xmin(pg_current_snapshot())
+        "#,
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(id)
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_before(
+    tx: &mut Transaction<'_, Postgres>,
+    tidspunkt: &DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete endringer før tidspunkt");
+    let result = sqlx::query("DELETE FROM endringer WHERE tidspunkt < $1")
+        .bind(tidspunkt)
+        .execute(&mut **tx)
+        .await?;
+    Ok(result.rows_affected())
+}
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use sqlx::{FromRow, Postgres, Transaction};
 use uuid::Uuid;
 
-#[derive(Debug, FromRow)]
+#[derive(Debug, Clone, PartialEq, FromRow)]
 pub(crate) struct KartleggingRow {
     pub periode_id: Uuid,
     pub arbeidssoeker_id: i64,
@@ -170,3 +170,171 @@ pub async fn update<'a>(
     .await?;
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(skip(tx))]
+pub async fn count_by_arbeidssoeker_id(
+    tx: &mut Transaction<'_, Postgres>,
+    arbeidssoeker_id: &i64,
+) -> anyhow::Result<i64> {
+    tracing::debug!("Count kartlegginger by arbeidssoeker_id");
+    let count = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM kartlegginger
+        WHERE arbeidssoeker_id = $1
+        "#,
+    )
+    .bind(arbeidssoeker_id)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(count)
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_periode_ids_by_arbeidssoeker_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    arbeidssoeker_ider: &[i64],
+) -> anyhow::Result<Vec<Uuid>> {
+    tracing::debug!("Select periode_id for kartlegginger by arbeidssoeker_id");
+    let periode_ids = sqlx::query_scalar(
+        r#"
+        SELECT periode_id
+        FROM kartlegginger
+        WHERE arbeidssoeker_id = ANY($1)
+        "#,
+    )
+    .bind(arbeidssoeker_ider)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(periode_ids)
+}
+
+/// Flytter alle kartlegginger fra `fra_arbeidssoeker_ider` til `arbeidssoeker_id`.
+#[tracing::instrument(skip(tx))]
+pub async fn update_arbeidssoeker_id(
+    tx: &mut Transaction<'_, Postgres>,
+    fra_arbeidssoeker_ider: &[i64],
+    arbeidssoeker_id: &i64,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update arbeidssoeker_id for kartlegginger");
+    let result = sqlx::query(
+        r#"
+        UPDATE kartlegginger SET (
+            arbeidssoeker_id,
+            updated_timestamp
+        ) = ($2, $3) WHERE arbeidssoeker_id = ANY($1)
+        "#,
+    )
+    .bind(fra_arbeidssoeker_ider)
+    .bind(arbeidssoeker_id)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Flytter kartlegginger for perioder startet med et av `identitetsnumre` til `arbeidssoeker_id`.
+#[tracing::instrument(skip(tx, identitetsnumre))]
+pub async fn update_arbeidssoeker_id_by_identitetsnumre(
+    tx: &mut Transaction<'_, Postgres>,
+    identitetsnumre: &[String],
+    arbeidssoeker_id: &i64,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update arbeidssoeker_id for kartlegginger by identitetsnummer");
+    let result = sqlx::query(
+        r#"
+        UPDATE kartlegginger k SET (
+            arbeidssoeker_id,
+            updated_timestamp
+        ) = ($2, $3)
+        FROM perioder p
+        WHERE p.id = k.periode_id
+        AND p.identitetsnummer = ANY($1)
+        AND k.arbeidssoeker_id <> $2
+        "#,
+    )
+    .bind(identitetsnumre)
+    .bind(arbeidssoeker_id)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete kartlegginger by periode_id");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM kartlegginger WHERE periode_id = ANY($1)
+        "#,
+    )
+    .bind(periode_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_arbeidssoeker_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    arbeidssoeker_ider: &[i64],
+) -> anyhow::Result<Vec<KartleggingRow>> {
+    tracing::debug!("Select kartlegginger by arbeidssoeker_id");
+    let rows = sqlx::query_as::<_, KartleggingRow>(
+        r#"
+        SELECT
+            periode_id,
+            arbeidssoeker_id,
+            arbeidssoeker_fra AT TIME ZONE 'UTC' AS arbeidssoeker_fra,
+            arbeidssoeker_til AT TIME ZONE 'UTC' AS arbeidssoeker_til,
+            arbeidsledig_fra  AT TIME ZONE 'UTC' AS arbeidsledig_fra
+        FROM kartlegginger
+        WHERE arbeidssoeker_id = ANY($1)
+        ORDER BY arbeidssoeker_id, arbeidssoeker_fra, periode_id
+        "#,
+    )
+    .bind(arbeidssoeker_ider)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
+
+/// Oppdaterer kartleggingen til `beregnet` bare hvis den fortsatt er lik `lagret`, slik at
+/// endringer gjort av Kafka-konsumenten i mellomtiden ikke overskrives.
+#[tracing::instrument(skip(tx, lagret, beregnet))]
+pub async fn update_if_unchanged(
+    tx: &mut Transaction<'_, Postgres>,
+    lagret: &KartleggingRow,
+    beregnet: &KartleggingRow,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update kartlegging hvis uendret");
+    let result = sqlx::query(
+        r#"
+        UPDATE kartlegginger SET (
+            arbeidssoeker_fra,
+            arbeidssoeker_til,
+            arbeidsledig_fra,
+            updated_timestamp
+        ) = ($5, $6, $7, $8)
+        WHERE periode_id = $1
+        AND arbeidssoeker_fra = $2
+        AND arbeidssoeker_til IS NOT DISTINCT FROM $3
+        AND arbeidsledig_fra IS NOT DISTINCT FROM $4
+        "#,
+    )
+    .bind(lagret.periode_id)
+    .bind(lagret.arbeidssoeker_fra)
+    .bind(lagret.arbeidssoeker_til)
+    .bind(lagret.arbeidsledig_fra)
+    .bind(beregnet.arbeidssoeker_fra)
+    .bind(beregnet.arbeidssoeker_til)
+    .bind(beregnet.arbeidsledig_fra)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
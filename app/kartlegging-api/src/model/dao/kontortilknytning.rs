@@ -54,7 +54,6 @@ pub async fn count_by_id<'a>(
     Ok(count)
 }
 
-#[allow(unused)]
 #[tracing::instrument(skip(tx, id))]
 pub async fn select_by_id<'a>(
     tx: &mut Transaction<'_, Postgres>,
@@ -107,6 +106,32 @@ pub async fn select_by_aktor_id<'a>(
     Ok(rows)
 }
 
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_aktor_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    aktor_ids: &[String],
+) -> anyhow::Result<Vec<KontortilknytningRow>> {
+    tracing::debug!("Select kontortilknytninger by aktor_ids");
+    let rows = sqlx::query_as::<_, KontortilknytningRow>(
+        r#"
+        SELECT
+            id,
+            aktor_id,
+            identitetsnummer,
+            kontor_id,
+            kontor_navn,
+            kontor_type,
+            tidspunkt  AT TIME ZONE 'UTC' AS tidspunkt
+        FROM kontortilknytninger
+        WHERE aktor_id = ANY($1)
+        "#,
+    )
+    .bind(aktor_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
+
 #[tracing::instrument(skip(tx, row))]
 pub async fn insert<'a>(
     tx: &mut Transaction<'_, Postgres>,
@@ -185,3 +210,54 @@ pub async fn delete<'a>(tx: &mut Transaction<'_, Postgres>, id: &'a Uuid) -> any
     .await?;
     Ok(result.rows_affected())
 }
+
+/// Setter gjeldende identiteter på kontortilknytninger for en av `aktor_ider` eller
+/// `identitetsnumre`.
+#[tracing::instrument(skip(tx, aktor_ider, identitetsnumre, aktor_id, identitetsnummer))]
+pub async fn update_identiteter(
+    tx: &mut Transaction<'_, Postgres>,
+    aktor_ider: &[String],
+    identitetsnumre: &[String],
+    aktor_id: &str,
+    identitetsnummer: &str,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update identiteter for kontortilknytninger");
+    let result = sqlx::query(
+        r#"
+        UPDATE kontortilknytninger SET (
+            aktor_id,
+            identitetsnummer,
+            updated_timestamp
+        ) = ($3, $4, $5)
+        WHERE (aktor_id = ANY($1) OR identitetsnummer = ANY($2))
+        AND (aktor_id <> $3 OR identitetsnummer <> $4)
+        "#,
+    )
+    .bind(aktor_ider)
+    .bind(identitetsnumre)
+    .bind(aktor_id)
+    .bind(identitetsnummer)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx, aktor_ider, identitetsnumre))]
+pub async fn delete_by_identiteter(
+    tx: &mut Transaction<'_, Postgres>,
+    aktor_ider: &[String],
+    identitetsnumre: &[String],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete kontortilknytninger by identiteter");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM kontortilknytninger WHERE aktor_id = ANY($1) OR identitetsnummer = ANY($2)
+        "#,
+    )
+    .bind(aktor_ider)
+    .bind(identitetsnumre)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct KontortilknytningHistorikkRow {
+    pub kontortilknytning_id: Uuid,
+    pub aktor_id: String,
+    pub identitetsnummer: String,
+    pub endring_type: String,
+    pub kontor_id: String,
+    pub kontor_navn: String,
+    pub kontor_type: String,
+    pub tidspunkt: DateTime<Utc>,
+}
+
+/// Henter historikken i rekkefølgen endringene ble lagret
+#[tracing::instrument(skip(tx, aktor_id))]
+pub async fn select_by_aktor_id(
+    tx: &mut Transaction<'_, Postgres>,
+    aktor_id: &str,
+) -> anyhow::Result<Vec<KontortilknytningHistorikkRow>> {
+    tracing::debug!("Select kontortilknytning-historikk by aktor_id");
+    let rows = sqlx::query_as::<_, KontortilknytningHistorikkRow>(
+        r#"
+        SELECT
+            kontortilknytning_id,
+            aktor_id,
+            identitetsnummer,
+            endring_type,
+            kontor_id,
+            kontor_navn,
+            kontor_type,
+            tidspunkt  AT TIME ZONE 'UTC' AS tidspunkt
+        FROM kontortilknytning_historikk
+        WHERE aktor_id = $1
+        ORDER BY id
+        "#,
+    )
+    .bind(aktor_id)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
+
+#[tracing::instrument(skip(tx, row))]
+pub async fn insert(
+    tx: &mut Transaction<'_, Postgres>,
+    row: &KontortilknytningHistorikkRow,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Insert kontortilknytning-historikk");
+    let result = sqlx::query(
+        r#"
+        INSERT INTO kontortilknytning_historikk (
+            kontortilknytning_id,
+            aktor_id,
+            identitetsnummer,
+            endring_type,
+            kontor_id,
+            kontor_navn,
+            kontor_type,
+            tidspunkt,
+            inserted_timestamp
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(row.kontortilknytning_id)
+    .bind(&row.aktor_id)
+    .bind(&row.identitetsnummer)
+    .bind(&row.endring_type)
+    .bind(&row.kontor_id)
+    .bind(&row.kontor_navn)
+    .bind(&row.kontor_type)
+    .bind(row.tidspunkt)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Setter gjeldende identiteter på historikken for en av `aktor_ider` eller `identitetsnumre`,
+/// som for kontortilknytninger.
+#[tracing::instrument(skip(tx, aktor_ider, identitetsnumre, aktor_id, identitetsnummer))]
+pub async fn update_identiteter(
+    tx: &mut Transaction<'_, Postgres>,
+    aktor_ider: &[String],
+    identitetsnumre: &[String],
+    aktor_id: &str,
+    identitetsnummer: &str,
+) -> anyhow::Result<u64> {
+    tracing::debug!("Update identiteter for kontortilknytning-historikk");
+    let result = sqlx::query(
+        r#"
+        UPDATE kontortilknytning_historikk SET (
+            aktor_id,
+            identitetsnummer,
+            updated_timestamp
+        ) = ($3, $4, $5)
+        WHERE (aktor_id = ANY($1) OR identitetsnummer = ANY($2))
+        AND (aktor_id <> $3 OR identitetsnummer <> $4)
+        "#,
+    )
+    .bind(aktor_ider)
+    .bind(identitetsnumre)
+    .bind(aktor_id)
+    .bind(identitetsnummer)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx, aktor_ider, identitetsnumre))]
+pub async fn delete_by_identiteter(
+    tx: &mut Transaction<'_, Postgres>,
+    aktor_ider: &[String],
+    identitetsnumre: &[String],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete kontortilknytning-historikk by identiteter");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM kontortilknytning_historikk
+        WHERE aktor_id = ANY($1) OR identitetsnummer = ANY($2)
+        "#,
+    )
+    .bind(aktor_ider)
+    .bind(identitetsnumre)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -1,10 +1,14 @@
 pub(crate) mod arbeidssoeker;
+pub(crate) mod audit_logg;
 pub(crate) mod bekreftelse;
 pub(crate) mod bekreftelse_paavegneav;
 pub(crate) mod egenvurdering;
+pub(crate) mod endring;
 pub(crate) mod kartlegging;
 pub(crate) mod kontortilknytning;
+pub(crate) mod kontortilknytning_historikk;
 pub(crate) mod ledighetsperiode;
 pub(crate) mod opplysninger;
 pub(crate) mod periode;
 pub(crate) mod profilering;
+pub(crate) mod statistikk;
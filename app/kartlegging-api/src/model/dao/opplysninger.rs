@@ -121,3 +121,44 @@ pub async fn update<'a>(
     .await?;
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete opplysninger by periode_id");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM opplysninger WHERE periode_id = ANY($1)
+        "#,
+    )
+    .bind(periode_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<Vec<OpplysningerRow>> {
+    tracing::debug!("Select opplysninger by periode_ids");
+    let rows = sqlx::query_as::<_, OpplysningerRow>(
+        r#"
+        SELECT
+            id,
+            periode_id,
+            jobbsituasjon,
+            tidspunkt AT TIME ZONE 'UTC' AS tidspunkt
+        FROM opplysninger
+        WHERE periode_id = ANY($1)
+        ORDER BY periode_id, tidspunkt
+        "#,
+    )
+    .bind(periode_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
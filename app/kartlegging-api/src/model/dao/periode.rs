@@ -119,3 +119,62 @@ pub async fn update<'a>(
     .await?;
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(skip(tx, identitetsnumre))]
+pub async fn select_ids_by_identitetsnumre(
+    tx: &mut Transaction<'_, Postgres>,
+    identitetsnumre: &[String],
+) -> anyhow::Result<Vec<Uuid>> {
+    tracing::debug!("Select periode_id by identitetsnummer");
+    let ids = sqlx::query_scalar(
+        r#"
+        SELECT id
+        FROM perioder
+        WHERE identitetsnummer = ANY($1)
+        "#,
+    )
+    .bind(identitetsnumre)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(ids)
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete perioder by id");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM perioder WHERE id = ANY($1)
+        "#,
+    )
+    .bind(ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    ids: &[Uuid],
+) -> anyhow::Result<Vec<PeriodeRow>> {
+    tracing::debug!("Select perioder by id");
+    let rows = sqlx::query_as::<_, PeriodeRow>(
+        r#"
+        SELECT
+            id,
+            identitetsnummer,
+            startet_tidspunkt   AT TIME ZONE 'UTC' AS startet_tidspunkt,
+            avsluttet_tidspunkt AT TIME ZONE 'UTC' AS avsluttet_tidspunkt
+        FROM perioder
+        WHERE id = ANY($1)
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
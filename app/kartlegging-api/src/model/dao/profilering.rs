@@ -129,3 +129,45 @@ pub async fn update<'a>(
     .await?;
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(skip(tx))]
+pub async fn delete_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Delete profileringer by periode_id");
+    let result = sqlx::query(
+        r#"
+        DELETE FROM profileringer WHERE periode_id = ANY($1)
+        "#,
+    )
+    .bind(periode_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_by_periode_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    periode_ids: &[Uuid],
+) -> anyhow::Result<Vec<ProfileringRow>> {
+    tracing::debug!("Select profileringer by periode_ids");
+    let rows = sqlx::query_as::<_, ProfileringRow>(
+        r#"
+        SELECT
+            id,
+            periode_id,
+            opplysninger_id,
+            profilert_til,
+            tidspunkt AT TIME ZONE 'UTC' AS tidspunkt
+        FROM profileringer
+        WHERE periode_id = ANY($1)
+        ORDER BY periode_id, tidspunkt
+        "#,
+    )
+    .bind(periode_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
@@ -0,0 +1,211 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::{FromRow, Postgres, Transaction};
+
+/// Filtre for statistikk. `None` betyr ingen filtrering.
+#[derive(Debug)]
+pub(crate) struct StatistikkFilter {
+    pub kontor_id: Option<String>,
+    pub kontor_type: Option<String>,
+    pub profilert_til: Option<Vec<String>>,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct StatistikkRow {
+    pub total: i64,
+    pub is_null: i64,
+    pub is_not_null: i64,
+    /// Antall ledig lenger enn hver grense, i samme rekkefølge som grensene
+    pub over_grenser: Vec<i64>,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct StatistikkSnapshotRow {
+    pub dato: NaiveDate,
+    pub grenser: Vec<i32>,
+    pub total: i64,
+    pub is_null: i64,
+    pub is_not_null: i64,
+    pub over_grenser: Vec<i64>,
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn count_by_filter(
+    tx: &mut Transaction<'_, Postgres>,
+    filter: &StatistikkFilter,
+    grenser: &[i32],
+) -> anyhow::Result<StatistikkRow> {
+    tracing::debug!("Count statistikk by filter");
+    let row = sqlx::query_as::<_, StatistikkRow>(
+        r#"
+        WITH utvalg AS (
+            SELECT k.arbeidsledig_fra
+            FROM arbeidssoekere a
+            JOIN kartlegginger k ON a.id = k.arbeidssoeker_id
+            WHERE (($1::varchar IS NULL AND $2::varchar IS NULL) OR EXISTS (
+                SELECT 1 FROM kontortilknytninger kt
+                WHERE kt.aktor_id = a.aktor_id
+                AND ($1::varchar IS NULL OR kt.kontor_id = $1)
+                AND ($2::varchar IS NULL OR kt.kontor_type = $2)
+            ))
+            AND ($3::varchar[] IS NULL OR COALESCE((
+                SELECT pr.profilert_til FROM profileringer pr
+                WHERE pr.periode_id = k.periode_id
+                ORDER BY pr.tidspunkt DESC
+                LIMIT 1
+            ), 'INGEN') = ANY($3))
+        )
+        SELECT
+            COUNT(*)                                             AS total,
+            COUNT(*) FILTER (WHERE arbeidsledig_fra IS NULL)     AS is_null,
+            COUNT(*) FILTER (WHERE arbeidsledig_fra IS NOT NULL) AS is_not_null,
+            ARRAY(
+                SELECT COUNT(u.arbeidsledig_fra)
+                FROM unnest($4::int[]) WITH ORDINALITY AS g(grense, nr)
+                LEFT JOIN utvalg u ON u.arbeidsledig_fra < NOW() - make_interval(days => g.grense)
+                GROUP BY g.nr
+                ORDER BY g.nr
+            )                                                    AS over_grenser
+        FROM utvalg
+        "#,
+    )
+    .bind(filter.kontor_id.as_deref())
+    .bind(filter.kontor_type.as_deref())
+    .bind(filter.profilert_til.as_deref())
+    .bind(grenser)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(row)
+}
+
+/// Erstatter snapshot for `dato` med statistikk per profilering, gruppert på alle kombinasjoner
+/// av kontor og kontortype. Perioder telles én gang per gruppe selv om arbeidssøkeren har flere
+/// kontortilknytninger.
+#[tracing::instrument(skip(tx))]
+pub async fn replace_snapshot(
+    tx: &mut Transaction<'_, Postgres>,
+    dato: NaiveDate,
+    grenser: &[i32],
+) -> anyhow::Result<u64> {
+    tracing::debug!("Replace statistikk snapshot");
+    sqlx::query(
+        r#"
+        DELETE FROM statistikk_snapshots WHERE dato = $1
+        "#,
+    )
+    .bind(dato)
+    .execute(&mut **tx)
+    .await?;
+    let result = sqlx::query(
+        r#"
+        WITH base AS (
+            SELECT
+                k.periode_id,
+                k.arbeidsledig_fra,
+                kt.kontor_id,
+                kt.kontor_type,
+                COALESCE((
+                    SELECT pr.profilert_til FROM profileringer pr
+                    WHERE pr.periode_id = k.periode_id
+                    ORDER BY pr.tidspunkt DESC
+                    LIMIT 1
+                ), 'INGEN') AS profilert_til
+            FROM arbeidssoekere a
+            JOIN kartlegginger k ON a.id = k.arbeidssoeker_id
+            LEFT JOIN kontortilknytninger kt ON kt.aktor_id = a.aktor_id
+        ),
+        grupper AS (
+            SELECT
+                CASE WHEN GROUPING(b.kontor_id) = 1 THEN '' ELSE b.kontor_id END     AS kontor_id,
+                CASE WHEN GROUPING(b.kontor_type) = 1 THEN '' ELSE b.kontor_type END AS kontor_type,
+                b.profilert_til,
+                g.nr,
+                COUNT(DISTINCT b.periode_id) AS total,
+                COUNT(DISTINCT b.periode_id) FILTER (
+                    WHERE b.arbeidsledig_fra IS NULL
+                ) AS is_null,
+                COUNT(DISTINCT b.periode_id) FILTER (
+                    WHERE b.arbeidsledig_fra IS NOT NULL
+                ) AS is_not_null,
+                COUNT(DISTINCT b.periode_id) FILTER (
+                    WHERE b.arbeidsledig_fra < NOW() - make_interval(days => g.grense)
+                ) AS over_grense
+            FROM base b
+            CROSS JOIN unnest($2::int[]) WITH ORDINALITY AS g(grense, nr)
+            GROUP BY GROUPING SETS (
+                (b.profilert_til, g.nr),
+                (b.kontor_id, b.profilert_til, g.nr),
+                (b.kontor_type, b.profilert_til, g.nr),
+                (b.kontor_id, b.kontor_type, b.profilert_til, g.nr)
+            )
+            HAVING (GROUPING(b.kontor_id) = 1 OR b.kontor_id IS NOT NULL)
+            AND (GROUPING(b.kontor_type) = 1 OR b.kontor_type IS NOT NULL)
+        )
+        INSERT INTO statistikk_snapshots (
+            dato,
+            kontor_id,
+            kontor_type,
+            profilert_til,
+            grenser,
+            total,
+            is_null,
+            is_not_null,
+            over_grenser,
+            inserted_timestamp
+        )
+        SELECT
+            $1,
+            kontor_id,
+            kontor_type,
+            profilert_til,
+            $2,
+            MAX(total),
+            MAX(is_null),
+            MAX(is_not_null),
+            array_agg(over_grense ORDER BY nr),
+            $3
+        FROM grupper
+        GROUP BY kontor_id, kontor_type, profilert_til
+        "#,
+    )
+    .bind(dato)
+    .bind(grenser)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip(tx))]
+pub async fn select_snapshots(
+    tx: &mut Transaction<'_, Postgres>,
+    filter: &StatistikkFilter,
+    fra: NaiveDate,
+    til: NaiveDate,
+) -> anyhow::Result<Vec<StatistikkSnapshotRow>> {
+    tracing::debug!("Select statistikk snapshots");
+    let rows = sqlx::query_as::<_, StatistikkSnapshotRow>(
+        r#"
+        SELECT
+            dato,
+            grenser,
+            total,
+            is_null,
+            is_not_null,
+            over_grenser
+        FROM statistikk_snapshots
+        WHERE dato BETWEEN $1 AND $2
+        AND kontor_id = COALESCE($3, '')
+        AND kontor_type = COALESCE($4, '')
+        AND ($5::varchar[] IS NULL OR profilert_til = ANY($5))
+        ORDER BY dato, profilert_til
+        "#,
+    )
+    .bind(fra)
+    .bind(til)
+    .bind(filter.kontor_id.as_deref())
+    .bind(filter.kontor_type.as_deref())
+    .bind(filter.profilert_til.as_deref())
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows)
+}
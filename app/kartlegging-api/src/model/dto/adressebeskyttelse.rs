@@ -0,0 +1,112 @@
+use crate::model::parse::{enum_type_not_found, EnumTypeParseError};
+use async_graphql::Enum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
+
+/// Gradering av adressebeskyttelse fra PDL. Arbeidssøkere uten gradering har ingen verdi.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, EnumString, AsRefStr, ToSchema, Enum,
+)]
+#[strum(
+    serialize_all = "SCREAMING_SNAKE_CASE",
+    parse_err_fn = enum_type_not_found,
+    parse_err_ty = EnumTypeParseError
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Adressebeskyttelse {
+    StrengtFortroligUtland,
+    StrengtFortrolig,
+    Fortrolig,
+    Ugradert,
+    #[serde(other)]
+    UkjentVerdi,
+}
+
+impl Adressebeskyttelse {
+    /// Strengeste gradering først, slik at personer med flere graderinger får den strengeste
+    fn nivaa(&self) -> u8 {
+        match self {
+            Adressebeskyttelse::UkjentVerdi => 4,
+            Adressebeskyttelse::StrengtFortroligUtland => 3,
+            Adressebeskyttelse::StrengtFortrolig => 2,
+            Adressebeskyttelse::Fortrolig => 1,
+            Adressebeskyttelse::Ugradert => 0,
+        }
+    }
+
+    /// Lagret gradering, ukjente verdier blir `UkjentVerdi`
+    pub fn fra_lagret(verdi: &str) -> Self {
+        Self::from_str(verdi).unwrap_or(Adressebeskyttelse::UkjentVerdi)
+    }
+
+    /// Gradering fra PDL. Hver spørring i PDL-klienten har sin egen genererte enum, så verdien
+    /// leses fra serialisert form.
+    pub fn fra_pdl<T: Serialize>(gradering: &T) -> Self {
+        match serde_json::to_value(gradering) {
+            Ok(Value::String(verdi)) => Self::fra_lagret(&verdi),
+            _ => Adressebeskyttelse::UkjentVerdi,
+        }
+    }
+
+    /// Strengeste gradering, eller `None` hvis personen ikke har noen gradering
+    pub fn strengeste(graderinger: impl IntoIterator<Item = Self>) -> Option<Self> {
+        graderinger
+            .into_iter()
+            .filter(|gradering| *gradering != Adressebeskyttelse::Ugradert)
+            .max_by_key(|gradering| gradering.nivaa())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strengeste() {
+        assert_eq!(Adressebeskyttelse::strengeste([]), None);
+        assert_eq!(
+            Adressebeskyttelse::strengeste([Adressebeskyttelse::Ugradert]),
+            None
+        );
+        assert_eq!(
+            Adressebeskyttelse::strengeste([
+                Adressebeskyttelse::Fortrolig,
+                Adressebeskyttelse::StrengtFortroligUtland,
+                Adressebeskyttelse::StrengtFortrolig,
+            ]),
+            Some(Adressebeskyttelse::StrengtFortroligUtland)
+        );
+    }
+
+    #[test]
+    fn test_fra_str() {
+        assert_eq!(
+            Adressebeskyttelse::from_str("STRENGT_FORTROLIG_UTLAND").unwrap(),
+            Adressebeskyttelse::StrengtFortroligUtland
+        );
+        assert!(Adressebeskyttelse::from_str("HEMMELIG").is_err());
+        assert_eq!(
+            Adressebeskyttelse::fra_lagret("HEMMELIG"),
+            Adressebeskyttelse::UkjentVerdi
+        );
+    }
+
+    #[test]
+    fn test_fra_pdl() {
+        assert_eq!(
+            Adressebeskyttelse::fra_pdl(&"FORTROLIG"),
+            Adressebeskyttelse::Fortrolig
+        );
+        assert_eq!(
+            Adressebeskyttelse::fra_pdl(&"HEMMELIG"),
+            Adressebeskyttelse::UkjentVerdi
+        );
+        assert_eq!(
+            Adressebeskyttelse::fra_pdl(&42),
+            Adressebeskyttelse::UkjentVerdi
+        );
+    }
+}
@@ -1,9 +1,11 @@
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
 use crate::model::dto::kontortilknytning::Kontortilknytning;
 use crate::model::dto::ledighetsperiode::Ledighetsperiode;
 use serde::Serialize;
+use utoipa::ToSchema;
 
 #[serde_with::skip_serializing_none]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Arbeidssoeker {
     pub id: i64,
@@ -13,6 +15,7 @@ pub struct Arbeidssoeker {
     pub fornavn: Option<String>,
     pub mellomnavn: Option<String>,
     pub etternavn: Option<String>,
+    pub adressebeskyttelse: Option<Adressebeskyttelse>,
     pub ledighetsperioder: Vec<Ledighetsperiode>,
     pub kontortilknytninger: Vec<Kontortilknytning>,
 }
@@ -36,6 +39,7 @@ impl Arbeidssoeker {
             fornavn,
             mellomnavn,
             etternavn,
+            adressebeskyttelse: None,
             ledighetsperioder,
             kontortilknytninger,
         }
@@ -50,8 +54,17 @@ impl Arbeidssoeker {
             fornavn: None,
             mellomnavn: None,
             etternavn: None,
+            adressebeskyttelse: None,
             ledighetsperioder: Vec::new(),
             kontortilknytninger: Vec::new(),
         }
     }
+
+    /// Skjuler navn og kontor for personer med adressebeskyttelse som kaller ikke har tilgang til
+    pub fn skjerm(&mut self) {
+        self.fornavn = None;
+        self.mellomnavn = None;
+        self.etternavn = None;
+        self.kontortilknytninger.clear();
+    }
 }
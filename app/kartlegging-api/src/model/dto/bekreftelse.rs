@@ -1,11 +1,13 @@
 use crate::model::parse::{enum_type_not_found, EnumTypeParseError};
+use async_graphql::{Enum, SimpleObject};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema, SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct Bekreftelse {
     pub id: Uuid,
@@ -16,7 +18,9 @@ pub struct Bekreftelse {
     pub bekreftelsesloesning: Bekreftelsesloesning,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, EnumString, AsRefStr)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, EnumString, AsRefStr, ToSchema, Enum,
+)]
 #[strum(
     serialize_all = "SCREAMING_SNAKE_CASE",
     parse_err_fn = enum_type_not_found,
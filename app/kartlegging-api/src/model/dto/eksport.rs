@@ -0,0 +1,107 @@
+use crate::model::dao::arbeidssoeker::EksportRow;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Kolonnene i eksporten, i samme rekkefølge som `EksportRad::felter`
+pub const EKSPORT_KOLONNER: [&str; 7] = [
+    "Identitetsnummer",
+    "Etternavn",
+    "Fornavn",
+    "Mellomnavn",
+    "Ledig siden",
+    "Siste profilering",
+    "Siste egenvurdering",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EksportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl EksportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            EksportFormat::Csv => "text/csv; charset=utf-8",
+            EksportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    /// Filnavn for `Content-Disposition`. Bare alfanumeriske tegn fra kontor-id brukes.
+    pub fn filnavn(&self, kontor_id: &str) -> String {
+        let kontor_id: String = kontor_id
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect();
+        let endelse = match self {
+            EksportFormat::Csv => "csv",
+            EksportFormat::Xlsx => "xlsx",
+        };
+        format!("arbeidssoekere-{}.{}", kontor_id, endelse)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct EksportParams {
+    pub format: EksportFormat,
+}
+
+/// XLSX-fil med én rad per arbeidssøker
+#[derive(ToSchema)]
+#[schema(value_type = String, format = Binary)]
+pub struct XlsxFil(pub Vec<u8>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EksportRad {
+    pub identitetsnummer: String,
+    pub etternavn: String,
+    pub fornavn: String,
+    pub mellomnavn: String,
+    /// Dato på formen `YYYY-MM-DD`
+    pub ledig_siden: String,
+    pub siste_profilering: String,
+    pub siste_egenvurdering: String,
+}
+
+impl EksportRad {
+    pub fn felter(&self) -> [&str; 7] {
+        [
+            &self.identitetsnummer,
+            &self.etternavn,
+            &self.fornavn,
+            &self.mellomnavn,
+            &self.ledig_siden,
+            &self.siste_profilering,
+            &self.siste_egenvurdering,
+        ]
+    }
+
+    /// Skjuler navn for personer med adressebeskyttelse som kaller ikke har tilgang til
+    pub fn skjerm(&mut self) {
+        self.etternavn.clear();
+        self.fornavn.clear();
+        self.mellomnavn.clear();
+    }
+}
+
+impl From<EksportRow> for EksportRad {
+    fn from(row: EksportRow) -> Self {
+        Self {
+            identitetsnummer: row.identitetsnummer,
+            etternavn: row.etternavn.unwrap_or_default(),
+            fornavn: row.fornavn.unwrap_or_default(),
+            mellomnavn: row.mellomnavn.unwrap_or_default(),
+            ledig_siden: row
+                .arbeidsledig_fra
+                .map(|arbeidsledig_fra| arbeidsledig_fra.date().to_string())
+                .unwrap_or_default(),
+            siste_profilering: row.profilert_til.unwrap_or_default(),
+            siste_egenvurdering: row.egenvurdert_til.unwrap_or_default(),
+        }
+    }
+}
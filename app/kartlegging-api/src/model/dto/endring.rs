@@ -0,0 +1,162 @@
+use crate::model::dao::endring::EndringRow;
+use crate::model::parse::{enum_type_not_found, EnumTypeParseError};
+use chrono::{DateTime, Utc};
+use paw_error_handling::problem_details::ProblemDetails;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum::{AsRefStr, EnumString};
+use utoipa::{IntoParams, ToSchema};
+
+pub const DEFAULT_ENDRINGER_LIMIT: i32 = 500;
+pub const MAX_ENDRINGER_LIMIT: i32 = 1000;
+
+/// Hvilke data som ble endret for arbeidssøkeren
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, EnumString, AsRefStr, ToSchema,
+)]
+#[strum(
+    serialize_all = "SCREAMING_SNAKE_CASE",
+    parse_err_fn = enum_type_not_found,
+    parse_err_ty = EnumTypeParseError
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EndringType {
+    Periode,
+    Opplysninger,
+    Profilering,
+    Egenvurdering,
+    Bekreftelse,
+    BekreftelsePaaVegneAv,
+    Kontortilknytning,
+    Identitet,
+    #[serde(other)]
+    UkjentVerdi,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Endring {
+    /// Cursor for endringen, stigende
+    pub id: i64,
+    pub arbeidssoeker_id: i64,
+    pub identitetsnummer: String,
+    pub endring_type: EndringType,
+    pub tidspunkt: DateTime<Utc>,
+}
+
+impl TryFrom<EndringRow> for Endring {
+    type Error = anyhow::Error;
+
+    fn try_from(row: EndringRow) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: row.id,
+            arbeidssoeker_id: row.arbeidssoeker_id,
+            identitetsnummer: row.identitetsnummer,
+            endring_type: EndringType::from_str(row.endring_type.as_str())?,
+            tidspunkt: row.tidspunkt,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct EndringerParams {
+    pub kontor_id: String,
+    /// Id for siste mottatte endring. Uten cursor hentes endringer fra starten.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<i64>,
+    /// Maks antall endringer, standard er 500
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl EndringerParams {
+    pub fn validate(&self, path: &str) -> Result<(), ProblemDetails> {
+        if self.cursor.is_some_and(|cursor| cursor < 0) {
+            return Err(ProblemDetails::validation_error(
+                path,
+                "Felt 'cursor' kan ikke være negativ",
+            ));
+        }
+        if let Some(limit) = self.limit
+            && !(1..=MAX_ENDRINGER_LIMIT).contains(&limit)
+        {
+            return Err(ProblemDetails::validation_error(
+                path,
+                format!("Felt 'limit' har feil størrelse: {}", limit).as_str(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn limit(&self) -> i32 {
+        self.limit.unwrap_or(DEFAULT_ENDRINGER_LIMIT)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct EndringsstroemParams {
+    pub kontor_id: String,
+    /// Id for siste mottatte endring. Overstyres av `Last-Event-ID`. Uten cursor sendes bare
+    /// endringer registrert etter at strømmen startet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<i64>,
+}
+
+impl EndringsstroemParams {
+    pub fn validate(&self, path: &str) -> Result<(), ProblemDetails> {
+        if self.cursor.is_some_and(|cursor| cursor < 0) {
+            return Err(ProblemDetails::validation_error(
+                path,
+                "Felt 'cursor' kan ikke være negativ",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EndringerResponse {
+    pub endringer: Vec<Endring>,
+    /// Sendes som `cursor` i neste kall. Uendret når det ikke er nye endringer.
+    pub cursor: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(cursor: Option<i64>, limit: Option<i32>) -> EndringerParams {
+        EndringerParams {
+            kontor_id: "1337".to_string(),
+            cursor,
+            limit,
+        }
+    }
+
+    #[test]
+    fn test_validate_endringer_params() {
+        assert!(params(None, None).validate("/").is_ok());
+        assert!(params(Some(42), Some(MAX_ENDRINGER_LIMIT))
+            .validate("/")
+            .is_ok());
+        assert!(params(Some(-1), None).validate("/").is_err());
+        assert!(params(None, Some(0)).validate("/").is_err());
+        assert!(params(None, Some(MAX_ENDRINGER_LIMIT + 1))
+            .validate("/")
+            .is_err());
+    }
+
+    #[test]
+    fn test_endring_type_fra_str() {
+        assert_eq!(
+            EndringType::from_str("BEKREFTELSE_PAA_VEGNE_AV").unwrap(),
+            EndringType::BekreftelsePaaVegneAv
+        );
+        assert!(EndringType::from_str("UKJENT").is_err());
+    }
+}
@@ -1,8 +1,23 @@
 use crate::model::parse::{enum_type_not_found, EnumTypeParseError};
+use async_graphql::{Enum, SimpleObject};
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, EnumString, AsRefStr)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Default,
+    EnumString,
+    AsRefStr,
+    ToSchema,
+    Enum,
+)]
 #[strum(
     serialize_all = "SCREAMING_SNAKE_CASE",
     parse_err_fn = enum_type_not_found,
@@ -18,7 +33,21 @@ pub enum KontorType {
     UkjentVerdi,
 }
 
-#[derive(Debug, Serialize)]
+/// Hva som skjedde med en kontortilknytning i historikken
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, EnumString, AsRefStr, ToSchema)]
+#[strum(
+    serialize_all = "SCREAMING_SNAKE_CASE",
+    parse_err_fn = enum_type_not_found,
+    parse_err_ty = EnumTypeParseError
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum KontortilknytningEndringType {
+    Startet,
+    Endret,
+    Avsluttet,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct Kontortilknytning {
     pub kontor_id: String,
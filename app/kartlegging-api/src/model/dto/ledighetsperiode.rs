@@ -5,9 +5,10 @@ use crate::model::dto::periode::Periode;
 use crate::model::dto::profilering::Profilering;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use utoipa::ToSchema;
 
 #[serde_with::skip_serializing_none]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Ledighetsperiode {
     pub ledig_siden: Option<DateTime<Utc>>,
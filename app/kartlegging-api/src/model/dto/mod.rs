@@ -1,11 +1,16 @@
+pub mod adressebeskyttelse;
 pub mod arbeidssoeker;
 pub mod bekreftelse;
 pub mod egenvurdering;
+pub mod eksport;
+pub mod endring;
 pub mod kontortilknytning;
 pub mod ledighetsperiode;
 pub mod navn;
 pub mod opplysninger;
 pub mod periode;
 pub mod profilering;
+pub mod reprojeksjon;
 pub mod request;
 pub mod response;
+pub mod tidslinje;
@@ -1,10 +1,12 @@
 use crate::model::parse::{enum_type_not_found, EnumTypeParseError};
+use async_graphql::{Enum, SimpleObject};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema, SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct Opplysninger {
     pub id: Uuid,
@@ -12,7 +14,20 @@ pub struct Opplysninger {
     pub tidspunkt: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, EnumString, AsRefStr)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Default,
+    EnumString,
+    AsRefStr,
+    ToSchema,
+    Enum,
+)]
 #[strum(
     serialize_all = "SCREAMING_SNAKE_CASE",
     parse_err_fn = enum_type_not_found,
@@ -26,6 +41,7 @@ pub enum Jobbsituasjon {
     ErPermittert,
     AldriHattJobb,
     #[serde(rename = "IKKE_VAERT_I_JOBB_SISTE_2_AAR")]
+    #[graphql(name = "IKKE_VAERT_I_JOBB_SISTE_2_AAR")]
     IkkeVaertIJobbSiste2Aar,
     AkkuratFullfortUtdanning,
     VilBytteJobb,
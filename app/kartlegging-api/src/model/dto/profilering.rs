@@ -1,11 +1,13 @@
 use crate::model::parse::{enum_type_not_found, EnumTypeParseError};
+use async_graphql::{Enum, SimpleObject};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema, SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct Profilering {
     pub id: Uuid,
@@ -14,7 +16,18 @@ pub struct Profilering {
 }
 
 #[derive(
-    Debug, Clone, Serialize, Deserialize, PartialEq, Default, EnumString, AsRefStr,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Default,
+    EnumString,
+    AsRefStr,
+    ToSchema,
+    Enum,
 )]
 #[strum(
     serialize_all = "SCREAMING_SNAKE_CASE",
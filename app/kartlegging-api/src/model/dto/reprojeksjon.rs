@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use paw_error_handling::problem_details::ProblemDetails;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const DEFAULT_REPROJEKSJON_BATCH_SIZE: i64 = 500;
+pub const MAX_REPROJEKSJON_BATCH_SIZE: i64 = 10_000;
+/// Maks antall avvik som tas med i statusen, resten telles bare
+pub const MAX_AVVIK_EKSEMPLER: usize = 100;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReprojeksjonModus {
+    /// Rapporterer avvik uten å skrive
+    Verifiser,
+    /// Skriver beregnede verdier for alle avvik
+    Skriv,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReprojeksjonTilstand {
+    Kjoerer,
+    Fullfoert,
+    Avbrutt,
+    Feilet,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReprojeksjonRequest {
+    pub modus: ReprojeksjonModus,
+    pub batch_size: Option<i64>,
+}
+
+impl ReprojeksjonRequest {
+    pub fn validate(&self, path: &str) -> Result<(), ProblemDetails> {
+        if let Some(batch_size) = self.batch_size
+            && !(1..=MAX_REPROJEKSJON_BATCH_SIZE).contains(&batch_size)
+        {
+            return Err(ProblemDetails::validation_error(
+                path,
+                &format!(
+                    "batchSize må være mellom 1 og {}",
+                    MAX_REPROJEKSJON_BATCH_SIZE
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn batch_size(&self) -> i64 {
+        self.batch_size.unwrap_or(DEFAULT_REPROJEKSJON_BATCH_SIZE)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KartleggingVerdier {
+    pub arbeidssoeker_fra: DateTime<Utc>,
+    pub arbeidssoeker_til: Option<DateTime<Utc>>,
+    pub arbeidsledig_fra: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KartleggingAvvik {
+    pub periode_id: Uuid,
+    pub lagret: KartleggingVerdier,
+    pub beregnet: KartleggingVerdier,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReprojeksjonStatus {
+    pub modus: ReprojeksjonModus,
+    pub tilstand: ReprojeksjonTilstand,
+    pub startet: DateTime<Utc>,
+    pub avsluttet: Option<DateTime<Utc>>,
+    pub batch_size: i64,
+    pub arbeidssoekere_totalt: i64,
+    pub arbeidssoekere_behandlet: i64,
+    pub kartlegginger_kontrollert: i64,
+    pub avvik: i64,
+    /// Avvik som er skrevet, alltid 0 ved verifisering
+    pub oppdatert: i64,
+    /// Avvik som ikke ble skrevet fordi kartleggingen ble endret underveis
+    pub endret_underveis: i64,
+    pub avvik_eksempler: Vec<KartleggingAvvik>,
+    pub feil: Option<String>,
+}
+
+impl ReprojeksjonStatus {
+    pub fn new(modus: ReprojeksjonModus, batch_size: i64) -> Self {
+        Self {
+            modus,
+            tilstand: ReprojeksjonTilstand::Kjoerer,
+            startet: Utc::now(),
+            avsluttet: None,
+            batch_size,
+            arbeidssoekere_totalt: 0,
+            arbeidssoekere_behandlet: 0,
+            kartlegginger_kontrollert: 0,
+            avvik: 0,
+            oppdatert: 0,
+            endret_underveis: 0,
+            avvik_eksempler: Vec::new(),
+            feil: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_batch_size() {
+        let request: ReprojeksjonRequest =
+            serde_json::from_str(r#"{"modus": "VERIFISER"}"#).unwrap();
+        assert_eq!(request.modus, ReprojeksjonModus::Verifiser);
+        assert_eq!(request.batch_size(), DEFAULT_REPROJEKSJON_BATCH_SIZE);
+        assert!(request.validate("/test").is_ok());
+
+        let request: ReprojeksjonRequest =
+            serde_json::from_str(r#"{"modus": "SKRIV", "batchSize": 0}"#).unwrap();
+        assert!(request.validate("/test").is_err());
+    }
+}
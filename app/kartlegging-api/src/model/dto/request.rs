@@ -1,10 +1,22 @@
+use crate::model::cursor::Cursor;
 use crate::model::dto::kontortilknytning::KontorType;
+use crate::model::dto::profilering::ProfilertTil;
 use crate::model::parse::{enum_type_not_found, EnumTypeParseError};
-use crate::model::sort::SortOrder;
-use chrono::NaiveDate;
+use crate::model::sort::{SortField, SortOrder};
+use async_graphql::{Enum, InputObject};
+use chrono::{Duration, NaiveDate};
 use paw_error_handling::problem_details::ProblemDetails;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use strum::{AsRefStr, EnumString};
+use utoipa::{IntoParams, ToSchema};
+
+pub const DEFAULT_PAGE_SIZE: i32 = 1000;
+pub const MAX_PAGE_SIZE: i32 = 1000;
+pub const MAX_STATISTICS_GRENSER: usize = 20;
+pub const MAX_STATISTICS_GRENSE_DAGER: i32 = 3650;
+pub const DEFAULT_TIDSSERIE_DAGER: i64 = 30;
+pub const MAX_TIDSSERIE_DAGER: i64 = 731;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, EnumString, AsRefStr)]
 #[strum(
@@ -16,12 +28,16 @@ use strum::{AsRefStr, EnumString};
 pub enum QueryType {
     Identitetsnummer,
     TilknyttetKontor,
+    Tidslinje,
+    Eksport,
+    Graphql,
+    Endringer,
     #[serde(other)]
     #[default]
     UkjentVerdi,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type")]
 pub enum QueryRequest {
     #[serde(rename = "IDENTITETSNUMMER")]
@@ -30,7 +46,7 @@ pub enum QueryRequest {
     TilknyttetKontor(TilknyttetKontorQueryRequest),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IdentitetsnummerQueryRequest {
     pub identitetsnummer: String,
@@ -43,6 +59,12 @@ impl IdentitetsnummerQueryRequest {
             if let Err(error) = paging.validate(path) {
                 return Err(error);
             }
+            if paging.cursor.is_some() {
+                return Err(ProblemDetails::validation_error(
+                    path,
+                    "Felt 'cursor' støttes bare for TILKNYTTET_KONTOR",
+                ));
+            }
         }
         if self.identitetsnummer.len() != 11 {
             let error = ProblemDetails::validation_error(
@@ -60,12 +82,45 @@ impl IdentitetsnummerQueryRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TidslinjeRequest {
+    pub identitetsnummer: String,
+}
+
+impl TidslinjeRequest {
+    pub fn validate(&self, path: &str) -> Result<(), ProblemDetails> {
+        if self.identitetsnummer.len() != 11 {
+            return Err(ProblemDetails::validation_error(
+                path,
+                format!(
+                    "Felt 'identitetsnummer' har feil lengde: {}",
+                    self.identitetsnummer.len()
+                )
+                .as_str(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, InputObject)]
 #[serde(rename_all = "camelCase")]
 pub struct TilknyttetKontorQueryRequest {
     pub kontor_id: String,
     pub kontor_type: Option<KontorType>,
     pub ledig_siden: Option<NaiveDate>,
+    /// Øvre grense (eksklusiv) for når arbeidssøkeren ble ledig
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ledig_siden_til: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub periode_status: Option<PeriodeStatus>,
+    /// Siste profilering for perioden må være en av disse
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profilert_til: Option<Vec<ProfilertTil>>,
+    /// Om siste egenvurdering for perioden avviker fra profileringen den vurderer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egenvurdering_avvik: Option<bool>,
     pub paging: Option<PagingRequest>,
 }
 
@@ -74,16 +129,59 @@ impl TilknyttetKontorQueryRequest {
         if let Some(paging) = &self.paging {
             paging.validate(path)?
         }
+        if let (Some(fra), Some(til)) = (self.ledig_siden, self.ledig_siden_til)
+            && fra >= til
+        {
+            return Err(ProblemDetails::validation_error(
+                path,
+                "Felt 'ledigSiden' må være før 'ledigSidenTil'",
+            ));
+        }
+        if self.profilert_til.as_ref().is_some_and(Vec::is_empty) {
+            return Err(ProblemDetails::validation_error(
+                path,
+                "Felt 'profilertTil' kan ikke være tomt",
+            ));
+        }
         Ok(())
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema, Enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PeriodeStatus {
+    Aktiv,
+    Avsluttet,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, InputObject)]
 #[serde(rename_all = "camelCase")]
 pub struct PagingRequest {
     pub page: i32,
     pub page_size: i32,
     pub sort_order: SortOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_field: Option<SortField>,
+    /// `nextCursor` fra forrige side, med samme `sortField` og `sortOrder`. Når cursor er satt
+    /// ignoreres `page`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Om `totalCount` skal beregnes. Standard er bare for første side.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_total_count: Option<bool>,
+}
+
+impl Default for PagingRequest {
+    fn default() -> Self {
+        PagingRequest {
+            page: 1,
+            page_size: DEFAULT_PAGE_SIZE,
+            sort_order: SortOrder::Ascending,
+            sort_field: None,
+            cursor: None,
+            include_total_count: None,
+        }
+    }
 }
 
 impl PagingRequest {
@@ -95,6 +193,17 @@ impl PagingRequest {
         self.page_size
     }
 
+    pub fn decoded_cursor(&self) -> Option<Cursor> {
+        self.cursor
+            .as_deref()
+            .and_then(|cursor| Cursor::decode(cursor).ok())
+    }
+
+    pub fn include_total_count(&self) -> bool {
+        self.include_total_count
+            .unwrap_or(self.cursor.is_none())
+    }
+
     pub fn validate(&self, path: &str) -> Result<(), ProblemDetails> {
         if self.page < 1 {
             let error = ProblemDetails::validation_error(
@@ -102,18 +211,154 @@ impl PagingRequest {
                 format!("Felt 'page' har feil størrelse: {}", self.page).as_str(),
             );
             Err(error)
-        } else if self.page_size < 1 {
+        } else if self.page_size < 1 || self.page_size > MAX_PAGE_SIZE {
             let error = ProblemDetails::validation_error(
                 path,
                 format!("Felt 'page_size' har feil størrelse: {}", self.page_size).as_str(),
             );
             Err(error)
         } else {
-            Ok(())
+            match self.cursor.as_deref().map(Cursor::decode) {
+                Some(Err(error)) => Err(ProblemDetails::validation_error(path, &error.to_string())),
+                Some(Ok(cursor))
+                    if cursor.sort_field != self.sort_field.unwrap_or_default()
+                        || cursor.sort_order != self.sort_order =>
+                {
+                    Err(ProblemDetails::validation_error(
+                        path,
+                        "Felt 'cursor' er laget for en annen 'sortField' eller 'sortOrder'",
+                    ))
+                }
+                _ => Ok(()),
+            }
         }
     }
 }
 
+/// Filtre for statistikk som query-parametre. Lister er kommaseparerte.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct StatisticsRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kontor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kontor_type: Option<KontorType>,
+    /// Siste profilering for perioden må være en av disse, f.eks. `ANTATT_GODE_MULIGHETER`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profilert_til: Option<String>,
+    /// Grenser i antall dager ledig, f.eks. `30,90,365`. Standard er fra config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grenser: Option<String>,
+}
+
+impl StatisticsRequest {
+    pub fn validate(&self, path: &str) -> Result<(), ProblemDetails> {
+        if self.kontor_type == Some(KontorType::UkjentVerdi) {
+            return Err(ProblemDetails::validation_error(
+                path,
+                "Felt 'kontorType' har ukjent verdi",
+            ));
+        }
+        if let Some(profilert_til) = &self.profilert_til {
+            for verdi in profilert_til.split(',') {
+                if ProfilertTil::from_str(verdi.trim()).is_err() {
+                    return Err(ProblemDetails::validation_error(
+                        path,
+                        format!("Felt 'profilertTil' har ukjent verdi: {}", verdi).as_str(),
+                    ));
+                }
+            }
+        }
+        if let Some(grenser) = &self.grenser {
+            let antall = grenser.split(',').count();
+            if antall > MAX_STATISTICS_GRENSER {
+                return Err(ProblemDetails::validation_error(
+                    path,
+                    format!("Felt 'grenser' har for mange verdier: {}", antall).as_str(),
+                ));
+            }
+            for verdi in grenser.split(',') {
+                match verdi.trim().parse::<i32>() {
+                    Ok(grense) if (1..=MAX_STATISTICS_GRENSE_DAGER).contains(&grense) => {}
+                    _ => {
+                        return Err(ProblemDetails::validation_error(
+                            path,
+                            format!("Felt 'grenser' har ugyldig verdi: {}", verdi).as_str(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Profileringer fra `profilert_til`, ukjente verdier er fjernet av `validate`
+    pub fn profilert_til(&self) -> Option<Vec<ProfilertTil>> {
+        self.profilert_til.as_ref().map(|profilert_til| {
+            profilert_til
+                .split(',')
+                .filter_map(|verdi| ProfilertTil::from_str(verdi.trim()).ok())
+                .collect()
+        })
+    }
+
+    /// Sorterte grenser uten duplikater, eller `None` for standard
+    pub fn grenser(&self) -> Option<Vec<i32>> {
+        self.grenser.as_ref().map(|grenser| {
+            let mut grenser = grenser
+                .split(',')
+                .filter_map(|verdi| verdi.trim().parse::<i32>().ok())
+                .collect::<Vec<i32>>();
+            grenser.sort();
+            grenser.dedup();
+            grenser
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsTidsserieRequest {
+    /// Første dato, standard er 30 dager før `til`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fra: Option<NaiveDate>,
+    /// Siste dato (inklusiv), standard er i dag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub til: Option<NaiveDate>,
+    #[serde(flatten)]
+    pub filter: StatisticsRequest,
+}
+
+impl StatisticsTidsserieRequest {
+    pub fn validate(&self, path: &str, idag: NaiveDate) -> Result<(), ProblemDetails> {
+        self.filter.validate(path)?;
+        let (fra, til) = self.periode(idag);
+        if fra > til {
+            return Err(ProblemDetails::validation_error(
+                path,
+                "Felt 'fra' må være før eller lik 'til'",
+            ));
+        }
+        if (til - fra).num_days() >= MAX_TIDSSERIE_DAGER {
+            return Err(ProblemDetails::validation_error(
+                path,
+                format!("Tidsserien kan ikke være lengre enn {} dager", MAX_TIDSSERIE_DAGER)
+                    .as_str(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn periode(&self, idag: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let til = self.til.unwrap_or(idag);
+        let fra = self
+            .fra
+            .unwrap_or(til - Duration::days(DEFAULT_TIDSSERIE_DAGER));
+        (fra, til)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +371,7 @@ mod tests {
                 page: 1,
                 page_size: 10,
                 sort_order: SortOrder::Descending,
+                ..Default::default()
             }),
         };
 
@@ -198,4 +444,164 @@ mod tests {
             _ => panic!("Wrong query"),
         }
     }
+
+    #[test]
+    fn test_deserialize_tilknyttet_kontor_med_filtre_og_cursor() {
+        let json = r#"
+        {
+            "type": "TILKNYTTET_KONTOR",
+            "kontorId": "12345",
+            "ledigSiden": "2025-01-01",
+            "ledigSidenTil": "2026-01-01",
+            "periodeStatus": "AVSLUTTET",
+            "profilertTil": ["ANTATT_GODE_MULIGHETER", "OPPGITT_HINDRINGER"],
+            "egenvurderingAvvik": true,
+            "paging": {
+                "page": 1,
+                "pageSize": 50,
+                "sortOrder": "DESC",
+                "sortField": "ARBEIDSLEDIG_FRA",
+                "includeTotalCount": false
+            }
+        }
+        "#;
+
+        let request: QueryRequest =
+            serde_json::from_str(json).expect("Failed to deserialize request");
+
+        match request {
+            QueryRequest::TilknyttetKontor(query) => {
+                assert!(query.validate("/test").is_ok());
+                assert_eq!(query.periode_status, Some(PeriodeStatus::Avsluttet));
+                assert_eq!(
+                    query.profilert_til,
+                    Some(vec![
+                        ProfilertTil::AntattGodeMuligheter,
+                        ProfilertTil::OppgittHindringer
+                    ])
+                );
+                assert_eq!(query.egenvurdering_avvik, Some(true));
+                let paging = query.paging.unwrap();
+                assert_eq!(paging.sort_field, Some(SortField::ArbeidsledigFra));
+                assert!(!paging.include_total_count());
+            }
+            _ => panic!("Wrong query"),
+        }
+    }
+
+    #[test]
+    fn test_validate_paging() {
+        let paging = PagingRequest::default();
+        assert!(paging.validate("/test").is_ok());
+        assert!(paging.include_total_count());
+
+        let for_stor_side = PagingRequest {
+            page_size: MAX_PAGE_SIZE + 1,
+            ..Default::default()
+        };
+        assert!(for_stor_side.validate("/test").is_err());
+
+        let ugyldig_cursor = PagingRequest {
+            cursor: Some("ugyldig".to_string()),
+            ..Default::default()
+        };
+        assert!(ugyldig_cursor.validate("/test").is_err());
+
+        let cursor = Cursor {
+            sort_field: SortField::ArbeidsledigFra,
+            sort_order: SortOrder::Descending,
+            sort_verdi: NaiveDate::from_ymd_opt(2026, 1, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap(),
+            periode_id: uuid::Uuid::new_v4(),
+        }
+        .encode();
+        let med_cursor = PagingRequest {
+            sort_order: SortOrder::Descending,
+            sort_field: Some(SortField::ArbeidsledigFra),
+            cursor: Some(cursor),
+            ..Default::default()
+        };
+        assert!(med_cursor.validate("/test").is_ok());
+        let annen_sort_field = PagingRequest {
+            sort_field: None,
+            ..med_cursor.clone()
+        };
+        assert!(annen_sort_field.validate("/test").is_err());
+        let annen_sort_order = PagingRequest {
+            sort_order: SortOrder::Ascending,
+            ..med_cursor
+        };
+        assert!(annen_sort_order.validate("/test").is_err());
+    }
+
+    #[test]
+    fn test_validate_ledig_siden_intervall() {
+        let query = TilknyttetKontorQueryRequest {
+            kontor_id: "12345".to_string(),
+            kontor_type: None,
+            ledig_siden: NaiveDate::from_ymd_opt(2026, 1, 1),
+            ledig_siden_til: NaiveDate::from_ymd_opt(2025, 1, 1),
+            periode_status: None,
+            profilert_til: None,
+            egenvurdering_avvik: None,
+            paging: None,
+        };
+        assert!(query.validate("/test").is_err());
+    }
+
+    #[test]
+    fn test_statistics_request() {
+        let request = StatisticsRequest {
+            kontor_id: Some("1337".to_string()),
+            kontor_type: Some(KontorType::Arbeidsoppfolging),
+            profilert_til: Some("ANTATT_GODE_MULIGHETER, OPPGITT_HINDRINGER".to_string()),
+            grenser: Some("90,30,90,365".to_string()),
+        };
+        assert!(request.validate("/test").is_ok());
+        assert_eq!(
+            request.profilert_til(),
+            Some(vec![
+                ProfilertTil::AntattGodeMuligheter,
+                ProfilertTil::OppgittHindringer
+            ])
+        );
+        assert_eq!(request.grenser(), Some(vec![30, 90, 365]));
+
+        let request = StatisticsRequest {
+            profilert_til: Some("IKKE_EN_PROFILERING".to_string()),
+            ..Default::default()
+        };
+        assert!(request.validate("/test").is_err());
+
+        let request = StatisticsRequest {
+            grenser: Some("30,0".to_string()),
+            ..Default::default()
+        };
+        assert!(request.validate("/test").is_err());
+    }
+
+    #[test]
+    fn test_statistics_tidsserie_request() {
+        let idag = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let request = StatisticsTidsserieRequest::default();
+        assert!(request.validate("/test", idag).is_ok());
+        assert_eq!(
+            request.periode(idag),
+            (NaiveDate::from_ymd_opt(2026, 9, 19).unwrap(), idag)
+        );
+
+        let request = StatisticsTidsserieRequest {
+            fra: Some(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap()),
+            ..Default::default()
+        };
+        assert!(request.validate("/test", idag).is_err());
+
+        let request = StatisticsTidsserieRequest {
+            fra: Some(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()),
+            ..Default::default()
+        };
+        assert!(request.validate("/test", idag).is_err());
+    }
 }
@@ -1,33 +1,68 @@
 use crate::model::dto::arbeidssoeker::Arbeidssoeker;
+use crate::model::dto::tidslinje::ArbeidssoekerTidslinje;
 use crate::model::sort::SortOrder;
+use async_graphql::SimpleObject;
+use chrono::NaiveDate;
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct KartleggingResponse {
     pub arbeidssoekere: Vec<Arbeidssoeker>,
     pub paging: PagingResponse,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TidslinjeResponse {
+    pub arbeidssoekere: Vec<ArbeidssoekerTidslinje>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StatisticsResponse {
     pub total: i64,
     pub is_null: i64,
     pub is_not_null: i64,
-    pub over_30_days: i64,
-    pub over_60_days: i64,
-    pub over_90_days: i64,
-    pub over_180_days: i64,
-    pub over_365_days: i64,
+    pub ledig_over: Vec<LedigOver>,
+}
+
+/// Antall arbeidssøkere som har vært ledige i mer enn `dager`
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedigOver {
+    pub dager: i32,
+    pub antall: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsTidsserieResponse {
+    /// Ett punkt per dag med snapshot, sortert på dato
+    pub tidsserie: Vec<StatisticsPunkt>,
+}
+
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsPunkt {
+    pub dato: NaiveDate,
+    pub total: i64,
+    pub is_null: i64,
+    pub is_not_null: i64,
+    /// Grenser som ikke fantes da snapshotet ble tatt er utelatt
+    pub ledig_over: Vec<LedigOver>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema, SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct PagingResponse {
     pub page: i32,
     pub page_size: i32,
     pub hit_size: i32,
-    pub total_count: i64,
+    /// Beregnes bare når det er bedt om, se `PagingRequest::include_total_count`
+    pub total_count: Option<i64>,
     pub sort_order: SortOrder,
+    /// Cursor for neste side, `None` når dette er siste side
+    pub next_cursor: Option<String>,
 }
@@ -0,0 +1,134 @@
+use crate::model::dto::adressebeskyttelse::Adressebeskyttelse;
+use crate::model::dto::bekreftelse::Bekreftelsesloesning;
+use crate::model::dto::kontortilknytning::{KontorType, KontortilknytningEndringType};
+use crate::model::dto::opplysninger::Jobbsituasjon;
+use crate::model::dto::profilering::ProfilertTil;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ArbeidssoekerTidslinje {
+    pub id: i64,
+    pub aktor_id: String,
+    pub identitetsnummer: String,
+    pub adressebeskyttelse: Option<Adressebeskyttelse>,
+    /// Alle hendelser for arbeidssøkeren i kronologisk rekkefølge. Kontortilknytninger utelates
+    /// for personer med adressebeskyttelse som kaller ikke har tilgang til.
+    pub hendelser: Vec<TidslinjeHendelse>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TidslinjeHendelse {
+    pub tidspunkt: DateTime<Utc>,
+    /// Mangler for hendelser som ikke hører til en periode, f.eks. kontortilknytning
+    pub periode_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub data: TidslinjeData,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TidslinjeData {
+    PeriodeStartet,
+    PeriodeAvsluttet,
+    #[serde(rename_all = "camelCase")]
+    Opplysninger {
+        id: Uuid,
+        jobbsituasjon: Vec<Jobbsituasjon>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Profilering {
+        id: Uuid,
+        opplysninger_id: Uuid,
+        profilert_til: ProfilertTil,
+    },
+    #[serde(rename_all = "camelCase")]
+    Egenvurdering {
+        id: Uuid,
+        profilering_id: Uuid,
+        profilert_til: ProfilertTil,
+        egenvurdert_til: ProfilertTil,
+    },
+    #[serde(rename_all = "camelCase")]
+    Bekreftelse {
+        id: Uuid,
+        gjelder_fra: DateTime<Utc>,
+        gjelder_til: DateTime<Utc>,
+        har_jobbet: bool,
+        vil_fortsette: bool,
+        bekreftelsesloesning: Bekreftelsesloesning,
+    },
+    /// Gjeldende løsninger som bekrefter på vegne av arbeidssøker. Bare siste endring er lagret.
+    #[serde(rename_all = "camelCase")]
+    BekreftelsePaaVegneAv {
+        bekreftelsesloesninger: Vec<Bekreftelsesloesning>,
+    },
+    /// Start, endring eller avslutning av en kontortilknytning. `id` er oppfølgingsperioden
+    /// tilknytningen hører til, og ved avslutning er kontoret det siste personen var tilknyttet.
+    #[serde(rename_all = "camelCase")]
+    Kontortilknytning {
+        id: Uuid,
+        endring_type: KontortilknytningEndringType,
+        kontor_id: String,
+        kontor_navn: String,
+        kontor_type: KontorType,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_serialize_tidslinje_hendelse() {
+        let tidspunkt = DateTime::parse_from_rfc3339("2021-01-01T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let periode_id = Uuid::parse_str("069f40c9-c47c-4ee2-9105-bc87bdb58af2").unwrap();
+
+        let hendelse = TidslinjeHendelse {
+            tidspunkt,
+            periode_id: Some(periode_id),
+            data: TidslinjeData::PeriodeStartet,
+        };
+        assert_eq!(
+            serde_json::to_value(&hendelse).unwrap(),
+            json!({
+                "tidspunkt": "2021-01-01T12:00:00Z",
+                "periodeId": "069f40c9-c47c-4ee2-9105-bc87bdb58af2",
+                "type": "PERIODE_STARTET"
+            })
+        );
+
+        let hendelse = TidslinjeHendelse {
+            tidspunkt,
+            periode_id: None,
+            data: TidslinjeData::Kontortilknytning {
+                id: periode_id,
+                endring_type: KontortilknytningEndringType::Endret,
+                kontor_id: "1337".to_string(),
+                kontor_navn: "NAV Bouvetøya".to_string(),
+                kontor_type: KontorType::Arbeidsoppfolging,
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&hendelse).unwrap(),
+            json!({
+                "tidspunkt": "2021-01-01T12:00:00Z",
+                "type": "KONTORTILKNYTNING",
+                "id": "069f40c9-c47c-4ee2-9105-bc87bdb58af2",
+                "endringType": "ENDRET",
+                "kontorId": "1337",
+                "kontorNavn": "NAV Bouvetøya",
+                "kontorType": "ARBEIDSOPPFOLGING"
+            })
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod cursor;
 pub mod dao;
 pub mod dto;
 pub mod parse;
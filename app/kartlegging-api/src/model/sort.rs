@@ -1,14 +1,20 @@
+use async_graphql::Enum;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, EnumString, AsRefStr)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, EnumString, AsRefStr, ToSchema, Enum,
+)]
 pub enum SortOrder {
     #[strum(serialize = "ASC")]
     #[serde(rename = "ASC")]
+    #[graphql(name = "ASC")]
     Ascending,
     #[strum(serialize = "DESC")]
     #[serde(rename = "DESC")]
+    #[graphql(name = "DESC")]
     Descending,
 }
 
@@ -17,3 +23,35 @@ impl fmt::Display for SortOrder {
         write!(f, "{:?}", self.as_ref().to_string())
     }
 }
+
+/// Feltet arbeidssøkere sorteres på ved søk på tilknyttet kontor.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Default,
+    EnumString,
+    AsRefStr,
+    ToSchema,
+    Enum,
+)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SortField {
+    #[default]
+    ArbeidssoekerFra,
+    ArbeidsledigFra,
+}
+
+impl SortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            SortField::ArbeidssoekerFra => "k.arbeidssoeker_fra",
+            SortField::ArbeidsledigFra => "k.arbeidsledig_fra",
+        }
+    }
+}
@@ -1,12 +1,18 @@
+use crate::config::AppConfig;
 use sqlx::PgPool;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct RouterState {
+    pub app_config: Arc<AppConfig>,
     pub pg_pool: PgPool,
 }
 
 impl RouterState {
-    pub const fn new(pg_pool: PgPool) -> Self {
-        Self { pg_pool }
+    pub const fn new(app_config: Arc<AppConfig>, pg_pool: PgPool) -> Self {
+        Self {
+            app_config,
+            pg_pool,
+        }
     }
 }
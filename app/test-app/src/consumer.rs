@@ -4,11 +4,13 @@ use paw_rdkafka::kafka_config::KafkaConfig;
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use sqlx::PgPool;
 use std::sync::Arc;
+use paw_rdkafka_hwm::rebalance::consumer_health::KafkaConsumerHealth;
 use paw_rdkafka_hwm::rebalance::hwm_rebalance_handler::HwmRebalanceHandler;
 
 pub fn create_consumer(
     app_state: Arc<AppState>,
     pg_pool: PgPool,
+    consumer_health: Arc<KafkaConsumerHealth>,
     kafka_config: KafkaConfig,
     topics: &[&str],
 ) -> Result<StreamConsumer<HwmRebalanceHandler>> {
@@ -18,6 +20,7 @@ pub fn create_consumer(
         pg_pool,
         app_state,
         version: hwm_version,
+        consumer_health,
     };
     let consumer: StreamConsumer<HwmRebalanceHandler> = config.create_with_context(context)?;
     consumer.subscribe(topics)?;
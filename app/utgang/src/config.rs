@@ -0,0 +1,43 @@
+use crate::pdl::pdl_config::PDLClientConfig;
+use nais_schema_registry::config::require_schema_registry_env;
+use paw_app_config::validation::{ConfigReport, ConfigValidator};
+use paw_app_config::{config::read_toml_config, read_config_file};
+use paw_rdkafka::kafka_config::KafkaConfig;
+use paw_rust_base::env::runtime_env;
+use paw_sqlx::config::DatabaseConfig;
+use texas_client::config::TokenClientConfig;
+
+/// Sjekker alle config-filer, miljøvariablene de refererer til og schema registry.
+pub fn validate_config() -> ConfigReport {
+    let validator = ConfigValidator::new(runtime_env())
+        .require_placeholders(read_config_file!("token_client_config.toml"))
+        .require_placeholders(read_config_file!("database_config.toml"))
+        .require_placeholders(read_config_file!("kafka_config.toml"))
+        .require_placeholders(read_config_file!("pdl_config.toml"));
+    require_schema_registry_env(validator)
+        .check_config("token_client_config.toml", read_token_client_config())
+        .check_config("database_config.toml", read_database_config())
+        .check_config("kafka_config.toml", read_kafka_config())
+        .check_config("pdl_config.toml", read_pdl_client_config())
+        .report()
+}
+
+pub fn read_token_client_config() -> anyhow::Result<TokenClientConfig> {
+    let content = read_config_file!("token_client_config.toml");
+    Ok(read_toml_config::<TokenClientConfig>(content)?)
+}
+
+pub fn read_database_config() -> anyhow::Result<DatabaseConfig> {
+    let content = read_config_file!("database_config.toml");
+    Ok(read_toml_config::<DatabaseConfig>(content)?)
+}
+
+pub fn read_kafka_config() -> anyhow::Result<KafkaConfig> {
+    let content = read_config_file!("kafka_config.toml");
+    Ok(read_toml_config::<KafkaConfig>(content)?)
+}
+
+pub fn read_pdl_client_config() -> anyhow::Result<PDLClientConfig> {
+    let content = read_config_file!("pdl_config.toml");
+    Ok(read_toml_config::<PDLClientConfig>(content)?)
+}
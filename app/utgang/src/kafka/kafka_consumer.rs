@@ -2,6 +2,7 @@ use std::{error::Error, sync::Arc};
 
 use health_and_monitoring::simple_app_state::AppState;
 use paw_rdkafka::kafka_config::KafkaConfig;
+use paw_rdkafka_hwm::rebalance::consumer_health::KafkaConsumerHealth;
 use paw_rdkafka_hwm::rebalance::hwm_rebalance_handler::HwmRebalanceHandler;
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use sqlx::PgPool;
@@ -9,6 +10,7 @@ use sqlx::PgPool;
 pub fn create_kafka_consumer(
     app_state: Arc<AppState>,
     pg_pool: PgPool,
+    consumer_health: Arc<KafkaConsumerHealth>,
     kafka_config: KafkaConfig,
     topics: &[&str],
 ) -> Result<StreamConsumer<HwmRebalanceHandler>, Box<dyn Error>> {
@@ -17,6 +19,7 @@ pub fn create_kafka_consumer(
         version: *kafka_config.hwm_version,
         pg_pool,
         app_state,
+        consumer_health,
     };
     let consumer: StreamConsumer<HwmRebalanceHandler> = config.create_with_context(context)?;
     consumer.subscribe(topics)?;
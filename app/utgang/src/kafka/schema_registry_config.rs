@@ -1,3 +1,4 @@
+use paw_app_config::secret::Secret;
 use paw_rust_base::env::nais_cluster_name;
 use schema_registry_converter::async_impl::schema_registry::SrSettings;
 use std::env;
@@ -20,12 +21,14 @@ pub fn create_nais_schema_registry_settings() -> Result<SrSettings, SchemaRegist
         SchemaRegistryConfigError::MissingEnvVar("KAFKA_SCHEMA_REGISTRY_USER".to_string())
     })?;
 
-    let password = env::var("KAFKA_SCHEMA_REGISTRY_PASSWORD").map_err(|_| {
-        SchemaRegistryConfigError::MissingEnvVar("KAFKA_SCHEMA_REGISTRY_PASSWORD".to_string())
-    })?;
+    let password = env::var("KAFKA_SCHEMA_REGISTRY_PASSWORD")
+        .map(Secret::new)
+        .map_err(|_| {
+            SchemaRegistryConfigError::MissingEnvVar("KAFKA_SCHEMA_REGISTRY_PASSWORD".to_string())
+        })?;
 
     let sr_settings = SrSettings::new_builder(schema_registry_url)
-        .set_basic_authorization(&username, Some(&password))
+        .set_basic_authorization(&username, Some(password.expose()))
         .build()
         .map_err(|e| SchemaRegistryConfigError::InvalidUrl(e.to_string()))?;
 
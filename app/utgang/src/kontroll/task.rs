@@ -5,6 +5,7 @@ use anyhow::Result;
 use sqlx::PgPool;
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 
 use regler_arbeidssoeker::regler::regelsett::Regelsett;
@@ -42,13 +43,19 @@ impl KontrollTask {
 pub fn start_kontroll_task(
     kontroll: KontrollTask,
     intervall: std::time::Duration,
+    cancellation_token: CancellationToken,
 ) -> JoinHandle<Result<()>> {
     tokio::spawn(async move {
-        loop {
+        while !cancellation_token.is_cancelled() {
             let hadde_arbeid = kontroll.kjoer_kontroll().await?;
             if !hadde_arbeid {
-                sleep(intervall).await;
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => {}
+                    _ = sleep(intervall) => {}
+                }
             }
         }
+        tracing::info!("Kontroll stoppet");
+        Ok(())
     })
 }
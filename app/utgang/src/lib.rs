@@ -1,3 +1,4 @@
+pub mod config;
 pub mod consumer_function;
 pub mod dao;
 pub mod kafka;
@@ -1,26 +1,28 @@
 use anyhow::Result;
 use chrono::TimeDelta;
+use health_and_monitoring::compound_health::CompoundHealth;
+use health_and_monitoring::dependency_health::DependencyHealth;
 use health_and_monitoring::{nais_otel_setup::setup_nais_otel, simple_app_state};
-use paw_app_config::read_config_file;
-use paw_rdkafka::kafka_config::KafkaConfig;
 use paw_rdkafka_hwm::hwm_message_processor::hwm_process_message;
+use paw_rdkafka_hwm::rebalance::consumer_health::{KafkaConsumerHealth, add_kafka_consumer_checks};
 use paw_rust_base::error::ServerError;
 use paw_rust_base::panic_logger::register_panic_logger;
-use paw_sqlx::config::DatabaseConfig;
+use paw_rust_base::task_supervisor::TaskSupervisor;
 use paw_sqlx::postgres::{clear_db, init_db};
 use rdkafka::Message;
+use rdkafka::consumer::Consumer;
 use std::num::NonZeroU16;
 use std::{sync::Arc, time::Duration};
 use texas_client::token_client::create_token_client;
-use tokio::{
-    signal::{unix::SignalKind, unix::signal},
-    task::{JoinError, JoinHandle},
+use tokio::task::JoinHandle;
+use utgang::config::{
+    read_database_config, read_kafka_config, read_pdl_client_config, read_token_client_config,
+    validate_config,
 };
 use utgang::consumer_function::UtgangMessageProcessor;
 use utgang::kafka::kafka_consumer::create_kafka_consumer;
 use utgang::kafka::periode_processor::PeriodeProcessorError::ProcessingError;
 use utgang::kontroll::{KontrollTask, start_kontroll_task};
-use utgang::pdl::pdl_config::PDLClientConfig;
 use utgang::pdl::pdl_query::PDLClient;
 use utgang::pdl_oppdatering::{PdlDataOppdatering, start_pdl_oppdatering_task};
 use utgang::{ARBEIDSSOKERPERIODER_TOPIC, HENDELSELOGG_TOPIC};
@@ -33,27 +35,47 @@ const KONTROLL_BATCH_SIZE: NonZeroU16 =
 async fn main() -> Result<()> {
     register_panic_logger();
     setup_nais_otel()?;
+    let config_report = validate_config();
+    config_report.log();
+    config_report.into_result()?;
     let reqwest_client = reqwest::Client::new();
-    let token_client_config = toml::from_str(read_config_file!("token_client_config.toml"))?;
+    let token_client_config = read_token_client_config()?;
     let token_client = Arc::new(create_token_client(
         token_client_config,
         reqwest_client.clone(),
     ));
     let app_state = Arc::new(simple_app_state::AppState::new());
-    let health_routes = axum_health::routes(app_state.clone());
+    let mut supervisor = TaskSupervisor::default();
+    let shutdown_app_state = app_state.clone();
+    supervisor.on_shutdown(move || shutdown_app_state.set_is_ready(false));
+    let consumer_health = Arc::new(KafkaConsumerHealth::new());
+    let mut dependency_health = DependencyHealth::default();
+    add_kafka_consumer_checks(&mut dependency_health, consumer_health.clone());
+    let dependency_health = Arc::new(dependency_health);
+    let mut health = CompoundHealth::new();
+    health.add_health_check(app_state.clone());
+    health.add_health_check(dependency_health.clone());
+    let health_task = dependency_health.spawn_refresh_task(supervisor.cancellation_token());
+    supervisor.add_task("Helsesjekker", health_task);
+    let health_routes = axum_health::routes(Arc::new(health));
+    let web_server_token = supervisor.cancellation_token();
     let web_server_task: JoinHandle<Result<()>> = tokio::spawn(async move {
         let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
-        axum::serve(listener, health_routes).await?;
+        axum::serve(listener, health_routes)
+            .with_graceful_shutdown(web_server_token.cancelled_owned())
+            .await?;
         Ok(())
     });
-    let db_config = toml::from_str::<DatabaseConfig>(read_config_file!("database_config.toml"))?;
+    supervisor.add_task("Webserver", web_server_task);
+    let db_config = read_database_config()?;
     let pg_pool = init_db(db_config).await?;
     sqlx::migrate!("./migrations").run(&pg_pool).await?;
-    let kafka_config = toml::from_str::<KafkaConfig>(read_config_file!("kafka_config.toml"))?;
+    let kafka_config = read_kafka_config()?;
     let hwm_version = *kafka_config.hwm_version;
     let consumer = create_kafka_consumer(
         app_state.clone(),
         pg_pool.clone(),
+        consumer_health,
         kafka_config,
         &[HENDELSELOGG_TOPIC, ARBEIDSSOKERPERIODER_TOPIC],
     )
@@ -64,10 +86,13 @@ async fn main() -> Result<()> {
     let utgang_processor = UtgangMessageProcessor::new()?;
     let pdl_pool = pg_pool.clone();
     let consumer_pool = pg_pool.clone();
+    let consumer_token = supervisor.cancellation_token();
     let consumer_task: JoinHandle<Result<()>> = tokio::spawn(async move {
         loop {
-            let msg = consumer.recv().await?;
-            let msg = msg.detach();
+            let msg = tokio::select! {
+                _ = consumer_token.cancelled() => break,
+                msg = consumer.recv() => msg?.detach(),
+            };
             hwm_process_message(hwm_version, consumer_pool.clone(), &msg, &utgang_processor)
                 .await
                 .map_err(|e| ProcessingError {
@@ -77,9 +102,12 @@ async fn main() -> Result<()> {
                     offset: msg.offset(),
                 })?;
         }
+        consumer.unsubscribe();
+        tracing::info!("Kafka consumer stoppet");
+        Ok(())
     });
-    let pdl_client_config =
-        toml::from_str::<PDLClientConfig>(read_config_file!("pdl_config.toml"))?;
+    supervisor.add_task("KafkaConsumer", consumer_task);
+    let pdl_client_config = read_pdl_client_config()?;
     tracing::info!("Lastet pdl config: {:?}", pdl_client_config);
     let pdl_client =
         PDLClient::from_config(pdl_client_config, reqwest_client.clone(), token_client);
@@ -89,55 +117,24 @@ async fn main() -> Result<()> {
         PDL_BATCH_SIZE,
         TimeDelta::hours(24),
     );
-    let pdl_oppdatering_task = start_pdl_oppdatering_task(pdl_oppdatering, Duration::from_mins(1));
+    let pdl_oppdatering_task = start_pdl_oppdatering_task(
+        pdl_oppdatering,
+        Duration::from_mins(1),
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("PDLOppdatering", pdl_oppdatering_task);
     let regelsett = regler_arbeidssoeker::regelsett_v4::regelsett_v4();
     let kontroll = KontrollTask::new(pg_pool.clone(), KONTROLL_BATCH_SIZE, regelsett);
-    let kontroll_task = start_kontroll_task(kontroll, Duration::from_mins(5));
-    let signal_task = get_shutdown_signal();
+    let kontroll_task = start_kontroll_task(
+        kontroll,
+        Duration::from_mins(5),
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("Kontroll", kontroll_task);
     app_state.set_has_started(true);
-    tokio::select! {
-        res = web_server_task      => haandter_task_resultat("Webserver", res),
-        res = consumer_task        => haandter_task_resultat("KafkaConsumer", res),
-        res = pdl_oppdatering_task => haandter_task_resultat("PDLOppdatering", res),
-        res = kontroll_task        => haandter_task_resultat("Kontroll", res),
-        signal = signal_task => {
-            tracing::info!("Mottok shutdown-signal: {}", signal?);
-            Ok(())
-        },
-    }?;
-    Ok(())
-}
-
-async fn get_shutdown_signal() -> Result<String> {
-    let mut term_signal = signal(SignalKind::terminate())?;
-    let mut interrupt_signal = signal(SignalKind::interrupt())?;
-    tokio::select! {
-        _ = term_signal.recv() => Ok("SIGTERM".to_string()),
-        _ = interrupt_signal.recv() => Ok("SIGINT".to_string())
-    }
-}
-
-fn haandter_task_resultat(navn: &str, res: Result<Result<()>, JoinError>) -> Result<()> {
-    match res {
-        Ok(Ok(())) => {
-            tracing::info!("{} avsluttet normalt", navn);
-            Ok(())
-        }
-        Ok(Err(e)) => {
-            tracing::error!("{} avsluttet med feil: {}", navn, e);
-            Err(ServerError::InternalProcessTerminated {
-                process: navn.to_string(),
-                message: e.to_string(),
-            }
-            .into())
-        }
-        Err(e) => {
-            tracing::error!("Feil i spawned task for {}: {}", navn, e);
-            Err(ServerError::InternalProcessTerminated {
-                process: navn.to_string(),
-                message: e.to_string(),
-            }
-            .into())
-        }
-    }
+    let result = supervisor.run().await;
+    app_state.set_is_alive(false);
+    pg_pool.close().await;
+    tracing::info!("Pg pool lukket");
+    result
 }
@@ -2,17 +2,24 @@ use super::oppdater::PdlDataOppdatering;
 use anyhow::Result;
 use chrono::Utc;
 use tokio::{task::JoinHandle, time::sleep};
+use tokio_util::sync::CancellationToken;
 
 pub fn start_pdl_oppdatering_task(
     oppdatering: PdlDataOppdatering,
     intervall: std::time::Duration,
+    cancellation_token: CancellationToken,
 ) -> JoinHandle<Result<()>> {
     tokio::spawn(async move {
-        loop {
+        while !cancellation_token.is_cancelled() {
             let data_tilgjengelig = oppdatering.kjoer_oppdatering(Utc::now()).await?;
             if !data_tilgjengelig {
-                sleep(intervall).await;
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => {}
+                    _ = sleep(intervall) => {}
+                }
             }
         }
+        tracing::info!("PDL-oppdatering stoppet");
+        Ok(())
     })
 }
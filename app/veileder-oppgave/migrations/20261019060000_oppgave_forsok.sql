@@ -0,0 +1,11 @@
+ALTER TABLE oppgaver
+    ADD COLUMN antall_forsok INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN neste_forsok  TIMESTAMP;
+
+UPDATE oppgaver o
+SET antall_forsok = (SELECT COUNT(*)
+                     FROM oppgave_hendelse_logg l
+                     WHERE l.oppgave_id = o.id
+                       AND l.status = 'EKSTERN_OPPGAVE_OPPRETTELSE_FEILET');
+
+CREATE INDEX oppgaver_neste_forsok_idx ON oppgaver (neste_forsok);
@@ -0,0 +1,14 @@
+CREATE TABLE ekstern_oppgave_tilstand
+(
+    oppgave_id              BIGINT PRIMARY KEY REFERENCES oppgaver (id) ON DELETE CASCADE,
+    ekstern_oppgave_id      BIGINT      NOT NULL,
+    versjon                 INTEGER     NOT NULL,
+    enhetsnr                VARCHAR(10),
+    nav_ident               VARCHAR(20),
+    prioritet               VARCHAR(20),
+    frist                   DATE,
+    forst_tildelt_tidspunkt TIMESTAMP,
+    oppdatert_tidspunkt     TIMESTAMP   NOT NULL
+);
+
+CREATE INDEX ekstern_oppgave_tilstand_enhetsnr_idx ON ekstern_oppgave_tilstand (enhetsnr);
@@ -0,0 +1,2 @@
+ALTER TABLE oppgaver
+    ADD COLUMN sist_avstemt TIMESTAMP;
@@ -0,0 +1,73 @@
+-- Sammendragstabeller for metrikker. Oppdateres i samme transaksjon som oppgaver og hendelse-logg.
+
+CREATE TABLE metrikk_oppgaver_per_status
+(
+    type   VARCHAR(50) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    antall BIGINT      NOT NULL,
+    PRIMARY KEY (type, status)
+);
+
+CREATE TABLE metrikk_hendelser_per_dag
+(
+    type   VARCHAR(50) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    dato   DATE        NOT NULL,
+    antall BIGINT      NOT NULL,
+    PRIMARY KEY (type, status, dato)
+);
+
+CREATE TABLE metrikk_forsok_per_person
+(
+    type             VARCHAR(50) NOT NULL,
+    identitetsnummer VARCHAR(11) NOT NULL,
+    forste_oppgave   TIMESTAMP   NOT NULL,
+    antall_forsok    BIGINT      NOT NULL,
+    PRIMARY KEY (type, identitetsnummer)
+);
+
+CREATE TABLE metrikk_saksbehandlingstid
+(
+    oppgave_id  BIGINT PRIMARY KEY REFERENCES oppgaver (id) ON DELETE CASCADE,
+    type        VARCHAR(50) NOT NULL,
+    uke         DATE        NOT NULL,
+    opprettet   TIMESTAMP   NOT NULL,
+    ferdigstilt TIMESTAMP   NOT NULL
+);
+
+CREATE INDEX metrikk_saksbehandlingstid_ferdigstilt_idx ON metrikk_saksbehandlingstid (ferdigstilt);
+
+INSERT INTO metrikk_oppgaver_per_status (type, status, antall)
+SELECT type, status, COUNT(*)
+FROM oppgaver
+GROUP BY type, status;
+
+INSERT INTO metrikk_hendelser_per_dag (type, status, dato, antall)
+SELECT o.type, ohl.status, DATE(ohl.tidspunkt), COUNT(*)
+FROM oppgave_hendelse_logg ohl
+JOIN oppgaver o ON o.id = ohl.oppgave_id
+GROUP BY o.type, ohl.status, DATE(ohl.tidspunkt);
+
+INSERT INTO metrikk_forsok_per_person (type, identitetsnummer, forste_oppgave, antall_forsok)
+SELECT o.type, o.identitetsnummer, MIN(o.tidspunkt), COUNT(ohl.id)
+FROM oppgaver o
+LEFT JOIN oppgave_hendelse_logg ohl
+    ON ohl.oppgave_id = o.id
+    AND ohl.status = 'OPPGAVE_FINNES_ALLEREDE'
+GROUP BY o.type, o.identitetsnummer;
+
+INSERT INTO metrikk_saksbehandlingstid (oppgave_id, type, uke, opprettet, ferdigstilt)
+SELECT o.id, o.type, DATE_TRUNC('week', ferdigstilt.tidspunkt)::DATE, eksternt_opprettet.tidspunkt, ferdigstilt.tidspunkt
+FROM (
+    SELECT oppgave_id, MIN(tidspunkt) AS tidspunkt
+    FROM oppgave_hendelse_logg
+    WHERE status = 'EKSTERN_OPPGAVE_FERDIGSTILT'
+    GROUP BY oppgave_id
+) AS ferdigstilt
+JOIN (
+    SELECT oppgave_id, MIN(tidspunkt) AS tidspunkt
+    FROM oppgave_hendelse_logg
+    WHERE status = 'EKSTERN_OPPGAVE_OPPRETTET'
+    GROUP BY oppgave_id
+) AS eksternt_opprettet ON eksternt_opprettet.oppgave_id = ferdigstilt.oppgave_id
+JOIN oppgaver o ON o.id = ferdigstilt.oppgave_id;
@@ -0,0 +1,20 @@
+-- Endringer i antall skrives som egne rader i stedet for å oppdatere delte rader i
+-- sammendragstabellene, slik at samtidige transaksjoner ikke låser hverandre. Metrikkjobben
+-- summerer endringene inn i sammendragstabellene og sletter dem.
+
+CREATE TABLE metrikk_oppgaver_per_status_endring
+(
+    id      BIGSERIAL PRIMARY KEY,
+    type    VARCHAR(50) NOT NULL,
+    status  VARCHAR(50) NOT NULL,
+    endring BIGINT      NOT NULL
+);
+
+CREATE TABLE metrikk_hendelser_per_dag_endring
+(
+    id      BIGSERIAL PRIMARY KEY,
+    type    VARCHAR(50) NOT NULL,
+    status  VARCHAR(50) NOT NULL,
+    dato    DATE        NOT NULL,
+    endring BIGINT      NOT NULL
+);
@@ -0,0 +1,26 @@
+-- Gjentatte forsøk telles per oppgave, slik at metrikken kan regnes som før: per person, summert
+-- over oppgavene personen har fått etter cutoff.
+
+CREATE TABLE metrikk_forsok_per_oppgave
+(
+    oppgave_id       BIGINT PRIMARY KEY REFERENCES oppgaver (id) ON DELETE CASCADE,
+    type             VARCHAR(50) NOT NULL,
+    identitetsnummer VARCHAR(11) NOT NULL,
+    tidspunkt        TIMESTAMP   NOT NULL,
+    antall_forsok    BIGINT      NOT NULL
+);
+
+CREATE INDEX metrikk_forsok_per_oppgave_tidspunkt_idx ON metrikk_forsok_per_oppgave (tidspunkt);
+
+INSERT INTO metrikk_forsok_per_oppgave (oppgave_id, type, identitetsnummer, tidspunkt, antall_forsok)
+SELECT o.id, o.type, o.identitetsnummer, o.tidspunkt, COUNT(ohl.id)
+FROM oppgaver o
+LEFT JOIN oppgave_hendelse_logg ohl
+    ON ohl.oppgave_id = o.id
+    AND ohl.status = 'OPPGAVE_FINNES_ALLEREDE'
+GROUP BY o.id;
+
+DROP TABLE metrikk_forsok_per_person;
+
+-- Dagen cutoff faller på telles eksakt fra hendelse-loggen
+CREATE INDEX oppgave_hendelse_logg_status_tidspunkt_idx ON oppgave_hendelse_logg (status, tidspunkt);
@@ -0,0 +1,288 @@
+use crate::domain::ekstern_oppgave_tilstand::EksternOppgaveTilstand;
+use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
+use crate::domain::oppgave::Oppgave;
+use crate::domain::oppgave_status::OppgaveStatus;
+use crate::domain::oppgave_type::OppgaveType;
+use anyhow::{Result, ensure};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use types::arbeidssoeker_id::ArbeidssoekerId;
+use uuid::Uuid;
+
+pub const DEFAULT_OPPGAVER_LIMIT: i64 = 100;
+pub const MAX_OPPGAVER_LIMIT: i64 = 1000;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OppgaverParams {
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub arbeidssoeker_id: Option<i64>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Validerte filtre for oppgavelisten
+#[derive(Debug, PartialEq)]
+pub struct OppgaveFilter {
+    pub status: Option<OppgaveStatus>,
+    pub type_: Option<OppgaveType>,
+    pub arbeidssoeker_id: Option<ArbeidssoekerId>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl OppgaverParams {
+    pub fn filter(&self) -> Result<OppgaveFilter> {
+        let limit = self.limit.unwrap_or(DEFAULT_OPPGAVER_LIMIT);
+        ensure!(
+            (1..=MAX_OPPGAVER_LIMIT).contains(&limit),
+            "Felt 'limit' har feil størrelse: {}",
+            limit
+        );
+        let offset = self.offset.unwrap_or_default();
+        ensure!(offset >= 0, "Felt 'offset' kan ikke være negativ");
+        Ok(OppgaveFilter {
+            status: self.status.as_deref().map(str::parse).transpose()?,
+            type_: self.type_.as_deref().map(str::parse).transpose()?,
+            arbeidssoeker_id: self.arbeidssoeker_id.map(ArbeidssoekerId::from),
+            limit,
+            offset,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminHandlingRequest {
+    /// Hvorfor handlingen utføres, lagres i hendelse-loggen
+    pub begrunnelse: String,
+}
+
+impl AdminHandlingRequest {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.begrunnelse.trim().is_empty(),
+            "Felt 'begrunnelse' kan ikke være tom"
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HendelseLoggResponse {
+    pub status: String,
+    pub melding: String,
+    pub tidspunkt: DateTime<Utc>,
+}
+
+impl From<&HendelseLoggEntry> for HendelseLoggResponse {
+    fn from(entry: &HendelseLoggEntry) -> Self {
+        Self {
+            status: entry.status.to_string(),
+            melding: entry.melding.clone(),
+            tidspunkt: entry.tidspunkt,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OppgaveResponse {
+    pub id: i64,
+    pub melding_id: Uuid,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub status: String,
+    pub opplysninger: Vec<String>,
+    pub arbeidssoeker_id: i64,
+    pub identitetsnummer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ekstern_oppgave_id: Option<i64>,
+    pub tidspunkt: DateTime<Utc>,
+    /// Nyeste hendelse først. Utelates i oppgavelisten.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hendelse_logg: Option<Vec<HendelseLoggResponse>>,
+    /// Siste kjente tilstand i Oppgave API. Utelates i oppgavelisten.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ekstern_tilstand: Option<EksternTilstandResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EksternTilstandResponse {
+    pub versjon: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enhetsnr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nav_ident: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prioritet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frist: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forst_tildelt_tidspunkt: Option<DateTime<Utc>>,
+    pub oppdatert_tidspunkt: DateTime<Utc>,
+}
+
+impl From<&EksternOppgaveTilstand> for EksternTilstandResponse {
+    fn from(tilstand: &EksternOppgaveTilstand) -> Self {
+        Self {
+            versjon: tilstand.versjon,
+            enhetsnr: tilstand.enhetsnr.clone(),
+            nav_ident: tilstand.nav_ident.clone(),
+            prioritet: tilstand.prioritet.clone(),
+            frist: tilstand.frist,
+            forst_tildelt_tidspunkt: tilstand.forst_tildelt_tidspunkt,
+            oppdatert_tidspunkt: tilstand.oppdatert_tidspunkt,
+        }
+    }
+}
+
+impl OppgaveResponse {
+    pub fn uten_hendelse_logg(oppgave: &Oppgave) -> Self {
+        Self {
+            id: i64::from(oppgave.id()),
+            melding_id: oppgave.melding_id,
+            type_: oppgave.type_.to_string(),
+            status: oppgave.status.to_string(),
+            opplysninger: oppgave.opplysninger.clone(),
+            arbeidssoeker_id: i64::from(oppgave.arbeidssoeker_id),
+            identitetsnummer: String::from(oppgave.identitetsnummer.clone()),
+            ekstern_oppgave_id: oppgave.ekstern_oppgave_id.map(i64::from),
+            tidspunkt: oppgave.tidspunkt,
+            hendelse_logg: None,
+            ekstern_tilstand: None,
+        }
+    }
+
+    pub fn med_hendelse_logg(oppgave: &Oppgave) -> Self {
+        Self {
+            hendelse_logg: Some(
+                oppgave
+                    .hendelse_logg
+                    .iter()
+                    .map(HendelseLoggResponse::from)
+                    .collect(),
+            ),
+            ..Self::uten_hendelse_logg(oppgave)
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OppgaverResponse {
+    pub oppgaver: Vec<OppgaveResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProsesseringResponse {
+    /// Oppgavene som ble plukket opp av kjøringen
+    pub oppgave_ider: Vec<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::hendelse_logg_status::HendelseLoggStatus;
+    use crate::domain::oppgave_id::OppgaveId;
+    use types::identitetsnummer::Identitetsnummer;
+
+    #[test]
+    fn test_filter() {
+        let params = OppgaverParams {
+            status: Some("UBEHANDLET".to_string()),
+            type_: Some("AVVIST_UNDER_18".to_string()),
+            arbeidssoeker_id: Some(12345),
+            limit: Some(10),
+            offset: Some(20),
+        };
+        assert_eq!(
+            params.filter().unwrap(),
+            OppgaveFilter {
+                status: Some(OppgaveStatus::Ubehandlet),
+                type_: Some(OppgaveType::AvvistUnder18),
+                arbeidssoeker_id: Some(ArbeidssoekerId(12345)),
+                limit: 10,
+                offset: 20,
+            }
+        );
+
+        let filter = OppgaverParams::default().filter().unwrap();
+        assert_eq!(filter.limit, DEFAULT_OPPGAVER_LIMIT);
+        assert_eq!(filter.offset, 0);
+    }
+
+    #[test]
+    fn test_ugyldig_filter() {
+        let ugyldige = [
+            OppgaverParams {
+                status: Some("UKJENT".to_string()),
+                ..Default::default()
+            },
+            OppgaverParams {
+                type_: Some("UKJENT".to_string()),
+                ..Default::default()
+            },
+            OppgaverParams {
+                limit: Some(MAX_OPPGAVER_LIMIT + 1),
+                ..Default::default()
+            },
+            OppgaverParams {
+                offset: Some(-1),
+                ..Default::default()
+            },
+        ];
+        for params in ugyldige {
+            assert!(params.filter().is_err(), "{:?}", params);
+        }
+    }
+
+    #[test]
+    fn test_validate_admin_handling_request() {
+        let request = AdminHandlingRequest {
+            begrunnelse: "Oppgaven er behandlet manuelt i Gosys".to_string(),
+        };
+        assert!(request.validate().is_ok());
+        let request = AdminHandlingRequest {
+            begrunnelse: "  ".to_string(),
+        };
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_oppgave_response() {
+        let oppgave = Oppgave::fra_db(
+            OppgaveId(1),
+            Uuid::nil(),
+            OppgaveType::AvvistUnder18,
+            OppgaveStatus::Opprettet,
+            vec!["ER_UNDER_18_AAR".to_string()],
+            None,
+            ArbeidssoekerId(12345),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            None,
+            DateTime::UNIX_EPOCH,
+            vec![HendelseLoggEntry::new(
+                HendelseLoggStatus::OppgaveOpprettet,
+                "Oppgave opprettet".to_string(),
+                DateTime::UNIX_EPOCH,
+            )],
+        );
+
+        let json = serde_json::to_value(OppgaveResponse::uten_hendelse_logg(&oppgave)).unwrap();
+        assert_eq!(json["type"], "AVVIST_UNDER_18");
+        assert_eq!(json["status"], "OPPRETTET");
+        assert_eq!(json["arbeidssoekerId"], 12345);
+        assert!(json.get("eksternOppgaveId").is_none());
+        assert!(json.get("hendelseLogg").is_none());
+        assert!(json.get("eksternTilstand").is_none());
+
+        let json = serde_json::to_value(OppgaveResponse::med_hendelse_logg(&oppgave)).unwrap();
+        assert_eq!(json["hendelseLogg"][0]["status"], "OPPGAVE_OPPRETTET");
+    }
+}
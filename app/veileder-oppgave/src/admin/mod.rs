@@ -0,0 +1,3 @@
+pub mod dto;
+pub mod routes;
+pub mod tilgang;
@@ -0,0 +1,633 @@
+use crate::admin::dto::{
+    AdminHandlingRequest, EksternTilstandResponse, OppgaveResponse, OppgaverParams,
+    OppgaverResponse, ProsesseringResponse,
+};
+use crate::admin::tilgang::{er_admin, utfoert_av};
+use crate::client::oppgave_client::OppgaveApiClient;
+use crate::config::{ApplicationConfig, OpprettOppgaverTaskConfig};
+use crate::db::ekstern_oppgave_tilstand_functions::hent_ekstern_tilstand;
+use crate::db::oppgave_functions::{
+    bytt_oppgave_status, finn_oppgaver, hent_de_eldste_ubehandlede_oppgavene, hent_oppgave,
+    oppdater_hendelse_logg, tilbakestill_oppgave,
+};
+use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
+use crate::domain::hendelse_logg_status::HendelseLoggStatus::{
+    ManuellProsessering, ManueltFerdigbehandlet, ManueltTilbakestilt,
+};
+use crate::domain::oppgave_id::OppgaveId;
+use crate::domain::oppgave_status::OppgaveStatus::Ferdigbehandlet;
+use crate::domain::oppgavetyper::fra_tidspunkt_per_type;
+use crate::opprett_ekstern_oppgave_task::prosesser_oppgaver;
+use axum::extract::rejection::{PathRejection, QueryRejection};
+use axum::extract::{Path, Query, Request, State};
+use axum::middleware::{Next, from_fn_with_state};
+use axum::response::Response;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use chrono::Utc;
+use oauth2::principal::Principal;
+use paw_app_config::watch::ConfigWatch;
+use paw_error_handling::problem_details::ProblemDetails;
+use paw_oauth2_resource_server::middleware::oauth2_middleware;
+use paw_oauth2_resource_server::state::AuthState;
+use sqlx::PgPool;
+use std::fmt::Display;
+use std::sync::Arc;
+
+pub const ADMIN_OPPGAVER_PATH: &str = "/api/v1/admin/oppgaver";
+pub const ADMIN_OPPGAVE_PATH: &str = "/api/v1/admin/oppgaver/{oppgave_id}";
+pub const ADMIN_TILBAKESTILL_PATH: &str = "/api/v1/admin/oppgaver/{oppgave_id}/tilbakestill";
+pub const ADMIN_FERDIGBEHANDLE_PATH: &str = "/api/v1/admin/oppgaver/{oppgave_id}/ferdigbehandle";
+pub const ADMIN_PROSESSERING_PATH: &str = "/api/v1/admin/prosessering";
+
+#[derive(Clone)]
+pub struct AdminState {
+    pub db_pool: PgPool,
+    pub oppgave_api_client: Arc<OppgaveApiClient>,
+    pub app_config: ApplicationConfig,
+    pub task_config: ConfigWatch<OpprettOppgaverTaskConfig>,
+}
+
+pub fn routes(state: AdminState, auth_state: Arc<AuthState>) -> Router {
+    Router::new()
+        .route(ADMIN_OPPGAVER_PATH, get(hent_oppgaver))
+        .route(ADMIN_OPPGAVE_PATH, get(hent_oppgave_med_logg))
+        .route(ADMIN_TILBAKESTILL_PATH, post(tilbakestill))
+        .route(ADMIN_FERDIGBEHANDLE_PATH, post(ferdigbehandle))
+        .route(ADMIN_PROSESSERING_PATH, post(prosesser))
+        .route_layer(from_fn_with_state(state.clone(), krev_admin))
+        .route_layer(oauth2_middleware(auth_state))
+        .with_state(state)
+}
+
+/// Kjører etter OAuth2-middleware, så principal er satt
+async fn krev_admin(
+    State(state): State<AdminState>,
+    Extension(principal): Extension<Principal>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ProblemDetails> {
+    if !er_admin(&principal, &state.app_config.admin_grupper) {
+        tracing::warn!("Avviser kall til admin-API uten tilgang");
+        return Err(ProblemDetails::forbidden(
+            request.uri().path(),
+            "Mangler tilgang til admin-API",
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+fn database_feil<E: Display>(path: &'static str) -> impl Fn(E) -> ProblemDetails {
+    move |e| {
+        tracing::error!("Spørring mot database feilet: {}", e);
+        ProblemDetails::database_error(path, "Spørring mot database feilet")
+    }
+}
+
+fn ugyldig_path(path: &'static str) -> impl Fn(PathRejection) -> ProblemDetails {
+    move |e| {
+        tracing::warn!("Ugyldig oppgave-id: {}", e);
+        ProblemDetails::validation_error(path, "Ugyldig oppgave-id")
+    }
+}
+
+fn ugyldig_body(path: &'static str) -> impl Fn(anyhow::Error) -> ProblemDetails {
+    move |e| {
+        tracing::warn!("Ugyldig request body: {}", e);
+        ProblemDetails::validation_error(path, &e.to_string())
+    }
+}
+
+/// Oppgaver filtrert på status, type og arbeidssøker, nyeste først
+#[tracing::instrument(skip(state, params))]
+async fn hent_oppgaver(
+    State(state): State<AdminState>,
+    params: Result<Query<OppgaverParams>, QueryRejection>,
+) -> Result<Json<OppgaverResponse>, ProblemDetails> {
+    let Query(params) = params.map_err(|e| {
+        tracing::warn!("Feil ved deserialisering av query-parametre: {}", e);
+        ProblemDetails::validation_error(ADMIN_OPPGAVER_PATH, "Ugyldige query-parametre")
+    })?;
+    let filter = params.filter().map_err(|e| {
+        tracing::warn!("Ugyldig filter: {}", e);
+        ProblemDetails::validation_error(ADMIN_OPPGAVER_PATH, &e.to_string())
+    })?;
+
+    let mut tx = state
+        .db_pool
+        .begin()
+        .await
+        .map_err(database_feil(ADMIN_OPPGAVER_PATH))?;
+    let oppgaver = finn_oppgaver(
+        filter.status.as_ref(),
+        filter.type_,
+        filter.arbeidssoeker_id,
+        filter.limit,
+        filter.offset,
+        &mut tx,
+    )
+    .await
+    .map_err(database_feil(ADMIN_OPPGAVER_PATH))?;
+    tx.commit()
+        .await
+        .map_err(database_feil(ADMIN_OPPGAVER_PATH))?;
+
+    Ok(Json(OppgaverResponse {
+        oppgaver: oppgaver
+            .iter()
+            .map(OppgaveResponse::uten_hendelse_logg)
+            .collect(),
+    }))
+}
+
+/// Oppgaven med hele hendelse-loggen og speilet tilstand fra Oppgave API
+#[tracing::instrument(skip(state))]
+async fn hent_oppgave_med_logg(
+    State(state): State<AdminState>,
+    oppgave_id: Result<Path<i64>, PathRejection>,
+) -> Result<Json<OppgaveResponse>, ProblemDetails> {
+    let Path(oppgave_id) = oppgave_id.map_err(ugyldig_path(ADMIN_OPPGAVE_PATH))?;
+    let mut tx = state
+        .db_pool
+        .begin()
+        .await
+        .map_err(database_feil(ADMIN_OPPGAVE_PATH))?;
+    let oppgave = hent_oppgave(OppgaveId(oppgave_id), &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_OPPGAVE_PATH))?
+        .ok_or_else(|| ProblemDetails::not_found(ADMIN_OPPGAVE_PATH, "Fant ikke oppgaven"))?;
+    let ekstern_tilstand = hent_ekstern_tilstand(oppgave.id(), &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_OPPGAVE_PATH))?;
+    tx.commit()
+        .await
+        .map_err(database_feil(ADMIN_OPPGAVE_PATH))?;
+    Ok(Json(OppgaveResponse {
+        ekstern_tilstand: ekstern_tilstand.as_ref().map(EksternTilstandResponse::from),
+        ..OppgaveResponse::med_hendelse_logg(&oppgave)
+    }))
+}
+
+/// Setter en oppgave som sitter fast i `OPPRETTET` uten ekstern id, eller som har status
+/// `GITT_OPP`, tilbake til `UBEHANDLET`, slik at neste kjøring prøver å opprette den i
+/// Oppgave API igjen
+#[tracing::instrument(skip(state, principal, request))]
+async fn tilbakestill(
+    State(state): State<AdminState>,
+    Extension(principal): Extension<Principal>,
+    oppgave_id: Result<Path<i64>, PathRejection>,
+    request: String,
+) -> Result<Json<OppgaveResponse>, ProblemDetails> {
+    let Path(oppgave_id) = oppgave_id.map_err(ugyldig_path(ADMIN_TILBAKESTILL_PATH))?;
+    let oppgave_id = OppgaveId(oppgave_id);
+    let request = les_admin_handling(&request).map_err(ugyldig_body(ADMIN_TILBAKESTILL_PATH))?;
+
+    let mut tx = state
+        .db_pool
+        .begin()
+        .await
+        .map_err(database_feil(ADMIN_TILBAKESTILL_PATH))?;
+    if hent_oppgave(oppgave_id, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_TILBAKESTILL_PATH))?
+        .is_none()
+    {
+        return Err(ProblemDetails::not_found(
+            ADMIN_TILBAKESTILL_PATH,
+            "Fant ikke oppgaven",
+        ));
+    }
+    if !tilbakestill_oppgave(oppgave_id, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_TILBAKESTILL_PATH))?
+    {
+        return Err(ProblemDetails::conflict(
+            ADMIN_TILBAKESTILL_PATH,
+            "Bare oppgaver i status GITT_OPP, eller OPPRETTET uten ekstern oppgave-id, kan tilbakestilles",
+        ));
+    }
+    let hendelse_logg = HendelseLoggEntry::new(
+        ManueltTilbakestilt,
+        format!(
+            "Tilbakestilt til Ubehandlet av {}: {}",
+            utfoert_av(&principal),
+            request.begrunnelse.trim()
+        ),
+        Utc::now(),
+    );
+    oppdater_hendelse_logg(oppgave_id, hendelse_logg, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_TILBAKESTILL_PATH))?;
+    let oppgave = hent_oppgave(oppgave_id, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_TILBAKESTILL_PATH))?
+        .ok_or_else(|| ProblemDetails::not_found(ADMIN_TILBAKESTILL_PATH, "Fant ikke oppgaven"))?;
+    tx.commit()
+        .await
+        .map_err(database_feil(ADMIN_TILBAKESTILL_PATH))?;
+
+    tracing::info!("Oppgave {} tilbakestilt til Ubehandlet", oppgave_id);
+    Ok(Json(OppgaveResponse::med_hendelse_logg(&oppgave)))
+}
+
+/// Markerer oppgaven som `FERDIGBEHANDLET` uansett nåværende status, med begrunnelse
+#[tracing::instrument(skip(state, principal, request))]
+async fn ferdigbehandle(
+    State(state): State<AdminState>,
+    Extension(principal): Extension<Principal>,
+    oppgave_id: Result<Path<i64>, PathRejection>,
+    request: String,
+) -> Result<Json<OppgaveResponse>, ProblemDetails> {
+    let Path(oppgave_id) = oppgave_id.map_err(ugyldig_path(ADMIN_FERDIGBEHANDLE_PATH))?;
+    let oppgave_id = OppgaveId(oppgave_id);
+    let request = les_admin_handling(&request).map_err(ugyldig_body(ADMIN_FERDIGBEHANDLE_PATH))?;
+
+    let mut tx = state
+        .db_pool
+        .begin()
+        .await
+        .map_err(database_feil(ADMIN_FERDIGBEHANDLE_PATH))?;
+    let oppgave = hent_oppgave(oppgave_id, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_FERDIGBEHANDLE_PATH))?
+        .ok_or_else(|| {
+            ProblemDetails::not_found(ADMIN_FERDIGBEHANDLE_PATH, "Fant ikke oppgaven")
+        })?;
+    if oppgave.status == Ferdigbehandlet {
+        return Err(ProblemDetails::conflict(
+            ADMIN_FERDIGBEHANDLE_PATH,
+            "Oppgaven er allerede ferdigbehandlet",
+        ));
+    }
+    if !bytt_oppgave_status(oppgave_id, oppgave.status.clone(), Ferdigbehandlet, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_FERDIGBEHANDLE_PATH))?
+    {
+        return Err(ProblemDetails::conflict(
+            ADMIN_FERDIGBEHANDLE_PATH,
+            "Oppgaven ble endret samtidig, prøv igjen",
+        ));
+    }
+    let hendelse_logg = HendelseLoggEntry::new(
+        ManueltFerdigbehandlet,
+        format!(
+            "Ferdigbehandlet fra status {} av {}: {}",
+            oppgave.status,
+            utfoert_av(&principal),
+            request.begrunnelse.trim()
+        ),
+        Utc::now(),
+    );
+    oppdater_hendelse_logg(oppgave_id, hendelse_logg, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_FERDIGBEHANDLE_PATH))?;
+    let oppgave = hent_oppgave(oppgave_id, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_FERDIGBEHANDLE_PATH))?
+        .ok_or_else(|| {
+            ProblemDetails::not_found(ADMIN_FERDIGBEHANDLE_PATH, "Fant ikke oppgaven")
+        })?;
+    tx.commit()
+        .await
+        .map_err(database_feil(ADMIN_FERDIGBEHANDLE_PATH))?;
+
+    tracing::info!("Oppgave {} manuelt ferdigbehandlet", oppgave_id);
+    Ok(Json(OppgaveResponse::med_hendelse_logg(&oppgave)))
+}
+
+/// Kjører én runde av opprett-oppgave-tasken med batch-størrelsen fra config, uten å vente på
+/// neste intervall, med `fra_tidspunkt` fra definisjonen til hver oppgavetype. Oppgavene som
+/// plukkes opp får en `MANUELL_PROSESSERING` i hendelse-loggen.
+#[tracing::instrument(skip(state, principal))]
+async fn prosesser(
+    State(state): State<AdminState>,
+    Extension(principal): Extension<Principal>,
+) -> Result<Json<ProsesseringResponse>, ProblemDetails> {
+    let batch_size = *state.task_config.current().opprett_oppgaver_task_batch_size;
+    let retry_policy = state.task_config.current().retry_policy();
+    let fra_tidspunkt = fra_tidspunkt_per_type(&state.app_config);
+    let utfoert_av = utfoert_av(&principal);
+
+    let mut tx = state
+        .db_pool
+        .begin()
+        .await
+        .map_err(database_feil(ADMIN_PROSESSERING_PATH))?;
+    let oppgaver = hent_de_eldste_ubehandlede_oppgavene(batch_size, &fra_tidspunkt, &mut tx)
+        .await
+        .map_err(database_feil(ADMIN_PROSESSERING_PATH))?;
+    for oppgave in &oppgaver {
+        let hendelse_logg = HendelseLoggEntry::new(
+            ManuellProsessering,
+            format!("Manuell prosessering startet av {}", utfoert_av),
+            Utc::now(),
+        );
+        oppdater_hendelse_logg(oppgave.id(), hendelse_logg, &mut tx)
+            .await
+            .map_err(database_feil(ADMIN_PROSESSERING_PATH))?;
+    }
+    tx.commit()
+        .await
+        .map_err(database_feil(ADMIN_PROSESSERING_PATH))?;
+
+    tracing::info!("Starter manuell prosessering av {} oppgaver", oppgaver.len());
+    prosesser_oppgaver(
+        &state.db_pool,
+        &state.oppgave_api_client,
+        &state.app_config,
+        &retry_policy,
+        &oppgaver,
+    )
+    .await;
+    Ok(Json(ProsesseringResponse {
+        oppgave_ider: oppgaver.iter().map(|oppgave| i64::from(oppgave.id())).collect(),
+    }))
+}
+
+fn les_admin_handling(request: &str) -> anyhow::Result<AdminHandlingRequest> {
+    let request: AdminHandlingRequest = serde_json::from_str(request)?;
+    request.validate()?;
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::dto::OppgaverParams;
+    use crate::config::{
+        OppgaveClientConfig, read_application_config, read_opprett_oppgaver_task_config,
+    };
+    use crate::db::oppgave_functions::lagre_oppgave;
+    use crate::domain::hendelse_logg_status::HendelseLoggStatus::EksternOppgaveOpprettet;
+    use crate::domain::oppgave::Oppgave;
+    use crate::domain::oppgave_status::OppgaveStatus;
+    use crate::domain::oppgave_status::OppgaveStatus::{GittOpp, Opprettet, Ubehandlet};
+    use crate::domain::oppgave_type::OppgaveType;
+    use chrono::{DateTime, Duration};
+    use oauth2::principal::NavAnsatt;
+    use oppgave_api_mock::OppgaveApiMock;
+    use paw_test::setup_test_db::{TestDbGuard, setup_test_db};
+    use paw_test::stub_token_client::StubTokenClient;
+    use types::arbeidssoeker_id::ArbeidssoekerId;
+    use types::identitetsnummer::Identitetsnummer;
+    use types::nav_ident::NavIdent;
+    use uuid::Uuid;
+
+    const BEGRUNNELSE: &str = r#"{"begrunnelse": "Behandlet i Gosys"}"#;
+
+    async fn admin_state() -> anyhow::Result<(AdminState, OppgaveApiMock, TestDbGuard)> {
+        let (db_pool, db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&db_pool).await?;
+        let oppgave_api = OppgaveApiMock::default();
+        let base_url = oppgave_api
+            .start()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        let oppgave_api_client = Arc::new(OppgaveApiClient::new(
+            OppgaveClientConfig {
+                base_url: base_url.into(),
+                scope: "test-scope".to_string().into(),
+            },
+            Arc::new(StubTokenClient),
+        ));
+        let state = AdminState {
+            db_pool,
+            oppgave_api_client,
+            app_config: read_application_config()?,
+            task_config: ConfigWatch::fixed(read_opprett_oppgaver_task_config()?),
+        };
+        Ok((state, oppgave_api, db_container))
+    }
+
+    fn admin() -> Extension<Principal> {
+        Extension(Principal::NavAnsatt(NavAnsatt {
+            oid: "oid".to_string(),
+            ident: NavIdent::new("A123456".to_string()).unwrap(),
+            name: None,
+            roles: vec![],
+            groups: vec![],
+        }))
+    }
+
+    async fn lagre(
+        state: &AdminState,
+        oppgave_type: OppgaveType,
+        status: OppgaveStatus,
+        arbeidssoeker_id: i64,
+        tidspunkt: DateTime<Utc>,
+    ) -> anyhow::Result<OppgaveId> {
+        let oppgave = Oppgave::new(
+            Uuid::new_v4(),
+            oppgave_type,
+            status,
+            vec![],
+            ArbeidssoekerId(arbeidssoeker_id),
+            Identitetsnummer::new(format!("{:011}", arbeidssoeker_id)).unwrap(),
+            tidspunkt,
+        );
+        let mut tx = state.db_pool.begin().await?;
+        let oppgave_id = lagre_oppgave(&oppgave, &mut tx).await?;
+        tx.commit().await?;
+        Ok(oppgave_id)
+    }
+
+    async fn hent(state: &AdminState, oppgave_id: OppgaveId) -> anyhow::Result<Oppgave> {
+        let mut tx = state.db_pool.begin().await?;
+        let oppgave = hent_oppgave(oppgave_id, &mut tx)
+            .await?
+            .expect("Forventet oppgave");
+        tx.commit().await?;
+        Ok(oppgave)
+    }
+
+    async fn oppgave_ider(state: &AdminState, params: OppgaverParams) -> Vec<i64> {
+        let Json(response) = hent_oppgaver(State(state.clone()), Ok(Query(params)))
+            .await
+            .unwrap();
+        response.oppgaver.iter().map(|oppgave| oppgave.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_hent_oppgaver_filtrerer_og_pager() -> anyhow::Result<()> {
+        let (state, _oppgave_api, _db_container) = admin_state().await?;
+        let now = Utc::now();
+        let a = i64::from(lagre(&state, OppgaveType::AvvistUnder18, Ubehandlet, 1, now).await?);
+        let b = i64::from(lagre(&state, OppgaveType::AvvistUnder18, GittOpp, 2, now).await?);
+        let c = i64::from(
+            lagre(
+                &state,
+                OppgaveType::VurderOppholdsstatus,
+                Ubehandlet,
+                1,
+                now,
+            )
+            .await?,
+        );
+
+        assert_eq!(
+            oppgave_ider(&state, OppgaverParams::default()).await,
+            vec![c, b, a]
+        );
+        let status = OppgaverParams {
+            status: Some(Ubehandlet.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(oppgave_ider(&state, status).await, vec![c, a]);
+        let type_ = OppgaverParams {
+            type_: Some(OppgaveType::AvvistUnder18.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(oppgave_ider(&state, type_).await, vec![b, a]);
+        let arbeidssoeker = OppgaverParams {
+            arbeidssoeker_id: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(oppgave_ider(&state, arbeidssoeker).await, vec![b]);
+        let side = OppgaverParams {
+            limit: Some(1),
+            offset: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(oppgave_ider(&state, side).await, vec![b]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tilbakestill() -> anyhow::Result<()> {
+        let (state, _oppgave_api, _db_container) = admin_state().await?;
+        let gitt_opp = lagre(&state, OppgaveType::AvvistUnder18, GittOpp, 1, Utc::now()).await?;
+        let ubehandlet = lagre(
+            &state,
+            OppgaveType::AvvistUnder18,
+            Ubehandlet,
+            2,
+            Utc::now(),
+        )
+        .await?;
+
+        let Json(response) = tilbakestill(
+            State(state.clone()),
+            admin(),
+            Ok(Path(i64::from(gitt_opp))),
+            BEGRUNNELSE.to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status, Ubehandlet.to_string());
+        let oppgave = hent(&state, gitt_opp).await?;
+        assert_eq!(oppgave.status, Ubehandlet);
+        let logg = oppgave
+            .hendelse_logg
+            .iter()
+            .find(|entry| entry.status == ManueltTilbakestilt)
+            .expect("Forventet ManueltTilbakestilt i hendelse-loggen");
+        assert!(logg.melding.contains("A123456"));
+        assert!(logg.melding.contains("Behandlet i Gosys"));
+
+        let feil = tilbakestill(
+            State(state.clone()),
+            admin(),
+            Ok(Path(i64::from(ubehandlet))),
+            BEGRUNNELSE.to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(feil.status, 409);
+        assert!(hent(&state, ubehandlet).await?.hendelse_logg.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ferdigbehandle() -> anyhow::Result<()> {
+        let (state, _oppgave_api, _db_container) = admin_state().await?;
+        let oppgave_id = lagre(&state, OppgaveType::AvvistUnder18, GittOpp, 1, Utc::now()).await?;
+
+        let Json(response) = ferdigbehandle(
+            State(state.clone()),
+            admin(),
+            Ok(Path(i64::from(oppgave_id))),
+            BEGRUNNELSE.to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status, Ferdigbehandlet.to_string());
+        let oppgave = hent(&state, oppgave_id).await?;
+        assert_eq!(oppgave.status, Ferdigbehandlet);
+        let logg = oppgave
+            .hendelse_logg
+            .iter()
+            .find(|entry| entry.status == ManueltFerdigbehandlet)
+            .expect("Forventet ManueltFerdigbehandlet i hendelse-loggen");
+        assert!(logg.melding.contains(&GittOpp.to_string()));
+
+        let feil = ferdigbehandle(
+            State(state.clone()),
+            admin(),
+            Ok(Path(i64::from(oppgave_id))),
+            BEGRUNNELSE.to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(feil.status, 409);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prosesser_bruker_fra_tidspunkt_per_oppgavetype() -> anyhow::Result<()> {
+        let (mut state, oppgave_api, _db_container) = admin_state().await?;
+        let now = Utc::now();
+        state
+            .app_config
+            .opprett_avvist_under_18_oppgaver_fra_tidspunkt = (now - Duration::hours(1)).into();
+        let for_gammel = lagre(
+            &state,
+            OppgaveType::AvvistUnder18,
+            Ubehandlet,
+            1,
+            now - Duration::days(1),
+        )
+        .await?;
+        let avvist_under_18 = lagre(&state, OppgaveType::AvvistUnder18, Ubehandlet, 2, now).await?;
+        let vurder_oppholdsstatus = lagre(
+            &state,
+            OppgaveType::VurderOppholdsstatus,
+            Ubehandlet,
+            3,
+            now - Duration::days(1),
+        )
+        .await?;
+
+        let Json(response) = prosesser(State(state.clone()), admin()).await.unwrap();
+        let mut prosesserte = response.oppgave_ider;
+        prosesserte.sort();
+        assert_eq!(
+            prosesserte,
+            vec![i64::from(avvist_under_18), i64::from(vurder_oppholdsstatus)]
+        );
+        assert_eq!(oppgave_api.antall_oppgaver(), 2);
+
+        for oppgave_id in [avvist_under_18, vurder_oppholdsstatus] {
+            let oppgave = hent(&state, oppgave_id).await?;
+            assert_eq!(oppgave.status, Opprettet);
+            let statuser: Vec<_> = oppgave
+                .hendelse_logg
+                .iter()
+                .map(|entry| &entry.status)
+                .collect();
+            assert!(statuser.contains(&&ManuellProsessering));
+            assert!(statuser.contains(&&EksternOppgaveOpprettet));
+        }
+        let for_gammel = hent(&state, for_gammel).await?;
+        assert_eq!(for_gammel.status, Ubehandlet);
+        assert!(for_gammel.hendelse_logg.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_les_admin_handling() {
+        let request = les_admin_handling(r#"{"begrunnelse": "Behandlet i Gosys"}"#).unwrap();
+        assert_eq!(request.begrunnelse, "Behandlet i Gosys");
+        assert!(les_admin_handling(r#"{"begrunnelse": ""}"#).is_err());
+        assert!(les_admin_handling("{}").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use oauth2::principal::Principal;
+
+/// Bare NAV-ansatte i en av `admin_grupper` har tilgang til admin-API-et
+pub fn er_admin(principal: &Principal, admin_grupper: &[String]) -> bool {
+    match principal {
+        Principal::NavAnsatt(nav_ansatt) => nav_ansatt
+            .groups
+            .iter()
+            .any(|gruppe| admin_grupper.contains(gruppe)),
+        _ => false,
+    }
+}
+
+/// Hvem som utførte en admin-handling, for hendelse-loggen
+pub fn utfoert_av(principal: &Principal) -> String {
+    match principal {
+        Principal::NavAnsatt(nav_ansatt) => nav_ansatt.ident.as_ref().to_string(),
+        _ => "ukjent".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oauth2::principal::{NavAnsatt, NavSystem};
+    use types::nav_ident::NavIdent;
+
+    fn nav_ansatt(groups: Vec<&str>) -> Principal {
+        Principal::NavAnsatt(NavAnsatt {
+            oid: "oid".to_string(),
+            ident: NavIdent::new("A123456".to_string()).unwrap(),
+            name: None,
+            roles: vec![],
+            groups: groups.into_iter().map(String::from).collect(),
+        })
+    }
+
+    #[test]
+    fn test_er_admin() {
+        let admin_grupper = vec!["admin".to_string()];
+        assert!(er_admin(&nav_ansatt(vec!["annen", "admin"]), &admin_grupper));
+        assert!(!er_admin(&nav_ansatt(vec!["annen"]), &admin_grupper));
+        assert!(!er_admin(&nav_ansatt(vec!["admin"]), &[]));
+
+        let nav_system = Principal::NavSystem(NavSystem {
+            oid: "oid".to_string(),
+            roles: vec![],
+        });
+        assert!(!er_admin(&nav_system, &admin_grupper));
+    }
+
+    #[test]
+    fn test_utfoert_av() {
+        assert_eq!(utfoert_av(&nav_ansatt(vec![])), "A123456");
+    }
+}
@@ -0,0 +1,421 @@
+use crate::client::oppgave_client::OppgaveApiClient;
+use crate::client::oppgave_dto::{OppgaveDto, OppgavestatusDto};
+use crate::config::ApplicationConfig;
+use crate::db::oppgave_functions::{
+    bytt_oppgave_status, hent_oppgaver_til_avstemming, oppdater_hendelse_logg,
+    oppdater_sist_avstemt, tilbakestill_ikke_funnet_oppgave,
+};
+use crate::domain::ekstern_oppgave_id::EksternOppgaveId;
+use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
+use crate::domain::hendelse_logg_status::HendelseLoggStatus::{
+    EksternOppgaveFeilregistrert, EksternOppgaveFerdigstilt, EksternOppgaveIkkeFunnet,
+};
+use crate::domain::oppgave::Oppgave;
+use crate::domain::oppgave_status::OppgaveStatus::{Ferdigbehandlet, Opprettet};
+use crate::metrics::avstemming_avvik;
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::PgPool;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+use strum::{Display, EnumIter};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Avvik mellom en lokal `Opprettet`-oppgave og tilstanden i Oppgave API
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum Avvik {
+    FerdigstiltEksternt,
+    FeilregistrertEksternt,
+    IkkeFunnetEksternt,
+}
+
+/// Fanger opp oppgaver som står i `Opprettet` fordi vi har gått glipp av hendelsen om at de ble
+/// ferdigstilt eller feilregistrert i Oppgave API, eller fordi de ikke finnes der.
+pub fn spawn_avstemming_task(
+    db_pool: PgPool,
+    oppgave_api_client: Arc<OppgaveApiClient>,
+    app_config: ApplicationConfig,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<Result<()>> {
+    tokio::spawn(async move {
+        let interval = Duration::from_mins(app_config.avstemming_interval_minutes.get() as u64);
+        let terskel = Duration::from_hours(app_config.avstemming_terskel_timer.get() as u64);
+        let batch_size = *app_config.avstemming_batch_size;
+        let dry_run = *app_config.avstemming_dry_run;
+        while !cancellation_token.is_cancelled() {
+            if let Err(e) =
+                avstem_oppgaver(&db_pool, &oppgave_api_client, terskel, batch_size, dry_run).await
+            {
+                tracing::warn!(error = %e, "Avstemming mot Oppgave API feilet");
+            }
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+        tracing::info!("Avstemming task stoppet");
+        Ok(())
+    })
+}
+
+/// Avstemmer oppgaver som har vært `Opprettet` lenger enn `terskel` og ikke er avstemt innenfor
+/// `terskel`. Returnerer antall avvik.
+pub async fn avstem_oppgaver(
+    db_pool: &PgPool,
+    oppgave_api_client: &OppgaveApiClient,
+    terskel: Duration,
+    batch_size: NonZeroU32,
+    dry_run: bool,
+) -> Result<usize> {
+    let grense = Utc::now() - chrono::Duration::from_std(terskel)?;
+    let mut tx = db_pool.begin().await?;
+    let oppgaver = hent_oppgaver_til_avstemming(grense, grense, batch_size, &mut tx).await?;
+    tx.commit().await?;
+
+    let mut avstemt = Vec::with_capacity(oppgaver.len());
+    let mut antall_avvik = 0;
+    for oppgave in &oppgaver {
+        let Some(ekstern_oppgave_id) = oppgave.ekstern_oppgave_id else {
+            continue;
+        };
+        let ekstern_oppgave = match oppgave_api_client.hent_oppgave(ekstern_oppgave_id).await {
+            Ok(ekstern_oppgave) => ekstern_oppgave,
+            Err(e) => {
+                tracing::warn!(
+                    "Kunne ikke hente ekstern oppgave {} for avstemming: {}",
+                    ekstern_oppgave_id,
+                    e
+                );
+                continue;
+            }
+        };
+        avstemt.push(oppgave.id());
+
+        let Some(avvik) = finn_avvik(ekstern_oppgave.as_ref()) else {
+            continue;
+        };
+        antall_avvik += 1;
+        avstemming_avvik::inkrement(&avvik, dry_run);
+        tracing::warn!(
+            oppgave_id = %oppgave.id(),
+            dry_run,
+            "Avvik ved avstemming av ekstern oppgave {}: {}",
+            ekstern_oppgave_id,
+            avvik
+        );
+        if !dry_run {
+            rett_avvik(db_pool, oppgave, ekstern_oppgave_id, avvik).await?;
+        }
+    }
+
+    let mut tx = db_pool.begin().await?;
+    oppdater_sist_avstemt(&avstemt, Utc::now(), &mut tx).await?;
+    tx.commit().await?;
+    tracing::info!(
+        "Avstemte {} oppgaver mot Oppgave API, fant {} avvik",
+        avstemt.len(),
+        antall_avvik
+    );
+    Ok(antall_avvik)
+}
+
+fn finn_avvik(ekstern_oppgave: Option<&OppgaveDto>) -> Option<Avvik> {
+    match ekstern_oppgave.map(|oppgave| &oppgave.status) {
+        None => Some(Avvik::IkkeFunnetEksternt),
+        Some(OppgavestatusDto::Ferdigstilt) => Some(Avvik::FerdigstiltEksternt),
+        Some(OppgavestatusDto::Feilregistrert) => Some(Avvik::FeilregistrertEksternt),
+        Some(_) => None,
+    }
+}
+
+/// Ferdigbehandler oppgaven lokalt når den er lukket i Oppgave API. Oppgaver som ikke finnes
+/// eksternt settes tilbake til `Ubehandlet` og opprettes på nytt.
+async fn rett_avvik(
+    db_pool: &PgPool,
+    oppgave: &Oppgave,
+    ekstern_oppgave_id: EksternOppgaveId,
+    avvik: Avvik,
+) -> Result<()> {
+    let mut tx = db_pool.begin().await?;
+    let (endret, logg_status, melding) = match avvik {
+        Avvik::FerdigstiltEksternt => (
+            bytt_oppgave_status(oppgave.id(), Opprettet, Ferdigbehandlet, &mut tx).await?,
+            EksternOppgaveFerdigstilt,
+            format!(
+                "Avstemming: ekstern oppgave {} er ferdigstilt i Oppgave API",
+                ekstern_oppgave_id
+            ),
+        ),
+        Avvik::FeilregistrertEksternt => (
+            bytt_oppgave_status(oppgave.id(), Opprettet, Ferdigbehandlet, &mut tx).await?,
+            EksternOppgaveFeilregistrert,
+            format!(
+                "Avstemming: ekstern oppgave {} er feilregistrert i Oppgave API",
+                ekstern_oppgave_id
+            ),
+        ),
+        Avvik::IkkeFunnetEksternt => (
+            tilbakestill_ikke_funnet_oppgave(oppgave.id(), ekstern_oppgave_id, &mut tx).await?,
+            EksternOppgaveIkkeFunnet,
+            format!(
+                "Avstemming: ekstern oppgave {} finnes ikke i Oppgave API, tilbakestilt til Ubehandlet",
+                ekstern_oppgave_id
+            ),
+        ),
+    };
+    if endret {
+        let hendelse_logg = HendelseLoggEntry::new(logg_status, melding, Utc::now());
+        oppdater_hendelse_logg(oppgave.id(), hendelse_logg, &mut tx).await?;
+        tracing::info!(
+            "Oppgave {} oppdatert etter avstemming: {}",
+            oppgave.id(),
+            avvik
+        );
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::oppgave_client::OPPGAVER_PATH;
+    use crate::config::OppgaveClientConfig;
+    use crate::db::oppgave_functions::{
+        hent_oppgave, lagre_oppgave, oppdater_oppgave_med_ekstern_id,
+    };
+    use crate::domain::oppgave_id::OppgaveId;
+    use crate::domain::oppgave_status::OppgaveStatus::Ubehandlet;
+    use crate::domain::oppgave_type::OppgaveType;
+    use crate::ferdigstilling::ferdigstill_oppgave::ferdigstill_oppgave;
+    use mockito::Server;
+    use paw_test::setup_test_db::setup_test_db;
+    use paw_test::stub_token_client::StubTokenClient;
+    use serde_json::json;
+    use types::arbeidssoeker_id::ArbeidssoekerId;
+    use types::identitetsnummer::Identitetsnummer;
+    use uuid::Uuid;
+
+    fn oppgave_dto(status: &str) -> OppgaveDto {
+        serde_json::from_value(oppgave_json(1, status)).unwrap()
+    }
+
+    fn oppgave_json(id: i64, status: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "tildeltEnhetsnr": "4863",
+            "oppgavetype": "KONT_BRUK",
+            "tema": "GEN",
+            "prioritet": "NORM",
+            "status": status,
+            "aktivDato": "2026-02-16",
+            "versjon": 2,
+        })
+    }
+
+    #[test]
+    fn test_finn_avvik() {
+        assert_eq!(finn_avvik(None), Some(Avvik::IkkeFunnetEksternt));
+        assert_eq!(
+            finn_avvik(Some(&oppgave_dto("FERDIGSTILT"))),
+            Some(Avvik::FerdigstiltEksternt)
+        );
+        assert_eq!(
+            finn_avvik(Some(&oppgave_dto("FEILREGISTRERT"))),
+            Some(Avvik::FeilregistrertEksternt)
+        );
+        assert_eq!(finn_avvik(Some(&oppgave_dto("OPPRETTET"))), None);
+        assert_eq!(finn_avvik(Some(&oppgave_dto("UNDER_BEHANDLING"))), None);
+    }
+
+    async fn lagre_opprettet_oppgave(
+        pg_pool: &PgPool,
+        arbeidssoeker_id: i64,
+        ekstern_oppgave_id: i64,
+    ) -> Result<OppgaveId> {
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = Oppgave::new(
+            Uuid::new_v4(),
+            OppgaveType::AvvistUnder18,
+            Opprettet,
+            vec![],
+            ArbeidssoekerId(arbeidssoeker_id),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            Utc::now() - chrono::Duration::days(2),
+        );
+        let oppgave_id = lagre_oppgave(&oppgave, &mut tx).await?;
+        oppdater_oppgave_med_ekstern_id(
+            oppgave_id,
+            EksternOppgaveId::from(ekstern_oppgave_id),
+            &mut tx,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(oppgave_id)
+    }
+
+    #[tokio::test]
+    async fn test_avstem_oppgaver() -> Result<()> {
+        let mut server = Server::new_async().await;
+        for (id, status) in [(100, "FERDIGSTILT"), (200, "UNDER_BEHANDLING")] {
+            server
+                .mock("GET", format!("{}/{}", OPPGAVER_PATH, id).as_str())
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(oppgave_json(id, status).to_string())
+                .create_async()
+                .await;
+        }
+        server
+            .mock("GET", format!("{}/300", OPPGAVER_PATH).as_str())
+            .with_status(404)
+            .create_async()
+            .await;
+        let oppgave_api_client = OppgaveApiClient::new(
+            OppgaveClientConfig {
+                base_url: server.url().into(),
+                scope: "test-scope".to_string().into(),
+            },
+            Arc::new(StubTokenClient),
+        );
+
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+        let ferdigstilt_id = lagre_opprettet_oppgave(&pg_pool, 1, 100).await?;
+        let aapen_id = lagre_opprettet_oppgave(&pg_pool, 2, 200).await?;
+        let ukjent_id = lagre_opprettet_oppgave(&pg_pool, 3, 300).await?;
+
+        let terskel = Duration::from_hours(24);
+        let batch_size = NonZeroU32::new(10).unwrap();
+
+        // Dry-run finner avvik uten å endre noe
+        assert_eq!(
+            avstem_oppgaver(&pg_pool, &oppgave_api_client, terskel, batch_size, true).await?,
+            2
+        );
+        let mut tx = pg_pool.begin().await?;
+        assert_eq!(
+            hent_oppgave(ferdigstilt_id, &mut tx).await?.unwrap().status,
+            Opprettet
+        );
+        tx.commit().await?;
+
+        // Avstemte oppgaver hoppes over til terskelen er passert igjen
+        assert_eq!(
+            avstem_oppgaver(&pg_pool, &oppgave_api_client, terskel, batch_size, false).await?,
+            0
+        );
+        sqlx::query("UPDATE oppgaver SET sist_avstemt = NULL")
+            .execute(&pg_pool)
+            .await?;
+        assert_eq!(
+            avstem_oppgaver(&pg_pool, &oppgave_api_client, terskel, batch_size, false).await?,
+            2
+        );
+
+        let mut tx = pg_pool.begin().await?;
+        let ferdigstilt = hent_oppgave(ferdigstilt_id, &mut tx).await?.unwrap();
+        assert_eq!(ferdigstilt.status, Ferdigbehandlet);
+        assert!(ferdigstilt
+            .hendelse_logg
+            .iter()
+            .any(|logg| logg.status == EksternOppgaveFerdigstilt));
+        assert_eq!(
+            hent_oppgave(aapen_id, &mut tx).await?.unwrap().status,
+            Opprettet
+        );
+        let ukjent = hent_oppgave(ukjent_id, &mut tx).await?.unwrap();
+        assert_eq!(ukjent.status, Ubehandlet);
+        assert_eq!(ukjent.ekstern_oppgave_id, None);
+        assert!(ukjent
+            .hendelse_logg
+            .iter()
+            .any(|logg| logg.status == EksternOppgaveIkkeFunnet));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ferdigstilling_foer_ekstern_id_er_lagret_rettes_av_avstemming() -> Result<()> {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", format!("{}/400", OPPGAVER_PATH).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(oppgave_json(400, "FERDIGSTILT").to_string())
+            .create_async()
+            .await;
+        let oppgave_api_client = OppgaveApiClient::new(
+            OppgaveClientConfig {
+                base_url: server.url().into(),
+                scope: "test-scope".to_string().into(),
+            },
+            Arc::new(StubTokenClient),
+        );
+
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = Oppgave::new(
+            Uuid::new_v4(),
+            OppgaveType::AvvistUnder18,
+            Opprettet,
+            vec![],
+            ArbeidssoekerId(4),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            Utc::now() - chrono::Duration::days(2),
+        );
+        let oppgave_id = lagre_oppgave(&oppgave, &mut tx).await?;
+        tx.commit().await?;
+
+        // Hendelsen kommer før opprettelsen har lagret ekstern id, og hoppes over
+        let ferdigstilt = json!({
+            "hendelse": {
+                "hendelsestype": "OPPGAVE_FERDIGSTILT",
+                "tidspunkt": [2026, 10, 19, 9, 0, 0]
+            },
+            "utfortAv": null,
+            "oppgave": {
+                "oppgaveId": 400,
+                "versjon": 2,
+                "tilordning": null,
+                "kategorisering": null,
+                "behandlingsperiode": null,
+                "bruker": null
+            }
+        });
+        let mut tx = pg_pool.begin().await?;
+        ferdigstill_oppgave(ferdigstilt.to_string().as_bytes(), &mut tx).await?;
+        oppdater_oppgave_med_ekstern_id(oppgave_id, EksternOppgaveId::from(400), &mut tx).await?;
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        assert_eq!(
+            hent_oppgave(oppgave_id, &mut tx).await?.unwrap().status,
+            Opprettet
+        );
+        tx.commit().await?;
+
+        let terskel = Duration::from_hours(24);
+        let batch_size = NonZeroU32::new(10).unwrap();
+        assert_eq!(
+            avstem_oppgaver(&pg_pool, &oppgave_api_client, terskel, batch_size, false).await?,
+            1
+        );
+
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = hent_oppgave(oppgave_id, &mut tx).await?.unwrap();
+        assert_eq!(oppgave.status, Ferdigbehandlet);
+        assert!(
+            oppgave
+                .hendelse_logg
+                .iter()
+                .any(|logg| logg.status == EksternOppgaveFerdigstilt)
+        );
+
+        Ok(())
+    }
+}
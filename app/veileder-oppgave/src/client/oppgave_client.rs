@@ -1,11 +1,13 @@
-use crate::client::oppgave_dto::OppgaveDto;
+use crate::client::oppgave_dto::{OppgaveDto, SokOppgaverResponse};
 use crate::client::opprett_oppgave_request::OpprettOppgaveRequest;
 use crate::config::OppgaveClientConfig;
+use crate::domain::ekstern_oppgave_id::EksternOppgaveId;
 use anyhow::Result;
 use reqwest::Client;
 use std::sync::Arc;
 use std::time::Duration;
 use texas_client::token_client::M2MTokenClient;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct OppgaveApiClient {
@@ -56,6 +58,61 @@ impl OppgaveApiClient {
         }
     }
 
+    /// Henter oppgaven fra Oppgave API. `None` hvis den ikke finnes.
+    pub async fn hent_oppgave(
+        &self,
+        ekstern_oppgave_id: EksternOppgaveId,
+    ) -> Result<Option<OppgaveDto>, OppgaveApiError> {
+        let url = format!(
+            "{}{}/{}",
+            self.config.base_url, OPPGAVER_PATH, ekstern_oppgave_id
+        );
+        let token = self.hent_token().await?;
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Correlation-ID", uuid::Uuid::new_v4().to_string())
+            .bearer_auth(token)
+            .send()
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(Some(response.json().await?)),
+            reqwest::StatusCode::NOT_FOUND => Ok(None),
+            status => Err(OppgaveApiError::ApiError {
+                status,
+                message: response.text().await.unwrap_or_default(),
+            }),
+        }
+    }
+
+    /// Finner oppgaven som ble opprettet med gitt `uuid`. `None` hvis den ikke finnes.
+    pub async fn finn_oppgave_med_uuid(
+        &self,
+        uuid: Uuid,
+    ) -> Result<Option<OppgaveDto>, OppgaveApiError> {
+        let url = format!("{}{}?uuid={}", self.config.base_url, OPPGAVER_PATH, uuid);
+        let token = self.hent_token().await?;
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Correlation-ID", uuid::Uuid::new_v4().to_string())
+            .bearer_auth(token)
+            .send()
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => {
+                let sok: SokOppgaverResponse = response.json().await?;
+                Ok(sok.oppgaver.into_iter().next())
+            }
+            status => Err(OppgaveApiError::ApiError {
+                status,
+                message: response.text().await.unwrap_or_default(),
+            }),
+        }
+    }
+
     async fn hent_token(&self) -> Result<String> {
         let token_response = self
             .token_client
@@ -77,11 +134,40 @@ pub enum OppgaveApiError {
     },
     #[error("Token-feil: {0}")]
     TokenError(#[from] anyhow::Error),
+    /// Oppgave API svarte 409, men søk på `uuid` fant ikke oppgaven (f.eks. forsinket indeksering)
+    #[error("Oppgave med uuid {uuid} finnes allerede, men ble ikke funnet ved søk")]
+    IkkeFunnetEtterKonflikt { uuid: Uuid },
+}
+
+impl OppgaveApiError {
+    /// Oppgave API svarer 409 når en oppgave med samme `uuid` allerede er opprettet.
+    pub fn finnes_allerede(&self) -> bool {
+        matches!(
+            self,
+            OppgaveApiError::ApiError { status, .. } if *status == reqwest::StatusCode::CONFLICT
+        )
+    }
+
+    /// 4xx-feil fra Oppgave API er permanente, bortsett fra timeout og rate limiting.
+    /// Nettverks-, token- og serverfeil kan gå over av seg selv.
+    pub fn kan_proeves_igjen(&self) -> bool {
+        match self {
+            OppgaveApiError::ApiError { status, .. } => {
+                !status.is_client_error()
+                    || *status == reqwest::StatusCode::REQUEST_TIMEOUT
+                    || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            OppgaveApiError::ReqwestError(_)
+            | OppgaveApiError::TokenError(_)
+            | OppgaveApiError::IkkeFunnetEtterKonflikt { .. } => true,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::oppgave_dto::OppgavestatusDto;
     use crate::client::opprett_oppgave_request::PrioritetV1;
     use chrono::Utc;
     use mockito::Server;
@@ -137,5 +223,153 @@ mod tests {
 
         oppgave_mock_api.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn test_hent_oppgave() {
+        let mut server = Server::new_async().await;
+        let oppgave_mock_api = server
+            .mock("GET", format!("{}/12345", OPPGAVER_PATH).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "id": 12345,
+                    "tildeltEnhetsnr": "4863",
+                    "oppgavetype": "KONT_BRUK",
+                    "tema": "GEN",
+                    "prioritet": "NORM",
+                    "status": "FERDIGSTILT",
+                    "aktivDato": "2026-02-16",
+                    "versjon": 4,
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let ukjent_mock_api = server
+            .mock("GET", format!("{}/54321", OPPGAVER_PATH).as_str())
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let config = OppgaveClientConfig {
+            base_url: server.url().into(),
+            scope: "test-scope".to_string().into(),
+        };
+        let client = OppgaveApiClient::new(config, Arc::new(StubTokenClient));
+
+        let oppgave = client
+            .hent_oppgave(EksternOppgaveId::from(12345))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(oppgave.status, OppgavestatusDto::Ferdigstilt);
+        assert_eq!(oppgave.versjon, 4);
+        assert!(
+            client
+                .hent_oppgave(EksternOppgaveId::from(54321))
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        oppgave_mock_api.assert_async().await;
+        ukjent_mock_api.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_finn_oppgave_med_uuid() {
+        let mut server = Server::new_async().await;
+        let uuid = Uuid::new_v4();
+        let ukjent_uuid = Uuid::new_v4();
+        let oppgave_mock_api = server
+            .mock("GET", OPPGAVER_PATH)
+            .match_query(mockito::Matcher::UrlEncoded(
+                "uuid".into(),
+                uuid.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "antallTreffTotalt": 1,
+                    "oppgaver": [{
+                        "id": 12345,
+                        "tildeltEnhetsnr": "4863",
+                        "oppgavetype": "KONT_BRUK",
+                        "tema": "GEN",
+                        "prioritet": "NORM",
+                        "status": "OPPRETTET",
+                        "aktivDato": "2026-02-16",
+                        "versjon": 1,
+                    }],
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let ukjent_mock_api = server
+            .mock("GET", OPPGAVER_PATH)
+            .match_query(mockito::Matcher::UrlEncoded(
+                "uuid".into(),
+                ukjent_uuid.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "antallTreffTotalt": 0, "oppgaver": [] }).to_string())
+            .create_async()
+            .await;
+
+        let config = OppgaveClientConfig {
+            base_url: server.url().into(),
+            scope: "test-scope".to_string().into(),
+        };
+        let client = OppgaveApiClient::new(config, Arc::new(StubTokenClient));
+
+        let oppgave = client.finn_oppgave_med_uuid(uuid).await.unwrap().unwrap();
+        assert_eq!(oppgave.id, 12345);
+        assert!(
+            client
+                .finn_oppgave_med_uuid(ukjent_uuid)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        oppgave_mock_api.assert_async().await;
+        ukjent_mock_api.assert_async().await;
+    }
+
+    #[test]
+    fn test_finnes_allerede() {
+        let api_error = |status: reqwest::StatusCode| OppgaveApiError::ApiError {
+            status,
+            message: String::new(),
+        };
+        assert!(api_error(reqwest::StatusCode::CONFLICT).finnes_allerede());
+        assert!(!api_error(reqwest::StatusCode::BAD_REQUEST).finnes_allerede());
+        assert!(!OppgaveApiError::TokenError(anyhow::anyhow!("utløpt")).finnes_allerede());
+    }
+
+    #[test]
+    fn test_kan_proeves_igjen() {
+        let api_error = |status: reqwest::StatusCode| OppgaveApiError::ApiError {
+            status,
+            message: String::new(),
+        };
+        assert!(api_error(reqwest::StatusCode::INTERNAL_SERVER_ERROR).kan_proeves_igjen());
+        assert!(api_error(reqwest::StatusCode::SERVICE_UNAVAILABLE).kan_proeves_igjen());
+        assert!(api_error(reqwest::StatusCode::TOO_MANY_REQUESTS).kan_proeves_igjen());
+        assert!(api_error(reqwest::StatusCode::REQUEST_TIMEOUT).kan_proeves_igjen());
+        assert!(!api_error(reqwest::StatusCode::BAD_REQUEST).kan_proeves_igjen());
+        assert!(!api_error(reqwest::StatusCode::NOT_FOUND).kan_proeves_igjen());
+        assert!(OppgaveApiError::TokenError(anyhow::anyhow!("utløpt")).kan_proeves_igjen());
+        assert!(
+            OppgaveApiError::IkkeFunnetEtterKonflikt {
+                uuid: Uuid::new_v4()
+            }
+            .kan_proeves_igjen()
+        );
+    }
 }
 
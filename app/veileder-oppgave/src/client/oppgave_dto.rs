@@ -55,6 +55,13 @@ pub struct OppgaveDto {
     pub bruker: Option<BrukerDto>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SokOppgaverResponse {
+    pub antall_treff_totalt: i64,
+    pub oppgaver: Vec<OppgaveDto>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BrukerDto {
@@ -1,7 +1,10 @@
-use crate::domain::oppgave_type::OppgaveType;
-use chrono::Utc;
+use crate::config::ApplicationConfig;
+use crate::domain::oppgave::Oppgave;
+use crate::domain::oppgavetyper::definisjon_for;
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+use paw_date_time::virkedager::{neste_virkedag, norsk_dato};
 use serde::{Deserialize, Serialize};
-use types::identitetsnummer::Identitetsnummer;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -39,54 +42,44 @@ pub struct OpprettOppgaveRequest {
 }
 
 pub fn create_oppgave_request(
-    identitetsnummer: Identitetsnummer,
-    oppgave_type: &OppgaveType,
-) -> OpprettOppgaveRequest {
-    match oppgave_type {
-        OppgaveType::AvvistUnder18 => avvist_under_18(identitetsnummer),
-        OppgaveType::VurderOppholdsstatus => vurder_oppholdsstatus(identitetsnummer),
-    }
-}
-
-const GENERELL: &str = "GEN";
-
-const BESKRIVELSE_AVVIST_UNDER_18: &str = r#"Personen har forsøkt å registrere seg som arbeidssøker, men er sperret fra å gjøre dette da personen er under 18 år.
-For mindreårige arbeidssøkere trengs det samtykke fra begge foresatte for å kunne registrere seg.
-Se "Samtykke fra foresatte til unge under 18 år - registrering som arbeidssøker, øvrige tiltak og tjenester".
-
-Når samtykke er innhentet kan du registrere arbeidssøker via flate for manuell registrering i modia."#;
-
-const KONTAKT_BRUKER: &str = "KONT_BRUK";
-
-fn avvist_under_18(identitetsnummer: Identitetsnummer) -> OpprettOppgaveRequest {
-    OpprettOppgaveRequest {
-        personident: Some(String::from(identitetsnummer)),
-        aktiv_dato: Utc::now().format("%Y-%m-%d").to_string(),
-        prioritet: PrioritetV1::Norm,
-        oppgavetype: KONTAKT_BRUKER.to_string(),
-        tema: GENERELL.to_string(),
-        beskrivelse: Some(BESKRIVELSE_AVVIST_UNDER_18.to_string()),
-        ..Default::default()
-    }
+    oppgave: &Oppgave,
+    app_config: &ApplicationConfig,
+) -> anyhow::Result<OpprettOppgaveRequest> {
+    oppgave_request_fra_dato(oppgave, app_config, norsk_dato(Utc::now()))
 }
 
-const BESKRIVELSE_VURDER_OPPHOLDSSTATUS: &str = r#"Vurder oppholdsstatus for EU/EØS-borger som har status utflyttet i folkeregisteret
-
-Personen har registrert seg som arbeidssøker og har status utflyttet i folkeregisteret. Nav trenger bekreftelse på at personen har lovlig opphold i Norge. Vurder personens oppholdsstatus i henhold til gjeldende rutine. Dersom vedkommende ikke har lovlig opphold skal personens arbeidssøkerperiode avsluttes.
-
-Se servicerutine: "EU/EØS-borgere med status utflyttet""#;
-const VURDER_HENVENDELSE: &str = "VURD_HENV";
-
-fn vurder_oppholdsstatus(identitetsnummer: Identitetsnummer) -> OpprettOppgaveRequest {
-    OpprettOppgaveRequest {
-        personident: Some(String::from(identitetsnummer)),
-        aktiv_dato: Utc::now().format("%Y-%m-%d").to_string(),
+/// Aktiv dato er første virkedag fra og med `idag`, og fristen regnes fra aktiv dato.
+fn oppgave_request_fra_dato(
+    oppgave: &Oppgave,
+    app_config: &ApplicationConfig,
+    idag: NaiveDate,
+) -> anyhow::Result<OpprettOppgaveRequest> {
+    let definisjon = definisjon_for(oppgave.type_)
+        .with_context(|| format!("Mangler definisjon for oppgavetype {}", oppgave.type_))?;
+    let felter = app_config
+        .oppgave_api_felter(oppgave.type_)
+        .with_context(|| {
+            format!(
+                "Mangler Oppgave API-felter for oppgavetype {}",
+                oppgave.type_
+            )
+        })?;
+    let aktiv_dato = neste_virkedag(idag);
+    Ok(OpprettOppgaveRequest {
+        personident: Some(String::from(oppgave.identitetsnummer.clone())),
+        aktiv_dato: aktiv_dato.format("%Y-%m-%d").to_string(),
         prioritet: PrioritetV1::Norm,
-        oppgavetype: VURDER_HENVENDELSE.to_string(),
-        tema: GENERELL.to_string(),
-        beskrivelse: Some(BESKRIVELSE_VURDER_OPPHOLDSSTATUS.to_string()),
+        oppgavetype: felter.oppgavetype.clone(),
+        tema: felter.tema.clone(),
+        behandlingstema: felter.behandlingstema.clone(),
+        behandlingstype: felter.behandlingstype.clone(),
+        beskrivelse: Some(definisjon.beskrivelse_for(oppgave)),
+        frist_ferdigstillelse: felter
+            .frist
+            .map(|frist| frist.fra(aktiv_dato).format("%Y-%m-%d").to_string()),
+        uuid: Some(oppgave.ekstern_uuid().to_string()),
         ..Default::default()
-    }
+    })
 }
 
 impl Default for OpprettOppgaveRequest {
@@ -126,29 +119,111 @@ pub enum PrioritetV1 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::read_application_config;
+    use crate::domain::oppgave_id::OppgaveId;
+    use crate::domain::oppgave_status::OppgaveStatus;
     use crate::domain::oppgave_type::OppgaveType;
+    use crate::domain::oppgavetyper::definisjon::Frist;
+    use crate::domain::oppgavetyper::{avvist_under_18, vurder_oppholdsstatus};
+    use types::arbeidssoeker_id::ArbeidssoekerId;
+    use types::identitetsnummer::Identitetsnummer;
+    use uuid::Uuid;
+
+    fn oppgave(oppgave_type: OppgaveType, identitetsnummer: &str) -> Oppgave {
+        Oppgave::fra_db(
+            OppgaveId(1),
+            Uuid::new_v4(),
+            oppgave_type,
+            OppgaveStatus::Ubehandlet,
+            vec![],
+            None,
+            ArbeidssoekerId(1),
+            Identitetsnummer::new(identitetsnummer.to_string()).unwrap(),
+            None,
+            Utc::now(),
+            vec![],
+        )
+    }
 
     #[test]
     fn test_avvist_under_18_request() {
-        let identitetsnummer = Identitetsnummer::new("12345678901".to_string()).unwrap();
-        let request = create_oppgave_request(identitetsnummer, &OppgaveType::AvvistUnder18);
+        let oppgave = oppgave(OppgaveType::AvvistUnder18, "12345678901");
+        let request =
+            create_oppgave_request(&oppgave, &read_application_config().unwrap()).unwrap();
 
         assert_eq!(request.personident, Some("12345678901".to_string()));
-        assert_eq!(request.oppgavetype, KONTAKT_BRUKER);
-        assert_eq!(request.tema, GENERELL);
+        assert_eq!(request.oppgavetype, "KONT_BRUK");
+        assert_eq!(request.tema, "GEN");
         assert_eq!(request.prioritet, PrioritetV1::Norm);
-        assert_eq!(request.beskrivelse, Some(BESKRIVELSE_AVVIST_UNDER_18.to_string()));
+        assert_eq!(
+            request.beskrivelse.as_deref(),
+            Some(avvist_under_18::DEFINISJON.beskrivelse)
+        );
+        assert_eq!(request.frist_ferdigstillelse, None);
+        assert_eq!(request.uuid, Some(oppgave.ekstern_uuid().to_string()));
     }
 
     #[test]
     fn test_vurder_oppholdsstatus_request() {
-        let identitetsnummer = Identitetsnummer::new("12345678902".to_string()).unwrap();
-        let request = create_oppgave_request(identitetsnummer, &OppgaveType::VurderOppholdsstatus);
+        let oppgave = oppgave(OppgaveType::VurderOppholdsstatus, "12345678902");
+        let request =
+            create_oppgave_request(&oppgave, &read_application_config().unwrap()).unwrap();
 
         assert_eq!(request.personident, Some("12345678902".to_string()));
-        assert_eq!(request.oppgavetype, VURDER_HENVENDELSE);
-        assert_eq!(request.tema, GENERELL);
+        assert_eq!(request.oppgavetype, "VURD_HENV");
+        assert_eq!(request.tema, "GEN");
         assert_eq!(request.prioritet, PrioritetV1::Norm);
-        assert_eq!(request.beskrivelse, Some(BESKRIVELSE_VURDER_OPPHOLDSSTATUS.to_string()));
+        assert_eq!(
+            request.beskrivelse.as_deref(),
+            Some(vurder_oppholdsstatus::DEFINISJON.beskrivelse)
+        );
+    }
+
+    #[test]
+    fn aktiv_dato_og_frist_regnes_i_virkedager() {
+        let oppgave = oppgave(OppgaveType::AvvistStoppAvPeriode, "12345678903");
+        // Lørdag før påske 2026: aktiv tirsdag etter 2. påskedag, frist 3 virkedager senere
+        let request = oppgave_request_fra_dato(
+            &oppgave,
+            &read_application_config().unwrap(),
+            NaiveDate::from_ymd_opt(2026, 4, 4).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(request.aktiv_dato, "2026-04-07");
+        assert_eq!(request.frist_ferdigstillelse.as_deref(), Some("2026-04-10"));
+    }
+
+    #[test]
+    fn aktiv_dato_er_idag_paa_virkedager_og_uten_frist_for_avvist_under_18() {
+        let oppgave = oppgave(OppgaveType::AvvistUnder18, "12345678901");
+        let request = oppgave_request_fra_dato(
+            &oppgave,
+            &read_application_config().unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(request.aktiv_dato, "2026-10-19");
+        assert_eq!(request.frist_ferdigstillelse, None);
+    }
+
+    #[test]
+    fn frist_fra_config_overstyrer_standard() {
+        let oppgave = oppgave(OppgaveType::AvvistUnder18, "12345678901");
+        let mut app_config = read_application_config().unwrap();
+        app_config
+            .oppgavetyper
+            .get_mut("AVVIST_UNDER_18")
+            .unwrap()
+            .frist = Some(Frist::Kalenderdager(14));
+        let request = oppgave_request_fra_dato(
+            &oppgave,
+            &app_config,
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(request.frist_ferdigstillelse.as_deref(), Some("2026-11-02"));
     }
 }
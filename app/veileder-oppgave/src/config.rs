@@ -1,22 +1,50 @@
+use crate::domain::oppgave_type::OppgaveType;
+use crate::domain::oppgavetyper::definisjon::OppgaveApiFelter;
+use crate::domain::oppgavetyper::valider_oppgavetyper;
+use crate::domain::retry_policy::RetryPolicy;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use paw_app_config::config::read_toml_config;
+use paw_app_config::layered::LayeredConfig;
+use paw_app_config::validation::{ConfigReport, ConfigValidator};
+use paw_app_config::watch::{ConfigWatch, watch_dynamic_config};
+use paw_oauth2_resource_server::config::AuthConfig;
 use paw_rdkafka::kafka_config::KafkaConfig;
 use paw_rust_base::env;
 use paw_sqlx::config::DatabaseConfig;
 use serde::Deserialize;
 use serde_env_field::env_field_wrap;
+use std::collections::HashMap;
 use std::num::NonZeroU32;
+use std::time::Duration;
 use texas_client::config::TokenClientConfig;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+const APPLICATION_CONFIG_MOUNT_PATH: &str =
+    "/var/run/configmaps/veileder-oppgave/application_config.toml";
+const APPLICATION_CONFIG_ENV_PREFIX: &str = "VEILEDER_OPPGAVE_";
+const DYNAMIC_CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(30);
 
 #[env_field_wrap]
 #[derive(Debug, Clone, Deserialize)]
 pub struct ApplicationConfig {
     pub topic_hendelseslogg: String,
     pub topic_oppgavehendelse: String,
-    pub opprett_oppgaver_task_interval_minutes: NonZeroU32,
-    pub opprett_oppgaver_task_batch_size: NonZeroU32,
     pub opprett_avvist_under_18_oppgaver_fra_tidspunkt: DateTime<Utc>,
+    pub avstemming_interval_minutes: NonZeroU32,
+    /// Oppgaver må ha vært `Opprettet` så lenge før de avstemmes mot Oppgave API
+    pub avstemming_terskel_timer: NonZeroU32,
+    pub avstemming_batch_size: NonZeroU32,
+    /// Logger og teller avvik uten å endre lokale oppgaver
+    pub avstemming_dry_run: bool,
+    /// Entra ID-grupper (objekt-id) som gir tilgang til admin-API-et
+    #[env_field_wrap(skip)]
+    #[serde(default)]
+    pub admin_grupper: Vec<String>,
+    /// Oppgave API-felter per oppgavetype, f.eks. `[oppgavetyper.AVVIST_UNDER_18]`
+    #[env_field_wrap(skip)]
+    pub oppgavetyper: HashMap<String, OppgaveApiFelter>,
 }
 
 impl ApplicationConfig {
@@ -26,6 +54,38 @@ impl ApplicationConfig {
             self.topic_oppgavehendelse.as_str(),
         ]
     }
+
+    /// Oppgave API-feltene for `oppgave_type`. Finnes for alle typer når config er lest med
+    /// [`read_application_config`].
+    pub fn oppgave_api_felter(&self, oppgave_type: OppgaveType) -> Option<&OppgaveApiFelter> {
+        self.oppgavetyper.get(&oppgave_type.to_string())
+    }
+}
+
+/// Delen av application_config.toml som kan endres mens applikasjonen kjører.
+#[env_field_wrap]
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpprettOppgaverTaskConfig {
+    pub opprett_oppgaver_task_interval_minutes: NonZeroU32,
+    pub opprett_oppgaver_task_batch_size: NonZeroU32,
+    /// Antall forsøk mot Oppgave API før oppgaven får status `GITT_OPP`
+    pub opprett_oppgaver_maks_forsok: NonZeroU32,
+    pub opprett_oppgaver_forste_ventetid_sekunder: NonZeroU32,
+    pub opprett_oppgaver_maks_ventetid_minutter: NonZeroU32,
+}
+
+impl OpprettOppgaverTaskConfig {
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            maks_forsok: *self.opprett_oppgaver_maks_forsok,
+            forste_ventetid: Duration::from_secs(
+                self.opprett_oppgaver_forste_ventetid_sekunder.get() as u64,
+            ),
+            maks_ventetid: Duration::from_mins(
+                self.opprett_oppgaver_maks_ventetid_minutter.get() as u64,
+            ),
+        }
+    }
 }
 
 #[env_field_wrap]
@@ -36,8 +96,56 @@ pub struct OppgaveClientConfig {
 }
 
 pub fn read_application_config() -> Result<ApplicationConfig> {
-    let file_content = read_application_config_file();
-    Ok(read_toml_config::<ApplicationConfig>(file_content)?)
+    let app_config = application_config_layers().load::<ApplicationConfig>()?;
+    valider_oppgavetyper(&app_config.oppgavetyper)?;
+    Ok(app_config)
+}
+
+pub fn read_opprett_oppgaver_task_config() -> Result<OpprettOppgaverTaskConfig> {
+    Ok(application_config_layers().load::<OpprettOppgaverTaskConfig>()?)
+}
+
+pub fn watch_opprett_oppgaver_task_config(
+    cancellation_token: CancellationToken,
+) -> Result<(
+    ConfigWatch<OpprettOppgaverTaskConfig>,
+    JoinHandle<Result<()>>,
+)> {
+    Ok(watch_dynamic_config(
+        application_config_layers(),
+        DYNAMIC_CONFIG_POLL_INTERVAL,
+        cancellation_token,
+    )?)
+}
+
+/// Innebygd config, overstyrt av configmap montert på `APPLICATION_CONFIG_MOUNT_PATH`
+/// og miljøvariabler med prefiks `APPLICATION_CONFIG_ENV_PREFIX`.
+fn application_config_layers() -> LayeredConfig {
+    LayeredConfig::new(read_application_config_file())
+        .with_file(APPLICATION_CONFIG_MOUNT_PATH)
+        .with_env_prefix(APPLICATION_CONFIG_ENV_PREFIX)
+}
+
+/// Sjekker alle config-filer og miljøvariablene de refererer til.
+pub fn validate_config() -> ConfigReport {
+    ConfigValidator::new(env::runtime_env())
+        .require_placeholders(read_application_config_file())
+        .require_placeholders(read_auth_config_file())
+        .require_placeholders(read_database_config_file())
+        .require_placeholders(read_kafka_config_file())
+        .require_placeholders(read_oppgave_client_config_file())
+        .require_placeholders(read_token_client_config_file())
+        .check_config("application_config.toml", read_application_config())
+        .check_config("auth_config.toml", read_auth_config())
+        .check_config("database_config.toml", read_database_config())
+        .check_config("kafka_config.toml", read_kafka_config())
+        .check_config("oppgave_client_config.toml", read_oppgave_client_config())
+        .check_config("token_client_config.toml", read_token_client_config())
+        .report()
+}
+
+pub fn read_auth_config() -> Result<AuthConfig> {
+    Ok(read_toml_config(read_auth_config_file())?)
 }
 
 pub fn read_database_config() -> Result<DatabaseConfig> {
@@ -66,6 +174,13 @@ fn read_application_config_file() -> &'static str {
     }
 }
 
+fn read_auth_config_file() -> &'static str {
+    match env::nais_cluster_name() {
+        Ok(_) => include_str!("../config/nais/auth_config.toml"),
+        Err(_) => include_str!("../config/local/auth_config.toml"),
+    }
+}
+
 fn read_database_config_file() -> &'static str {
     match env::nais_cluster_name() {
         Ok(_) => include_str!("../config/nais/database_config.toml"),
@@ -103,6 +218,23 @@ mod tests {
         read_application_config().unwrap();
     }
 
+    #[test]
+    fn test_read_opprett_oppgaver_task_config() {
+        let task_config = read_opprett_oppgaver_task_config().unwrap();
+        let retry_policy = task_config.retry_policy();
+        assert!(retry_policy.forste_ventetid <= retry_policy.maks_ventetid);
+    }
+
+    #[test]
+    fn test_validate_config() {
+        validate_config().into_result().unwrap();
+    }
+
+    #[test]
+    fn test_read_auth_config() {
+        read_auth_config().unwrap();
+    }
+
     #[test]
     fn test_read_database_config() {
         read_database_config().unwrap();
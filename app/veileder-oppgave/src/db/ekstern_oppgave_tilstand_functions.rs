@@ -0,0 +1,151 @@
+use crate::db::ekstern_oppgave_tilstand_row::EksternOppgaveTilstandRow;
+use crate::domain::ekstern_oppgave_id::EksternOppgaveId;
+use crate::domain::ekstern_oppgave_tilstand::EksternOppgaveTilstand;
+use crate::domain::oppgave_id::OppgaveId;
+use anyhow::Result;
+use sqlx::{Postgres, Transaction};
+
+pub async fn hent_ekstern_tilstand(
+    oppgave_id: OppgaveId,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Option<EksternOppgaveTilstand>> {
+    let row = sqlx::query_as::<_, EksternOppgaveTilstandRow>(
+        r#"
+        SELECT
+            ekstern_oppgave_id,
+            versjon,
+            enhetsnr,
+            nav_ident,
+            prioritet,
+            frist,
+            forst_tildelt_tidspunkt AT TIME ZONE 'UTC' as forst_tildelt_tidspunkt,
+            oppdatert_tidspunkt AT TIME ZONE 'UTC' as oppdatert_tidspunkt
+        FROM ekstern_oppgave_tilstand
+        WHERE oppgave_id = $1
+        "#,
+    )
+    .bind(i64::from(oppgave_id))
+    .fetch_optional(&mut **transaction)
+    .await?;
+
+    Ok(row.map(|row| EksternOppgaveTilstand {
+        ekstern_oppgave_id: EksternOppgaveId::from(row.ekstern_oppgave_id),
+        versjon: row.versjon,
+        enhetsnr: row.enhetsnr,
+        nav_ident: row.nav_ident,
+        prioritet: row.prioritet,
+        frist: row.frist,
+        forst_tildelt_tidspunkt: row.forst_tildelt_tidspunkt,
+        oppdatert_tidspunkt: row.oppdatert_tidspunkt,
+    }))
+}
+
+/// Lagrer tilstanden hvis den er nyere enn den lagrede. Returnerer `false` for utdaterte
+/// versjoner.
+pub async fn lagre_ekstern_tilstand(
+    oppgave_id: OppgaveId,
+    tilstand: &EksternOppgaveTilstand,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO ekstern_oppgave_tilstand (
+            oppgave_id,
+            ekstern_oppgave_id,
+            versjon,
+            enhetsnr,
+            nav_ident,
+            prioritet,
+            frist,
+            forst_tildelt_tidspunkt,
+            oppdatert_tidspunkt
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (oppgave_id) DO UPDATE
+        SET ekstern_oppgave_id = EXCLUDED.ekstern_oppgave_id,
+            versjon = EXCLUDED.versjon,
+            enhetsnr = EXCLUDED.enhetsnr,
+            nav_ident = EXCLUDED.nav_ident,
+            prioritet = EXCLUDED.prioritet,
+            frist = EXCLUDED.frist,
+            forst_tildelt_tidspunkt = EXCLUDED.forst_tildelt_tidspunkt,
+            oppdatert_tidspunkt = EXCLUDED.oppdatert_tidspunkt
+        WHERE ekstern_oppgave_tilstand.versjon < EXCLUDED.versjon
+        "#,
+    )
+    .bind(i64::from(oppgave_id))
+    .bind(i64::from(tilstand.ekstern_oppgave_id))
+    .bind(tilstand.versjon)
+    .bind(&tilstand.enhetsnr)
+    .bind(&tilstand.nav_ident)
+    .bind(&tilstand.prioritet)
+    .bind(tilstand.frist)
+    .bind(tilstand.forst_tildelt_tidspunkt)
+    .bind(tilstand.oppdatert_tidspunkt)
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::oppgave_functions::lagre_oppgave;
+    use crate::domain::oppgave::Oppgave;
+    use crate::domain::oppgave_status::OppgaveStatus::Opprettet;
+    use crate::domain::oppgave_type::OppgaveType::AvvistUnder18;
+    use chrono::{DateTime, Utc};
+    use paw_test::setup_test_db::setup_test_db;
+    use types::arbeidssoeker_id::ArbeidssoekerId;
+    use types::identitetsnummer::Identitetsnummer;
+    use uuid::Uuid;
+
+    fn tilstand(versjon: i32, nav_ident: Option<&str>) -> EksternOppgaveTilstand {
+        EksternOppgaveTilstand {
+            ekstern_oppgave_id: EksternOppgaveId::from(55555),
+            versjon,
+            enhetsnr: Some("4863".to_string()),
+            nav_ident: nav_ident.map(String::from),
+            prioritet: Some("NORMAL".to_string()),
+            frist: None,
+            forst_tildelt_tidspunkt: None,
+            oppdatert_tidspunkt: DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lagre_ekstern_tilstand_forkaster_utdaterte_versjoner() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = Oppgave::new(
+            Uuid::new_v4(),
+            AvvistUnder18,
+            Opprettet,
+            vec![],
+            ArbeidssoekerId(12345),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            Utc::now(),
+        );
+        let oppgave_id = lagre_oppgave(&oppgave, &mut tx).await?;
+        assert_eq!(hent_ekstern_tilstand(oppgave_id, &mut tx).await?, None);
+
+        assert!(lagre_ekstern_tilstand(oppgave_id, &tilstand(2, Some("Z991459")), &mut tx).await?);
+        assert!(!lagre_ekstern_tilstand(oppgave_id, &tilstand(1, None), &mut tx).await?);
+        assert!(!lagre_ekstern_tilstand(oppgave_id, &tilstand(2, None), &mut tx).await?);
+        assert_eq!(
+            hent_ekstern_tilstand(oppgave_id, &mut tx).await?,
+            Some(tilstand(2, Some("Z991459")))
+        );
+
+        assert!(lagre_ekstern_tilstand(oppgave_id, &tilstand(3, None), &mut tx).await?);
+        assert_eq!(
+            hent_ekstern_tilstand(oppgave_id, &mut tx).await?,
+            Some(tilstand(3, None))
+        );
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
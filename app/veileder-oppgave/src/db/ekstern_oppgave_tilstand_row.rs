@@ -0,0 +1,14 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub struct EksternOppgaveTilstandRow {
+    pub ekstern_oppgave_id: i64,
+    pub versjon: i32,
+    pub enhetsnr: Option<String>,
+    pub nav_ident: Option<String>,
+    pub prioritet: Option<String>,
+    pub frist: Option<NaiveDate>,
+    pub forst_tildelt_tidspunkt: Option<DateTime<Utc>>,
+    pub oppdatert_tidspunkt: DateTime<Utc>,
+}
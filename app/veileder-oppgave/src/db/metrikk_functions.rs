@@ -0,0 +1,285 @@
+use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
+use crate::domain::hendelse_logg_status::HendelseLoggStatus::{
+    EksternOppgaveFerdigstilt, EksternOppgaveOpprettet, OppgaveFinnesAllerede,
+};
+use crate::domain::oppgave::Oppgave;
+use crate::domain::oppgave_id::OppgaveId;
+use crate::domain::oppgave_status::OppgaveStatus;
+use anyhow::Result;
+use chrono::{Datelike, Days, NaiveDate};
+use sqlx::{Postgres, Transaction};
+
+/// Teller med en ny oppgave i metrikktabellene. Kalles i samme transaksjon som oppgaven lagres.
+///
+/// Antall per status og per dag skrives som endringsrader som `komprimer_metrikker` summerer,
+/// slik at transaksjonene ikke holder lås på delte rader i sammendragstabellene.
+pub async fn registrer_ny_oppgave(
+    oppgave_id: OppgaveId,
+    oppgave: &Oppgave,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO metrikk_oppgaver_per_status_endring (type, status, endring)
+        VALUES ($1, $2, 1)
+        "#,
+    )
+    .bind(oppgave.type_.to_string())
+    .bind(oppgave.status.to_string())
+    .execute(&mut **transaction)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO metrikk_forsok_per_oppgave (oppgave_id, type, identitetsnummer, tidspunkt, antall_forsok)
+        VALUES ($1, $2, $3, $4, 0)
+        "#,
+    )
+    .bind(i64::from(oppgave_id))
+    .bind(oppgave.type_.to_string())
+    .bind(String::from(oppgave.identitetsnummer.clone()))
+    .bind(oppgave.tidspunkt)
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// Flytter én oppgave av typen `oppgave_type` fra status `fra` til `til` i metrikktabellene.
+pub async fn registrer_statusendring(
+    oppgave_type: &str,
+    fra: OppgaveStatus,
+    til: OppgaveStatus,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<()> {
+    if fra == til {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO metrikk_oppgaver_per_status_endring (type, status, endring)
+        VALUES ($1, $2, -1), ($1, $3, 1)
+        "#,
+    )
+    .bind(oppgave_type)
+    .bind(fra.to_string())
+    .bind(til.to_string())
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// Teller med en ny rad i hendelse-loggen. Duplikater oppdaterer antall forsøk for oppgaven, og
+/// ferdigstilling gir en rad med saksbehandlingstid fra første `EksternOppgaveOpprettet`.
+pub async fn registrer_hendelse(
+    oppgave_id: OppgaveId,
+    entry: &HendelseLoggEntry,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<()> {
+    let oppgave_type: String = sqlx::query_scalar("SELECT type FROM oppgaver WHERE id = $1")
+        .bind(i64::from(oppgave_id))
+        .fetch_one(&mut **transaction)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO metrikk_hendelser_per_dag_endring (type, status, dato, endring)
+        VALUES ($1, $2, $3, 1)
+        "#,
+    )
+    .bind(&oppgave_type)
+    .bind(entry.status.to_string())
+    .bind(entry.tidspunkt.date_naive())
+    .execute(&mut **transaction)
+    .await?;
+
+    match entry.status {
+        OppgaveFinnesAllerede => {
+            sqlx::query(
+                r#"
+                UPDATE metrikk_forsok_per_oppgave
+                SET antall_forsok = antall_forsok + 1
+                WHERE oppgave_id = $1
+                "#,
+            )
+            .bind(i64::from(oppgave_id))
+            .execute(&mut **transaction)
+            .await?;
+        }
+        EksternOppgaveFerdigstilt => {
+            sqlx::query(
+                r#"
+                INSERT INTO metrikk_saksbehandlingstid (oppgave_id, type, uke, opprettet, ferdigstilt)
+                SELECT $1, $2, $3, MIN(tidspunkt), $4
+                FROM oppgave_hendelse_logg
+                WHERE oppgave_id = $1
+                  AND status = $5
+                HAVING MIN(tidspunkt) IS NOT NULL
+                ON CONFLICT (oppgave_id) DO NOTHING
+                "#,
+            )
+            .bind(i64::from(oppgave_id))
+            .bind(&oppgave_type)
+            .bind(uke(entry.tidspunkt.date_naive()))
+            .bind(entry.tidspunkt)
+            .bind(EksternOppgaveOpprettet.to_string())
+            .execute(&mut **transaction)
+            .await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Summerer endringsradene inn i sammendragstabellene og sletter dem. Rader fra transaksjoner som
+/// ikke er committet ennå blir liggende til neste gang.
+pub async fn komprimer_metrikker(transaction: &mut Transaction<'_, Postgres>) -> Result<()> {
+    sqlx::query(
+        r#"
+        WITH endringer AS (
+            DELETE FROM metrikk_oppgaver_per_status_endring
+            RETURNING type, status, endring
+        )
+        INSERT INTO metrikk_oppgaver_per_status (type, status, antall)
+        SELECT type, status, SUM(endring)
+        FROM endringer
+        GROUP BY type, status
+        ORDER BY type, status
+        ON CONFLICT (type, status) DO UPDATE
+            SET antall = metrikk_oppgaver_per_status.antall + EXCLUDED.antall
+        "#,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    sqlx::query(
+        r#"
+        WITH endringer AS (
+            DELETE FROM metrikk_hendelser_per_dag_endring
+            RETURNING type, status, dato, endring
+        )
+        INSERT INTO metrikk_hendelser_per_dag (type, status, dato, antall)
+        SELECT type, status, dato, SUM(endring)
+        FROM endringer
+        GROUP BY type, status, dato
+        ORDER BY type, status, dato
+        ON CONFLICT (type, status, dato) DO UPDATE
+            SET antall = metrikk_hendelser_per_dag.antall + EXCLUDED.antall
+        "#,
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// Mandagen i uken `dato` ligger i, som `DATE_TRUNC('week', ...)` i Postgres.
+fn uke(dato: NaiveDate) -> NaiveDate {
+    dato - Days::new(dato.weekday().num_days_from_monday().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::oppgave_functions::{
+        bytt_oppgave_status, lagre_oppgave, oppdater_hendelse_logg, tilbakestill_oppgave,
+    };
+    use crate::domain::oppgave_status::OppgaveStatus::{Ferdigbehandlet, Opprettet, Ubehandlet};
+    use crate::domain::oppgave_type::OppgaveType::AvvistUnder18;
+    use chrono::{Duration, Utc};
+    use paw_test::setup_test_db::setup_test_db;
+    use types::arbeidssoeker_id::ArbeidssoekerId;
+    use types::identitetsnummer::Identitetsnummer;
+    use uuid::Uuid;
+
+    #[test]
+    fn uke_er_mandagen_i_samme_uke() {
+        let mandag = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        assert_eq!(uke(mandag), mandag);
+        assert_eq!(uke(NaiveDate::from_ymd_opt(2026, 10, 25).unwrap()), mandag);
+        assert_eq!(
+            uke(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()),
+            NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn metrikktabellene_stemmer_med_oppgaver_og_hendelse_logg() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let naa = Utc::now();
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = Oppgave::new(
+            Uuid::new_v4(),
+            AvvistUnder18,
+            Ubehandlet,
+            vec![],
+            ArbeidssoekerId(12345),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            naa,
+        );
+        let oppgave_id = lagre_oppgave(&oppgave, &mut tx).await?;
+        let andre_id = lagre_oppgave(&oppgave, &mut tx).await?;
+
+        assert!(bytt_oppgave_status(oppgave_id, Ubehandlet, Opprettet, &mut tx).await?);
+        assert!(!bytt_oppgave_status(oppgave_id, Ubehandlet, Opprettet, &mut tx).await?);
+        assert!(bytt_oppgave_status(andre_id, Ubehandlet, Opprettet, &mut tx).await?);
+        assert!(tilbakestill_oppgave(andre_id, &mut tx).await?);
+
+        for (status, tidspunkt) in [
+            (EksternOppgaveOpprettet, naa - Duration::days(2)),
+            (OppgaveFinnesAllerede, naa - Duration::days(1)),
+            (EksternOppgaveFerdigstilt, naa),
+        ] {
+            let entry = HendelseLoggEntry::new(status, "test".to_string(), tidspunkt);
+            oppdater_hendelse_logg(oppgave_id, entry, &mut tx).await?;
+        }
+        assert!(bytt_oppgave_status(oppgave_id, Opprettet, Ferdigbehandlet, &mut tx).await?);
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        komprimer_metrikker(&mut tx).await?;
+        tx.commit().await?;
+        let gjenstaaende_endringer: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM metrikk_oppgaver_per_status_endring")
+                .fetch_one(&pg_pool)
+                .await?;
+        assert_eq!(gjenstaaende_endringer, 0);
+
+        let avvik: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM (SELECT type, status, COUNT(*) AS antall FROM oppgaver GROUP BY type, status) faktisk
+            FULL JOIN (SELECT * FROM metrikk_oppgaver_per_status WHERE antall <> 0) metrikk
+                USING (type, status)
+            WHERE faktisk.antall IS DISTINCT FROM metrikk.antall
+            "#,
+        )
+        .fetch_one(&pg_pool)
+        .await?;
+        assert_eq!(avvik, 0);
+
+        let antall_hendelser: i64 =
+            sqlx::query_scalar("SELECT SUM(antall)::BIGINT FROM metrikk_hendelser_per_dag")
+                .fetch_one(&pg_pool)
+                .await?;
+        assert_eq!(antall_hendelser, 3);
+
+        let antall_forsok: i64 = sqlx::query_scalar(
+            "SELECT antall_forsok FROM metrikk_forsok_per_oppgave WHERE oppgave_id = $1",
+        )
+        .bind(i64::from(oppgave_id))
+        .fetch_one(&pg_pool)
+        .await?;
+        assert_eq!(antall_forsok, 1);
+
+        let (opprettet, ferdigstilt): (chrono::NaiveDateTime, chrono::NaiveDateTime) =
+            sqlx::query_as(
+                "SELECT opprettet, ferdigstilt FROM metrikk_saksbehandlingstid WHERE oppgave_id = $1",
+            )
+            .bind(i64::from(oppgave_id))
+            .fetch_one(&pg_pool)
+            .await?;
+        assert_eq!(ferdigstilt - opprettet, Duration::days(2));
+        Ok(())
+    }
+}
@@ -1,3 +1,6 @@
 pub mod oppgave_row;
 pub mod oppgave_hendelse_logg_row;
-pub mod oppgave_functions;
\ No newline at end of file
+pub mod oppgave_functions;
+pub mod ekstern_oppgave_tilstand_row;
+pub mod ekstern_oppgave_tilstand_functions;
+pub mod metrikk_functions;
\ No newline at end of file
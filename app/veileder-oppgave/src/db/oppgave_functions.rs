@@ -1,3 +1,6 @@
+use crate::db::metrikk_functions::{
+    registrer_hendelse, registrer_ny_oppgave, registrer_statusendring,
+};
 use crate::db::oppgave_hendelse_logg_row::{OppgaveHendelseLoggBatchRow, OppgaveHendelseLoggRow};
 use crate::db::oppgave_row::OppgaveRow;
 use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
@@ -32,6 +35,7 @@ pub async fn hent_nyeste_oppgave(
         oppgave_row.type_.parse()?,
         oppgave_row.status.parse()?,
         oppgave_row.opplysninger,
+        oppgave_row.detaljer,
         ArbeidssoekerId::from(oppgave_row.arbeidssoeker_id),
         Identitetsnummer::new(oppgave_row.identitetsnummer)
             .expect("Identitetsnummer fra database er ugyldig — dataintegritetsfeil"),
@@ -56,6 +60,7 @@ async fn hent_nyeste_oppgave_for_arbeidssoeker(
             type AS type_,
             status,
             opplysninger,
+            detaljer,
             arbeidssoeker_id,
             identitetsnummer,
             ekstern_oppgave_id,
@@ -85,6 +90,7 @@ pub async fn finn_oppgave_for_ekstern_id(
             type AS type_,
             status,
             opplysninger,
+            detaljer,
             arbeidssoeker_id,
             identitetsnummer,
             ekstern_oppgave_id,
@@ -110,6 +116,7 @@ pub async fn finn_oppgave_for_ekstern_id(
         oppgave_row.type_.parse()?,
         oppgave_row.status.parse()?,
         oppgave_row.opplysninger,
+        oppgave_row.detaljer,
         ArbeidssoekerId::from(oppgave_row.arbeidssoeker_id),
         Identitetsnummer::new(oppgave_row.identitetsnummer)
             .expect("Identitetsnummer fra database er ugyldig — dataintegritetsfeil"),
@@ -157,8 +164,8 @@ pub async fn lagre_oppgave(
 ) -> Result<OppgaveId> {
     let oppgave_id = sqlx::query_scalar(
         r#"
-        INSERT INTO oppgaver (type, status, melding_id, opplysninger, arbeidssoeker_id, identitetsnummer, tidspunkt)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO oppgaver (type, status, melding_id, opplysninger, detaljer, arbeidssoeker_id, identitetsnummer, tidspunkt)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING id
         "#,
     )
@@ -166,13 +173,16 @@ pub async fn lagre_oppgave(
     .bind(oppgave.status.to_string())
     .bind(oppgave.melding_id)
     .bind(&oppgave.opplysninger)
+    .bind(&oppgave.detaljer)
     .bind(i64::from(oppgave.arbeidssoeker_id))
     .bind(String::from(oppgave.identitetsnummer.clone()))
     .bind(oppgave.tidspunkt)
     .fetch_one(&mut **tx)
     .await?;
+    let oppgave_id = OppgaveId(oppgave_id);
+    registrer_ny_oppgave(oppgave_id, oppgave, tx).await?;
 
-    Ok(OppgaveId(oppgave_id))
+    Ok(oppgave_id)
 }
 
 pub async fn oppdater_hendelse_logg(
@@ -192,6 +202,7 @@ pub async fn oppdater_hendelse_logg(
     .bind(entry.tidspunkt)
     .execute(&mut **tx)
     .await?;
+    registrer_hendelse(oppgave_id, &entry, tx).await?;
 
     Ok(result.rows_affected())
 }
@@ -221,25 +232,35 @@ pub async fn bytt_oppgave_status(
     new_status: OppgaveStatus,
     transaction: &mut Transaction<'_, Postgres>,
 ) -> Result<bool> {
-    let result = sqlx::query(
+    let oppgave_type: Option<String> = sqlx::query_scalar(
         r#"
         UPDATE oppgaver
         SET status = $1
         WHERE id = $2
           AND status = $3
+        RETURNING type
         "#,
     )
     .bind(new_status.to_string())
     .bind(i64::from(oppgave_id))
     .bind(expected_status.to_string())
-    .execute(&mut **transaction)
+    .fetch_optional(&mut **transaction)
     .await?;
-    Ok(result.rows_affected() == 1)
+    match oppgave_type {
+        Some(oppgave_type) => {
+            registrer_statusendring(&oppgave_type, expected_status, new_status, transaction)
+                .await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
 }
 
+/// Eldste ubehandlede oppgaver uten ekstern id. Oppgaver eldre enn `fra_tidspunkt` for sin
+/// type hoppes over; typer som ikke er med i `fra_tidspunkt` har ingen slik grense.
 pub async fn hent_de_eldste_ubehandlede_oppgavene(
     antall_oppgaver: NonZeroU32,
-    fra_tidspunkt: DateTime<Utc>,
+    fra_tidspunkt: &[(OppgaveType, DateTime<Utc>)],
     transaction: &mut Transaction<'_, Postgres>,
 ) -> Result<Vec<Oppgave>> {
     let oppgave_rows = sqlx::query_as::<_, OppgaveRow>(
@@ -250,6 +271,7 @@ pub async fn hent_de_eldste_ubehandlede_oppgavene(
             type AS type_,
             status,
             opplysninger,
+            detaljer,
             arbeidssoeker_id,
             identitetsnummer,
             ekstern_oppgave_id,
@@ -257,14 +279,30 @@ pub async fn hent_de_eldste_ubehandlede_oppgavene(
         FROM oppgaver
         WHERE status = $1
             AND ekstern_oppgave_id IS NULL
-            AND tidspunkt >= $2
+            AND NOT EXISTS (
+                SELECT 1 FROM UNNEST($2::text[], $5) AS fra(type, tidspunkt)
+                WHERE fra.type = oppgaver.type AND oppgaver.tidspunkt < fra.tidspunkt
+            )
+            AND (neste_forsok IS NULL OR neste_forsok <= $4)
         ORDER BY tidspunkt ASC
         LIMIT $3
         "#,
     )
     .bind(OppgaveStatus::Ubehandlet.to_string())
-    .bind(fra_tidspunkt)
+    .bind(
+        fra_tidspunkt
+            .iter()
+            .map(|(oppgave_type, _)| oppgave_type.to_string())
+            .collect::<Vec<_>>(),
+    )
     .bind(antall_oppgaver.get() as i64)
+    .bind(Utc::now())
+    .bind(
+        fra_tidspunkt
+            .iter()
+            .map(|(_, tidspunkt)| *tidspunkt)
+            .collect::<Vec<_>>(),
+    )
     .fetch_all(&mut **transaction)
     .await?;
 
@@ -280,6 +318,7 @@ pub async fn hent_de_eldste_ubehandlede_oppgavene(
             oppgave_row.type_.parse()?,
             oppgave_row.status.parse()?,
             oppgave_row.opplysninger,
+            oppgave_row.detaljer,
             ArbeidssoekerId::from(oppgave_row.arbeidssoeker_id),
             Identitetsnummer::new(oppgave_row.identitetsnummer)
                 .expect("Identitetsnummer fra database er ugyldig — dataintegritetsfeil"),
@@ -322,6 +361,315 @@ async fn hent_hendelse_logger(
     Ok(map)
 }
 
+pub async fn hent_oppgave(
+    oppgave_id: OppgaveId,
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Option<Oppgave>> {
+    let oppgave_row = sqlx::query_as::<_, OppgaveRow>(
+        r#"
+        SELECT
+            id,
+            melding_id,
+            type AS type_,
+            status,
+            opplysninger,
+            detaljer,
+            arbeidssoeker_id,
+            identitetsnummer,
+            ekstern_oppgave_id,
+            tidspunkt AT TIME ZONE 'UTC' as tidspunkt
+        FROM oppgaver
+        WHERE id = $1
+        "#,
+    )
+    .bind(i64::from(oppgave_id))
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let oppgave_row = match oppgave_row {
+        None => return Ok(None),
+        Some(row) => row,
+    };
+    let hendelse_logg = hent_hendelse_logg(oppgave_id, tx).await?;
+    Ok(Some(til_oppgave(oppgave_row, hendelse_logg)?))
+}
+
+/// Oppgaver som matcher alle filtrene som er satt, nyeste først
+pub async fn finn_oppgaver(
+    status: Option<&OppgaveStatus>,
+    oppgave_type: Option<OppgaveType>,
+    arbeidssoeker_id: Option<ArbeidssoekerId>,
+    limit: i64,
+    offset: i64,
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<Oppgave>> {
+    let oppgave_rows = sqlx::query_as::<_, OppgaveRow>(
+        r#"
+        SELECT
+            id,
+            melding_id,
+            type AS type_,
+            status,
+            opplysninger,
+            detaljer,
+            arbeidssoeker_id,
+            identitetsnummer,
+            ekstern_oppgave_id,
+            tidspunkt AT TIME ZONE 'UTC' as tidspunkt
+        FROM oppgaver
+        WHERE ($1::VARCHAR IS NULL OR status = $1)
+          AND ($2::VARCHAR IS NULL OR type = $2)
+          AND ($3::BIGINT IS NULL OR arbeidssoeker_id = $3)
+        ORDER BY id DESC
+        LIMIT $4
+        OFFSET $5
+        "#,
+    )
+    .bind(status.map(|status| status.to_string()))
+    .bind(oppgave_type.map(|oppgave_type| oppgave_type.to_string()))
+    .bind(arbeidssoeker_id.map(i64::from))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let oppgave_ider: Vec<i64> = oppgave_rows.iter().map(|r| r.id).collect();
+    let mut hendelse_logg_map = hent_hendelse_logger(&oppgave_ider, tx).await?;
+    oppgave_rows
+        .into_iter()
+        .map(|oppgave_row| {
+            let hendelse_logg = hendelse_logg_map.remove(&oppgave_row.id).unwrap_or_default();
+            til_oppgave(oppgave_row, hendelse_logg)
+        })
+        .collect()
+}
+
+/// Setter en oppgave som sitter fast i `Opprettet` uten ekstern id, eller som vi har gitt opp,
+/// tilbake til `Ubehandlet` med nullstilt antall forsøk. Oppgaver som har fått ekstern id i
+/// mellomtiden endres ikke.
+pub async fn tilbakestill_oppgave(
+    oppgave_id: OppgaveId,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<bool> {
+    let tilbakestilt: Option<(String, String)> = sqlx::query_as(
+        r#"
+        UPDATE oppgaver o
+        SET status = $1,
+            antall_forsok = 0,
+            neste_forsok = NULL
+        FROM (SELECT id, status FROM oppgaver WHERE id = $2 FOR UPDATE) AS forrige
+        WHERE o.id = forrige.id
+          AND o.status IN ($3, $4)
+          AND o.ekstern_oppgave_id IS NULL
+        RETURNING o.type, forrige.status
+        "#,
+    )
+    .bind(OppgaveStatus::Ubehandlet.to_string())
+    .bind(i64::from(oppgave_id))
+    .bind(OppgaveStatus::Opprettet.to_string())
+    .bind(OppgaveStatus::GittOpp.to_string())
+    .fetch_optional(&mut **transaction)
+    .await?;
+    match tilbakestilt {
+        Some((oppgave_type, forrige_status)) => {
+            registrer_statusendring(
+                &oppgave_type,
+                forrige_status.parse()?,
+                OppgaveStatus::Ubehandlet,
+                transaction,
+            )
+            .await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Setter en `Opprettet`-oppgave som ikke finnes i Oppgave API tilbake til `Ubehandlet` uten
+/// ekstern id, slik at den opprettes på nytt. Oppgaven endres ikke hvis status eller ekstern id
+/// er endret siden avstemmingen hentet den.
+pub async fn tilbakestill_ikke_funnet_oppgave(
+    oppgave_id: OppgaveId,
+    ekstern_oppgave_id: EksternOppgaveId,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<bool> {
+    let oppgave_type: Option<String> = sqlx::query_scalar(
+        r#"
+        UPDATE oppgaver
+        SET status = $1,
+            ekstern_oppgave_id = NULL,
+            antall_forsok = 0,
+            neste_forsok = NULL
+        WHERE id = $2
+          AND status = $3
+          AND ekstern_oppgave_id = $4
+        RETURNING type
+        "#,
+    )
+    .bind(OppgaveStatus::Ubehandlet.to_string())
+    .bind(i64::from(oppgave_id))
+    .bind(OppgaveStatus::Opprettet.to_string())
+    .bind(i64::from(ekstern_oppgave_id))
+    .fetch_optional(&mut **transaction)
+    .await?;
+    match oppgave_type {
+        Some(oppgave_type) => {
+            registrer_statusendring(
+                &oppgave_type,
+                OppgaveStatus::Opprettet,
+                OppgaveStatus::Ubehandlet,
+                transaction,
+            )
+            .await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Teller opp antall feilede forsøk for en oppgave vi har eierskap over (status `Opprettet`).
+/// Returnerer nytt antall, eller `None` hvis oppgaven ikke lenger har status `Opprettet`.
+pub async fn registrer_feilet_forsok(
+    oppgave_id: OppgaveId,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Option<u32>> {
+    let antall_forsok: Option<i32> = sqlx::query_scalar(
+        r#"
+        UPDATE oppgaver
+        SET antall_forsok = antall_forsok + 1
+        WHERE id = $1
+          AND status = $2
+        RETURNING antall_forsok
+        "#,
+    )
+    .bind(i64::from(oppgave_id))
+    .bind(OppgaveStatus::Opprettet.to_string())
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(antall_forsok.map(|antall| antall as u32))
+}
+
+/// Setter oppgaven tilbake til `Ubehandlet`, men den plukkes ikke opp igjen før `neste_forsok`
+pub async fn utsett_oppgave(
+    oppgave_id: OppgaveId,
+    neste_forsok: DateTime<Utc>,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<bool> {
+    let oppgave_type: Option<String> = sqlx::query_scalar(
+        r#"
+        UPDATE oppgaver
+        SET status = $1,
+            neste_forsok = $2
+        WHERE id = $3
+          AND status = $4
+        RETURNING type
+        "#,
+    )
+    .bind(OppgaveStatus::Ubehandlet.to_string())
+    .bind(neste_forsok)
+    .bind(i64::from(oppgave_id))
+    .bind(OppgaveStatus::Opprettet.to_string())
+    .fetch_optional(&mut **transaction)
+    .await?;
+    match oppgave_type {
+        Some(oppgave_type) => {
+            registrer_statusendring(
+                &oppgave_type,
+                OppgaveStatus::Opprettet,
+                OppgaveStatus::Ubehandlet,
+                transaction,
+            )
+            .await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Oppgaver med ekstern id som har vært `Opprettet` siden før `opprettet_foer`, og som ikke er
+/// avstemt siden `avstemt_foer`. De som aldri er avstemt kommer først.
+pub async fn hent_oppgaver_til_avstemming(
+    opprettet_foer: DateTime<Utc>,
+    avstemt_foer: DateTime<Utc>,
+    antall_oppgaver: NonZeroU32,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<Oppgave>> {
+    let oppgave_rows = sqlx::query_as::<_, OppgaveRow>(
+        r#"
+        SELECT
+            id,
+            melding_id,
+            type AS type_,
+            status,
+            opplysninger,
+            detaljer,
+            arbeidssoeker_id,
+            identitetsnummer,
+            ekstern_oppgave_id,
+            tidspunkt AT TIME ZONE 'UTC' as tidspunkt
+        FROM oppgaver
+        WHERE status = $1
+          AND ekstern_oppgave_id IS NOT NULL
+          AND tidspunkt < $2
+          AND (sist_avstemt IS NULL OR sist_avstemt < $3)
+        ORDER BY sist_avstemt ASC NULLS FIRST, tidspunkt ASC
+        LIMIT $4
+        "#,
+    )
+    .bind(OppgaveStatus::Opprettet.to_string())
+    .bind(opprettet_foer)
+    .bind(avstemt_foer)
+    .bind(antall_oppgaver.get() as i64)
+    .fetch_all(&mut **transaction)
+    .await?;
+
+    let oppgave_ider: Vec<i64> = oppgave_rows.iter().map(|r| r.id).collect();
+    let mut hendelse_logg_map = hent_hendelse_logger(&oppgave_ider, transaction).await?;
+    oppgave_rows
+        .into_iter()
+        .map(|oppgave_row| {
+            let hendelse_logg = hendelse_logg_map.remove(&oppgave_row.id).unwrap_or_default();
+            til_oppgave(oppgave_row, hendelse_logg)
+        })
+        .collect()
+}
+
+pub async fn oppdater_sist_avstemt(
+    oppgave_ider: &[OppgaveId],
+    tidspunkt: DateTime<Utc>,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<u64> {
+    let oppgave_ider: Vec<i64> = oppgave_ider.iter().copied().map(i64::from).collect();
+    let result = sqlx::query(
+        r#"
+        UPDATE oppgaver
+        SET sist_avstemt = $1
+        WHERE id = ANY($2)
+        "#,
+    )
+    .bind(tidspunkt)
+    .bind(oppgave_ider)
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+fn til_oppgave(oppgave_row: OppgaveRow, hendelse_logg: Vec<HendelseLoggEntry>) -> Result<Oppgave> {
+    Ok(Oppgave::fra_db(
+        OppgaveId::from(oppgave_row.id),
+        oppgave_row.melding_id,
+        oppgave_row.type_.parse()?,
+        oppgave_row.status.parse()?,
+        oppgave_row.opplysninger,
+        oppgave_row.detaljer,
+        ArbeidssoekerId::from(oppgave_row.arbeidssoeker_id),
+        Identitetsnummer::new(oppgave_row.identitetsnummer)
+            .expect("Identitetsnummer fra database er ugyldig — dataintegritetsfeil"),
+        oppgave_row.ekstern_oppgave_id.map(EksternOppgaveId::from),
+        oppgave_row.tidspunkt,
+        hendelse_logg,
+    ))
+}
 
 #[cfg(test)]
 mod tests {
@@ -358,8 +706,12 @@ mod tests {
         let mut tx = pg_pool.begin().await?;
         let antall_oppgaver = NonZeroU32::new(2).unwrap();
         let fra_tidspunkt = Utc::now() - chrono::Duration::days(1336);
-        let oppgaver =
-            hent_de_eldste_ubehandlede_oppgavene(antall_oppgaver, fra_tidspunkt, &mut tx).await?;
+        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(
+            antall_oppgaver,
+            &[(AvvistUnder18, fra_tidspunkt)],
+            &mut tx,
+        )
+        .await?;
 
         assert_eq!(oppgaver.len(), antall_oppgaver.get() as usize);
         let eldste = &oppgaver[0];
@@ -390,13 +742,23 @@ mod tests {
         let mut tx = pg_pool.begin().await?;
         let fra_tidspunkt = now - chrono::Duration::seconds(1);
         let antall_oppgaver = NonZeroU32::new(10).unwrap();
-        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(antall_oppgaver, fra_tidspunkt, &mut tx).await?;
+        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(
+            antall_oppgaver,
+            &[(AvvistUnder18, fra_tidspunkt)],
+            &mut tx,
+        )
+        .await?;
         assert_eq!(oppgaver.len(), 1, "Skal bare finne ny_oppgave");
         tx.commit().await?;
 
         let mut tx = pg_pool.begin().await?;
         let fra_tidspunkt = now + chrono::Duration::seconds(1);
-        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(antall_oppgaver, fra_tidspunkt, &mut tx).await?;
+        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(
+            antall_oppgaver,
+            &[(AvvistUnder18, fra_tidspunkt)],
+            &mut tx,
+        )
+        .await?;
         assert_eq!(
             oppgaver.len(),
             0,
@@ -406,6 +768,44 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_fra_tidspunkt_gjelder_bare_sin_oppgavetype() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+        let mut tx = pg_pool.begin().await?;
+
+        let now = Utc::now();
+        let gammel_avvist_under_18 = test_oppgave(
+            ArbeidssoekerId(1234567),
+            Ubehandlet,
+            now - chrono::Duration::days(1),
+        );
+        let gammel_vurder_oppholdsstatus = Oppgave::new(
+            Uuid::new_v4(),
+            OppgaveType::VurderOppholdsstatus,
+            Ubehandlet,
+            vec![],
+            ArbeidssoekerId(1234568),
+            Identitetsnummer::new("12345678902".to_string()).unwrap(),
+            now - chrono::Duration::days(1),
+        );
+        lagre_oppgave(&gammel_avvist_under_18, &mut tx).await?;
+        lagre_oppgave(&gammel_vurder_oppholdsstatus, &mut tx).await?;
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(
+            NonZeroU32::new(10).unwrap(),
+            &[(AvvistUnder18, now)],
+            &mut tx,
+        )
+        .await?;
+        let typer: Vec<OppgaveType> = oppgaver.iter().map(|oppgave| oppgave.type_).collect();
+        assert_eq!(typer, vec![OppgaveType::VurderOppholdsstatus]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_oppdater_ekstern_id() -> Result<()> {
         let (pg_pool, _db_container) = setup_test_db().await?;
@@ -497,6 +897,140 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_finn_oppgaver_med_filter() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let arbeidssoeker_id = ArbeidssoekerId(12345);
+        let ubehandlet_id = lagre_oppgave(&test_oppgave(arbeidssoeker_id, Ubehandlet, Utc::now()), &mut tx).await?;
+        lagre_oppgave(&test_oppgave(arbeidssoeker_id, Ferdigbehandlet, Utc::now()), &mut tx).await?;
+        lagre_oppgave(&test_oppgave(ArbeidssoekerId(99999), Ubehandlet, Utc::now()), &mut tx).await?;
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let alle = finn_oppgaver(None, None, None, 10, 0, &mut tx).await?;
+        assert_eq!(alle.len(), 3);
+        assert!(i64::from(alle[0].id()) > i64::from(alle[2].id()), "Nyeste oppgave skal komme først");
+
+        let oppgaver = finn_oppgaver(Some(&Ubehandlet), Some(AvvistUnder18), Some(arbeidssoeker_id), 10, 0, &mut tx).await?;
+        assert_eq!(oppgaver.len(), 1);
+        assert_eq!(oppgaver[0].id(), ubehandlet_id);
+
+        let side = finn_oppgaver(None, None, None, 1, 1, &mut tx).await?;
+        assert_eq!(side.len(), 1);
+        assert_eq!(side[0].id(), alle[1].id());
+
+        let oppgaver = finn_oppgaver(None, Some(OppgaveType::VurderOppholdsstatus), None, 10, 0, &mut tx).await?;
+        assert!(oppgaver.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tilbakestill_oppgave() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let fast_oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(1), OppgaveStatus::Opprettet, Utc::now()), &mut tx).await?;
+        let opprettet_oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(2), OppgaveStatus::Opprettet, Utc::now()), &mut tx).await?;
+        oppdater_oppgave_med_ekstern_id(opprettet_oppgave_id, EksternOppgaveId::from(1337), &mut tx).await?;
+        let gitt_opp_oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(3), OppgaveStatus::GittOpp, Utc::now()), &mut tx).await?;
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        assert!(tilbakestill_oppgave(fast_oppgave_id, &mut tx).await?);
+        assert!(!tilbakestill_oppgave(opprettet_oppgave_id, &mut tx).await?);
+        assert!(tilbakestill_oppgave(gitt_opp_oppgave_id, &mut tx).await?);
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let fast_oppgave = hent_oppgave(fast_oppgave_id, &mut tx).await?.unwrap();
+        assert_eq!(fast_oppgave.status, Ubehandlet);
+        let gitt_opp_oppgave = hent_oppgave(gitt_opp_oppgave_id, &mut tx).await?.unwrap();
+        assert_eq!(gitt_opp_oppgave.status, Ubehandlet);
+        let opprettet_oppgave = hent_oppgave(opprettet_oppgave_id, &mut tx).await?.unwrap();
+        assert_eq!(opprettet_oppgave.status, OppgaveStatus::Opprettet);
+        assert_eq!(hent_oppgave(OppgaveId(1337), &mut tx).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_utsatt_oppgave_hentes_ikke_foer_neste_forsok() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(1), OppgaveStatus::Opprettet, Utc::now()), &mut tx).await?;
+        assert_eq!(registrer_feilet_forsok(oppgave_id, &mut tx).await?, Some(1));
+        assert!(utsett_oppgave(oppgave_id, Utc::now() + chrono::Duration::minutes(5), &mut tx).await?);
+        tx.commit().await?;
+
+        let antall_oppgaver = NonZeroU32::new(10).unwrap();
+        let mut tx = pg_pool.begin().await?;
+        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(antall_oppgaver, &[], &mut tx).await?;
+        assert!(oppgaver.is_empty(), "Utsatt oppgave skal ikke hentes før neste forsøk");
+
+        assert!(bytt_oppgave_status(oppgave_id, Ubehandlet, OppgaveStatus::Opprettet, &mut tx).await?);
+        assert_eq!(registrer_feilet_forsok(oppgave_id, &mut tx).await?, Some(2));
+        assert!(utsett_oppgave(oppgave_id, Utc::now() - chrono::Duration::minutes(1), &mut tx).await?);
+        let oppgaver = hent_de_eldste_ubehandlede_oppgavene(antall_oppgaver, &[], &mut tx).await?;
+        assert_eq!(oppgaver.len(), 1);
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_registrer_feilet_forsok_krever_status_opprettet() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(1), Ubehandlet, Utc::now()), &mut tx).await?;
+        assert_eq!(registrer_feilet_forsok(oppgave_id, &mut tx).await?, None);
+        assert!(!utsett_oppgave(oppgave_id, Utc::now(), &mut tx).await?);
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_hent_oppgaver_til_avstemming() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let gammel = Utc::now() - chrono::Duration::days(2);
+        let mut tx = pg_pool.begin().await?;
+        let gammel_oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(1), OppgaveStatus::Opprettet, gammel), &mut tx).await?;
+        oppdater_oppgave_med_ekstern_id(gammel_oppgave_id, EksternOppgaveId::from(1), &mut tx).await?;
+        let ny_oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(2), OppgaveStatus::Opprettet, Utc::now()), &mut tx).await?;
+        oppdater_oppgave_med_ekstern_id(ny_oppgave_id, EksternOppgaveId::from(2), &mut tx).await?;
+        let uten_ekstern_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(3), OppgaveStatus::Opprettet, gammel), &mut tx).await?;
+        let ferdig_oppgave_id = lagre_oppgave(&test_oppgave(ArbeidssoekerId(4), Ferdigbehandlet, gammel), &mut tx).await?;
+        oppdater_oppgave_med_ekstern_id(ferdig_oppgave_id, EksternOppgaveId::from(4), &mut tx).await?;
+        tx.commit().await?;
+
+        let opprettet_foer = Utc::now() - chrono::Duration::days(1);
+        let antall_oppgaver = NonZeroU32::new(10).unwrap();
+        let mut tx = pg_pool.begin().await?;
+        let oppgaver = hent_oppgaver_til_avstemming(opprettet_foer, Utc::now(), antall_oppgaver, &mut tx).await?;
+        let oppgave_ider: Vec<OppgaveId> = oppgaver.iter().map(Oppgave::id).collect();
+        assert_eq!(oppgave_ider, vec![gammel_oppgave_id]);
+        assert!(!oppgave_ider.contains(&uten_ekstern_id));
+
+        oppdater_sist_avstemt(&oppgave_ider, Utc::now(), &mut tx).await?;
+        let avstemt_foer = Utc::now() - chrono::Duration::hours(1);
+        let oppgaver = hent_oppgaver_til_avstemming(opprettet_foer, avstemt_foer, antall_oppgaver, &mut tx).await?;
+        assert!(oppgaver.is_empty(), "Nylig avstemte oppgaver skal ikke hentes");
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     fn test_oppgave(
         arbeidssoeker_id: ArbeidssoekerId,
         status: OppgaveStatus,
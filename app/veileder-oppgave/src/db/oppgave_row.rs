@@ -9,6 +9,7 @@ pub struct OppgaveRow {
     pub type_: String,
     pub status: String,
     pub opplysninger: Vec<String>,
+    pub detaljer: Option<String>,
     pub arbeidssoeker_id: i64,
     pub identitetsnummer: String,
     pub ekstern_oppgave_id: Option<i64>,
@@ -0,0 +1,16 @@
+use crate::domain::ekstern_oppgave_id::EksternOppgaveId;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Siste kjente tilstand for oppgaven i Oppgave API, speilet fra oppgavehendelser
+#[derive(Debug, Clone, PartialEq)]
+pub struct EksternOppgaveTilstand {
+    pub ekstern_oppgave_id: EksternOppgaveId,
+    pub versjon: i32,
+    pub enhetsnr: Option<String>,
+    pub nav_ident: Option<String>,
+    pub prioritet: Option<String>,
+    pub frist: Option<NaiveDate>,
+    /// Første gang vi så oppgaven tildelt en veileder
+    pub forst_tildelt_tidspunkt: Option<DateTime<Utc>>,
+    pub oppdatert_tidspunkt: DateTime<Utc>,
+}
@@ -11,9 +11,14 @@ pub enum HendelseLoggStatus {
     OppgaveOpprettet,
     OppgaveFinnesAllerede,
     EksternOppgaveOpprettelseFeilet,
+    EksternOppgaveOpprettelseGittOpp,
     EksternOppgaveOpprettet,
     EksternOppgaveFerdigstilt,
     EksternOppgaveFeilregistrert,
+    EksternOppgaveIkkeFunnet,
+    ManueltTilbakestilt,
+    ManueltFerdigbehandlet,
+    ManuellProsessering,
 }
 
 fn hendelse_logg_status_not_found(status: &str) -> HendelseLoggStatusParseError {
@@ -45,6 +50,10 @@ mod tests {
             HendelseLoggStatus::from_str("EKSTERN_OPPGAVE_OPPRETTELSE_FEILET"),
             Ok(HendelseLoggStatus::EksternOppgaveOpprettelseFeilet)
         );
+        assert_eq!(
+            HendelseLoggStatus::from_str("EKSTERN_OPPGAVE_OPPRETTELSE_GITT_OPP"),
+            Ok(HendelseLoggStatus::EksternOppgaveOpprettelseGittOpp)
+        );
         assert_eq!(
             HendelseLoggStatus::from_str("EKSTERN_OPPGAVE_OPPRETTET"),
             Ok(HendelseLoggStatus::EksternOppgaveOpprettet)
@@ -57,6 +66,22 @@ mod tests {
             HendelseLoggStatus::from_str("EKSTERN_OPPGAVE_FEILREGISTRERT"),
             Ok(HendelseLoggStatus::EksternOppgaveFeilregistrert)
         );
+        assert_eq!(
+            HendelseLoggStatus::from_str("EKSTERN_OPPGAVE_IKKE_FUNNET"),
+            Ok(HendelseLoggStatus::EksternOppgaveIkkeFunnet)
+        );
+        assert_eq!(
+            HendelseLoggStatus::from_str("MANUELT_TILBAKESTILT"),
+            Ok(HendelseLoggStatus::ManueltTilbakestilt)
+        );
+        assert_eq!(
+            HendelseLoggStatus::from_str("MANUELT_FERDIGBEHANDLET"),
+            Ok(HendelseLoggStatus::ManueltFerdigbehandlet)
+        );
+        assert_eq!(
+            HendelseLoggStatus::from_str("MANUELL_PROSESSERING"),
+            Ok(HendelseLoggStatus::ManuellProsessering)
+        );
 
         let ukjent_status = "UkjentStatus";
         assert!(HendelseLoggStatus::from_str(ukjent_status).is_err());
@@ -0,0 +1,55 @@
+use crate::domain::kriterier::kriterium::{Kriterium, OppgaveKriterier};
+use crate::domain::oppgave_type::OppgaveType;
+use interne_hendelser::AutomatiskIdMergeIkkeMulig;
+
+pub const KRITERIER: OppgaveKriterier<AutomatiskIdMergeIkkeMulig> = OppgaveKriterier::new(
+    OppgaveType::AutomatiskIdMergeIkkeMulig,
+    &[Kriterium {
+        navn: "har_aktiv_periode",
+        sjekk: |hendelse| hendelse.perioder.iter().any(|periode| periode.er_aktiv()),
+    }],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interne_hendelser::PeriodeRad;
+    use paw_test::hendelse_builder::{AutomatiskIdMergeIkkeMuligBuilder, rfc3339};
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    fn periode(til: Option<&str>) -> PeriodeRad {
+        PeriodeRad {
+            periode_id: Uuid::new_v4(),
+            identitetsnummer: "12345678901".to_string(),
+            fra: rfc3339("2024-01-01T12:00:00Z"),
+            til: til.map(rfc3339),
+        }
+    }
+
+    #[test]
+    fn oppfylt_med_aktiv_periode() {
+        let hendelse = AutomatiskIdMergeIkkeMuligBuilder {
+            perioder: HashSet::from([periode(None), periode(Some("2024-06-01T12:00:00Z"))]),
+            ..Default::default()
+        }
+        .build();
+        assert!(KRITERIER.oppfylt_av(&hendelse));
+    }
+
+    #[test]
+    fn ikke_oppfylt_med_kun_avsluttede_perioder() {
+        let hendelse = AutomatiskIdMergeIkkeMuligBuilder {
+            perioder: HashSet::from([periode(Some("2024-06-01T12:00:00Z"))]),
+            ..Default::default()
+        }
+        .build();
+        assert!(KRITERIER.ikke_oppfylt_av(&hendelse));
+    }
+
+    #[test]
+    fn ikke_oppfylt_uten_perioder() {
+        let hendelse = AutomatiskIdMergeIkkeMuligBuilder::default().build();
+        assert!(KRITERIER.ikke_oppfylt_av(&hendelse));
+    }
+}
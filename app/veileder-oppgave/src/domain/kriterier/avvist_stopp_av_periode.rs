@@ -0,0 +1,46 @@
+use crate::domain::kriterier::kriterium::{Kriterium, OppgaveKriterier};
+use crate::domain::oppgave_type::OppgaveType;
+use interne_hendelser::AvvistStoppAvPeriode;
+use interne_hendelser::vo::BrukerType;
+
+pub const KRITERIER: OppgaveKriterier<AvvistStoppAvPeriode> = OppgaveKriterier::new(
+    OppgaveType::AvvistStoppAvPeriode,
+    &[Kriterium {
+        navn: "initiert_av_system",
+        sjekk: |hendelse| hendelse.metadata.utfoert_av.bruker_type == BrukerType::System,
+    }],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paw_test::hendelse_builder::AvvistStoppAvPeriodeBuilder;
+
+    #[test]
+    fn oppfylt_naar_initiert_av_system() {
+        let hendelse = AvvistStoppAvPeriodeBuilder::default().build();
+        assert!(KRITERIER.oppfylt_av(&hendelse));
+    }
+
+    #[test]
+    fn ikke_oppfylt_naar_initiert_av_veileder() {
+        let hendelse = AvvistStoppAvPeriodeBuilder {
+            bruker_type: BrukerType::Veileder,
+            utfoert_av_id: "Z991459".to_string(),
+            ..Default::default()
+        }
+        .build();
+        assert!(KRITERIER.ikke_oppfylt_av(&hendelse));
+    }
+
+    #[test]
+    fn ikke_oppfylt_naar_initiert_av_sluttbruker() {
+        let hendelse = AvvistStoppAvPeriodeBuilder {
+            bruker_type: BrukerType::Sluttbruker,
+            utfoert_av_id: "12345678901".to_string(),
+            ..Default::default()
+        }
+        .build();
+        assert!(KRITERIER.ikke_oppfylt_av(&hendelse));
+    }
+}
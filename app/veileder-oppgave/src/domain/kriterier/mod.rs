@@ -1,3 +1,5 @@
+pub mod automatisk_id_merge_ikke_mulig;
+pub mod avvist_stopp_av_periode;
 pub mod avvist_under_18;
 pub mod kriterium;
 pub mod vurder_oppholdsstatus;
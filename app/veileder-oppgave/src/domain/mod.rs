@@ -5,4 +5,7 @@ pub mod oppgave_id;
 pub mod ekstern_oppgave_id;
 pub mod hendelse_logg_entry;
 pub mod hendelse_logg_status;
-pub mod kriterier;
\ No newline at end of file
+pub mod kriterier;
+pub mod retry_policy;
+pub mod ekstern_oppgave_tilstand;
+pub mod oppgavetyper;
@@ -15,6 +15,8 @@ pub struct Oppgave {
     pub type_: OppgaveType,
     pub status: OppgaveStatus,
     pub opplysninger: Vec<String>,
+    /// Fritekst fra hendelsen som fylles inn i beskrivelsen til Oppgave API
+    pub detaljer: Option<String>,
     pub arbeidssoeker_id: ArbeidssoekerId,
     pub identitetsnummer: Identitetsnummer,
     pub ekstern_oppgave_id: Option<EksternOppgaveId>,
@@ -27,6 +29,18 @@ impl Oppgave {
         self.id.expect("Oppgave mangler id — ikke persistert")
     }
 
+    /// Stabil uuid som sendes til Oppgave API, slik at et nytt forsøk etter krasj
+    /// gjenkjennes som samme oppgave i stedet for å lage et duplikat.
+    pub fn ekstern_uuid(&self) -> Uuid {
+        let mut bytes = *self.melding_id.as_bytes();
+        let id_bytes = i64::from(self.id()).to_be_bytes();
+        bytes
+            .iter_mut()
+            .zip(id_bytes)
+            .for_each(|(byte, id_byte)| *byte ^= id_byte);
+        Uuid::new_v8(bytes)
+    }
+
     pub fn new(
         melding_id: Uuid,
         type_: OppgaveType,
@@ -42,6 +56,7 @@ impl Oppgave {
             type_,
             status,
             opplysninger,
+            detaljer: None,
             arbeidssoeker_id,
             identitetsnummer,
             ekstern_oppgave_id: None,
@@ -50,12 +65,17 @@ impl Oppgave {
         }
     }
 
+    pub fn med_detaljer(self, detaljer: Option<String>) -> Self {
+        Self { detaljer, ..self }
+    }
+
     pub fn fra_db(
         id: OppgaveId,
         melding_id: Uuid,
         type_: OppgaveType,
         status: OppgaveStatus,
         opplysninger: Vec<String>,
+        detaljer: Option<String>,
         arbeidssoeker_id: ArbeidssoekerId,
         identitetsnummer: Identitetsnummer,
         ekstern_oppgave_id: Option<EksternOppgaveId>,
@@ -68,6 +88,7 @@ impl Oppgave {
             type_,
             status,
             opplysninger,
+            detaljer,
             arbeidssoeker_id,
             identitetsnummer,
             ekstern_oppgave_id,
@@ -76,3 +97,43 @@ impl Oppgave {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oppgave_med_id(id: i64, melding_id: Uuid) -> Oppgave {
+        Oppgave::fra_db(
+            OppgaveId(id),
+            melding_id,
+            OppgaveType::AvvistUnder18,
+            OppgaveStatus::Ubehandlet,
+            vec![],
+            None,
+            ArbeidssoekerId::from(1),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            None,
+            Utc::now(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn ekstern_uuid_er_stabil() {
+        let melding_id = Uuid::new_v4();
+        let oppgave = oppgave_med_id(42, melding_id);
+
+        assert_eq!(oppgave.ekstern_uuid(), oppgave_med_id(42, melding_id).ekstern_uuid());
+        assert_eq!(oppgave.ekstern_uuid().get_version_num(), 8);
+    }
+
+    #[test]
+    fn ekstern_uuid_er_ulik_for_ulike_oppgaver_fra_samme_melding() {
+        let melding_id = Uuid::new_v4();
+
+        assert_ne!(
+            oppgave_med_id(1, melding_id).ekstern_uuid(),
+            oppgave_med_id(2, melding_id).ekstern_uuid()
+        );
+    }
+}
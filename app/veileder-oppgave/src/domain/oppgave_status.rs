@@ -11,6 +11,15 @@ pub enum OppgaveStatus {
     Ubehandlet,
     Opprettet,
     Ferdigbehandlet,
+    /// Opprettelse i Oppgave API feilet permanent eller for mange ganger
+    GittOpp,
+}
+
+impl OppgaveStatus {
+    /// Avsluttede oppgaver blokkerer ikke for at arbeidssøkeren får en ny oppgave av samme type
+    pub fn er_avsluttet(&self) -> bool {
+        matches!(self, OppgaveStatus::Ferdigbehandlet | OppgaveStatus::GittOpp)
+    }
 }
 
 fn oppgave_status_not_found(status: &str) -> OppgaveStatusParseError {
@@ -42,6 +51,10 @@ mod tests {
             OppgaveStatus::from_str("FERDIGBEHANDLET"),
             Ok(OppgaveStatus::Ferdigbehandlet)
         );
+        assert_eq!(
+            OppgaveStatus::from_str("GITT_OPP"),
+            Ok(OppgaveStatus::GittOpp)
+        );
 
         let ugyldig_status = "UgyldigStatus";
         assert!(OppgaveStatus::from_str(ugyldig_status).is_err());
@@ -50,4 +63,12 @@ mod tests {
             format!("Ugyldig oppgavestatus: {}", ugyldig_status)
         );
     }
+
+    #[test]
+    fn test_er_avsluttet() {
+        assert!(!OppgaveStatus::Ubehandlet.er_avsluttet());
+        assert!(!OppgaveStatus::Opprettet.er_avsluttet());
+        assert!(OppgaveStatus::Ferdigbehandlet.er_avsluttet());
+        assert!(OppgaveStatus::GittOpp.er_avsluttet());
+    }
 }
@@ -12,6 +12,10 @@ pub enum OppgaveType {
     AvvistUnder18,
     #[strum(serialize = "VURDER_OPPHOLDSSTATUS")]
     VurderOppholdsstatus,
+    #[strum(serialize = "AUTOMATISK_ID_MERGE_IKKE_MULIG")]
+    AutomatiskIdMergeIkkeMulig,
+    #[strum(serialize = "AVVIST_STOPP_AV_PERIODE")]
+    AvvistStoppAvPeriode,
 }
 
 fn oppgave_type_not_found(type_: &str) -> OppgaveTypeParseError {
@@ -39,6 +43,14 @@ mod tests {
             OppgaveType::from_str("VURDER_OPPHOLDSSTATUS"),
             Ok(OppgaveType::VurderOppholdsstatus)
         );
+        assert_eq!(
+            OppgaveType::from_str("AUTOMATISK_ID_MERGE_IKKE_MULIG"),
+            Ok(OppgaveType::AutomatiskIdMergeIkkeMulig)
+        );
+        assert_eq!(
+            OppgaveType::from_str("AVVIST_STOPP_AV_PERIODE"),
+            Ok(OppgaveType::AvvistStoppAvPeriode)
+        );
         let ukjent_type = "UkjentType";
         assert!(OppgaveType::from_str(ukjent_type).is_err());
         assert_eq!(
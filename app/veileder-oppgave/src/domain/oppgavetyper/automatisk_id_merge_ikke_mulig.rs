@@ -0,0 +1,83 @@
+use crate::domain::kriterier::automatisk_id_merge_ikke_mulig::KRITERIER;
+use crate::domain::oppgavetyper::definisjon::{DuplikatPolicy, OppgaveDefinisjon};
+use interne_hendelser::{AUTOMATISK_ID_MERGE_IKKE_MULIG, AutomatiskIdMergeIkkeMulig};
+
+const BESKRIVELSE: &str = r#"Automatisk sammenslåing av arbeidssøkerperioder var ikke mulig
+
+Personen har arbeidssøkerperioder på flere identiteter som ikke kunne slås sammen automatisk. Vurder periodene under og avslutt eller rett opp de som ikke skal være aktive.
+
+{detaljer}"#;
+
+pub static DEFINISJON: OppgaveDefinisjon<AutomatiskIdMergeIkkeMulig> = OppgaveDefinisjon {
+    hendelse_type: AUTOMATISK_ID_MERGE_IKKE_MULIG,
+    kriterier: KRITERIER,
+    visningsnavn: "automatisk id-sammenslåing som ikke var mulig",
+    duplikat_policy: DuplikatPolicy::EnAktivPerArbeidssoeker,
+    fra_tidspunkt: |_| None,
+    detaljer: |hendelse| Some(perioder(hendelse)),
+    beskrivelse: BESKRIVELSE,
+};
+
+/// Én linje per periode, eldste først.
+fn perioder(hendelse: &AutomatiskIdMergeIkkeMulig) -> String {
+    let mut perioder: Vec<_> = hendelse.perioder.iter().collect();
+    perioder.sort_by_key(|periode| (periode.fra, periode.periode_id));
+    let linjer: Vec<String> = perioder
+        .iter()
+        .map(|periode| {
+            let til = periode
+                .til
+                .map(|til| til.format("%d.%m.%Y").to_string())
+                .unwrap_or_else(|| "aktiv".to_string());
+            format!(
+                "- Periode {} på {}: {} – {}",
+                periode.periode_id,
+                periode.identitetsnummer,
+                periode.fra.format("%d.%m.%Y"),
+                til
+            )
+        })
+        .collect();
+    format!("Perioder:\n{}", linjer.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interne_hendelser::PeriodeRad;
+    use paw_test::hendelse_builder::{AutomatiskIdMergeIkkeMuligBuilder, rfc3339};
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    #[test]
+    fn perioder_listes_eldste_foerst() {
+        let aktiv_id = Uuid::new_v4();
+        let avsluttet_id = Uuid::new_v4();
+        let hendelse = AutomatiskIdMergeIkkeMuligBuilder {
+            perioder: HashSet::from([
+                PeriodeRad {
+                    periode_id: aktiv_id,
+                    identitetsnummer: "12345678901".to_string(),
+                    fra: rfc3339("2024-03-01T12:00:00Z"),
+                    til: None,
+                },
+                PeriodeRad {
+                    periode_id: avsluttet_id,
+                    identitetsnummer: "12345678902".to_string(),
+                    fra: rfc3339("2023-01-15T12:00:00Z"),
+                    til: Some(rfc3339("2023-06-30T12:00:00Z")),
+                },
+            ]),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(
+            perioder(&hendelse),
+            format!(
+                "Perioder:\n- Periode {} på 12345678902: 15.01.2023 – 30.06.2023\n- Periode {} på 12345678901: 01.03.2024 – aktiv",
+                avsluttet_id, aktiv_id
+            )
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::domain::kriterier::avvist_stopp_av_periode::KRITERIER;
+use crate::domain::oppgavetyper::definisjon::{DuplikatPolicy, OppgaveDefinisjon};
+use interne_hendelser::{AVVIST_STOPP_AV_PERIODE_HENDELSE_TYPE, AvvistStoppAvPeriode};
+
+const BESKRIVELSE: &str = r#"Automatisk avslutning av arbeidssøkerperiode ble avvist
+
+Et system forsøkte å avslutte personens arbeidssøkerperiode, men avslutningen ble avvist. Vurder om perioden skal avsluttes manuelt.
+
+Opplysninger: {opplysninger}"#;
+
+pub static DEFINISJON: OppgaveDefinisjon<AvvistStoppAvPeriode> = OppgaveDefinisjon {
+    hendelse_type: AVVIST_STOPP_AV_PERIODE_HENDELSE_TYPE,
+    kriterier: KRITERIER,
+    visningsnavn: "avvist stopp av periode",
+    duplikat_policy: DuplikatPolicy::EnAktivPerArbeidssoeker,
+    fra_tidspunkt: |_| None,
+    detaljer: |_| None,
+    beskrivelse: BESKRIVELSE,
+};
@@ -0,0 +1,19 @@
+use crate::domain::kriterier::avvist_under_18::KRITERIER;
+use crate::domain::oppgavetyper::definisjon::{DuplikatPolicy, OppgaveDefinisjon};
+use interne_hendelser::{AVVIST_HENDELSE_TYPE, Avvist};
+
+const BESKRIVELSE: &str = r#"Personen har forsøkt å registrere seg som arbeidssøker, men er sperret fra å gjøre dette da personen er under 18 år.
+For mindreårige arbeidssøkere trengs det samtykke fra begge foresatte for å kunne registrere seg.
+Se "Samtykke fra foresatte til unge under 18 år - registrering som arbeidssøker, øvrige tiltak og tjenester".
+
+Når samtykke er innhentet kan du registrere arbeidssøker via flate for manuell registrering i modia."#;
+
+pub static DEFINISJON: OppgaveDefinisjon<Avvist> = OppgaveDefinisjon {
+    hendelse_type: AVVIST_HENDELSE_TYPE,
+    kriterier: KRITERIER,
+    visningsnavn: "avvist registrering",
+    duplikat_policy: DuplikatPolicy::EnAktivPerArbeidssoeker,
+    fra_tidspunkt: |app_config| Some(*app_config.opprett_avvist_under_18_oppgaver_fra_tidspunkt),
+    detaljer: |_| None,
+    beskrivelse: BESKRIVELSE,
+};
@@ -0,0 +1,264 @@
+use crate::config::ApplicationConfig;
+use crate::domain::kriterier::kriterium::OppgaveKriterier;
+use crate::domain::oppgave::Oppgave;
+use crate::domain::oppgave_status::OppgaveStatus;
+use crate::domain::oppgave_type::OppgaveType;
+use anyhow::Context;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use interne_hendelser::Hendelse;
+use paw_date_time::virkedager::legg_til_virkedager;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use types::arbeidssoeker_id::ArbeidssoekerId;
+use types::identitetsnummer::Identitetsnummer;
+
+/// Hvordan en ny hendelse forholder seg til oppgaver arbeidssøkeren allerede har av samme type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplikatPolicy {
+    /// Ny oppgave opprettes bare når forrige oppgave av samme type er avsluttet
+    EnAktivPerArbeidssoeker,
+    /// Hver hendelse som oppfyller kriteriene gir en ny oppgave
+    Ingen,
+}
+
+impl DuplikatPolicy {
+    /// Om en ny oppgave kan opprettes når arbeidssøkeren allerede har `eksisterende`.
+    pub fn tillater_ny_oppgave(&self, eksisterende: &Oppgave) -> bool {
+        match self {
+            DuplikatPolicy::EnAktivPerArbeidssoeker => eksisterende.status.er_avsluttet(),
+            DuplikatPolicy::Ingen => true,
+        }
+    }
+}
+
+/// Feltene som sendes til Oppgave API når oppgaven opprettes eksternt. Leses fra
+/// `[oppgavetyper.<OPPGAVETYPE>]` i application_config.toml.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OppgaveApiFelter {
+    pub tema: String,
+    pub oppgavetype: String,
+    pub behandlingstema: Option<String>,
+    pub behandlingstype: Option<String>,
+    /// Regel for `fristFerdigstillelse`, regnet fra aktiv dato
+    pub frist: Option<Frist>,
+}
+
+/// Frist for ferdigstillelse av en oppgave, f.eks. `{ virkedager = 3 }` eller
+/// `{ kalenderdager = 14 }` i config.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Frist {
+    Virkedager(u32),
+    Kalenderdager(u32),
+}
+
+impl Frist {
+    pub fn fra(&self, dato: NaiveDate) -> NaiveDate {
+        match self {
+            Frist::Virkedager(antall) => legg_til_virkedager(dato, *antall),
+            Frist::Kalenderdager(antall) => dato + Days::new((*antall).into()),
+        }
+    }
+}
+
+/// Fyller inn `{opplysninger}` og `{detaljer}` i `beskrivelse` med oppgavens opplysninger og detaljer.
+fn fyll_inn_beskrivelse(beskrivelse: &str, oppgave: &Oppgave) -> String {
+    beskrivelse
+        .replace("{opplysninger}", &oppgave.opplysninger.join(", "))
+        .replace(
+            "{detaljer}",
+            oppgave.detaljer.as_deref().unwrap_or_default(),
+        )
+}
+
+/// Alt som skal til for å opprette én oppgavetype fra én hendelsetype.
+pub struct OppgaveDefinisjon<H: Hendelse + 'static> {
+    pub hendelse_type: &'static str,
+    pub kriterier: OppgaveKriterier<H>,
+    /// Brukes i hendelse-loggen, f.eks. "avvist registrering"
+    pub visningsnavn: &'static str,
+    pub duplikat_policy: DuplikatPolicy,
+    /// Hendelser før dette tidspunktet gir ikke oppgave
+    pub fra_tidspunkt: fn(&ApplicationConfig) -> Option<DateTime<Utc>>,
+    /// Detaljer fra hendelsen som lagres på oppgaven, f.eks. perioder
+    pub detaljer: fn(&H) -> Option<String>,
+    /// Beskrivelsen i Oppgave API. `{opplysninger}` og `{detaljer}` erstattes med oppgavens
+    /// opplysninger og detaljer
+    pub beskrivelse: &'static str,
+}
+
+/// Typeløs tilgang til en [`OppgaveDefinisjon`], slik at alle definisjoner kan ligge i samme register.
+pub trait Definisjon: Sync {
+    fn oppgave_type(&self) -> OppgaveType;
+    fn hendelse_type(&self) -> &'static str;
+    fn visningsnavn(&self) -> &'static str;
+    fn duplikat_policy(&self) -> DuplikatPolicy;
+    fn fra_tidspunkt(&self, app_config: &ApplicationConfig) -> Option<DateTime<Utc>>;
+    fn beskrivelse_for(&self, oppgave: &Oppgave) -> String;
+    /// Deserialiserer hendelsen og lager en ubehandlet oppgave hvis kriteriene er oppfylt.
+    fn ny_oppgave(&self, hendelse: &Value) -> anyhow::Result<Option<Oppgave>>;
+}
+
+impl<H> Definisjon for OppgaveDefinisjon<H>
+where
+    H: Hendelse + DeserializeOwned + 'static,
+{
+    fn oppgave_type(&self) -> OppgaveType {
+        self.kriterier.oppgave_type
+    }
+
+    fn hendelse_type(&self) -> &'static str {
+        self.hendelse_type
+    }
+
+    fn visningsnavn(&self) -> &'static str {
+        self.visningsnavn
+    }
+
+    fn duplikat_policy(&self) -> DuplikatPolicy {
+        self.duplikat_policy
+    }
+
+    fn fra_tidspunkt(&self, app_config: &ApplicationConfig) -> Option<DateTime<Utc>> {
+        (self.fra_tidspunkt)(app_config)
+    }
+
+    fn beskrivelse_for(&self, oppgave: &Oppgave) -> String {
+        fyll_inn_beskrivelse(self.beskrivelse, oppgave)
+    }
+
+    fn ny_oppgave(&self, hendelse: &Value) -> anyhow::Result<Option<Oppgave>> {
+        let hendelse = H::deserialize(hendelse).with_context(|| {
+            format!("Kunne ikke deserialisere hendelse {}", self.hendelse_type)
+        })?;
+        if self.kriterier.ikke_oppfylt_av(&hendelse) {
+            return Ok(None);
+        }
+
+        let identitetsnummer = Identitetsnummer::new(hendelse.identitetsnummer().to_string())
+            .expect("Ugyldig identitetsnummer i Kafka-hendelse som oppfyller kriteriene");
+        let opplysninger = hendelse
+            .opplysninger()
+            .iter()
+            .map(|opplysning| opplysning.to_string())
+            .collect();
+
+        Ok(Some(Oppgave::new(
+            hendelse.hendelse_id(),
+            self.kriterier.oppgave_type,
+            OppgaveStatus::Ubehandlet,
+            opplysninger,
+            ArbeidssoekerId::from(hendelse.id()),
+            identitetsnummer,
+            hendelse.metadata().tidspunkt,
+        )
+        .med_detaljer((self.detaljer)(&hendelse))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::oppgave_id::OppgaveId;
+    use paw_app_config::config::read_toml_config;
+    use uuid::Uuid;
+
+    fn oppgave_med_status(status: OppgaveStatus) -> Oppgave {
+        Oppgave::fra_db(
+            OppgaveId(1),
+            Uuid::new_v4(),
+            OppgaveType::AvvistUnder18,
+            status,
+            vec!["ER_UNDER_18_AAR".to_string(), "BOSATT_ETTER_FREG_LOVEN".to_string()],
+            None,
+            ArbeidssoekerId(1),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            None,
+            Utc::now(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn en_aktiv_per_arbeidssoeker_tillater_ny_oppgave_naar_eksisterende_er_avsluttet() {
+        let policy = DuplikatPolicy::EnAktivPerArbeidssoeker;
+
+        assert!(policy.tillater_ny_oppgave(&oppgave_med_status(OppgaveStatus::Ferdigbehandlet)));
+        assert!(policy.tillater_ny_oppgave(&oppgave_med_status(OppgaveStatus::GittOpp)));
+        assert!(!policy.tillater_ny_oppgave(&oppgave_med_status(OppgaveStatus::Ubehandlet)));
+        assert!(!policy.tillater_ny_oppgave(&oppgave_med_status(OppgaveStatus::Opprettet)));
+    }
+
+    #[test]
+    fn ingen_duplikat_policy_tillater_alltid_ny_oppgave() {
+        assert!(
+            DuplikatPolicy::Ingen
+                .tillater_ny_oppgave(&oppgave_med_status(OppgaveStatus::Ubehandlet))
+        );
+    }
+
+    #[test]
+    fn beskrivelse_fyller_inn_opplysninger_og_detaljer() {
+        let beskrivelse = "Opplysninger: {opplysninger}\n{detaljer}";
+        let oppgave = oppgave_med_status(OppgaveStatus::Ubehandlet);
+
+        assert_eq!(
+            fyll_inn_beskrivelse(beskrivelse, &oppgave),
+            "Opplysninger: ER_UNDER_18_AAR, BOSATT_ETTER_FREG_LOVEN\n"
+        );
+        assert_eq!(
+            fyll_inn_beskrivelse(
+                beskrivelse,
+                &oppgave.med_detaljer(Some("Periode 1".to_string()))
+            ),
+            "Opplysninger: ER_UNDER_18_AAR, BOSATT_ETTER_FREG_LOVEN\nPeriode 1"
+        );
+    }
+
+    #[test]
+    fn frist_i_virkedager_hopper_over_helg_og_helligdager() {
+        let fredag = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let onsdag_foer_jul = NaiveDate::from_ymd_opt(2026, 12, 23).unwrap();
+
+        assert_eq!(
+            Frist::Virkedager(3).fra(fredag),
+            NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+        );
+        assert_eq!(
+            Frist::Virkedager(3).fra(onsdag_foer_jul),
+            NaiveDate::from_ymd_opt(2026, 12, 29).unwrap()
+        );
+        assert_eq!(
+            Frist::Kalenderdager(3).fra(fredag),
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+        );
+    }
+
+    #[test]
+    fn oppgave_api_felter_leses_fra_toml() {
+        let felter: OppgaveApiFelter = read_toml_config(
+            r#"
+            tema = "GEN"
+            oppgavetype = "VURD_HENV"
+            frist = { virkedager = 3 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(felter.tema, "GEN");
+        assert_eq!(felter.oppgavetype, "VURD_HENV");
+        assert_eq!(felter.behandlingstema, None);
+        assert_eq!(felter.frist, Some(Frist::Virkedager(3)));
+
+        let felter: OppgaveApiFelter = read_toml_config(
+            r#"
+            tema = "GEN"
+            oppgavetype = "KONT_BRUK"
+            frist = { kalenderdager = 14 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(felter.frist, Some(Frist::Kalenderdager(14)));
+    }
+}
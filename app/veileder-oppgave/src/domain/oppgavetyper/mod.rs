@@ -0,0 +1,169 @@
+//! Register over oppgavetypene. En ny type legges til med en variant i [`OppgaveType`],
+//! kriterier i `domain::kriterier`, en [`OppgaveDefinisjon`](definisjon::OppgaveDefinisjon)
+//! her, en linje i [`DEFINISJONER`] og Oppgave API-felter under `[oppgavetyper.<TYPE>]` i
+//! application_config.toml. Ruting og opprettelse følger av det.
+
+pub mod automatisk_id_merge_ikke_mulig;
+pub mod avvist_stopp_av_periode;
+pub mod avvist_under_18;
+pub mod definisjon;
+pub mod vurder_oppholdsstatus;
+
+use crate::config::ApplicationConfig;
+use crate::domain::oppgave_type::OppgaveType;
+use anyhow::{bail, ensure};
+use chrono::{DateTime, Utc};
+use definisjon::{Definisjon, OppgaveApiFelter};
+use std::collections::HashMap;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+pub static DEFINISJONER: &[&dyn Definisjon] = &[
+    &avvist_under_18::DEFINISJON,
+    &vurder_oppholdsstatus::DEFINISJON,
+    &automatisk_id_merge_ikke_mulig::DEFINISJON,
+    &avvist_stopp_av_periode::DEFINISJON,
+];
+
+/// Definisjonene som skal vurderes for en hendelse av typen `hendelse_type`.
+pub fn definisjoner_for_hendelse(
+    hendelse_type: &str,
+) -> impl Iterator<Item = &'static dyn Definisjon> {
+    DEFINISJONER
+        .iter()
+        .copied()
+        .filter(move |definisjon| definisjon.hendelse_type() == hendelse_type)
+}
+
+/// Definisjonen for `oppgave_type`. Finnes for alle typer når [`valider_oppgavetyper`] er ok.
+pub fn definisjon_for(oppgave_type: OppgaveType) -> Option<&'static dyn Definisjon> {
+    DEFINISJONER
+        .iter()
+        .copied()
+        .find(|definisjon| definisjon.oppgave_type() == oppgave_type)
+}
+
+/// Tidspunktet hver oppgavetype opprettes fra. Typer uten slik grense er ikke med.
+pub fn fra_tidspunkt_per_type(app_config: &ApplicationConfig) -> Vec<(OppgaveType, DateTime<Utc>)> {
+    DEFINISJONER
+        .iter()
+        .filter_map(|definisjon| {
+            definisjon
+                .fra_tidspunkt(app_config)
+                .map(|fra_tidspunkt| (definisjon.oppgave_type(), fra_tidspunkt))
+        })
+        .collect()
+}
+
+/// Sjekker at hver oppgavetype har nøyaktig én definisjon og Oppgave API-felter i config, og at
+/// config ikke har felter for ukjente oppgavetyper.
+pub fn valider_oppgavetyper(
+    oppgavetyper: &HashMap<String, OppgaveApiFelter>,
+) -> anyhow::Result<()> {
+    for oppgave_type in OppgaveType::iter() {
+        let antall = DEFINISJONER
+            .iter()
+            .filter(|definisjon| definisjon.oppgave_type() == oppgave_type)
+            .count();
+        ensure!(
+            antall == 1,
+            "Oppgavetype {} har {} definisjoner",
+            oppgave_type,
+            antall
+        );
+        let Some(felter) = oppgavetyper.get(&oppgave_type.to_string()) else {
+            bail!("Mangler [oppgavetyper.{}] i config", oppgave_type);
+        };
+        ensure!(
+            !felter.tema.is_empty() && !felter.oppgavetype.is_empty(),
+            "Oppgavetype {} mangler tema eller oppgavetype for Oppgave API",
+            oppgave_type
+        );
+    }
+    for navn in oppgavetyper.keys() {
+        ensure!(
+            OppgaveType::from_str(navn).is_ok(),
+            "Ukjent oppgavetype [oppgavetyper.{}] i config",
+            navn
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::read_application_config;
+
+    #[test]
+    fn hver_oppgavetype_har_noyaktig_en_definisjon() {
+        for oppgave_type in OppgaveType::iter() {
+            let antall = DEFINISJONER
+                .iter()
+                .filter(|definisjon| definisjon.oppgave_type() == oppgave_type)
+                .count();
+            assert_eq!(antall, 1, "Oppgavetype {} har {} definisjoner", oppgave_type, antall);
+        }
+    }
+
+    #[test]
+    fn oppgavetypene_i_config_er_gyldige() {
+        let mut oppgavetyper = read_application_config().unwrap().oppgavetyper;
+        assert!(valider_oppgavetyper(&oppgavetyper).is_ok());
+
+        let felter = oppgavetyper.remove("AVVIST_UNDER_18").unwrap();
+        assert!(valider_oppgavetyper(&oppgavetyper).is_err());
+
+        oppgavetyper.insert("AVVIST_UNDER_18".to_string(), felter.clone());
+        oppgavetyper.insert("UKJENT".to_string(), felter.clone());
+        assert!(valider_oppgavetyper(&oppgavetyper).is_err());
+
+        oppgavetyper.remove("UKJENT");
+        oppgavetyper.insert(
+            "AVVIST_UNDER_18".to_string(),
+            OppgaveApiFelter {
+                tema: String::new(),
+                ..felter
+            },
+        );
+        assert!(valider_oppgavetyper(&oppgavetyper).is_err());
+    }
+
+    #[test]
+    fn fra_tidspunkt_gjelder_bare_avvist_under_18() {
+        let app_config = read_application_config().unwrap();
+        assert_eq!(
+            fra_tidspunkt_per_type(&app_config),
+            vec![(
+                OppgaveType::AvvistUnder18,
+                *app_config.opprett_avvist_under_18_oppgaver_fra_tidspunkt
+            )]
+        );
+    }
+
+    #[test]
+    fn ruter_hendelser_til_riktig_definisjon() {
+        let avvist: Vec<_> = definisjoner_for_hendelse(interne_hendelser::AVVIST_HENDELSE_TYPE)
+            .map(|definisjon| definisjon.oppgave_type())
+            .collect();
+        let startet: Vec<_> = definisjoner_for_hendelse(interne_hendelser::STARTET_HENDELSE_TYPE)
+            .map(|definisjon| definisjon.oppgave_type())
+            .collect();
+
+        assert_eq!(avvist, vec![OppgaveType::AvvistUnder18]);
+        assert_eq!(startet, vec![OppgaveType::VurderOppholdsstatus]);
+        assert_eq!(
+            definisjoner_for_hendelse(interne_hendelser::AUTOMATISK_ID_MERGE_IKKE_MULIG)
+                .map(|definisjon| definisjon.oppgave_type())
+                .collect::<Vec<_>>(),
+            vec![OppgaveType::AutomatiskIdMergeIkkeMulig]
+        );
+        assert_eq!(
+            definisjoner_for_hendelse(interne_hendelser::AVVIST_STOPP_AV_PERIODE_HENDELSE_TYPE)
+                .map(|definisjon| definisjon.oppgave_type())
+                .collect::<Vec<_>>(),
+            vec![OppgaveType::AvvistStoppAvPeriode]
+        );
+        assert_eq!(definisjoner_for_hendelse("ukjent").count(), 0);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::domain::kriterier::vurder_oppholdsstatus::KRITERIER;
+use crate::domain::oppgavetyper::definisjon::{DuplikatPolicy, OppgaveDefinisjon};
+use interne_hendelser::{STARTET_HENDELSE_TYPE, Startet};
+
+const BESKRIVELSE: &str = r#"Vurder oppholdsstatus for EU/EØS-borger som har status utflyttet i folkeregisteret
+
+Personen har registrert seg som arbeidssøker og har status utflyttet i folkeregisteret. Nav trenger bekreftelse på at personen har lovlig opphold i Norge. Vurder personens oppholdsstatus i henhold til gjeldende rutine. Dersom vedkommende ikke har lovlig opphold skal personens arbeidssøkerperiode avsluttes.
+
+Se servicerutine: "EU/EØS-borgere med status utflyttet""#;
+
+pub static DEFINISJON: OppgaveDefinisjon<Startet> = OppgaveDefinisjon {
+    hendelse_type: STARTET_HENDELSE_TYPE,
+    kriterier: KRITERIER,
+    visningsnavn: "vurder oppholdsstatus",
+    duplikat_policy: DuplikatPolicy::EnAktivPerArbeidssoeker,
+    fra_tidspunkt: |_| None,
+    detaljer: |_| None,
+    beskrivelse: BESKRIVELSE,
+};
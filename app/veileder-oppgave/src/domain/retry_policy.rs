@@ -0,0 +1,58 @@
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+/// Når opprettelse av ekstern oppgave skal prøves igjen, og når vi gir opp
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub maks_forsok: NonZeroU32,
+    pub forste_ventetid: Duration,
+    pub maks_ventetid: Duration,
+}
+
+impl RetryPolicy {
+    /// Ventetid etter `antall_forsok` feilede forsøk. Dobles for hvert forsøk, men blir aldri
+    /// lengre enn `maks_ventetid`.
+    pub fn ventetid(&self, antall_forsok: u32) -> Duration {
+        let eksponent = antall_forsok.saturating_sub(1).min(31);
+        self.forste_ventetid
+            .saturating_mul(2u32.pow(eksponent))
+            .min(self.maks_ventetid)
+    }
+
+    pub fn skal_gi_opp(&self, antall_forsok: u32, kan_proeves_igjen: bool) -> bool {
+        !kan_proeves_igjen || antall_forsok >= self.maks_forsok.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            maks_forsok: NonZeroU32::new(5).unwrap(),
+            forste_ventetid: Duration::from_secs(60),
+            maks_ventetid: Duration::from_mins(10),
+        }
+    }
+
+    #[test]
+    fn test_ventetid_dobles_opp_til_maks() {
+        let retry_policy = retry_policy();
+        assert_eq!(retry_policy.ventetid(1), Duration::from_mins(1));
+        assert_eq!(retry_policy.ventetid(2), Duration::from_mins(2));
+        assert_eq!(retry_policy.ventetid(3), Duration::from_mins(4));
+        assert_eq!(retry_policy.ventetid(4), Duration::from_mins(8));
+        assert_eq!(retry_policy.ventetid(5), Duration::from_mins(10));
+        assert_eq!(retry_policy.ventetid(u32::MAX), Duration::from_mins(10));
+    }
+
+    #[test]
+    fn test_skal_gi_opp() {
+        let retry_policy = retry_policy();
+        assert!(!retry_policy.skal_gi_opp(1, true));
+        assert!(!retry_policy.skal_gi_opp(4, true));
+        assert!(retry_policy.skal_gi_opp(5, true));
+        assert!(retry_policy.skal_gi_opp(1, false));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::db::ekstern_oppgave_tilstand_functions::{
+    hent_ekstern_tilstand, lagre_ekstern_tilstand,
+};
+use crate::domain::ekstern_oppgave_id::EksternOppgaveId;
+use crate::domain::ekstern_oppgave_tilstand::EksternOppgaveTilstand;
+use crate::domain::oppgave_id::OppgaveId;
+use crate::ferdigstilling::oppgave_hendelse::{EksternOppgave, OppgaveHendelseMelding};
+use chrono::{DateTime, Utc};
+use paw_date_time::virkedager::fra_norsk_tid;
+use sqlx::{Postgres, Transaction};
+
+/// Speiler tildeling, prioritet og frist fra Oppgave API. Hendelser med lavere eller samme
+/// versjon som den lagrede forkastes. Tidspunktene tas fra hendelsen, som er i norsk tid.
+pub async fn oppdater_ekstern_tilstand(
+    oppgave_id: OppgaveId,
+    melding: &OppgaveHendelseMelding,
+    tx: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<()> {
+    let ekstern_oppgave = &melding.oppgave;
+    let forrige = hent_ekstern_tilstand(oppgave_id, tx).await?;
+    let tidspunkt = fra_norsk_tid(melding.hendelse.tidspunkt);
+    match neste_tilstand(forrige.as_ref(), ekstern_oppgave, tidspunkt) {
+        Some(tilstand) => {
+            lagre_ekstern_tilstand(oppgave_id, &tilstand, tx).await?;
+        }
+        None => tracing::info!(
+            "Forkaster utdatert versjon {} av ekstern oppgave {}",
+            ekstern_oppgave.versjon,
+            ekstern_oppgave.oppgave_id
+        ),
+    }
+    Ok(())
+}
+
+/// Tilstanden etter hendelsen, eller `None` hvis hendelsen ikke er nyere enn `forrige`.
+/// Deler som mangler i hendelsen beholdes fra forrige tilstand.
+fn neste_tilstand(
+    forrige: Option<&EksternOppgaveTilstand>,
+    ekstern_oppgave: &EksternOppgave,
+    tidspunkt: DateTime<Utc>,
+) -> Option<EksternOppgaveTilstand> {
+    if let Some(forrige) = forrige
+        && forrige.versjon >= ekstern_oppgave.versjon
+    {
+        return None;
+    }
+
+    let (enhetsnr, nav_ident) = match &ekstern_oppgave.tilordning {
+        Some(tilordning) => (tilordning.enhetsnr.clone(), tilordning.nav_ident.clone()),
+        None => (
+            forrige.and_then(|forrige| forrige.enhetsnr.clone()),
+            forrige.and_then(|forrige| forrige.nav_ident.clone()),
+        ),
+    };
+    let prioritet = match &ekstern_oppgave.kategorisering {
+        Some(kategorisering) => Some(kategorisering.prioritet.to_string()),
+        None => forrige.and_then(|forrige| forrige.prioritet.clone()),
+    };
+    let frist = match &ekstern_oppgave.behandlingsperiode {
+        Some(behandlingsperiode) => behandlingsperiode.frist,
+        None => forrige.and_then(|forrige| forrige.frist),
+    };
+    let forst_tildelt_tidspunkt = forrige
+        .and_then(|forrige| forrige.forst_tildelt_tidspunkt)
+        .or(nav_ident.as_ref().map(|_| tidspunkt));
+
+    Some(EksternOppgaveTilstand {
+        ekstern_oppgave_id: EksternOppgaveId::from(ekstern_oppgave.oppgave_id),
+        versjon: ekstern_oppgave.versjon,
+        enhetsnr,
+        nav_ident,
+        prioritet,
+        frist,
+        forst_tildelt_tidspunkt,
+        oppdatert_tidspunkt: tidspunkt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ferdigstilling::oppgave_hendelse::{
+        OppgaveBehandlingsperiode, OppgaveKategorisering, OppgavePrioritet, OppgaveTilordning,
+    };
+    use chrono::{Duration, NaiveDate};
+
+    fn ekstern_oppgave(versjon: i32, nav_ident: Option<&str>) -> EksternOppgave {
+        EksternOppgave {
+            oppgave_id: 55555,
+            versjon,
+            tilordning: Some(OppgaveTilordning {
+                enhetsnr: Some("4863".to_string()),
+                enhetsmappe_id: None,
+                nav_ident: nav_ident.map(String::from),
+            }),
+            kategorisering: Some(OppgaveKategorisering {
+                tema: "GEN".to_string(),
+                oppgavetype: "KONT_BRUK".to_string(),
+                behandlingstema: None,
+                behandlingstype: None,
+                prioritet: OppgavePrioritet::Hoy,
+            }),
+            behandlingsperiode: Some(OppgaveBehandlingsperiode {
+                aktiv: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+                frist: NaiveDate::from_ymd_opt(2026, 10, 26),
+            }),
+            bruker: None,
+        }
+    }
+
+    #[test]
+    fn test_ny_tilstand() {
+        let tidspunkt = Utc::now();
+        let tilstand = neste_tilstand(None, &ekstern_oppgave(1, None), tidspunkt).unwrap();
+        assert_eq!(tilstand.ekstern_oppgave_id, EksternOppgaveId::from(55555));
+        assert_eq!(tilstand.versjon, 1);
+        assert_eq!(tilstand.enhetsnr.as_deref(), Some("4863"));
+        assert_eq!(tilstand.nav_ident, None);
+        assert_eq!(tilstand.prioritet.as_deref(), Some("HOY"));
+        assert_eq!(tilstand.frist, NaiveDate::from_ymd_opt(2026, 10, 26));
+        assert_eq!(tilstand.forst_tildelt_tidspunkt, None);
+        assert_eq!(tilstand.oppdatert_tidspunkt, tidspunkt);
+    }
+
+    #[test]
+    fn test_utdatert_versjon_forkastes() {
+        let forrige = neste_tilstand(None, &ekstern_oppgave(2, None), Utc::now()).unwrap();
+        assert_eq!(
+            neste_tilstand(Some(&forrige), &ekstern_oppgave(1, None), Utc::now()),
+            None
+        );
+        assert_eq!(
+            neste_tilstand(Some(&forrige), &ekstern_oppgave(2, None), Utc::now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_forst_tildelt_tidspunkt_beholdes() {
+        let tildelt = Utc::now() - Duration::hours(2);
+        let forrige = neste_tilstand(None, &ekstern_oppgave(1, Some("Z991459")), tildelt).unwrap();
+        assert_eq!(forrige.forst_tildelt_tidspunkt, Some(tildelt));
+
+        let tilstand = neste_tilstand(
+            Some(&forrige),
+            &ekstern_oppgave(2, Some("Z990000")),
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(tilstand.nav_ident.as_deref(), Some("Z990000"));
+        assert_eq!(tilstand.forst_tildelt_tidspunkt, Some(tildelt));
+    }
+
+    #[test]
+    fn test_manglende_deler_beholdes_fra_forrige_tilstand() {
+        let forrige =
+            neste_tilstand(None, &ekstern_oppgave(1, Some("Z991459")), Utc::now()).unwrap();
+        let uten_detaljer = EksternOppgave {
+            tilordning: None,
+            kategorisering: None,
+            behandlingsperiode: None,
+            ..ekstern_oppgave(2, None)
+        };
+        let tilstand = neste_tilstand(Some(&forrige), &uten_detaljer, Utc::now()).unwrap();
+        assert_eq!(tilstand.versjon, 2);
+        assert_eq!(tilstand.enhetsnr, forrige.enhetsnr);
+        assert_eq!(tilstand.nav_ident, forrige.nav_ident);
+        assert_eq!(tilstand.prioritet, forrige.prioritet);
+        assert_eq!(tilstand.frist, forrige.frist);
+    }
+}
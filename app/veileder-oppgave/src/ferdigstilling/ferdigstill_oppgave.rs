@@ -9,6 +9,7 @@ use OppgaveStatus::{Ferdigbehandlet, Opprettet};
 use chrono::Utc;
 use sqlx::{Postgres, Transaction};
 use crate::domain::ekstern_oppgave_id::EksternOppgaveId;
+use crate::ferdigstilling::ekstern_tilstand::oppdater_ekstern_tilstand;
 use crate::ferdigstilling::oppgave_hendelse::{OppgaveHendelseMelding, OppgaveHendelsetype};
 
 pub async fn ferdigstill_oppgave(
@@ -23,6 +24,24 @@ pub async fn ferdigstill_oppgave(
         }
     };
 
+    let ekstern_oppgave_id = EksternOppgaveId::from(melding.oppgave.oppgave_id);
+    let oppgave = match finn_oppgave_for_ekstern_id(ekstern_oppgave_id, tx).await? {
+        // Som regel en oppgave vi ikke har opprettet, men hendelsen kan også komme før
+        // `ekstern_oppgave_id` er lagret. Ferdigstilling og feilregistrering rettes da
+        // av avstemmingen, og tildeling og frist speiles igjen ved neste endring.
+        None => {
+            tracing::info!(
+                "Fant ingen oppgave for ekstern oppgave {}, hopper over {}",
+                ekstern_oppgave_id,
+                melding.hendelse.hendelsestype.to_string().to_lowercase()
+            );
+            return Ok(());
+        }
+        Some(oppgave) => oppgave,
+    };
+
+    oppdater_ekstern_tilstand(oppgave.id(), &melding, tx).await?;
+
     let logg_status = match melding.hendelse.hendelsestype {
         OppgaveHendelsetype::OppgaveFerdigstilt => EksternOppgaveFerdigstilt,
         OppgaveHendelsetype::OppgaveFeilregistrert => EksternOppgaveFeilregistrert,
@@ -30,12 +49,6 @@ pub async fn ferdigstill_oppgave(
         OppgaveHendelsetype::OppgaveEndret => return Ok(()),
     };
 
-    let ekstern_oppgave_id = EksternOppgaveId::from(melding.oppgave.oppgave_id);
-    let oppgave = match finn_oppgave_for_ekstern_id(ekstern_oppgave_id, tx).await? {
-        None => return Ok(()),
-        Some(oppgave) => oppgave,
-    };
-
     if bytt_oppgave_status(oppgave.id(), Opprettet, Ferdigbehandlet, tx).await? {
         let hendelse_logg = HendelseLoggEntry::new(
             logg_status,
@@ -60,8 +73,9 @@ pub async fn ferdigstill_oppgave(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::ekstern_oppgave_tilstand_functions::hent_ekstern_tilstand;
     use crate::db::oppgave_functions::{
-        hent_nyeste_oppgave, lagre_oppgave, oppdater_oppgave_med_ekstern_id,
+        hent_nyeste_oppgave, hent_oppgave, lagre_oppgave, oppdater_oppgave_med_ekstern_id,
     };
     use crate::domain::oppgave::Oppgave;
     use crate::domain::oppgave_type::OppgaveType;
@@ -76,6 +90,7 @@ mod tests {
 
     const EKSTERN_OPPGAVE_ID_FERDIGSTILT: i64 = 55555;
     const EKSTERN_OPPGAVE_ID_FEILREGISTRERT: i64 = 66666;
+    const EKSTERN_OPPGAVE_ID_ENDRET: i64 = 77777;
 
     #[tokio::test]
     async fn test_irrelevante_meldinger_ignoreres() -> Result<()> {
@@ -203,6 +218,102 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_endret_oppgave_speiler_ekstern_tilstand() -> Result<()> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = Oppgave::new(
+            Uuid::new_v4(),
+            OppgaveType::AvvistUnder18,
+            Opprettet,
+            vec![],
+            ArbeidssoekerId(12345),
+            Identitetsnummer::new("12345678901".to_string()).unwrap(),
+            Utc::now(),
+        );
+        let oppgave_id = lagre_oppgave(&oppgave, &mut tx).await?;
+        oppdater_oppgave_med_ekstern_id(oppgave_id, EksternOppgaveId::from(EKSTERN_OPPGAVE_ID_ENDRET), &mut tx)
+            .await?;
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        ferdigstill_oppgave(OPPGAVE_ENDRET_JSON.as_bytes(), &mut tx).await?;
+        // Utdatert versjon skal ikke overskrive tilordningen
+        ferdigstill_oppgave(OPPGAVE_ENDRET_UTDATERT_JSON.as_bytes(), &mut tx).await?;
+        tx.commit().await?;
+
+        let mut tx = pg_pool.begin().await?;
+        let tilstand = hent_ekstern_tilstand(oppgave_id, &mut tx).await?.unwrap();
+        assert_eq!(tilstand.versjon, 3);
+        assert_eq!(tilstand.enhetsnr.as_deref(), Some("4863"));
+        assert_eq!(tilstand.nav_ident.as_deref(), Some("Z991459"));
+        assert_eq!(tilstand.prioritet.as_deref(), Some("HOY"));
+        // Hendelsen var 09:15 norsk normaltid
+        let tidspunkt = "2023-02-23T08:15:00Z".parse::<chrono::DateTime<Utc>>()?;
+        assert_eq!(tilstand.forst_tildelt_tidspunkt, Some(tidspunkt));
+        assert_eq!(tilstand.oppdatert_tidspunkt, tidspunkt);
+        let oppgave = hent_oppgave(oppgave_id, &mut tx).await?.unwrap();
+        assert_eq!(oppgave.status, Opprettet, "Endret-hendelser skal ikke endre status");
+
+        Ok(())
+    }
+
+    //language=JSON
+    const OPPGAVE_ENDRET_JSON: &str = r#"{
+        "hendelse": {
+            "hendelsestype": "OPPGAVE_ENDRET",
+            "tidspunkt": [2023, 2, 23, 9, 15, 0]
+        },
+        "utfortAv": {
+            "navIdent": "Z991459",
+            "enhetsnr": "4863"
+        },
+        "oppgave": {
+            "oppgaveId": 77777,
+            "versjon": 3,
+            "tilordning": {
+                "enhetsnr": "4863",
+                "enhetsmappeId": null,
+                "navIdent": "Z991459"
+            },
+            "kategorisering": {
+                "tema": "GEN",
+                "oppgavetype": "KONT_BRUK",
+                "behandlingstema": null,
+                "behandlingstype": null,
+                "prioritet": "HOY"
+            },
+            "behandlingsperiode": {
+                "aktiv": [2023, 2, 23],
+                "frist": [2023, 3, 2]
+            },
+            "bruker": null
+        }
+    }"#;
+
+    //language=JSON
+    const OPPGAVE_ENDRET_UTDATERT_JSON: &str = r#"{
+        "hendelse": {
+            "hendelsestype": "OPPGAVE_ENDRET",
+            "tidspunkt": [2023, 2, 23, 9, 0, 0]
+        },
+        "utfortAv": null,
+        "oppgave": {
+            "oppgaveId": 77777,
+            "versjon": 2,
+            "tilordning": {
+                "enhetsnr": "4863",
+                "enhetsmappeId": null,
+                "navIdent": null
+            },
+            "kategorisering": null,
+            "behandlingsperiode": null,
+            "bruker": null
+        }
+    }"#;
+
     //language=JSON
     const OPPGAVE_FERDIGSTILT_JSON: &str = r#"{
         "hendelse": {
@@ -1,2 +1,3 @@
 pub mod oppgave_hendelse;
-pub mod ferdigstill_oppgave;
\ No newline at end of file
+pub mod ferdigstill_oppgave;
+pub mod ekstern_tilstand;
\ No newline at end of file
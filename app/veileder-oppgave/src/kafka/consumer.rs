@@ -1,6 +1,7 @@
 use anyhow::Result;
 use health_and_monitoring::simple_app_state::AppState;
 use paw_rdkafka::kafka_config::KafkaConfig;
+use paw_rdkafka_hwm::rebalance::consumer_health::KafkaConsumerHealth;
 use paw_rdkafka_hwm::rebalance::hwm_rebalance_handler::HwmRebalanceHandler;
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use sqlx::PgPool;
@@ -9,6 +10,7 @@ use std::sync::Arc;
 pub fn create(
     app_state: Arc<AppState>,
     pg_pool: PgPool,
+    consumer_health: Arc<KafkaConsumerHealth>,
     kafka_config: KafkaConfig,
     topics: &[&str],
 ) -> Result<StreamConsumer<HwmRebalanceHandler>> {
@@ -18,6 +20,7 @@ pub fn create(
         pg_pool,
         app_state,
         version: hwm_version,
+        consumer_health,
     };
     let consumer: StreamConsumer<HwmRebalanceHandler> = config.create_with_context(context)?;
     consumer.subscribe(topics)?;
@@ -3,19 +3,24 @@ use anyhow::Result;
 use paw_rdkafka_hwm::hwm_message_processor::hwm_process_message;
 use paw_rdkafka_hwm::rebalance::hwm_rebalance_handler::HwmRebalanceHandler;
 use paw_rust_base::error::ServerError;
-use rdkafka::consumer::StreamConsumer;
+use rdkafka::consumer::{Consumer, StreamConsumer};
 use sqlx::PgPool;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 pub fn spawn_kafka_consumer_task(
     consumer: StreamConsumer<HwmRebalanceHandler>,
     hwm_version: i16,
     pg_pool: PgPool,
     processor: VeilederOppgaveMessageProcessor,
+    cancellation_token: CancellationToken,
 ) -> JoinHandle<Result<()>> {
     tokio::spawn(async move {
         loop {
-            let msg = consumer.recv().await?.detach();
+            let msg = tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                msg = consumer.recv() => msg?.detach(),
+            };
             hwm_process_message(hwm_version, pg_pool.clone(), &msg, &processor)
                 .await
                 .map_err(|e| ServerError::InternalProcessTerminated {
@@ -23,5 +28,8 @@ pub fn spawn_kafka_consumer_task(
                     message: e.to_string(),
                 })?;
         }
+        consumer.unsubscribe();
+        tracing::info!("Kafka consumer stoppet");
+        Ok(())
     })
 }
@@ -1,3 +1,5 @@
+pub mod admin;
+pub mod avstemming_task;
 pub mod client;
 pub mod config;
 pub mod db;
@@ -7,4 +9,5 @@ pub mod message_processor;
 pub mod metrics;
 pub mod opprett_ekstern_oppgave_task;
 pub mod opprettelse;
+pub mod server;
 pub mod ferdigstilling;
@@ -1,19 +1,25 @@
 use anyhow::Result;
-use axum_health::spawn_health_server;
 use errors::database::DatabaseError;
+use health_and_monitoring::compound_health::CompoundHealth;
+use health_and_monitoring::dependency_health::DependencyHealth;
 use health_and_monitoring::nais_otel_setup::setup_nais_otel;
 use health_and_monitoring::simple_app_state::AppState;
+use paw_oauth2_resource_server::state::AuthState;
 use paw_rdkafka::error::KafkaError;
-use paw_rust_base::error::ServerError;
+use paw_rdkafka_hwm::rebalance::consumer_health::{KafkaConsumerHealth, add_kafka_consumer_checks};
 use paw_rust_base::panic_logger::register_panic_logger;
+use paw_rust_base::task_supervisor::TaskSupervisor;
 use paw_sqlx::postgres::init_db;
 use std::sync::Arc;
 use std::time::Duration;
 use texas_client::token_client::create_token_client;
+use veileder_oppgave::admin::routes::{AdminState, routes as admin_routes};
+use veileder_oppgave::avstemming_task::spawn_avstemming_task;
 use veileder_oppgave::client::oppgave_client::OppgaveApiClient;
 use veileder_oppgave::config::{
-    read_application_config, read_database_config, read_kafka_config, read_oppgave_client_config,
-    read_token_client_config,
+    read_application_config, read_auth_config, read_database_config, read_kafka_config,
+    read_oppgave_client_config, read_token_client_config, validate_config,
+    watch_opprett_oppgaver_task_config,
 };
 use veileder_oppgave::kafka::consumer::create as create_kafka_consumer;
 use veileder_oppgave::kafka::consumer_task::spawn_kafka_consumer_task;
@@ -21,6 +27,7 @@ use veileder_oppgave::message_processor::VeilederOppgaveMessageProcessor;
 use veileder_oppgave::metrics::init_metrics;
 use veileder_oppgave::metrics::metrics_task::spawn_metrics_task;
 use veileder_oppgave::opprett_ekstern_oppgave_task::spawn_ekstern_oppgave_task;
+use veileder_oppgave::server::spawn_web_server;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -28,7 +35,13 @@ async fn main() -> Result<()> {
     setup_nais_otel()?;
     init_metrics();
     tracing::info!("Application started");
+    let config_report = validate_config();
+    config_report.log();
+    config_report.into_result()?;
     let appstate = Arc::new(AppState::new());
+    let mut supervisor = TaskSupervisor::default();
+    let shutdown_appstate = appstate.clone();
+    supervisor.on_shutdown(move || shutdown_appstate.set_is_ready(false));
     let app_config = read_application_config()?;
 
     let db_config = read_database_config()?;
@@ -42,20 +55,62 @@ async fn main() -> Result<()> {
     let hwm_version = *kafka_config.hwm_version;
     let topics = app_config.topics_as_str();
 
-    let consumer = create_kafka_consumer(appstate.clone(), pg_pool.clone(), kafka_config, &topics)
-        .map_err(|e| KafkaError::CreateConsumer(e.to_string()))?;
+    let consumer_health = Arc::new(KafkaConsumerHealth::new());
+    let mut dependency_health = DependencyHealth::default();
+    add_kafka_consumer_checks(&mut dependency_health, consumer_health.clone());
+    let dependency_health = Arc::new(dependency_health);
+    let mut health = CompoundHealth::new();
+    health.add_health_check(appstate.clone());
+    health.add_health_check(dependency_health.clone());
+
+    let consumer = create_kafka_consumer(
+        appstate.clone(),
+        pg_pool.clone(),
+        consumer_health,
+        kafka_config,
+        &topics,
+    )
+    .map_err(|e| KafkaError::CreateConsumer(e.to_string()))?;
 
     let reqwest_client = reqwest::Client::builder()
         .timeout(Duration::from_secs(5))
         .build()?;
 
     let token_client_config = read_token_client_config()?;
-    let token_client = Arc::new(create_token_client(token_client_config, reqwest_client));
+    let token_client = Arc::new(create_token_client(token_client_config, reqwest_client.clone()));
     let oppgave_client_config = read_oppgave_client_config()?;
     let oppgave_api_client = Arc::new(OppgaveApiClient::new(oppgave_client_config, token_client));
 
-    let opprett_ekstern_oppgave_task =
-        spawn_ekstern_oppgave_task(pg_pool.clone(), oppgave_api_client, app_config.clone());
+    let (opprett_oppgaver_task_config, config_watch_task) =
+        watch_opprett_oppgaver_task_config(supervisor.cancellation_token())?;
+    supervisor.add_task("ConfigWatch", config_watch_task);
+
+    let auth_state = AuthState::new(read_auth_config()?, reqwest_client)
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let admin_state = AdminState {
+        db_pool: pg_pool.clone(),
+        oppgave_api_client: oppgave_api_client.clone(),
+        app_config: app_config.clone(),
+        task_config: opprett_oppgaver_task_config.clone(),
+    };
+
+    let avstemming_task = spawn_avstemming_task(
+        pg_pool.clone(),
+        oppgave_api_client.clone(),
+        app_config.clone(),
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("Avstemming", avstemming_task);
+
+    let opprett_ekstern_oppgave_task = spawn_ekstern_oppgave_task(
+        pg_pool.clone(),
+        oppgave_api_client,
+        app_config.clone(),
+        opprett_oppgaver_task_config,
+        supervisor.cancellation_token(),
+    );
+    supervisor.add_task("OpprettOppgave", opprett_ekstern_oppgave_task);
 
     let kafka_consumer_task = spawn_kafka_consumer_task(
         consumer,
@@ -64,33 +119,20 @@ async fn main() -> Result<()> {
         VeilederOppgaveMessageProcessor {
             app_config: app_config.clone(),
         },
+        supervisor.cancellation_token(),
     );
-    let web_server_task = spawn_health_server(appstate.clone());
-    let metrikk_task = spawn_metrics_task(pg_pool.clone());
+    supervisor.add_task("KafkaConsumer", kafka_consumer_task);
+    let router = axum_health::routes(Arc::new(health)).merge(admin_routes(admin_state, auth_state));
+    let web_server_task = spawn_web_server(router, supervisor.cancellation_token());
+    supervisor.add_task("Webserver", web_server_task);
+    let metrikk_task = spawn_metrics_task(pg_pool.clone(), supervisor.cancellation_token());
+    supervisor.add_task("Metrikker", metrikk_task);
+    let health_task = dependency_health.spawn_refresh_task(supervisor.cancellation_token());
+    supervisor.add_task("Helsesjekker", health_task);
 
     appstate.set_has_started(true);
 
-    let result: Result<()> = tokio::select! {
-        result = web_server_task => match result {
-            Ok(Ok(())) => { tracing::info!("Web server exited successfully"); Ok(()) }
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(ServerError::ThreadSpawn.into()),
-        },
-        result = kafka_consumer_task => match result {
-            Ok(Ok(())) => { tracing::info!("Kafka consumer stopped"); Ok(()) }
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(ServerError::ThreadSpawn.into()),
-        },
-        result = opprett_ekstern_oppgave_task => match result {
-            Ok(Ok(())) => { tracing::info!("Opprett oppgave task stopped"); Ok(()) }
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(ServerError::ThreadSpawn.into()),
-        },
-        result = metrikk_task => match result {
-            Ok(()) => { tracing::warn!("Metrikk task stoppet uventet"); Ok(()) }
-            Err(join_error) => { tracing::warn!(error = %join_error, "Metrikk task panicked"); Ok(()) }
-        },
-    };
+    let result = supervisor.run().await;
 
     appstate.set_is_alive(false);
     let _ = pg_pool.close().await;
@@ -0,0 +1,29 @@
+use crate::avstemming_task::Avvik;
+use prometheus::{register_counter_vec, CounterVec};
+use std::sync::LazyLock;
+use strum::IntoEnumIterator;
+
+static AVSTEMMING_AVVIK: LazyLock<CounterVec> = LazyLock::new(|| {
+    let counter = register_counter_vec!(
+        "veileder_oppgave_avstemming_avvik_total",
+        "Antall avvik mellom lokale oppgaver og Oppgave API funnet ved avstemming",
+        &["avvik", "dry_run"]
+    )
+    .expect("Failed to register veileder_oppgave_avstemming_avvik_total counter");
+    for avvik in Avvik::iter() {
+        for dry_run in [true, false] {
+            counter.with_label_values(&[&avvik.to_string(), &dry_run.to_string()]);
+        }
+    }
+    counter
+});
+
+pub fn init() {
+    LazyLock::force(&AVSTEMMING_AVVIK);
+}
+
+pub fn inkrement(avvik: &Avvik, dry_run: bool) {
+    AVSTEMMING_AVVIK
+        .with_label_values(&[&avvik.to_string(), &dry_run.to_string()])
+        .inc();
+}
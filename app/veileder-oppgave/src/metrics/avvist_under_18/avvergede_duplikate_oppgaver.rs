@@ -1,3 +1,4 @@
+use super::cutoff::forste_hele_dag;
 use crate::domain::hendelse_logg_status::HendelseLoggStatus::OppgaveFinnesAllerede;
 use crate::domain::oppgave_type::OppgaveType;
 use anyhow::Result;
@@ -27,16 +28,28 @@ async fn hent_antall_duplikater_avverget(
     let antall: i64 = sqlx::query_scalar(
         //language=PostgreSQL
         r#"
-        SELECT COUNT(*)
-        FROM oppgave_hendelse_logg ohl
-        JOIN oppgaver o ON o.id = ohl.oppgave_id
-        WHERE ohl.status = $1
-          AND ohl.tidspunkt >= $2
-          AND o.type = $3
+        SELECT (
+            (
+                SELECT COALESCE(SUM(antall), 0)
+                FROM metrikk_hendelser_per_dag
+                WHERE status = $1
+                  AND dato >= $3
+                  AND type = $4
+            ) + (
+                SELECT COUNT(*)
+                FROM oppgave_hendelse_logg ohl
+                JOIN oppgaver o ON o.id = ohl.oppgave_id
+                WHERE ohl.status = $1
+                  AND ohl.tidspunkt >= $2
+                  AND ohl.tidspunkt < $3
+                  AND o.type = $4
+            )
+        )::BIGINT
         "#,
     )
     .bind(OppgaveFinnesAllerede.to_string())
     .bind(fra_tidspunkt)
+    .bind(forste_hele_dag(fra_tidspunkt))
     .bind(OppgaveType::AvvistUnder18.to_string())
     .fetch_one(&mut **transaction)
     .await?;
@@ -47,6 +60,7 @@ async fn hent_antall_duplikater_avverget(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::metrikk_functions::komprimer_metrikker;
     use crate::db::oppgave_functions::{lagre_oppgave, oppdater_hendelse_logg};
     use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
     use crate::domain::hendelse_logg_status::HendelseLoggStatus;
@@ -84,6 +98,7 @@ mod tests {
         tx.commit().await?;
 
         let mut tx = pg_pool.begin().await?;
+        komprimer_metrikker(&mut tx).await?;
         let cutoff = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
         let antall = hent_antall_duplikater_avverget(cutoff, &mut tx).await?;
 
@@ -92,6 +107,16 @@ mod tests {
             "Skal kun telle OPPGAVE_FINNES_ALLEREDE for AVVIST_UNDER_18 etter cutoff"
         );
 
+        // Cutoff midt på dagen: hendelser tidligere samme dag skal ikke telles
+        let midt_paa_dagen = etter_cutoff + Duration::hours(12);
+        oppdater_hendelse_logg(avvist_oppgave_id, HendelseLoggEntry::new(OppgaveFinnesAllerede, String::new(), midt_paa_dagen - Duration::hours(2)), &mut tx).await?;
+        oppdater_hendelse_logg(avvist_oppgave_id, HendelseLoggEntry::new(OppgaveFinnesAllerede, String::new(), midt_paa_dagen + Duration::hours(2)), &mut tx).await?;
+        oppdater_hendelse_logg(avvist_oppgave_id, HendelseLoggEntry::new(OppgaveFinnesAllerede, String::new(), midt_paa_dagen + Duration::days(1)), &mut tx).await?;
+        komprimer_metrikker(&mut tx).await?;
+        let antall = hent_antall_duplikater_avverget(midt_paa_dagen, &mut tx).await?;
+
+        assert_eq!(antall, 2, "Skal bare telle hendelser fra og med cutoff-tidspunktet");
+
         Ok(())
     }
 }
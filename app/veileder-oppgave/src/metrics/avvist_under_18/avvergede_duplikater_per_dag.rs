@@ -1,3 +1,4 @@
+use super::cutoff::forste_hele_dag;
 use crate::domain::hendelse_logg_status::HendelseLoggStatus::OppgaveFinnesAllerede;
 use crate::domain::oppgave_type::OppgaveType;
 use anyhow::Result;
@@ -39,19 +40,30 @@ async fn hent_avvergede_duplikater_per_dag(
     let vis_siste_dager: i64 = 30;
     let rader = sqlx::query_as::<_, AvvergedeDuplikaterPerDag>(
         r#"
-        SELECT TO_CHAR(DATE(ohl.tidspunkt), 'YYYY-MM-DD') AS dato, COUNT(*) AS antall
-        FROM oppgave_hendelse_logg ohl
-        JOIN oppgaver o ON o.id = ohl.oppgave_id
-        WHERE ohl.status = $1
-          AND ohl.tidspunkt >= $2
-          AND o.type = $3
-        GROUP BY DATE(ohl.tidspunkt)
-        ORDER BY DATE(ohl.tidspunkt) DESC
-        LIMIT $4
+        SELECT TO_CHAR(per_dag.dato, 'YYYY-MM-DD') AS dato, per_dag.antall
+        FROM (
+            SELECT dato, antall
+            FROM metrikk_hendelser_per_dag
+            WHERE status = $1
+              AND dato >= $3
+              AND type = $4
+            UNION ALL
+            SELECT DATE(ohl.tidspunkt), COUNT(*)
+            FROM oppgave_hendelse_logg ohl
+            JOIN oppgaver o ON o.id = ohl.oppgave_id
+            WHERE ohl.status = $1
+              AND ohl.tidspunkt >= $2
+              AND ohl.tidspunkt < $3
+              AND o.type = $4
+            GROUP BY DATE(ohl.tidspunkt)
+        ) AS per_dag
+        ORDER BY per_dag.dato DESC
+        LIMIT $5
         "#,
     )
     .bind(OppgaveFinnesAllerede.to_string())
     .bind(fra_tidspunkt)
+    .bind(forste_hele_dag(fra_tidspunkt))
     .bind(OppgaveType::AvvistUnder18.to_string())
     .bind(vis_siste_dager)
     .fetch_all(&mut **transaction)
@@ -63,6 +75,7 @@ async fn hent_avvergede_duplikater_per_dag(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::metrikk_functions::komprimer_metrikker;
     use crate::db::oppgave_functions::{lagre_oppgave, oppdater_hendelse_logg};
     use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
     use crate::domain::hendelse_logg_status::HendelseLoggStatus;
@@ -100,6 +113,7 @@ mod tests {
         tx.commit().await?;
 
         let mut tx = pg_pool.begin().await?;
+        komprimer_metrikker(&mut tx).await?;
         let cutoff = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
         let rader = hent_avvergede_duplikater_per_dag(cutoff, &mut tx).await?;
 
@@ -115,6 +129,17 @@ mod tests {
         assert_eq!(avvergede_duplikater_forste_dag.antall, 2);
         assert_eq!(avvergede_duplikater_andre_dag.antall, 1);
 
+        // Cutoff midt på første dag: bare hendelsen etter cutoff-tidspunktet telles den dagen
+        oppdater_hendelse_logg(avvist_oppgave_id, HendelseLoggEntry::new(OppgaveFinnesAllerede, String::new(), første_dag + Duration::hours(4)), &mut tx).await?;
+        komprimer_metrikker(&mut tx).await?;
+        let rader = hent_avvergede_duplikater_per_dag(første_dag + Duration::hours(1), &mut tx).await?;
+
+        assert_eq!(rader.len(), 2);
+        assert_eq!(rader[0].dato, "2026-03-16");
+        assert_eq!(rader[0].antall, 1);
+        assert_eq!(rader[1].dato, "2026-03-15");
+        assert_eq!(rader[1].antall, 1);
+
         Ok(())
     }
 }
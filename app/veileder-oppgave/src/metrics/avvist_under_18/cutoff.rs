@@ -1,6 +1,13 @@
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, Days, NaiveDate, TimeZone, Utc};
 
 /// Data før denne datoen er upålitelig pga. en bug i AvvistUnder18-håndteringen.
 pub fn cutoff_date() -> DateTime<Utc> {
     Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap()
 }
+
+/// Første hele dag etter dagen `fra_tidspunkt` ligger i. Fra og med denne dagen kan antall per dag
+/// leses fra `metrikk_hendelser_per_dag`, mens resten av dagen `fra_tidspunkt` ligger i telles eksakt
+/// fra hendelse-loggen.
+pub(super) fn forste_hele_dag(fra_tidspunkt: DateTime<Utc>) -> NaiveDate {
+    fra_tidspunkt.date_naive() + Days::new(1)
+}
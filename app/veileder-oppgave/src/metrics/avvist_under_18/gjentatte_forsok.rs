@@ -1,4 +1,3 @@
-use crate::domain::hendelse_logg_status::HendelseLoggStatus::OppgaveFinnesAllerede;
 use crate::domain::oppgave_type::OppgaveType;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -29,18 +28,14 @@ async fn hent_gjentatte_forsok_gjennomsnitt(
         r#"
         SELECT CAST(AVG(antall_forsok) AS FLOAT8)
         FROM (
-            SELECT o.identitetsnummer, COUNT(ohl.id) AS antall_forsok
-            FROM oppgaver o
-            LEFT JOIN oppgave_hendelse_logg ohl
-                ON ohl.oppgave_id = o.id
-                AND ohl.status = $1
-            WHERE o.tidspunkt >= $2
-              AND o.type = $3
-            GROUP BY o.identitetsnummer
-        ) AS antall_forsok_per_person
+            SELECT identitetsnummer, SUM(antall_forsok) AS antall_forsok
+            FROM metrikk_forsok_per_oppgave
+            WHERE tidspunkt >= $1
+              AND type = $2
+            GROUP BY identitetsnummer
+        ) AS forsok_per_person
         "#,
     )
-    .bind(OppgaveFinnesAllerede.to_string())
     .bind(fra_tidspunkt)
     .bind(OppgaveType::AvvistUnder18.to_string())
     .fetch_one(&mut **transaction)
@@ -55,6 +50,7 @@ mod tests {
     use crate::db::oppgave_functions::{lagre_oppgave, oppdater_hendelse_logg};
     use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
     use crate::domain::hendelse_logg_status::HendelseLoggStatus;
+    use crate::domain::hendelse_logg_status::HendelseLoggStatus::OppgaveFinnesAllerede;
     use crate::domain::oppgave::Oppgave;
     use crate::domain::oppgave_status::OppgaveStatus::Ubehandlet;
     use crate::domain::oppgave_type::OppgaveType::{AvvistUnder18, VurderOppholdsstatus};
@@ -97,14 +93,28 @@ mod tests {
         let oppgave_id_3 = lagre_oppgave(&avvist_foer_cutoff, &mut tx).await?;
         oppdater_hendelse_logg(oppgave_id_3, HendelseLoggEntry::new(OppgaveFinnesAllerede, String::new(), tidspunkt_foer_cutoff), &mut tx).await?;
 
+        // Person 1 har også en oppgave før cutoff — forsøkene på den skal ikke telles
+        let avvist_foer_cutoff_person_1 = Oppgave::new(Uuid::new_v4(), AvvistUnder18, Ubehandlet, vec![], ArbeidssoekerId(1), Identitetsnummer::new("12345678901".to_string()).unwrap(), tidspunkt_foer_cutoff);
+        let oppgave_id_4 = lagre_oppgave(&avvist_foer_cutoff_person_1, &mut tx).await?;
+        for _ in 0..3 {
+            oppdater_hendelse_logg(oppgave_id_4, HendelseLoggEntry::new(OppgaveFinnesAllerede, String::new(), tidspunkt_foer_cutoff), &mut tx).await?;
+        }
+
+        // Person 2 får en ny oppgave etter cutoff med fire forsøk — summeres med den første
+        let ny_oppgave_person_2 = Oppgave::new(Uuid::new_v4(), AvvistUnder18, Ubehandlet, vec![], ArbeidssoekerId(2), Identitetsnummer::new("12345678902".to_string()).unwrap(), tidspunkt_etter_cutoff);
+        let oppgave_id_5 = lagre_oppgave(&ny_oppgave_person_2, &mut tx).await?;
+        for _ in 0..4 {
+            oppdater_hendelse_logg(oppgave_id_5, HendelseLoggEntry::new(OppgaveFinnesAllerede, String::new(), tidspunkt_etter_cutoff), &mut tx).await?;
+        }
+
         tx.commit().await?;
 
         let mut tx = pg_pool.begin().await?;
         let cutoff = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
         let gjennomsnitt = hent_gjentatte_forsok_gjennomsnitt(cutoff, &mut tx).await?;
 
-        // Person 1: 2 forsøk, person 2: 0 forsøk → gjennomsnitt = 1.0 (VurderOppholdsstatus filtreres bort)
-        assert_eq!(gjennomsnitt, 1.0);
+        // Person 1: 2 forsøk, person 2: 0 + 4 forsøk → gjennomsnitt = 3.0 (VurderOppholdsstatus og oppgaver før cutoff filtreres bort)
+        assert_eq!(gjennomsnitt, 3.0);
 
         Ok(())
     }
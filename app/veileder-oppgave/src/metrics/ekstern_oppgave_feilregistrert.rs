@@ -16,8 +16,8 @@ pub async fn oppdater(transaction: &mut Transaction<'_, Postgres>) -> Result<()>
     let antall: i64 = sqlx::query_scalar(
         //language=PostgreSQL
         r#"
-        SELECT COUNT(*)
-        FROM oppgave_hendelse_logg
+        SELECT COALESCE(SUM(antall), 0)::BIGINT
+        FROM metrikk_hendelser_per_dag
         WHERE status = $1
         "#,
     )
@@ -0,0 +1,55 @@
+use crate::domain::oppgave_status::OppgaveStatus::Opprettet;
+use anyhow::Result;
+use prometheus::{register_gauge_vec, GaugeVec};
+use sqlx::{FromRow, Postgres, Transaction};
+use std::sync::LazyLock;
+
+static AAPNE_OPPGAVER_PER_ENHET: LazyLock<GaugeVec> = LazyLock::new(|| {
+    register_gauge_vec!(
+        "veileder_oppgave_aapne_oppgaver_per_enhet",
+        "Antall åpne oppgaver i Oppgave API per enhet og om de er tildelt en veileder",
+        &["enhetsnr", "type", "tildelt"]
+    )
+    .expect("Failed to register veileder_oppgave_aapne_oppgaver_per_enhet gauge")
+});
+
+pub async fn oppdater(transaction: &mut Transaction<'_, Postgres>) -> Result<()> {
+    let rader = hent_antall(transaction).await?;
+    AAPNE_OPPGAVER_PER_ENHET.reset();
+    for rad in rader {
+        AAPNE_OPPGAVER_PER_ENHET
+            .with_label_values(&[
+                rad.enhetsnr.as_deref().unwrap_or("ukjent"),
+                &rad.type_,
+                &rad.tildelt.to_string(),
+            ])
+            .set(rad.antall as f64);
+    }
+    Ok(())
+}
+
+#[derive(Debug, FromRow)]
+struct TilordningAntall {
+    enhetsnr: Option<String>,
+    #[sqlx(rename = "type")]
+    type_: String,
+    tildelt: bool,
+    antall: i64,
+}
+
+async fn hent_antall(transaction: &mut Transaction<'_, Postgres>) -> Result<Vec<TilordningAntall>> {
+    let rader = sqlx::query_as::<_, TilordningAntall>(
+        //language=PostgreSQL
+        r#"
+        SELECT t.enhetsnr, o.type, t.nav_ident IS NOT NULL AS tildelt, COUNT(*) AS antall
+        FROM oppgaver o
+        JOIN ekstern_oppgave_tilstand t ON t.oppgave_id = o.id
+        WHERE o.status = $1
+        GROUP BY t.enhetsnr, o.type, tildelt
+        "#,
+    )
+    .bind(Opprettet.to_string())
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(rader)
+}
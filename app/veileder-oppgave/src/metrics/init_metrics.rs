@@ -1,7 +1,9 @@
+use crate::metrics::avstemming_avvik;
 use crate::metrics::ekstern_oppgave_opprettelse_feil;
 use crate::metrics::kriterier_oppfylt;
 
 pub fn init_metrics() {
+    avstemming_avvik::init();
     ekstern_oppgave_opprettelse_feil::init();
     kriterier_oppfylt::init();
 }
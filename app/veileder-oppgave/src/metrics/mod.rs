@@ -1,6 +1,8 @@
+pub mod avstemming_avvik;
 pub mod avvist_under_18;
 pub mod ekstern_oppgave_feilregistrert;
 pub mod ekstern_oppgave_opprettelse_feil;
+pub mod ekstern_tilordning;
 mod init_metrics;
 pub mod kriterier_oppfylt;
 pub mod metrics_task;
@@ -48,9 +48,9 @@ async fn hent_antall(
 ) -> Result<Vec<OppgaveStatusAntall>> {
     let rader = sqlx::query_as::<_, OppgaveStatusAntall>(
         r#"
-        SELECT status, type, COUNT(*) as antall
-        FROM oppgaver
-        GROUP BY status, type
+        SELECT status, type, antall
+        FROM metrikk_oppgaver_per_status
+        WHERE antall > 0
         "#,
     )
     .fetch_all(&mut **transaction)
@@ -62,6 +62,7 @@ async fn hent_antall(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::metrikk_functions::komprimer_metrikker;
     use crate::db::oppgave_functions::lagre_oppgave;
     use crate::domain::oppgave::Oppgave;
     use crate::domain::oppgave_status::OppgaveStatus::{Ferdigbehandlet, Ubehandlet};
@@ -91,6 +92,7 @@ mod tests {
         tx.commit().await?;
 
         let mut tx = pg_pool.begin().await?;
+        komprimer_metrikker(&mut tx).await?;
         let rader = hent_antall(&mut tx).await?;
 
         let avvist_ubehandlet = rader
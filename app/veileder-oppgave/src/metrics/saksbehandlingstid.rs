@@ -1,10 +1,9 @@
-use crate::domain::hendelse_logg_status::HendelseLoggStatus::{
-    EksternOppgaveFerdigstilt, EksternOppgaveOpprettet,
-};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use prometheus::{register_gauge_vec, GaugeVec};
+use paw_date_time::virkedager::{norsk_dato, virkedager_mellom};
+use prometheus::{register_gauge_vec, register_histogram_vec, GaugeVec, HistogramVec};
 use sqlx::{FromRow, Postgres, Transaction};
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
 static SAKSBEHANDLINGSTID: LazyLock<GaugeVec> = LazyLock::new(|| {
@@ -16,26 +15,135 @@ static SAKSBEHANDLINGSTID: LazyLock<GaugeVec> = LazyLock::new(|| {
     .expect("Failed to register veileder_oppgave_saksbehandlingstid_sekunder gauge")
 });
 
+static SAKSBEHANDLINGSTID_VIRKEDAGER: LazyLock<GaugeVec> = LazyLock::new(|| {
+    register_gauge_vec!(
+        "veileder_oppgave_saksbehandlingstid_virkedager",
+        "Gjennomsnittlig saksbehandlingstid per uke og type i virkedager (helger og helligdager telles ikke)",
+        &["uke", "type"]
+    )
+    .expect("Failed to register veileder_oppgave_saksbehandlingstid_virkedager gauge")
+});
+
+static SAKSBEHANDLINGSTID_PERSENTIL: LazyLock<GaugeVec> = LazyLock::new(|| {
+    register_gauge_vec!(
+        "veileder_oppgave_saksbehandlingstid_persentil_sekunder",
+        "Eksakte persentiler (p50, p90, p99) for saksbehandlingstid per uke og type i sekunder",
+        &["uke", "type", "persentil"]
+    )
+    .expect("Failed to register veileder_oppgave_saksbehandlingstid_persentil_sekunder gauge")
+});
+
+static SAKSBEHANDLINGSTID_FORDELING: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "veileder_oppgave_saksbehandlingstid_fordeling_sekunder",
+        "Fordeling av saksbehandlingstid per type i sekunder for oppgaver ferdigstilt de siste 30 ukene",
+        &["type"],
+        SAKSBEHANDLINGSTID_BUCKETS.to_vec()
+    )
+    .expect("Failed to register veileder_oppgave_saksbehandlingstid_fordeling_sekunder histogram")
+});
+
+const TIME: f64 = 3600.0;
+const DAG: f64 = 24.0 * TIME;
+const SAKSBEHANDLINGSTID_BUCKETS: [f64; 12] = [
+    TIME,
+    6.0 * TIME,
+    12.0 * TIME,
+    DAG,
+    2.0 * DAG,
+    3.0 * DAG,
+    5.0 * DAG,
+    7.0 * DAG,
+    14.0 * DAG,
+    21.0 * DAG,
+    30.0 * DAG,
+    60.0 * DAG,
+];
+
 pub async fn oppdater(
     fra_tidspunkt: DateTime<Utc>,
     transaction: &mut Transaction<'_, Postgres>,
 ) -> Result<()> {
+    slett_utenfor_vindu(transaction).await?;
+
     let saksbehandlingstider_per_uke = hent_saksbehandlingstid_per_uke(fra_tidspunkt, transaction).await?;
     SAKSBEHANDLINGSTID.reset();
+    SAKSBEHANDLINGSTID_PERSENTIL.reset();
     for rad in &saksbehandlingstider_per_uke {
         SAKSBEHANDLINGSTID
             .with_label_values(&[&rad.uke, &rad.type_])
             .set(rad.gjennomsnitt_sekunder);
+        for (persentil, sekunder) in [("p50", rad.p50_sekunder), ("p90", rad.p90_sekunder), ("p99", rad.p99_sekunder)] {
+            SAKSBEHANDLINGSTID_PERSENTIL
+                .with_label_values(&[&rad.uke, &rad.type_, persentil])
+                .set(sekunder);
+        }
+    }
+
+    let saksbehandlingstider = hent_saksbehandlingstider(fra_tidspunkt, transaction).await?;
+    // Histogrammet bygges på nytt fra tabellen hver gang, slik at alle poder rapporterer det samme
+    SAKSBEHANDLINGSTID_FORDELING.reset();
+    for saksbehandlingstid in &saksbehandlingstider {
+        SAKSBEHANDLINGSTID_FORDELING
+            .with_label_values(&[&saksbehandlingstid.type_])
+            .observe((saksbehandlingstid.ferdigstilt - saksbehandlingstid.opprettet).as_seconds_f64());
+    }
+
+    SAKSBEHANDLINGSTID_VIRKEDAGER.reset();
+    for ((uke, type_), gjennomsnitt) in gjennomsnitt_virkedager_per_uke(&saksbehandlingstider) {
+        SAKSBEHANDLINGSTID_VIRKEDAGER
+            .with_label_values(&[&uke, &type_])
+            .set(gjennomsnitt);
     }
     Ok(())
 }
 
+/// Gjennomsnittlig antall virkedager fra opprettet til ferdigstilt, per (uke, type). Virkedagene
+/// telles fra norske datoer, så en oppgave opprettet like etter midnatt havner på riktig dag.
+fn gjennomsnitt_virkedager_per_uke(saksbehandlingstider: &[Saksbehandlingstid]) -> BTreeMap<(String, String), f64> {
+    let mut virkedager_per_uke: BTreeMap<(String, String), Vec<u32>> = BTreeMap::new();
+    for saksbehandlingstid in saksbehandlingstider {
+        virkedager_per_uke
+            .entry((saksbehandlingstid.uke.clone(), saksbehandlingstid.type_.clone()))
+            .or_default()
+            .push(virkedager_mellom(
+                norsk_dato(saksbehandlingstid.opprettet),
+                norsk_dato(saksbehandlingstid.ferdigstilt),
+            ));
+    }
+    virkedager_per_uke
+        .into_iter()
+        .map(|(nokkel, virkedager)| {
+            let gjennomsnitt = virkedager.iter().sum::<u32>() as f64 / virkedager.len() as f64;
+            (nokkel, gjennomsnitt)
+        })
+        .collect()
+}
+
 #[derive(Debug, FromRow)]
 struct SaksbehandlingstidPerUke {
     uke: String,
     #[sqlx(rename = "type")]
     type_: String,
     gjennomsnitt_sekunder: f64,
+    p50_sekunder: f64,
+    p90_sekunder: f64,
+    p99_sekunder: f64,
+}
+
+/// `metrikk_saksbehandlingstid` har én rad per ferdigstilt oppgave. Bare de siste 30 ukene vises,
+/// så eldre rader slettes for å holde tabellen liten.
+async fn slett_utenfor_vindu(transaction: &mut Transaction<'_, Postgres>) -> Result<u64> {
+    let result = sqlx::query(
+        //language=PostgreSQL
+        r#"
+        DELETE FROM metrikk_saksbehandlingstid
+        WHERE ferdigstilt < NOW() - INTERVAL '30 weeks'
+        "#,
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
 }
 
 async fn hent_saksbehandlingstid_per_uke(
@@ -46,26 +154,54 @@ async fn hent_saksbehandlingstid_per_uke(
         //language=PostgreSQL
         r#"
         SELECT
-            TO_CHAR(DATE_TRUNC('week', ferdigstilt.tidspunkt), 'YYYY-MM-DD') AS uke,
-            o.type,
-            AVG(EXTRACT(EPOCH FROM (ferdigstilt.tidspunkt - eksternt_opprettet.tidspunkt)))::FLOAT8 AS gjennomsnitt_sekunder
-        FROM oppgave_hendelse_logg AS ferdigstilt
-        JOIN (
-            SELECT oppgave_id, MIN(tidspunkt) AS tidspunkt
-            FROM oppgave_hendelse_logg
-            WHERE status = $1
-            GROUP BY oppgave_id
-        ) AS eksternt_opprettet ON eksternt_opprettet.oppgave_id = ferdigstilt.oppgave_id
-        JOIN oppgaver o ON o.id = ferdigstilt.oppgave_id
-        WHERE ferdigstilt.status = $2
-          AND ferdigstilt.tidspunkt >= $3
-          AND ferdigstilt.tidspunkt >= NOW() - INTERVAL '30 weeks'
-        GROUP BY DATE_TRUNC('week', ferdigstilt.tidspunkt), o.type
-        ORDER BY DATE_TRUNC('week', ferdigstilt.tidspunkt) DESC
+            TO_CHAR(uke, 'YYYY-MM-DD') AS uke,
+            type,
+            AVG(EXTRACT(EPOCH FROM (ferdigstilt - opprettet)))::FLOAT8 AS gjennomsnitt_sekunder,
+            PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM (ferdigstilt - opprettet)))::FLOAT8 AS p50_sekunder,
+            PERCENTILE_CONT(0.9) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM (ferdigstilt - opprettet)))::FLOAT8 AS p90_sekunder,
+            PERCENTILE_CONT(0.99) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM (ferdigstilt - opprettet)))::FLOAT8 AS p99_sekunder
+        FROM metrikk_saksbehandlingstid
+        WHERE ferdigstilt >= $1
+          AND ferdigstilt >= NOW() - INTERVAL '30 weeks'
+        GROUP BY uke, type
+        ORDER BY uke DESC
+        "#,
+    )
+    .bind(fra_tidspunkt)
+    .fetch_all(&mut **transaction)
+    .await?;
+
+    Ok(rader)
+}
+
+#[derive(Debug, FromRow)]
+struct Saksbehandlingstid {
+    uke: String,
+    #[sqlx(rename = "type")]
+    type_: String,
+    opprettet: DateTime<Utc>,
+    ferdigstilt: DateTime<Utc>,
+}
+
+/// Samme utvalg som [`hent_saksbehandlingstid_per_uke`], men én rad per oppgave slik at
+/// virkedager kan regnes ut med norsk kalender.
+async fn hent_saksbehandlingstider(
+    fra_tidspunkt: DateTime<Utc>,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<Saksbehandlingstid>> {
+    let rader = sqlx::query_as::<_, Saksbehandlingstid>(
+        //language=PostgreSQL
+        r#"
+        SELECT
+            TO_CHAR(uke, 'YYYY-MM-DD') AS uke,
+            type,
+            opprettet AT TIME ZONE 'UTC' AS opprettet,
+            ferdigstilt AT TIME ZONE 'UTC' AS ferdigstilt
+        FROM metrikk_saksbehandlingstid
+        WHERE ferdigstilt >= $1
+          AND ferdigstilt >= NOW() - INTERVAL '30 weeks'
         "#,
     )
-    .bind(EksternOppgaveOpprettet.to_string())
-    .bind(EksternOppgaveFerdigstilt.to_string())
     .bind(fra_tidspunkt)
     .fetch_all(&mut **transaction)
     .await?;
@@ -78,6 +214,9 @@ mod tests {
     use super::*;
     use crate::db::oppgave_functions::{lagre_oppgave, oppdater_hendelse_logg};
     use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
+    use crate::domain::hendelse_logg_status::HendelseLoggStatus::{
+        EksternOppgaveFerdigstilt, EksternOppgaveOpprettet,
+    };
     use crate::domain::oppgave::Oppgave;
     use crate::domain::oppgave_status::OppgaveStatus::Ubehandlet;
     use crate::domain::oppgave_type::OppgaveType::{AvvistUnder18, VurderOppholdsstatus};
@@ -182,8 +321,52 @@ mod tests {
             .expect("Skal ha uke2 AvvistUnder18 med ~1 dag saksbehandlingstid");
         let _ = uke2_avvist;
 
+        // Uke 1: A (2 dager) og B (4 dager) gir eksakt median 3 dager og p90 3,8 dager
+        let uke1_avvist = avvist_rader
+            .iter()
+            .find(|r| (r.gjennomsnitt_sekunder - Duration::days(3).num_seconds() as f64).abs() < 1.0)
+            .expect("Skal ha uke1 AvvistUnder18 med ~3 dager saksbehandlingstid");
+        assert!((uke1_avvist.p50_sekunder - Duration::days(3).num_seconds() as f64).abs() < 1.0);
+        assert!((uke1_avvist.p90_sekunder - Duration::hours(91).num_seconds() as f64 - 12.0 * 60.0).abs() < 1.0);
+
+        // Histogrammet bygges fra de samme radene: tre AvvistUnder18-oppgaver på til sammen 2 + 4 + 1 dager
+        oppdater(cutoff, &mut tx).await?;
+        let avvist = SAKSBEHANDLINGSTID_FORDELING.with_label_values(&[&AvvistUnder18.to_string()]);
+        assert_eq!(avvist.get_sample_count(), 3);
+        assert_eq!(avvist.get_sample_sum(), Duration::days(7).num_seconds() as f64);
+
         Ok(())
     }
-}
 
+    #[test]
+    fn test_gjennomsnitt_virkedager_per_uke() {
+        let tidspunkt = |dato: &str| -> DateTime<Utc> { format!("{dato}T10:00:00Z").parse().unwrap() };
+        let saksbehandlingstid = |type_: &str, opprettet: &str, ferdigstilt: &str| Saksbehandlingstid {
+            uke: "2026-04-06".to_string(),
+            type_: type_.to_string(),
+            opprettet: tidspunkt(opprettet),
+            ferdigstilt: tidspunkt(ferdigstilt),
+        };
+        let saksbehandlingstider = vec![
+            // Onsdag før påske til onsdag etter: 2 virkedager (skjærtorsdag, langfredag, helg og 2. påskedag telles ikke)
+            saksbehandlingstid("AVVIST_UNDER_18", "2026-04-01", "2026-04-08"),
+            // Tirsdag til fredag: 3 virkedager
+            saksbehandlingstid("AVVIST_UNDER_18", "2026-04-07", "2026-04-10"),
+            saksbehandlingstid("VURDER_OPPHOLDSSTATUS", "2026-04-09", "2026-04-09"),
+            // Opprettet 00:30 onsdag norsk tid (tirsdag i UTC) til fredag: 2 virkedager
+            Saksbehandlingstid {
+                uke: "2026-04-06".to_string(),
+                type_: "AVVIST_STOPP_AV_PERIODE".to_string(),
+                opprettet: "2026-04-07T22:30:00Z".parse().unwrap(),
+                ferdigstilt: tidspunkt("2026-04-10"),
+            },
+        ];
 
+        let gjennomsnitt = gjennomsnitt_virkedager_per_uke(&saksbehandlingstider);
+
+        assert_eq!(gjennomsnitt.len(), 3);
+        assert_eq!(gjennomsnitt[&("2026-04-06".to_string(), "AVVIST_UNDER_18".to_string())], 2.5);
+        assert_eq!(gjennomsnitt[&("2026-04-06".to_string(), "VURDER_OPPHOLDSSTATUS".to_string())], 0.0);
+        assert_eq!(gjennomsnitt[&("2026-04-06".to_string(), "AVVIST_STOPP_AV_PERIODE".to_string())], 2.0);
+    }
+}
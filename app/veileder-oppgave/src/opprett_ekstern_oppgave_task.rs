@@ -1,19 +1,23 @@
 use crate::client::oppgave_client::{OppgaveApiClient, OppgaveApiError};
-use crate::client::opprett_oppgave_request::create_oppgave_request;
-use crate::config::ApplicationConfig;
+use crate::client::oppgave_dto::OppgaveDto;
+use crate::client::opprett_oppgave_request::{OpprettOppgaveRequest, create_oppgave_request};
+use crate::config::{ApplicationConfig, OpprettOppgaverTaskConfig};
 use crate::db::oppgave_functions::{
     bytt_oppgave_status, hent_de_eldste_ubehandlede_oppgavene, oppdater_hendelse_logg,
-    oppdater_oppgave_med_ekstern_id,
+    oppdater_oppgave_med_ekstern_id, registrer_feilet_forsok, utsett_oppgave,
 };
 use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
 use crate::domain::hendelse_logg_status::HendelseLoggStatus::{
-    EksternOppgaveOpprettelseFeilet, EksternOppgaveOpprettet,
+    EksternOppgaveOpprettelseFeilet, EksternOppgaveOpprettelseGittOpp, EksternOppgaveOpprettet,
 };
 use crate::domain::oppgave::Oppgave;
-use crate::domain::oppgave_status::OppgaveStatus::{Opprettet, Ubehandlet};
+use crate::domain::oppgave_status::OppgaveStatus::{GittOpp, Opprettet, Ubehandlet};
+use crate::domain::oppgavetyper::fra_tidspunkt_per_type;
+use crate::domain::retry_policy::RetryPolicy;
 use crate::metrics::ekstern_oppgave_opprettelse_feil::inkrement_ekstern_oppgave_opprettelse_feil;
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
+use paw_app_config::watch::ConfigWatch;
 use rand::prelude::*;
 use sqlx::PgPool;
 use std::num::NonZeroU32;
@@ -21,17 +25,22 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::JoinHandle;
 use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
 use crate::domain::ekstern_oppgave_id::EksternOppgaveId;
 
 pub fn spawn_ekstern_oppgave_task(
     db_pool: PgPool,
     oppgave_api_client: Arc<OppgaveApiClient>,
     app_config: ApplicationConfig,
+    task_config: ConfigWatch<OpprettOppgaverTaskConfig>,
+    cancellation_token: CancellationToken,
 ) -> JoinHandle<Result<()>> {
     tokio::spawn(kjør_processing_loop(
         db_pool,
         oppgave_api_client,
         app_config,
+        task_config,
+        cancellation_token,
     ))
 }
 
@@ -39,18 +48,35 @@ async fn kjør_processing_loop(
     db_pool: PgPool,
     oppgave_api_client: Arc<OppgaveApiClient>,
     app_config: ApplicationConfig,
+    mut task_config: ConfigWatch<OpprettOppgaverTaskConfig>,
+    cancellation_token: CancellationToken,
 ) -> Result<()> {
-    let task_batch_size = *app_config.opprett_oppgaver_task_batch_size;
-    let opprett_avvist_under_18_oppgaver_fra_tidspunkt =
-        *app_config.opprett_avvist_under_18_oppgaver_fra_tidspunkt;
-    let task_interval_minutes = *app_config.opprett_oppgaver_task_interval_minutes;
-    let task_interval = Duration::from_mins(task_interval_minutes.get() as u64);
-    let mut interval = interval(task_interval);
+    let mut task_interval_minutes = *task_config.current().opprett_oppgaver_task_interval_minutes;
+    let mut interval = interval(task_interval(task_interval_minutes));
     loop {
-        interval.tick().await;
+        tokio::select! {
+            _ = cancellation_token.cancelled() => break,
+            _ = task_config.changed() => {
+                let nytt_interval_minutes = *task_config.current().opprett_oppgaver_task_interval_minutes;
+                if nytt_interval_minutes != task_interval_minutes {
+                    tracing::info!(
+                        "Endrer intervall for opprett oppgave task fra {} til {} minutter",
+                        task_interval_minutes,
+                        nytt_interval_minutes
+                    );
+                    task_interval_minutes = nytt_interval_minutes;
+                    interval = tokio::time::interval(task_interval(task_interval_minutes));
+                }
+                continue;
+            }
+            _ = interval.tick() => {}
+        }
+        let task_batch_size = *task_config.current().opprett_oppgaver_task_batch_size;
+        let retry_policy = task_config.current().retry_policy();
         if let Err(e) = prosesser_ubehandlede_oppgaver(
-            opprett_avvist_under_18_oppgaver_fra_tidspunkt,
+            &app_config,
             task_batch_size,
+            &retry_policy,
             oppgave_api_client.clone(),
             db_pool.clone(),
         )
@@ -59,33 +85,75 @@ async fn kjør_processing_loop(
             tracing::error!("Feil i prosesseringsloop: {}", e);
         }
     }
+    tracing::info!("Opprett oppgave task stoppet");
+    Ok(())
+}
+
+fn task_interval(minutes: NonZeroU32) -> Duration {
+    Duration::from_mins(minutes.get() as u64)
 }
 
+/// Henter en batch ubehandlede oppgaver, med `fra_tidspunkt` fra definisjonen til hver type, og
+/// prosesserer dem.
 pub async fn prosesser_ubehandlede_oppgaver(
-    fra_tidspunkt: DateTime<Utc>,
+    app_config: &ApplicationConfig,
     batch_size: NonZeroU32,
+    retry_policy: &RetryPolicy,
     oppgave_api_client: Arc<OppgaveApiClient>,
     db_pool: PgPool,
 ) -> Result<()> {
     let mut tx = db_pool.begin().await?;
-    let mut oppgaver =
-        hent_de_eldste_ubehandlede_oppgavene(batch_size, fra_tidspunkt, &mut tx).await?;
+    let mut oppgaver = hent_de_eldste_ubehandlede_oppgavene(
+        batch_size,
+        &fra_tidspunkt_per_type(app_config),
+        &mut tx,
+    )
+    .await?;
     oppgaver.shuffle(&mut rand::rng());
     tx.commit().await?;
 
+    prosesser_oppgaver(
+        &db_pool,
+        &oppgave_api_client,
+        app_config,
+        retry_policy,
+        &oppgaver,
+    )
+    .await;
+    Ok(())
+}
+
+/// Prosesserer oppgavene én etter én. En feilet oppgave stopper ikke resten.
+pub async fn prosesser_oppgaver(
+    db_pool: &PgPool,
+    oppgave_api_client: &OppgaveApiClient,
+    app_config: &ApplicationConfig,
+    retry_policy: &RetryPolicy,
+    oppgaver: &[Oppgave],
+) {
     for oppgave in oppgaver {
-        if let Err(e) = prosesser_oppgave(&db_pool, &oppgave_api_client, &oppgave).await {
+        if let Err(e) = prosesser_oppgave(
+            db_pool,
+            oppgave_api_client,
+            app_config,
+            retry_policy,
+            oppgave,
+        )
+        .await
+        {
             tracing::error!("Feil ved prosessering av oppgave {}: {}", oppgave.id(), e);
         }
     }
-    Ok(())
 }
 
 async fn prosesser_oppgave(
     db_pool: &PgPool,
     oppgave_client: &OppgaveApiClient,
+    app_config: &ApplicationConfig,
+    retry_policy: &RetryPolicy,
     oppgave: &Oppgave,
 ) -> Result<()> {
+    let request = create_oppgave_request(oppgave, app_config)?;
     let mut tx = db_pool.begin().await?;
 
     // CAS: prøv å ta eierskap over oppgaven
@@ -95,38 +163,33 @@ async fn prosesser_oppgave(
         return Ok(());
     }
 
-    warning_ved_gjentatte_feil(oppgave);
-
-    let opprett_oppgave_request =
-        create_oppgave_request(oppgave.identitetsnummer.clone(), &oppgave.type_);
-    let response = oppgave_client
-        .opprett_oppgave(&opprett_oppgave_request)
-        .await;
+    let response = opprett_eller_finn_ekstern_oppgave(oppgave_client, oppgave, &request).await;
 
     match response {
-        Ok(oppgave_dto) => {
+        Ok((oppgave_dto, fantes_fra_foer)) => {
             oppdater_oppgave_med_ekstern_id(oppgave.id(), EksternOppgaveId::from(oppgave_dto.id), &mut tx).await?;
-            let hendelse_logg = HendelseLoggEntry::new(
-                EksternOppgaveOpprettet,
-                "Ekstern oppgave_id opprettet".to_string(),
-                Utc::now(),
-            );
+            let melding = if fantes_fra_foer {
+                format!("Eksisterende ekstern oppgave {} tatt i bruk", oppgave_dto.id)
+            } else {
+                "Ekstern oppgave_id opprettet".to_string()
+            };
+            let hendelse_logg = HendelseLoggEntry::new(EksternOppgaveOpprettet, melding, Utc::now());
             oppdater_hendelse_logg(oppgave.id(), hendelse_logg, &mut tx).await?;
             tracing::info!("Oppgave {} opprettet i Oppgave API", oppgave.id());
             tx.commit().await?;
         }
         Err(error) => {
             inkrement_ekstern_oppgave_opprettelse_feil(&error);
-            if !bytt_oppgave_status(oppgave.id(), Opprettet, Ubehandlet, &mut tx).await? {
+            let Some(antall_forsok) = registrer_feilet_forsok(oppgave.id(), &mut tx).await? else {
                 tracing::error!(
                     oppgave_id = %oppgave.id(),
                     "Oppgave sitter fast i status Opprettet uten ekstern_id — manuell gjennomgang nødvendig"
                 );
                 return Err(anyhow::anyhow!(
-                    "Kunne ikke sette oppgave {} tilbake til Ubehandlet",
+                    "Kunne ikke registrere feilet forsøk for oppgave {}",
                     oppgave.id()
                 ));
-            }
+            };
 
             let error_melding = match &error {
                 OppgaveApiError::ApiError { status, message } => {
@@ -141,17 +204,39 @@ async fn prosesser_oppgave(
                 OppgaveApiError::TokenError(e) => {
                     format!("Token-feil ved kall til Oppgave API: {}", e)
                 }
+                OppgaveApiError::IkkeFunnetEtterKonflikt { .. } => {
+                    format!("Feil ved opprettelse av oppgave i Oppgave API: {}", error)
+                }
             };
             tracing::error!(
-                "Feil ved opprettelse av oppgave {} i Oppgave API: {}",
+                "Feil ved opprettelse av oppgave {} i Oppgave API (forsøk {}): {}",
                 oppgave.id(),
+                antall_forsok,
                 error_melding
             );
-
             let hendelse_logg = HendelseLoggEntry::new(
                 EksternOppgaveOpprettelseFeilet, error_melding, Utc::now(),
             );
             oppdater_hendelse_logg(oppgave.id(), hendelse_logg, &mut tx).await?;
+
+            let kan_proeves_igjen = error.kan_proeves_igjen();
+            if retry_policy.skal_gi_opp(antall_forsok, kan_proeves_igjen) {
+                bytt_oppgave_status(oppgave.id(), Opprettet, GittOpp, &mut tx).await?;
+                let melding = if kan_proeves_igjen {
+                    format!("Gir opp etter {} forsøk", antall_forsok)
+                } else {
+                    "Gir opp etter permanent feil fra Oppgave API".to_string()
+                };
+                tracing::warn!(oppgave_id = %oppgave.id(), "{}", melding);
+                let hendelse_logg = HendelseLoggEntry::new(
+                    EksternOppgaveOpprettelseGittOpp, melding, Utc::now(),
+                );
+                oppdater_hendelse_logg(oppgave.id(), hendelse_logg, &mut tx).await?;
+            } else {
+                let neste_forsok = Utc::now()
+                    + chrono::Duration::from_std(retry_policy.ventetid(antall_forsok))?;
+                utsett_oppgave(oppgave.id(), neste_forsok, &mut tx).await?;
+            }
             tx.commit().await?;
         }
     }
@@ -159,19 +244,26 @@ async fn prosesser_oppgave(
     Ok(())
 }
 
-fn warning_ved_gjentatte_feil(oppgave: &Oppgave) {
-    let antall_tidligere_feil = oppgave
-        .hendelse_logg
-        .iter()
-        .filter(|innslag| innslag.status == EksternOppgaveOpprettelseFeilet)
-        .count();
-
-    if antall_tidligere_feil >= 5 {
-        tracing::warn!(
-            oppgave_id = %oppgave.id(),
-            antall_feil = antall_tidligere_feil,
-            "Oppgave har feilet gjentatte ganger mot Oppgave API — mulig kork"
-        );
+/// Oppretter oppgaven i Oppgave API med en stabil `uuid`. Har et tidligere forsøk allerede
+/// opprettet den (f.eks. krasj før commit), svarer API-et 409 og vi tar i bruk den eksisterende.
+/// Returnerer `true` sammen med oppgaven hvis den fantes fra før. Finner ikke søket oppgaven
+/// etter 409, gis en feil som kan prøves igjen, slik at neste forsøk finner den.
+async fn opprett_eller_finn_ekstern_oppgave(
+    oppgave_client: &OppgaveApiClient,
+    oppgave: &Oppgave,
+    request: &OpprettOppgaveRequest,
+) -> Result<(OppgaveDto, bool), OppgaveApiError> {
+    let uuid = oppgave.ekstern_uuid();
+    match oppgave_client.opprett_oppgave(request).await {
+        Ok(oppgave_dto) => Ok((oppgave_dto, false)),
+        Err(error) if error.finnes_allerede() => {
+            tracing::info!("Oppgave {} finnes allerede i Oppgave API (uuid {})", oppgave.id(), uuid);
+            match oppgave_client.finn_oppgave_med_uuid(uuid).await? {
+                Some(oppgave_dto) => Ok((oppgave_dto, true)),
+                None => Err(OppgaveApiError::IkkeFunnetEtterKonflikt { uuid }),
+            }
+        }
+        Err(error) => Err(error),
     }
 }
 
@@ -179,7 +271,7 @@ fn warning_ved_gjentatte_feil(oppgave: &Oppgave) {
 mod tests {
     use super::*;
     use crate::client::oppgave_client::OPPGAVER_PATH;
-    use crate::config::OppgaveClientConfig;
+    use crate::config::{OppgaveClientConfig, read_application_config};
     use crate::db::oppgave_functions::{
         hent_de_eldste_ubehandlede_oppgavene, hent_nyeste_oppgave, lagre_oppgave,
     };
@@ -252,8 +344,9 @@ mod tests {
 
         // Tom batch: ingen oppgaver, ingen HTTP-kall forventet
         prosesser_ubehandlede_oppgaver(
-            DateTime::UNIX_EPOCH,
+            &read_application_config()?,
             NonZeroU32::try_from(3).unwrap(),
+            &test_retry_policy(),
             Arc::clone(&oppgave_api_client),
             pg_pool.clone(),
         )
@@ -276,9 +369,8 @@ mod tests {
 
         tx.commit().await?;
 
-        let fra_dato = DateTime::UNIX_EPOCH;
         let result =
-            prosesser_ubehandlede_oppgaver(fra_dato, NonZeroU32::new(3).unwrap(), oppgave_api_client, pg_pool.clone()).await;
+            prosesser_ubehandlede_oppgaver(&read_application_config()?, NonZeroU32::new(3).unwrap(), &test_retry_policy(), oppgave_api_client, pg_pool.clone()).await;
         assert!(result.is_ok(), "Funksjonen skulle returnere Ok(())");
 
         let mut tx = pg_pool.begin().await?;
@@ -297,12 +389,12 @@ mod tests {
                 .any(|logg| logg.status == EksternOppgaveOpprettet)
         );
 
-        // Oppgave 2: feilet — tilbake til Ubehandlet med feil-logg
+        // Oppgave 2: permanent feil — gitt opp med feil-logg
         let oppgave_2 =
             hent_nyeste_oppgave(arbeidssoeker_id_2, OppgaveType::AvvistUnder18, &mut tx)
                 .await?
                 .unwrap();
-        assert_eq!(oppgave_2.status, Ubehandlet);
+        assert_eq!(oppgave_2.status, GittOpp);
         assert!(oppgave_2.ekstern_oppgave_id.is_none());
         assert!(
             oppgave_2
@@ -310,6 +402,12 @@ mod tests {
                 .iter()
                 .any(|logg| logg.status == EksternOppgaveOpprettelseFeilet)
         );
+        assert!(
+            oppgave_2
+                .hendelse_logg
+                .iter()
+                .any(|logg| logg.status == EksternOppgaveOpprettelseGittOpp)
+        );
 
         // Oppgave 3: vellykket
         let oppgave_3 =
@@ -328,6 +426,77 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn retrybar_feil_utsetter_oppgaven_til_vi_gir_opp() -> Result<()> {
+        let mut server = Server::new_async().await;
+        let oppgave_mock = server
+            .mock("POST", OPPGAVER_PATH)
+            .with_status(503)
+            .with_body("Service Unavailable")
+            .expect(2)
+            .create_async()
+            .await;
+        let oppgave_api_client = Arc::new(OppgaveApiClient::new(
+            OppgaveClientConfig {
+                base_url: server.url().into(),
+                scope: "test-scope".to_string().into(),
+            },
+            Arc::new(StubTokenClient),
+        ));
+
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+        let arbeidssoeker_id = ArbeidssoekerId(12345);
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = Oppgave::new(Uuid::new_v4(), OppgaveType::AvvistUnder18, Ubehandlet, vec![], arbeidssoeker_id, Identitetsnummer::new("12345678901".to_string()).unwrap(), Utc::now());
+        lagre_oppgave(&oppgave, &mut tx).await?;
+        tx.commit().await?;
+
+        let retry_policy = RetryPolicy {
+            maks_forsok: NonZeroU32::new(2).unwrap(),
+            forste_ventetid: Duration::ZERO,
+            maks_ventetid: Duration::ZERO,
+        };
+        let batch_size = NonZeroU32::new(1).unwrap();
+        let app_config = read_application_config()?;
+
+        // Første forsøk: utsatt og tilbake i Ubehandlet
+        prosesser_ubehandlede_oppgaver(&app_config, batch_size, &retry_policy, oppgave_api_client.clone(), pg_pool.clone()).await?;
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = hent_nyeste_oppgave(arbeidssoeker_id, OppgaveType::AvvistUnder18, &mut tx).await?.unwrap();
+        tx.commit().await?;
+        assert_eq!(oppgave.status, Ubehandlet);
+
+        // Andre forsøk: maks antall forsøk nådd
+        prosesser_ubehandlede_oppgaver(&app_config, batch_size, &retry_policy, oppgave_api_client.clone(), pg_pool.clone()).await?;
+        let mut tx = pg_pool.begin().await?;
+        let oppgave = hent_nyeste_oppgave(arbeidssoeker_id, OppgaveType::AvvistUnder18, &mut tx).await?.unwrap();
+        tx.commit().await?;
+        assert_eq!(oppgave.status, GittOpp);
+        assert_eq!(
+            oppgave
+                .hendelse_logg
+                .iter()
+                .filter(|logg| logg.status == EksternOppgaveOpprettelseFeilet)
+                .count(),
+            2
+        );
+
+        // Oppgaver vi har gitt opp plukkes ikke opp igjen
+        prosesser_ubehandlede_oppgaver(&app_config, batch_size, &retry_policy, oppgave_api_client, pg_pool.clone()).await?;
+        oppgave_mock.assert_async().await;
+
+        Ok(())
+    }
+
+    fn test_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            maks_forsok: NonZeroU32::new(5).unwrap(),
+            forste_ventetid: Duration::from_secs(60),
+            maks_ventetid: Duration::from_mins(10),
+        }
+    }
+
     #[tokio::test]
     async fn to_tråder_prosesserer_samme_oppgave() -> Result<()> {
         let mut server = Server::new_async().await;
@@ -365,7 +534,7 @@ mod tests {
         let mut hent_eldste_oppgaver_tx = pg_pool.begin().await?;
         let mut oppgaver = hent_de_eldste_ubehandlede_oppgavene(
             NonZeroU32::new(1).unwrap(),
-            DateTime::UNIX_EPOCH,
+            &[],
             &mut hent_eldste_oppgaver_tx,
         )
         .await?;
@@ -380,8 +549,10 @@ mod tests {
         // Worker B: spawner prosesser_oppgave — blokkeres av Worker A sin row lock
         let pool_b = pg_pool.clone();
         let client_b = oppgave_api_client.clone();
-        let worker_b =
-            tokio::spawn(async move { prosesser_oppgave(&pool_b, &client_b, &oppgave).await });
+        let app_config = read_application_config()?;
+        let worker_b = tokio::spawn(async move {
+            prosesser_oppgave(&pool_b, &client_b, &app_config, &test_retry_policy(), &oppgave).await
+        });
 
         // Gi Worker B tid til å nå UPDATE og bli blokkert av row lock
         sleep(Duration::from_secs(1)).await;
@@ -1,5 +1,4 @@
-mod avvist_under_18;
-mod vurder_oppholdsstatus;
+mod opprett_oppgave;
 mod router;
 
 pub use router::process_hendelselogg_message;
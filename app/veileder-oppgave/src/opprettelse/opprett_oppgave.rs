@@ -0,0 +1,57 @@
+use crate::config::ApplicationConfig;
+use crate::db::oppgave_functions::{
+    hent_nyeste_oppgave, lagre_oppgave, oppdater_hendelse_logg,
+};
+use crate::domain::hendelse_logg_entry::HendelseLoggEntry;
+use crate::domain::hendelse_logg_status::HendelseLoggStatus;
+use crate::domain::oppgavetyper::definisjon::Definisjon;
+use crate::metrics;
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+
+pub async fn opprett_oppgave(
+    definisjon: &dyn Definisjon,
+    hendelse: &Value,
+    app_config: &ApplicationConfig,
+    tx: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<()> {
+    let Some(oppgave) = definisjon.ny_oppgave(hendelse)? else {
+        return Ok(());
+    };
+
+    let oppgave_type = definisjon.oppgave_type();
+
+    if let Some(fra_tidspunkt) = definisjon.fra_tidspunkt(app_config)
+        && oppgave.tidspunkt < fra_tidspunkt
+    {
+        metrics::kriterier_oppfylt::inkrement(oppgave_type, false);
+        return Ok(());
+    }
+
+    let eksisterende_oppgave = hent_nyeste_oppgave(oppgave.arbeidssoeker_id, oppgave_type, tx).await?;
+    if let Some(eksisterende_oppgave) = &eksisterende_oppgave
+        && !definisjon.duplikat_policy().tillater_ny_oppgave(eksisterende_oppgave)
+    {
+        metrics::kriterier_oppfylt::inkrement(oppgave_type, false);
+        let hendelse_logg = HendelseLoggEntry::new(
+            HendelseLoggStatus::OppgaveFinnesAllerede,
+            format!("Arbeidssøkeren har allerede en aktiv oppgave for {}", definisjon.visningsnavn()),
+            Utc::now(),
+        );
+        oppdater_hendelse_logg(eksisterende_oppgave.id(), hendelse_logg, tx).await?;
+        return Ok(());
+    }
+
+    let oppgave_id = lagre_oppgave(&oppgave, tx).await?;
+    metrics::kriterier_oppfylt::inkrement(oppgave_type, true);
+
+    let hendelse_logg = HendelseLoggEntry::new(
+        HendelseLoggStatus::OppgaveOpprettet,
+        format!("Oppretter oppgave for {}", definisjon.visningsnavn()),
+        oppgave.tidspunkt,
+    );
+    oppdater_hendelse_logg(oppgave_id, hendelse_logg, tx).await?;
+
+    Ok(())
+}
@@ -1,8 +1,6 @@
-use super::avvist_under_18::opprett_avvist_under_18_oppgave;
-use super::vurder_oppholdsstatus::opprett_vurder_oppholdsstatus_oppgave;
+use super::opprett_oppgave::opprett_oppgave;
 use crate::config::ApplicationConfig;
-use anyhow::Context;
-use interne_hendelser::{Avvist, Startet};
+use crate::domain::oppgavetyper::definisjoner_for_hendelse;
 use serde_json::Value;
 use sqlx::{Postgres, Transaction};
 
@@ -20,18 +18,8 @@ pub async fn process_hendelselogg_message(
     };
     let hendelse_type = hendelse_json["hendelseType"].as_str().unwrap_or_default();
 
-    match hendelse_type {
-        interne_hendelser::AVVIST_HENDELSE_TYPE => {
-            let avvist: Avvist = serde_json::from_value(hendelse_json)
-                .context("Kunne ikke deserialisere avvist hendelse")?;
-            opprett_avvist_under_18_oppgave(&avvist, app_config, tx).await?;
-        }
-        interne_hendelser::STARTET_HENDELSE_TYPE => {
-            let startet: Startet = serde_json::from_value(hendelse_json)
-                .context("Kunne ikke deserialisere startet hendelse")?;
-            opprett_vurder_oppholdsstatus_oppgave(&startet, tx).await?;
-        }
-        _ => {}
+    for definisjon in definisjoner_for_hendelse(hendelse_type) {
+        opprett_oppgave(definisjon, &hendelse_json, app_config, tx).await?;
     }
 
     Ok(())
@@ -0,0 +1,18 @@
+use axum::Router;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Webserver med helse-endepunkter og admin-API på port 8080
+pub fn spawn_web_server(
+    routes: Router,
+    cancellation_token: CancellationToken,
+) -> JoinHandle<anyhow::Result<()>> {
+    tracing::info!("Starter webserver på adresse 0.0.0.0:8080");
+    tokio::spawn(async move {
+        let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+        axum::serve(listener, routes)
+            .with_graceful_shutdown(cancellation_token.cancelled_owned())
+            .await?;
+        Ok(())
+    })
+}
@@ -0,0 +1,430 @@
+use anyhow::Result;
+use axum::http::StatusCode;
+use chrono::Utc;
+use interne_hendelser::Avvist;
+use interne_hendelser::vo::Opplysning::{BosattEtterFregLoven, ErUnder18Aar};
+use oppgave_api_mock::{InjisertFeil, OppgaveApiMock};
+use paw_test::hendelse_builder::{AsJson, AvvistBuilder, rfc3339};
+use paw_test::setup_test_db::{TestDbGuard, setup_test_db};
+use paw_test::stub_token_client::StubTokenClient;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use types::arbeidssoeker_id::ArbeidssoekerId;
+use types::identitetsnummer::Identitetsnummer;
+use veileder_oppgave::client::oppgave_client::OppgaveApiClient;
+use veileder_oppgave::client::opprett_oppgave_request::create_oppgave_request;
+use veileder_oppgave::config::{
+    ApplicationConfig, OppgaveClientConfig, read_application_config,
+    read_opprett_oppgaver_task_config,
+};
+use veileder_oppgave::db::oppgave_functions::{bytt_oppgave_status, hent_nyeste_oppgave};
+use veileder_oppgave::domain::ekstern_oppgave_id::EksternOppgaveId;
+use veileder_oppgave::domain::hendelse_logg_status::HendelseLoggStatus;
+use veileder_oppgave::domain::oppgave::Oppgave;
+use veileder_oppgave::domain::oppgave_id::OppgaveId;
+use veileder_oppgave::domain::oppgave_status::OppgaveStatus;
+use veileder_oppgave::domain::oppgave_type::OppgaveType;
+use veileder_oppgave::domain::retry_policy::RetryPolicy;
+use veileder_oppgave::ferdigstilling::oppgave_hendelse::{
+    OppgaveHendelseMelding, OppgaveHendelsetype,
+};
+use veileder_oppgave::opprett_ekstern_oppgave_task::prosesser_ubehandlede_oppgaver;
+use veileder_oppgave::opprettelse::process_hendelselogg_message;
+
+const ARBEIDSSOEKER_ID: ArbeidssoekerId = ArbeidssoekerId(100);
+const IDENT: &str = "10000000001";
+
+#[tokio::test]
+async fn krasj_foer_kall_til_oppgave_api() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let oppgave = test_context.opprett_lokal_oppgave().await?;
+
+    // Tar eierskap, men krasjer før kallet — transaksjonen rulles tilbake
+    let mut tx = test_context.pg_pool.begin().await?;
+    assert!(
+        bytt_oppgave_status(
+            oppgave.id(),
+            OppgaveStatus::Ubehandlet,
+            OppgaveStatus::Opprettet,
+            &mut tx
+        )
+        .await?
+    );
+    drop(tx);
+
+    test_context.kjor_opprett_oppgave_task().await?;
+
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 1);
+    assert_eq!(test_context.oppgave_api.antall_opprett_kall(), 1);
+    test_context
+        .assert_opprettet_med_ekstern_id(test_context.ekstern_id(&oppgave))
+        .await
+}
+
+#[tokio::test]
+async fn krasj_etter_kall_til_oppgave_api_foer_commit() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let oppgave = test_context.opprett_lokal_oppgave().await?;
+
+    // Oppgaven opprettes i Oppgave API, men vi krasjer før ekstern id er lagret
+    let mut tx = test_context.pg_pool.begin().await?;
+    assert!(
+        bytt_oppgave_status(
+            oppgave.id(),
+            OppgaveStatus::Ubehandlet,
+            OppgaveStatus::Opprettet,
+            &mut tx
+        )
+        .await?
+    );
+    let request = create_oppgave_request(&oppgave, &test_context.app_config)?;
+    test_context
+        .oppgave_api_client
+        .opprett_oppgave(&request)
+        .await?;
+    drop(tx);
+
+    test_context.kjor_opprett_oppgave_task().await?;
+
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 1);
+    assert_eq!(test_context.oppgave_api.antall_opprett_kall(), 2);
+    test_context
+        .assert_opprettet_med_ekstern_id(test_context.ekstern_id(&oppgave))
+        .await
+}
+
+#[tokio::test]
+async fn krasj_etter_commit() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let oppgave = test_context.opprett_lokal_oppgave().await?;
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    // Restart: oppgaven er ikke lenger Ubehandlet og skal ikke sendes på nytt
+    test_context.kjor_opprett_oppgave_task().await?;
+
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 1);
+    assert_eq!(test_context.oppgave_api.antall_opprett_kall(), 1);
+    test_context
+        .assert_opprettet_med_ekstern_id(test_context.ekstern_id(&oppgave))
+        .await
+}
+
+#[tokio::test]
+async fn serverfeil_proeves_igjen() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let oppgave = test_context.opprett_lokal_oppgave().await?;
+    test_context
+        .oppgave_api
+        .injiser_feil(InjisertFeil::Serverfeil(StatusCode::SERVICE_UNAVAILABLE));
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 0);
+    assert_eq!(
+        test_context.hent_oppgave().await?.status,
+        OppgaveStatus::Ubehandlet
+    );
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 1);
+    assert_eq!(test_context.oppgave_api.antall_opprett_kall(), 2);
+    test_context
+        .assert_opprettet_med_ekstern_id(test_context.ekstern_id(&oppgave))
+        .await?;
+    test_context
+        .assert_hendelse_logg(&[
+            HendelseLoggStatus::OppgaveOpprettet,
+            HendelseLoggStatus::EksternOppgaveOpprettelseFeilet,
+            HendelseLoggStatus::EksternOppgaveOpprettet,
+        ])
+        .await
+}
+
+#[tokio::test]
+async fn timeout_proeves_igjen() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let oppgave = test_context.opprett_lokal_oppgave().await?;
+    test_context
+        .oppgave_api
+        .injiser_feil(InjisertFeil::Timeout(Duration::from_millis(100)));
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    test_context.kjor_opprett_oppgave_task().await?;
+
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 1);
+    assert_eq!(test_context.oppgave_api.antall_opprett_kall(), 2);
+    test_context
+        .assert_opprettet_med_ekstern_id(test_context.ekstern_id(&oppgave))
+        .await
+}
+
+#[tokio::test]
+async fn konflikt_tar_i_bruk_eksisterende_oppgave() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let oppgave = test_context.opprett_lokal_oppgave().await?;
+    test_context
+        .oppgave_api
+        .injiser_feil(InjisertFeil::Konflikt);
+
+    test_context.kjor_opprett_oppgave_task().await?;
+
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 1);
+    assert_eq!(test_context.oppgave_api.antall_opprett_kall(), 1);
+    test_context
+        .assert_opprettet_med_ekstern_id(test_context.ekstern_id(&oppgave))
+        .await?;
+    test_context
+        .assert_hendelse_logg(&[
+            HendelseLoggStatus::OppgaveOpprettet,
+            HendelseLoggStatus::EksternOppgaveOpprettet,
+        ])
+        .await
+}
+
+#[tokio::test]
+async fn konflikt_uten_treff_ved_soek_proeves_igjen() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let oppgave = test_context.opprett_lokal_oppgave().await?;
+    test_context
+        .oppgave_api
+        .injiser_feil(InjisertFeil::KonfliktUtenOppgave);
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    assert_eq!(test_context.oppgave_api.antall_oppgaver(), 0);
+    assert_eq!(
+        test_context.hent_oppgave().await?.status,
+        OppgaveStatus::Ubehandlet
+    );
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    assert_eq!(test_context.oppgave_api.antall_opprett_kall(), 2);
+    test_context
+        .assert_opprettet_med_ekstern_id(test_context.ekstern_id(&oppgave))
+        .await?;
+    test_context
+        .assert_hendelse_logg(&[
+            HendelseLoggStatus::OppgaveOpprettet,
+            HendelseLoggStatus::EksternOppgaveOpprettelseFeilet,
+            HendelseLoggStatus::EksternOppgaveOpprettet,
+        ])
+        .await
+}
+
+/// Klienten og konsumenten av oppgavehendelser mot mocken, uten database
+#[tokio::test]
+async fn oppgave_api_mock_godtar_forespoersel_og_gir_lesbare_hendelser() -> Result<()> {
+    let (oppgave_api, client) = start_oppgave_api_mock().await?;
+    let oppgave = oppgave_uten_database();
+    let request = create_oppgave_request(&oppgave, &read_application_config()?)?;
+
+    let opprettet = client.opprett_oppgave(&request).await?;
+    let duplikat = client.opprett_oppgave(&request).await;
+    let funnet = client
+        .finn_oppgave_med_uuid(oppgave.ekstern_uuid())
+        .await?
+        .expect("Forventet oppgave med uuid");
+
+    assert!(duplikat.is_err_and(|feil| feil.finnes_allerede()));
+    assert_eq!(funnet.id, opprettet.id);
+    assert_eq!(funnet.frist_ferdigstillelse, request.frist_ferdigstillelse);
+
+    oppgave_api.tildel(opprettet.id, "Z991459");
+    oppgave_api.ferdigstill(opprettet.id, "Z991459");
+    let hendelser: Vec<OppgaveHendelseMelding> = oppgave_api
+        .hendelser()
+        .iter()
+        .map(|hendelse| serde_json::from_str(hendelse))
+        .collect::<Result<_, _>>()?;
+    let hendelsestyper: Vec<_> = hendelser
+        .iter()
+        .map(|melding| melding.hendelse.hendelsestype.clone())
+        .collect();
+    assert_eq!(
+        hendelsestyper,
+        vec![
+            OppgaveHendelsetype::OppgaveOpprettet,
+            OppgaveHendelsetype::OppgaveEndret,
+            OppgaveHendelsetype::OppgaveFerdigstilt,
+        ]
+    );
+    let ferdigstilt = &hendelser[2].oppgave;
+    assert_eq!(ferdigstilt.oppgave_id, opprettet.id);
+    assert_eq!(ferdigstilt.versjon, 3);
+    assert_eq!(
+        ferdigstilt
+            .tilordning
+            .as_ref()
+            .and_then(|t| t.nav_ident.as_deref()),
+        Some("Z991459")
+    );
+    Ok(())
+}
+
+/// Forespørsler som gir 409 bruker ikke feilen i køen, den går til neste nye oppgave
+#[tokio::test]
+async fn oppgave_api_mock_bruker_injisert_feil_bare_paa_nye_oppgaver() -> Result<()> {
+    let (oppgave_api, client) = start_oppgave_api_mock().await?;
+    let app_config = read_application_config()?;
+    let request = create_oppgave_request(&oppgave_uten_database(), &app_config)?;
+    client.opprett_oppgave(&request).await?;
+    oppgave_api.injiser_feil(InjisertFeil::Serverfeil(StatusCode::SERVICE_UNAVAILABLE));
+
+    let duplikat = client.opprett_oppgave(&request).await;
+    let ny = client
+        .opprett_oppgave(&create_oppgave_request(
+            &oppgave_uten_database(),
+            &app_config,
+        )?)
+        .await;
+
+    assert!(duplikat.is_err_and(|feil| feil.finnes_allerede()));
+    assert!(ny.is_err_and(|feil| !feil.finnes_allerede()));
+    assert_eq!(oppgave_api.antall_oppgaver(), 1);
+    Ok(())
+}
+
+async fn start_oppgave_api_mock() -> Result<(OppgaveApiMock, OppgaveApiClient)> {
+    let oppgave_api = OppgaveApiMock::default();
+    let base_url = oppgave_api
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let client = OppgaveApiClient::new(
+        OppgaveClientConfig {
+            base_url: base_url.into(),
+            scope: "test-scope".to_string().into(),
+        },
+        Arc::new(StubTokenClient),
+    );
+    Ok((oppgave_api, client))
+}
+
+fn oppgave_uten_database() -> Oppgave {
+    Oppgave::fra_db(
+        OppgaveId(1),
+        uuid::Uuid::new_v4(),
+        OppgaveType::AvvistStoppAvPeriode,
+        OppgaveStatus::Ubehandlet,
+        vec![],
+        None,
+        ARBEIDSSOEKER_ID,
+        Identitetsnummer::new(IDENT.to_string()).unwrap(),
+        None,
+        Utc::now(),
+        vec![],
+    )
+}
+
+struct TestContext {
+    pg_pool: PgPool,
+    _db_container: TestDbGuard,
+    oppgave_api: OppgaveApiMock,
+    app_config: ApplicationConfig,
+    oppgave_api_client: Arc<OppgaveApiClient>,
+}
+
+impl TestContext {
+    async fn ny() -> Result<Self> {
+        let (pg_pool, _db_container) = setup_test_db().await?;
+        sqlx::migrate!("./migrations").run(&pg_pool).await?;
+
+        let mut app_config = read_application_config()?;
+        app_config.opprett_avvist_under_18_oppgaver_fra_tidspunkt =
+            rfc3339("2021-01-01T00:00:00Z").into();
+
+        let oppgave_api = OppgaveApiMock::default();
+        let base_url = oppgave_api
+            .start()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        let oppgave_api_client = Arc::new(OppgaveApiClient::new(
+            OppgaveClientConfig {
+                base_url: base_url.into(),
+                scope: "test-scope".to_string().into(),
+            },
+            Arc::new(StubTokenClient),
+        ));
+
+        Ok(Self {
+            pg_pool,
+            _db_container,
+            oppgave_api,
+            app_config,
+            oppgave_api_client,
+        })
+    }
+
+    async fn opprett_lokal_oppgave(&self) -> Result<Oppgave> {
+        let avvist: Avvist = AvvistBuilder {
+            arbeidssoeker_id: ARBEIDSSOEKER_ID.0,
+            identitetsnummer: IDENT.to_string(),
+            tidspunkt: rfc3339("2024-09-01T12:00:00Z"),
+            opplysninger: HashSet::from([ErUnder18Aar, BosattEtterFregLoven]),
+            ..Default::default()
+        }
+        .build();
+        let mut tx = self.pg_pool.begin().await?;
+        process_hendelselogg_message(avvist.as_json().as_bytes(), &self.app_config, &mut tx)
+            .await?;
+        tx.commit().await?;
+        self.hent_oppgave().await
+    }
+
+    async fn hent_oppgave(&self) -> Result<Oppgave> {
+        let mut tx = self.pg_pool.begin().await?;
+        let oppgave = hent_nyeste_oppgave(ARBEIDSSOEKER_ID, OppgaveType::AvvistUnder18, &mut tx)
+            .await?
+            .expect("Forventet oppgave");
+        tx.commit().await?;
+        Ok(oppgave)
+    }
+
+    async fn kjor_opprett_oppgave_task(&self) -> Result<()> {
+        let task_config = read_opprett_oppgaver_task_config()?;
+        // Uten ventetid kan feilede forsøk prøves igjen med en gang
+        let retry_policy = RetryPolicy {
+            forste_ventetid: Duration::ZERO,
+            maks_ventetid: Duration::ZERO,
+            ..task_config.retry_policy()
+        };
+        prosesser_ubehandlede_oppgaver(
+            &self.app_config,
+            *task_config.opprett_oppgaver_task_batch_size,
+            &retry_policy,
+            self.oppgave_api_client.clone(),
+            self.pg_pool.clone(),
+        )
+        .await
+    }
+
+    fn ekstern_id(&self, oppgave: &Oppgave) -> EksternOppgaveId {
+        let ekstern_oppgave = self
+            .oppgave_api
+            .oppgave_med_uuid(&oppgave.ekstern_uuid().to_string())
+            .expect("Forventet oppgave i Oppgave API-mock");
+        EksternOppgaveId::from(ekstern_oppgave.id)
+    }
+
+    async fn assert_opprettet_med_ekstern_id(&self, forventet: EksternOppgaveId) -> Result<()> {
+        let oppgave = self.hent_oppgave().await?;
+        assert_eq!(oppgave.status, OppgaveStatus::Opprettet);
+        assert_eq!(oppgave.ekstern_oppgave_id, Some(forventet));
+        Ok(())
+    }
+
+    async fn assert_hendelse_logg(&self, forventede_statuser: &[HendelseLoggStatus]) -> Result<()> {
+        let mut faktiske: Vec<HendelseLoggStatus> = self
+            .hent_oppgave()
+            .await?
+            .hendelse_logg
+            .into_iter()
+            .map(|entry| entry.status)
+            .collect();
+        let mut forventede = forventede_statuser.to_vec();
+        faktiske.sort_by_key(|s| s.to_string());
+        forventede.sort_by_key(|s| s.to_string());
+        assert_eq!(faktiske, forventede);
+        Ok(())
+    }
+}
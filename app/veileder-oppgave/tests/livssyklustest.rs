@@ -3,18 +3,26 @@ use interne_hendelser::vo::BrukerType;
 use interne_hendelser::vo::Opplysning::{
     BosattEtterFregLoven, ErEuEoesStatsborger, ErOver18Aar, ErUnder18Aar, IkkeBosatt,
 };
-use interne_hendelser::{Avvist, Startet};
-use mockito::{Matcher, Server, ServerGuard};
-use paw_test::hendelse_builder::{AsJson, AvvistBuilder, StartetBuilder, rfc3339};
+use interne_hendelser::{
+    AutomatiskIdMergeIkkeMulig, Avvist, AvvistStoppAvPeriode, PeriodeRad, Startet,
+};
+use oppgave_api_mock::OppgaveApiMock;
+use paw_test::hendelse_builder::{
+    AsJson, AutomatiskIdMergeIkkeMuligBuilder, AvvistBuilder, AvvistStoppAvPeriodeBuilder,
+    StartetBuilder, rfc3339,
+};
 use paw_test::setup_test_db::{TestDbGuard, setup_test_db};
 use paw_test::stub_token_client::StubTokenClient;
-use serde_json::json;
 use sqlx::PgPool;
 use std::collections::HashSet;
 use std::sync::Arc;
 use types::arbeidssoeker_id::ArbeidssoekerId;
-use veileder_oppgave::client::oppgave_client::{OPPGAVER_PATH, OppgaveApiClient};
-use veileder_oppgave::config::{ApplicationConfig, OppgaveClientConfig, read_application_config};
+use uuid::Uuid;
+use veileder_oppgave::client::oppgave_client::OppgaveApiClient;
+use veileder_oppgave::config::{
+    ApplicationConfig, OppgaveClientConfig, read_application_config,
+    read_opprett_oppgaver_task_config,
+};
 use veileder_oppgave::db::oppgave_functions::{bytt_oppgave_status, hent_nyeste_oppgave};
 use veileder_oppgave::domain::hendelse_logg_entry::HendelseLoggEntry;
 use veileder_oppgave::domain::hendelse_logg_status::HendelseLoggStatus;
@@ -35,9 +43,15 @@ const VEILEDER_IDENT: &str = "Z991459";
 const VURDER_OPPHOLDSSTATUS_ARBEIDSSOEKER_ID: ArbeidssoekerId = ArbeidssoekerId(400);
 const VURDER_OPPHOLDSSTATUS_IDENT: &str = "40000000004";
 
+const ID_MERGE_ARBEIDSSOEKER_ID: ArbeidssoekerId = ArbeidssoekerId(500);
+const ID_MERGE_IDENT: &str = "50000000005";
+const ID_MERGE_ALIAS_IDENT: &str = "50000000015";
+const STOPP_AV_PERIODE_ARBEIDSSOEKER_ID: ArbeidssoekerId = ArbeidssoekerId(600);
+const STOPP_AV_PERIODE_IDENT: &str = "60000000006";
+
 #[tokio::test]
 async fn test_livssyklus_happy_path() -> Result<()> {
-    let mut test_context = TestContext::ny().await?;
+    let test_context = TestContext::ny().await?;
     let tidspunkt = rfc3339("2024-09-01T12:00:00Z");
 
     let avvist_under_18: Avvist = AvvistBuilder {
@@ -58,8 +72,6 @@ async fn test_livssyklus_happy_path() -> Result<()> {
     }
     .build();
 
-    let avvist_under_18_ekstern_id: i64 = 700_001;
-    let vurder_oppholdsstatus_ekstern_id: i64 = 700_002;
     test_context.send_hendelselogg(&avvist_under_18.as_json()).await?;
     test_context.send_hendelselogg(&startet_vurder_oppholdsstatus.as_json()).await?;
 
@@ -76,8 +88,6 @@ async fn test_livssyklus_happy_path() -> Result<()> {
     )
     .await?;
 
-    test_context.stub_opprett_oppgave_201(UNDER_18_IDENT, avvist_under_18_ekstern_id).await;
-    test_context.stub_opprett_oppgave_201(VURDER_OPPHOLDSSTATUS_IDENT, vurder_oppholdsstatus_ekstern_id).await;
     test_context.kjor_opprett_oppgave_task().await?;
 
     test_context.assert_oppgave_status(
@@ -93,10 +103,8 @@ async fn test_livssyklus_happy_path() -> Result<()> {
     )
     .await?;
 
-    test_context.send_oppgavehendelse(&bygg_oppgave_ferdigstilt_json(avvist_under_18_ekstern_id))
-        .await?;
-    test_context.send_oppgavehendelse(&bygg_oppgave_ferdigstilt_json(vurder_oppholdsstatus_ekstern_id))
-        .await?;
+    test_context.ferdigstill_eksternt(UNDER_18_IDENT).await?;
+    test_context.ferdigstill_eksternt(VURDER_OPPHOLDSSTATUS_IDENT).await?;
 
     let forventet_logg = &[
         HendelseLoggStatus::OppgaveOpprettet,
@@ -353,10 +361,177 @@ async fn test_ny_hendelse_etter_ferdigbehandlet_gir_ny_oppgave() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_livssyklus_automatisk_id_merge_ikke_mulig() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+    let aktiv_periode_id = Uuid::new_v4();
+    let aktiv_periode = PeriodeRad {
+        periode_id: aktiv_periode_id,
+        identitetsnummer: ID_MERGE_IDENT.to_string(),
+        fra: rfc3339("2024-03-01T12:00:00Z"),
+        til: None,
+    };
+    let alias_periode = PeriodeRad {
+        periode_id: Uuid::new_v4(),
+        identitetsnummer: ID_MERGE_ALIAS_IDENT.to_string(),
+        fra: rfc3339("2024-05-01T12:00:00Z"),
+        til: None,
+    };
+
+    let merge_ikke_mulig: AutomatiskIdMergeIkkeMulig = AutomatiskIdMergeIkkeMuligBuilder {
+        arbeidssoeker_id: ID_MERGE_ARBEIDSSOEKER_ID.0,
+        identitetsnummer: ID_MERGE_IDENT.to_string(),
+        tidspunkt: rfc3339("2024-09-01T12:00:00Z"),
+        perioder: HashSet::from([aktiv_periode, alias_periode]),
+        ..Default::default()
+    }
+    .build();
+    let merge_uten_aktive_perioder: AutomatiskIdMergeIkkeMulig = AutomatiskIdMergeIkkeMuligBuilder {
+        arbeidssoeker_id: OVER_18_ARBEIDSSOEKER_ID.0,
+        identitetsnummer: OVER_18_IDENT.to_string(),
+        tidspunkt: rfc3339("2024-09-01T12:00:00Z"),
+        perioder: HashSet::from([PeriodeRad {
+            periode_id: Uuid::new_v4(),
+            identitetsnummer: OVER_18_IDENT.to_string(),
+            fra: rfc3339("2024-01-01T12:00:00Z"),
+            til: Some(rfc3339("2024-02-01T12:00:00Z")),
+        }]),
+        ..Default::default()
+    }
+    .build();
+
+    test_context.send_hendelselogg(&merge_ikke_mulig.as_json()).await?;
+    test_context.send_hendelselogg(&merge_ikke_mulig.as_json()).await?;
+    test_context.send_hendelselogg(&merge_uten_aktive_perioder.as_json()).await?;
+
+    test_context.assert_oppgave_status(
+        ID_MERGE_ARBEIDSSOEKER_ID,
+        OppgaveType::AutomatiskIdMergeIkkeMulig,
+        OppgaveStatus::Ubehandlet,
+    )
+    .await?;
+    test_context.assert_hendelse_logg(
+        ID_MERGE_ARBEIDSSOEKER_ID,
+        OppgaveType::AutomatiskIdMergeIkkeMulig,
+        &[
+            HendelseLoggStatus::OppgaveOpprettet,
+            HendelseLoggStatus::OppgaveFinnesAllerede,
+        ],
+    )
+    .await?;
+    let mut tx = test_context.pg_pool.begin().await?;
+    assert!(
+        hent_nyeste_oppgave(OVER_18_ARBEIDSSOEKER_ID, OppgaveType::AutomatiskIdMergeIkkeMulig, &mut tx)
+            .await?
+            .is_none(),
+        "Uten aktive perioder skal det ikke opprettes oppgave"
+    );
+    tx.commit().await?;
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    let ekstern_oppgave = test_context
+        .oppgave_api
+        .oppgave_for_personident(ID_MERGE_IDENT)
+        .expect("Forventet oppgave i Oppgave API");
+    let beskrivelse = ekstern_oppgave.request["beskrivelse"].as_str().unwrap_or_default();
+    assert!(beskrivelse.contains(&aktiv_periode_id.to_string()));
+    assert!(beskrivelse.contains(ID_MERGE_ALIAS_IDENT));
+
+    test_context.ferdigstill_eksternt(ID_MERGE_IDENT).await?;
+
+    test_context.assert_oppgave_status(
+        ID_MERGE_ARBEIDSSOEKER_ID,
+        OppgaveType::AutomatiskIdMergeIkkeMulig,
+        OppgaveStatus::Ferdigbehandlet,
+    )
+    .await?;
+    test_context.assert_hendelse_logg(
+        ID_MERGE_ARBEIDSSOEKER_ID,
+        OppgaveType::AutomatiskIdMergeIkkeMulig,
+        &[
+            HendelseLoggStatus::OppgaveOpprettet,
+            HendelseLoggStatus::OppgaveFinnesAllerede,
+            HendelseLoggStatus::EksternOppgaveOpprettet,
+            HendelseLoggStatus::EksternOppgaveFerdigstilt,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_livssyklus_avvist_stopp_av_periode() -> Result<()> {
+    let test_context = TestContext::ny().await?;
+
+    let stopp_fra_system: AvvistStoppAvPeriode = AvvistStoppAvPeriodeBuilder {
+        arbeidssoeker_id: STOPP_AV_PERIODE_ARBEIDSSOEKER_ID.0,
+        identitetsnummer: STOPP_AV_PERIODE_IDENT.to_string(),
+        tidspunkt: rfc3339("2024-09-01T12:00:00Z"),
+        ..Default::default()
+    }
+    .build();
+    let stopp_fra_veileder: AvvistStoppAvPeriode = AvvistStoppAvPeriodeBuilder {
+        arbeidssoeker_id: OVER_18_ARBEIDSSOEKER_ID.0,
+        identitetsnummer: OVER_18_IDENT.to_string(),
+        tidspunkt: rfc3339("2024-09-01T12:00:00Z"),
+        bruker_type: BrukerType::Veileder,
+        utfoert_av_id: VEILEDER_IDENT.to_string(),
+        ..Default::default()
+    }
+    .build();
+
+    test_context.send_hendelselogg(&stopp_fra_system.as_json()).await?;
+    test_context.send_hendelselogg(&stopp_fra_veileder.as_json()).await?;
+
+    test_context.assert_oppgave_status(
+        STOPP_AV_PERIODE_ARBEIDSSOEKER_ID,
+        OppgaveType::AvvistStoppAvPeriode,
+        OppgaveStatus::Ubehandlet,
+    )
+    .await?;
+    let mut tx = test_context.pg_pool.begin().await?;
+    assert!(
+        hent_nyeste_oppgave(OVER_18_ARBEIDSSOEKER_ID, OppgaveType::AvvistStoppAvPeriode, &mut tx)
+            .await?
+            .is_none(),
+        "Stopp avvist for veileder skal ikke gi oppgave"
+    );
+    tx.commit().await?;
+
+    test_context.kjor_opprett_oppgave_task().await?;
+    test_context.assert_oppgave_status(
+        STOPP_AV_PERIODE_ARBEIDSSOEKER_ID,
+        OppgaveType::AvvistStoppAvPeriode,
+        OppgaveStatus::Opprettet,
+    )
+    .await?;
+
+    test_context.ferdigstill_eksternt(STOPP_AV_PERIODE_IDENT).await?;
+    test_context.send_hendelselogg(&stopp_fra_system.as_json()).await?;
+
+    let mut tx = test_context.pg_pool.begin().await?;
+    let ny_oppgave = hent_nyeste_oppgave(
+        STOPP_AV_PERIODE_ARBEIDSSOEKER_ID,
+        OppgaveType::AvvistStoppAvPeriode,
+        &mut tx,
+    )
+    .await?
+    .expect("Forventet ny oppgave etter ferdigbehandlet");
+    tx.commit().await?;
+    assert_eq!(ny_oppgave.status, OppgaveStatus::Ubehandlet);
+    assert_hendelse_logg_inneholder(
+        &ny_oppgave.hendelse_logg,
+        &[HendelseLoggStatus::OppgaveOpprettet],
+    );
+
+    Ok(())
+}
+
 struct TestContext {
     pg_pool: PgPool,
     _db_container: TestDbGuard,
-    server: ServerGuard,
+    oppgave_api: OppgaveApiMock,
     app_config: ApplicationConfig,
     oppgave_api_client: Arc<OppgaveApiClient>,
 }
@@ -370,16 +545,20 @@ impl TestContext {
         app_config.opprett_avvist_under_18_oppgaver_fra_tidspunkt =
             rfc3339("2021-01-01T00:00:00Z").into();
 
-        let server = Server::new_async().await;
+        let oppgave_api = OppgaveApiMock::default();
+        let base_url = oppgave_api
+            .start()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
         let oppgave_api_client = Arc::new(OppgaveApiClient::new(
-            ny_test_oppgave_client_config(server.url()),
+            ny_test_oppgave_client_config(base_url),
             Arc::new(StubTokenClient),
         ));
 
         Ok(Self {
             pg_pool,
             _db_container,
-            server,
+            oppgave_api,
             app_config,
             oppgave_api_client,
         })
@@ -399,33 +578,22 @@ impl TestContext {
         Ok(())
     }
 
-    async fn stub_opprett_oppgave_201(&mut self, ident: &str, ekstern_oppgave_id: i64) {
-        self.server
-            .mock("POST", OPPGAVER_PATH)
-            .match_body(Matcher::Regex(format!(r#""personident":"{}"#, ident)))
-            .with_status(201)
-            .with_header("content-type", "application/json")
-            .with_body(
-                json!({
-                    "id": ekstern_oppgave_id,
-                    "tildeltEnhetsnr": "4863",
-                    "oppgavetype": "JFR",
-                    "tema": "KON",
-                    "prioritet": "NORM",
-                    "status": "OPPRETTET",
-                    "aktivDato": "2026-02-16",
-                    "versjon": 1,
-                })
-                .to_string(),
-            )
-            .create_async()
-            .await;
+    /// Ferdigstiller personens oppgave i Oppgave API og sender hendelsen videre
+    async fn ferdigstill_eksternt(&self, ident: &str) -> Result<()> {
+        let ekstern_oppgave = self
+            .oppgave_api
+            .oppgave_for_personident(ident)
+            .unwrap_or_else(|| panic!("Forventet oppgave i Oppgave API for {}", ident));
+        let hendelse = self.oppgave_api.ferdigstill(ekstern_oppgave.id, VEILEDER_IDENT);
+        self.send_oppgavehendelse(&hendelse).await
     }
 
     async fn kjor_opprett_oppgave_task(&self) -> Result<()> {
+        let task_config = read_opprett_oppgaver_task_config()?;
         prosesser_ubehandlede_oppgaver(
-            *self.app_config.opprett_avvist_under_18_oppgaver_fra_tidspunkt,
-            *self.app_config.opprett_oppgaver_task_batch_size,
+            &self.app_config,
+            *task_config.opprett_oppgaver_task_batch_size,
+            &task_config.retry_policy(),
             self.oppgave_api_client.clone(),
             self.pg_pool.clone(),
         )
@@ -495,28 +663,6 @@ fn assert_hendelse_logg_inneholder(
     assert_eq!(faktiske, forventede);
 }
 
-fn bygg_oppgave_ferdigstilt_json(ekstern_oppgave_id: i64) -> String {
-    json!({
-        "hendelse": {
-            "hendelsestype": "OPPGAVE_FERDIGSTILT",
-            "tidspunkt": [2024, 9, 5, 10, 0, 0, 0]
-        },
-        "utfortAv": {
-            "navIdent": VEILEDER_IDENT,
-            "enhetsnr": "2990"
-        },
-        "oppgave": {
-            "oppgaveId": ekstern_oppgave_id,
-            "versjon": 2,
-            "tilordning": null,
-            "kategorisering": null,
-            "behandlingsperiode": null,
-            "bruker": null
-        }
-    })
-    .to_string()
-}
-
 fn ny_test_oppgave_client_config(base_url: String) -> OppgaveClientConfig {
     OppgaveClientConfig {
         base_url: base_url.into(),
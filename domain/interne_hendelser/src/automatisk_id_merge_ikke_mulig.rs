@@ -2,9 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, TimestampSecondsWithFrac};
 use std::collections::HashSet;
+use std::sync::LazyLock;
 use uuid::Uuid;
 
-use crate::vo::Metadata;
+use crate::vo::{Metadata, Opplysning};
+use crate::Hendelse;
 
 pub const AUTOMATISK_ID_MERGE_IKKE_MULIG: &str = "intern.v1.automatisk_id_merge_ikke_mulig";
 
@@ -28,6 +30,27 @@ impl AutomatiskIdMergeIkkeMulig {
     }
 }
 
+/// Hendelsen har ingen opplysninger
+static INGEN_OPPLYSNINGER: LazyLock<HashSet<Opplysning>> = LazyLock::new(HashSet::new);
+
+impl Hendelse for AutomatiskIdMergeIkkeMulig {
+    fn hendelse_id(&self) -> Uuid {
+        self.hendelse_id
+    }
+    fn id(&self) -> i64 {
+        self.id
+    }
+    fn identitetsnummer(&self) -> &str {
+        &self.identitetsnummer
+    }
+    fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+    fn opplysninger(&self) -> &HashSet<Opplysning> {
+        &INGEN_OPPLYSNINGER
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Alias {
@@ -3,6 +3,7 @@ use uuid::Uuid;
 use std::collections::HashSet;
 
 use crate::vo::{Metadata, Opplysning};
+use crate::Hendelse;
 
 pub const AVVIST_STOPP_AV_PERIODE_HENDELSE_TYPE: &str = "intent.v1.avvist_stopp_av_periode";
 
@@ -22,3 +23,21 @@ impl AvvistStoppAvPeriode {
         AVVIST_STOPP_AV_PERIODE_HENDELSE_TYPE
     }
 }
+
+impl Hendelse for AvvistStoppAvPeriode {
+    fn hendelse_id(&self) -> Uuid {
+        self.hendelse_id
+    }
+    fn id(&self) -> i64 {
+        self.id
+    }
+    fn identitetsnummer(&self) -> &str {
+        &self.identitetsnummer
+    }
+    fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+    fn opplysninger(&self) -> &HashSet<Opplysning> {
+        &self.opplysninger
+    }
+}
@@ -3,6 +3,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub const PAW_IDENTITETER_TOPIC: &str = "paw.arbeidssoeker-identiteter-v1";
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "hendelseType")]
 pub enum IdentitetHendelse {
@@ -5,6 +5,7 @@ pub mod avsluttet;
 pub mod avvist;
 pub mod avvist_stopp_av_periode;
 mod hendelse;
+pub mod identiteter;
 pub mod identitetsnummer_sammenslaatt;
 pub mod opplysninger_om_arbeidssoeker_mottatt;
 pub mod startet;
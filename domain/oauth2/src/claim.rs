@@ -12,6 +12,8 @@ pub struct EntraIdClaims {
     #[serde(rename = "NAVident")]
     pub nav_ident: Option<String>,
     pub roles: Option<Vec<String>>,
+    /// Objekt-id for Entra ID-gruppene brukeren er medlem av, hvis applikasjonen ber om dem
+    pub groups: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -16,6 +16,7 @@ pub struct NavAnsatt {
     pub ident: NavIdent,
     pub name: Option<String>,
     pub roles: Vec<String>,
+    pub groups: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -71,6 +72,7 @@ impl AsPrincipal for EntraIdClaims {
             oid: self.oid.clone(),
             name: self.name.clone(),
             roles: self.roles.clone().unwrap_or_default(),
+            groups: self.groups.clone().unwrap_or_default(),
         }))
     }
 }
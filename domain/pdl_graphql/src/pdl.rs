@@ -239,3 +239,10 @@ pub struct HentPersonBolk;
     query_path = "graphql/hentPersonNavn.graphql"
 )]
 pub struct HentPersonNavn;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/pdl-schema.graphql",
+    query_path = "graphql/hentPersonNavnBolk.graphql"
+)]
+pub struct HentPersonNavnBolk;
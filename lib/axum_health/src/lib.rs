@@ -1,22 +1,28 @@
 pub mod paw_tracing;
 pub use paw_tracing::extract_trace_context;
 
-use axum::{extract::State, http::StatusCode, routing::get, Router};
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
 use health_and_monitoring::{
+    dependency_health::{DependencyStatus, DependencyStatusDetails},
     CheckType::{HasStarted, IsAlive, IsReady},
     HealthCheck,
 };
 use prometheus::{Encoder, TextEncoder};
+use serde::Serialize;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 pub fn spawn_health_server(
     health_check: Arc<dyn HealthCheck + Send + Sync>,
+    cancellation_token: CancellationToken,
 ) -> JoinHandle<anyhow::Result<()>> {
     let health_routes = routes(health_check);
     tokio::spawn(async move {
         let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
-        axum::serve(listener, health_routes).await?;
+        axum::serve(listener, health_routes)
+            .with_graceful_shutdown(cancellation_token.cancelled_owned())
+            .await?;
         Ok(())
     })
 }
@@ -26,6 +32,7 @@ pub fn routes(health_check: Arc<dyn HealthCheck + Send + Sync>) -> Router {
         .route("/internal/isAlive", get(is_alive))
         .route("/internal/isReady", get(is_ready))
         .route("/internal/hasStarted", get(has_started))
+        .route("/internal/health", get(health_details))
         .route("/internal/metrics", get(prometheus))
         .with_state(health_check)
 }
@@ -60,6 +67,29 @@ async fn has_started(
     }
 }
 
+#[derive(Serialize)]
+struct HealthDetailsResponse {
+    status: DependencyStatus,
+    checks: Vec<DependencyStatusDetails>,
+}
+
+async fn health_details(
+    State(health_check): State<Arc<dyn HealthCheck + Send + Sync>>,
+) -> (StatusCode, Json<HealthDetailsResponse>) {
+    let healthy = health_check.check(&IsAlive) != Some(false)
+        && health_check.check(&IsReady) != Some(false);
+    let (status_code, status) = if healthy {
+        (StatusCode::OK, DependencyStatus::Up)
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, DependencyStatus::Down)
+    };
+    let response = HealthDetailsResponse {
+        status,
+        checks: health_check.details(),
+    };
+    (status_code, Json(response))
+}
+
 async fn prometheus() -> (StatusCode, [(&'static str, &'static str); 1], String) {
     let mut buffer = Vec::new();
     let encoder = TextEncoder::new();
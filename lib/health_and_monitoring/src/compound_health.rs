@@ -1,5 +1,6 @@
 use crate::CheckType;
 use crate::HealthCheck;
+use crate::dependency_health::DependencyStatusDetails;
 
 pub struct CompoundHealth {
     health_checks: Vec<Box<dyn HealthCheck + Send + Sync + 'static>>,
@@ -45,4 +46,11 @@ impl HealthCheck for CompoundHealth {
                 (Some(a), Some(b)) => Some(a && b),
             })
     }
+
+    fn details(&self) -> Vec<DependencyStatusDetails> {
+        self.health_checks
+            .iter()
+            .flat_map(|check| check.details())
+            .collect()
+    }
 }
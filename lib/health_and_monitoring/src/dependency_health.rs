@@ -0,0 +1,305 @@
+use crate::{CheckType, HealthCheck};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+pub const DEFAULT_TTL: Duration = Duration::from_secs(15);
+
+/// En sjekk mot en ekstern avhengighet (database, kafka, schema registry osv.).
+#[async_trait]
+pub trait DependencyCheck {
+    fn name(&self) -> String;
+    async fn check(&self) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DependencyStatus {
+    Up,
+    Down,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatusDetails {
+    pub name: String,
+    pub status: DependencyStatus,
+    pub latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+struct CachedResult {
+    status: DependencyStatus,
+    latency: Duration,
+    checked_at: DateTime<Utc>,
+    checked_instant: Instant,
+}
+
+struct RegisteredCheck {
+    check: Box<dyn DependencyCheck + Send + Sync + 'static>,
+    check_type: CheckType,
+    timeout: Duration,
+    ttl: Duration,
+    result: Mutex<Option<CachedResult>>,
+    last_error: Mutex<Option<String>>,
+}
+
+impl RegisteredCheck {
+    fn is_stale(&self) -> bool {
+        self.result
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_none_or(|result| result.checked_instant.elapsed() >= self.ttl)
+    }
+
+    fn status(&self) -> DependencyStatus {
+        self.result
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(DependencyStatus::Unknown, |result| result.status)
+    }
+
+    async fn run(&self) {
+        let start = Instant::now();
+        let outcome = match tokio::time::timeout(self.timeout, self.check.check()).await {
+            Ok(outcome) => outcome,
+            Err(_) => Err(format!(
+                "Tidsavbrudd etter {} ms",
+                self.timeout.as_millis()
+            )),
+        };
+        let latency = start.elapsed();
+        let status = match outcome {
+            Ok(()) => DependencyStatus::Up,
+            Err(error) => {
+                tracing::warn!(dependency = %self.check.name(), error = %error, "Helsesjekk feilet");
+                *self.last_error.lock().unwrap() = Some(error);
+                DependencyStatus::Down
+            }
+        };
+        *self.result.lock().unwrap() = Some(CachedResult {
+            status,
+            latency,
+            checked_at: Utc::now(),
+            checked_instant: Instant::now(),
+        });
+    }
+
+    fn details(&self) -> DependencyStatusDetails {
+        let result = self.result.lock().unwrap();
+        DependencyStatusDetails {
+            name: self.check.name(),
+            status: result
+                .as_ref()
+                .map_or(DependencyStatus::Unknown, |r| r.status),
+            latency_ms: result
+                .as_ref()
+                .map(|r| u64::try_from(r.latency.as_millis()).unwrap_or(u64::MAX)),
+            last_error: self.last_error.lock().unwrap().clone(),
+            checked_at: result.as_ref().map(|r| r.checked_at),
+        }
+    }
+}
+
+/// Samling av avhengighetssjekker med timeout og caching. Resultatene caches i `ttl`,
+/// slik at probes fra kubernetes ikke fører til et kall mot avhengighetene hver gang.
+/// En sjekk som ikke er kjørt ennå påvirker ikke helsestatusen.
+pub struct DependencyHealth {
+    checks: Vec<RegisteredCheck>,
+    timeout: Duration,
+    ttl: Duration,
+}
+
+impl Default for DependencyHealth {
+    fn default() -> Self {
+        Self::new(DEFAULT_TIMEOUT, DEFAULT_TTL)
+    }
+}
+
+impl DependencyHealth {
+    pub fn new(timeout: Duration, ttl: Duration) -> Self {
+        DependencyHealth {
+            checks: Vec::new(),
+            timeout,
+            ttl,
+        }
+    }
+
+    /// Legger til en sjekk som påvirker `check_type` (typisk `IsReady` eller `IsAlive`).
+    pub fn add_check<C: DependencyCheck + Send + Sync + 'static>(
+        &mut self,
+        check: C,
+        check_type: CheckType,
+    ) {
+        self.checks.push(RegisteredCheck {
+            check: Box::new(check),
+            check_type,
+            timeout: self.timeout,
+            ttl: self.ttl,
+            result: Mutex::new(None),
+            last_error: Mutex::new(None),
+        });
+    }
+
+    /// Kjører alle sjekker der cachet resultat er eldre enn `ttl`.
+    pub async fn refresh(&self) {
+        let stale = self.checks.iter().filter(|check| check.is_stale());
+        futures::future::join_all(stale.map(|check| check.run())).await;
+    }
+
+    pub fn spawn_refresh_task(
+        self: Arc<Self>,
+        cancellation_token: CancellationToken,
+    ) -> JoinHandle<anyhow::Result<()>> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.ttl);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => break,
+                    _ = interval.tick() => self.refresh().await,
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl HealthCheck for DependencyHealth {
+    fn name(&self) -> String {
+        let names = self
+            .checks
+            .iter()
+            .map(|registered| registered.check.name())
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!("DependencyHealth: [{}]", names)
+    }
+
+    fn check(&self, check_type: &CheckType) -> Option<bool> {
+        self.checks
+            .iter()
+            .filter(|registered| registered.check_type == *check_type)
+            .filter_map(|registered| match registered.status() {
+                DependencyStatus::Up => Some(true),
+                DependencyStatus::Down => Some(false),
+                DependencyStatus::Unknown => None,
+            })
+            .reduce(|a, b| a && b)
+    }
+
+    fn details(&self) -> Vec<DependencyStatusDetails> {
+        self.checks.iter().map(RegisteredCheck::details).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    struct TestCheck {
+        ok: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl DependencyCheck for TestCheck {
+        fn name(&self) -> String {
+            "test".to_string()
+        }
+
+        async fn check(&self) -> Result<(), String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            if self.ok.load(Ordering::SeqCst) {
+                Ok(())
+            } else {
+                Err("nede".to_string())
+            }
+        }
+    }
+
+    fn test_check(delay: Duration) -> (TestCheck, Arc<AtomicBool>, Arc<AtomicUsize>) {
+        let ok = Arc::new(AtomicBool::new(true));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let check = TestCheck {
+            ok: ok.clone(),
+            calls: calls.clone(),
+            delay,
+        };
+        (check, ok, calls)
+    }
+
+    #[tokio::test]
+    async fn ukjent_status_foer_foerste_sjekk() {
+        let (check, _, _) = test_check(Duration::ZERO);
+        let mut health = DependencyHealth::default();
+        health.add_check(check, CheckType::IsReady);
+        assert_eq!(health.check(&CheckType::IsReady), None);
+        assert_eq!(health.details()[0].status, DependencyStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn resultat_caches_i_ttl() {
+        let (check, ok, calls) = test_check(Duration::ZERO);
+        let mut health = DependencyHealth::new(DEFAULT_TIMEOUT, Duration::from_secs(60));
+        health.add_check(check, CheckType::IsReady);
+        health.refresh().await;
+        ok.store(false, Ordering::SeqCst);
+        health.refresh().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(health.check(&CheckType::IsReady), Some(true));
+        assert_eq!(health.check(&CheckType::IsAlive), None);
+    }
+
+    #[tokio::test]
+    async fn feil_rapporteres_og_siste_feil_beholdes() {
+        let (check, ok, _) = test_check(Duration::ZERO);
+        let mut health = DependencyHealth::new(DEFAULT_TIMEOUT, Duration::ZERO);
+        health.add_check(check, CheckType::IsAlive);
+        ok.store(false, Ordering::SeqCst);
+        health.refresh().await;
+        assert_eq!(health.check(&CheckType::IsAlive), Some(false));
+        ok.store(true, Ordering::SeqCst);
+        health.refresh().await;
+        assert_eq!(health.check(&CheckType::IsAlive), Some(true));
+        let details = health.details();
+        assert_eq!(details[0].status, DependencyStatus::Up);
+        assert_eq!(details[0].last_error.as_deref(), Some("nede"));
+    }
+
+    #[tokio::test]
+    async fn informativ_sjekk_paavirker_ikke_probes() {
+        let (check, ok, _) = test_check(Duration::ZERO);
+        let mut health = DependencyHealth::default();
+        health.add_check(check, CheckType::Informational);
+        ok.store(false, Ordering::SeqCst);
+        health.refresh().await;
+        assert_eq!(health.check(&CheckType::IsReady), None);
+        assert_eq!(health.check(&CheckType::IsAlive), None);
+        assert_eq!(health.details()[0].status, DependencyStatus::Down);
+    }
+
+    #[tokio::test]
+    async fn tidsavbrudd_gir_nede() {
+        let (check, _, _) = test_check(Duration::from_secs(5));
+        let mut health = DependencyHealth::new(Duration::from_millis(10), DEFAULT_TTL);
+        health.add_check(check, CheckType::IsReady);
+        health.refresh().await;
+        assert_eq!(health.check(&CheckType::IsReady), Some(false));
+        assert_eq!(
+            health.details()[0].last_error.as_deref(),
+            Some("Tidsavbrudd etter 10 ms")
+        );
+    }
+}
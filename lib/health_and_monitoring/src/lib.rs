@@ -1,16 +1,42 @@
 pub mod compound_health;
+pub mod dependency_health;
 pub mod error;
 pub mod nais_otel_setup;
 pub mod otel_json_format_layer;
 pub mod simple_app_state;
 
+use crate::dependency_health::DependencyStatusDetails;
+use std::sync::Arc;
+
 pub trait HealthCheck {
     fn name(&self) -> String;
     fn check(&self, check_type: &CheckType) -> Option<bool>;
+
+    fn details(&self) -> Vec<DependencyStatusDetails> {
+        Vec::new()
+    }
+}
+
+impl<T: HealthCheck + ?Sized> HealthCheck for Arc<T> {
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn check(&self, check_type: &CheckType) -> Option<bool> {
+        (**self).check(check_type)
+    }
+
+    fn details(&self) -> Vec<DependencyStatusDetails> {
+        (**self).details()
+    }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CheckType {
     IsReady,
     IsAlive,
     HasStarted,
+    /// Vises i `/internal/health`, men påvirker ikke probes. For avhengigheter som ikke
+    /// skal gjøre podden unready eller føre til restart når de er nede.
+    Informational,
 }
@@ -61,6 +61,7 @@ impl HealthCheck for AppState {
             CheckType::IsAlive => Some(self.is_alive()),
             CheckType::IsReady => Some(self.is_ready()),
             CheckType::HasStarted => Some(self.has_started()),
+            CheckType::Informational => None,
         }
     }
 }
@@ -1,4 +1,6 @@
 use crate::error::SchemaRegistryConfigError;
+use paw_app_config::secret::Secret;
+use paw_app_config::validation::{ConfigValidator, RequiredIn, ValueKind};
 use paw_rust_base::env::nais_cluster_name;
 use schema_registry_converter::async_impl::schema_registry::SrSettings;
 use std::env;
@@ -12,18 +14,32 @@ fn create_nais_schema_registry_settings() -> Result<SrSettings, SchemaRegistryCo
         SchemaRegistryConfigError::MissingEnvVar("KAFKA_SCHEMA_REGISTRY_USER".to_string())
     })?;
 
-    let password = env::var("KAFKA_SCHEMA_REGISTRY_PASSWORD").map_err(|_| {
-        SchemaRegistryConfigError::MissingEnvVar("KAFKA_SCHEMA_REGISTRY_PASSWORD".to_string())
-    })?;
+    let password = env::var("KAFKA_SCHEMA_REGISTRY_PASSWORD")
+        .map(Secret::new)
+        .map_err(|_| {
+            SchemaRegistryConfigError::MissingEnvVar("KAFKA_SCHEMA_REGISTRY_PASSWORD".to_string())
+        })?;
 
     let sr_settings = SrSettings::new_builder(schema_registry_url)
-        .set_basic_authorization(&username, Some(&password))
+        .set_basic_authorization(&username, Some(password.expose()))
         .build()
         .map_err(|e| SchemaRegistryConfigError::InvalidUrl(e.to_string()))?;
 
     Ok(sr_settings)
 }
 
+/// Miljøvariablene `create_schema_registry_settings` krever på nais.
+pub fn require_schema_registry_env(validator: ConfigValidator) -> ConfigValidator {
+    validator
+        .require_env("KAFKA_SCHEMA_REGISTRY", ValueKind::Url, RequiredIn::Nais)
+        .require_env("KAFKA_SCHEMA_REGISTRY_USER", ValueKind::Text, RequiredIn::Nais)
+        .require_env(
+            "KAFKA_SCHEMA_REGISTRY_PASSWORD",
+            ValueKind::Secret,
+            RequiredIn::Nais,
+        )
+}
+
 fn create_local_schema_registry_settings() -> SrSettings {
     let schema_registry_url =
         env::var("KAFKA_SCHEMA_REGISTRY").unwrap_or_else(|_| "http://localhost:8082".to_string());
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use health_and_monitoring::dependency_health::DependencyCheck;
+use schema_registry_converter::async_impl::schema_registry::{SrSettings, get_all_subjects};
+
+pub struct SchemaRegistryCheck {
+    sr_settings: SrSettings,
+}
+
+impl SchemaRegistryCheck {
+    pub fn new(sr_settings: SrSettings) -> Self {
+        Self { sr_settings }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for SchemaRegistryCheck {
+    fn name(&self) -> String {
+        "schema-registry".to_string()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        get_all_subjects(&self.sr_settings)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
@@ -5,4 +5,11 @@ use thiserror::Error;
 pub enum ConfigError {
     #[error("Failed to deserialize TOML config: {0}")]
     DeserializeTomlFile(#[from] toml::de::Error),
+    #[error("Failed to read config file {path}: {source}")]
+    ReadFile {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Invalid configuration: {0}")]
+    Validation(String),
 }
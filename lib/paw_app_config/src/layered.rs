@@ -0,0 +1,183 @@
+use crate::error::ConfigError;
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/// Konfigurasjon i lag, der senere lag overstyrer tidligere:
+/// 1. innebygde standardverdier (typisk fra `read_config_file!`)
+/// 2. montert fil (f.eks. et configmap), dersom den finnes
+/// 3. miljøvariabler på formen `<PREFIX><NØKKEL>`, f.eks. `VEILEDER_OPPGAVE_BATCH_SIZE`
+///
+/// Miljøvariabler overstyrer bare nøkler på toppnivå.
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    embedded: &'static str,
+    file: Option<PathBuf>,
+    env_prefix: Option<String>,
+}
+
+impl LayeredConfig {
+    pub fn new(embedded: &'static str) -> Self {
+        LayeredConfig {
+            embedded,
+            file: None,
+            env_prefix: None,
+        }
+    }
+
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    pub fn with_env_prefix(mut self, prefix: &str) -> Self {
+        self.env_prefix = Some(prefix.to_string());
+        self
+    }
+
+    pub fn load<T: DeserializeOwned>(&self) -> Result<T, ConfigError> {
+        let table = self.merged_table()?;
+        Ok(table.try_into()?)
+    }
+
+    pub(crate) fn merged_table(&self) -> Result<Table, ConfigError> {
+        let mut table = self.embedded.parse::<Table>()?;
+        if let Some(path) = &self.file
+            && path.exists()
+        {
+            let content = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadFile {
+                path: path.display().to_string(),
+                source: e,
+            })?;
+            merge(&mut table, content.parse::<Table>()?);
+        }
+        if let Some(prefix) = &self.env_prefix {
+            for (key, value) in std::env::vars() {
+                if let Some(field) = key.strip_prefix(prefix.as_str()) {
+                    table.insert(field.to_lowercase(), parse_env_value(&value));
+                }
+            }
+        }
+        Ok(table)
+    }
+}
+
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(overlay_table)) => {
+                merge(base_table, overlay_table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Tolker verdien som en TOML-verdi (tall, bool, dato), ellers som streng.
+fn parse_env_value(raw: &str) -> Value {
+    format!("value = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::io::Write;
+
+    const EMBEDDED: &str = r#"
+        navn = "standard"
+        batch_size = 10
+        aktiv = true
+
+        [nested]
+        a = 1
+        b = 2
+    "#;
+
+    #[derive(Debug, Deserialize)]
+    struct Nested {
+        a: i64,
+        b: i64,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct TestConfig {
+        navn: String,
+        batch_size: u32,
+        aktiv: bool,
+        nested: Nested,
+    }
+
+    #[test]
+    fn bruker_innebygde_verdier_uten_andre_lag() {
+        let config: TestConfig = LayeredConfig::new(EMBEDDED).load().unwrap();
+        assert_eq!(config.navn, "standard");
+        assert_eq!(config.batch_size, 10);
+        assert!(config.aktiv);
+        assert_eq!(config.nested.a, 1);
+    }
+
+    #[test]
+    fn fil_overstyrer_innebygde_verdier() {
+        let mut file = tempfile();
+        writeln!(file.1, "batch_size = 50\n[nested]\nb = 3").unwrap();
+        let config: TestConfig = LayeredConfig::new(EMBEDDED)
+            .with_file(&file.0)
+            .load()
+            .unwrap();
+        assert_eq!(config.batch_size, 50);
+        assert_eq!(config.navn, "standard");
+        assert_eq!(config.nested.a, 1);
+        assert_eq!(config.nested.b, 3);
+        std::fs::remove_file(&file.0).unwrap();
+    }
+
+    #[test]
+    fn manglende_fil_ignoreres() {
+        let config: TestConfig = LayeredConfig::new(EMBEDDED)
+            .with_file("/finnes/ikke/config.toml")
+            .load()
+            .unwrap();
+        assert_eq!(config.batch_size, 10);
+    }
+
+    #[test]
+    fn miljoevariabler_overstyrer_fil() {
+        let mut file = tempfile();
+        writeln!(file.1, "batch_size = 50").unwrap();
+        temp_env::with_vars(
+            [
+                ("PAW_TEST_LAYERED_BATCH_SIZE", Some("100")),
+                ("PAW_TEST_LAYERED_NAVN", Some("fra-miljoe")),
+                ("PAW_TEST_LAYERED_AKTIV", Some("false")),
+            ],
+            || {
+                let config: TestConfig = LayeredConfig::new(EMBEDDED)
+                    .with_file(&file.0)
+                    .with_env_prefix("PAW_TEST_LAYERED_")
+                    .load()
+                    .unwrap();
+                assert_eq!(config.batch_size, 100);
+                assert_eq!(config.navn, "fra-miljoe");
+                assert!(!config.aktiv);
+            },
+        );
+        std::fs::remove_file(&file.0).unwrap();
+    }
+
+    fn tempfile() -> (PathBuf, std::fs::File) {
+        let path = std::env::temp_dir().join(format!(
+            "paw_app_config_{}_{:?}.toml",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let file = std::fs::File::create(&path).unwrap();
+        (path, file)
+    }
+}
@@ -1,2 +1,6 @@
 pub mod config;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod layered;
+pub mod secret;
+pub mod validation;
+pub mod watch;
@@ -0,0 +1,74 @@
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+const REDACTED: &str = "********";
+
+/// Verdi som aldri skrives ut av `Debug` eller `Display`, f.eks. passord og nøkler.
+/// Selve verdien hentes med `expose()`.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+impl<T: FromStr> FromStr for Secret<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        T::from_str(s).map(Secret)
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+pub(crate) fn redacted() -> String {
+    REDACTED.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct TestConfig {
+        username: String,
+        password: Secret<String>,
+    }
+
+    #[test]
+    fn secret_skrives_ikke_ut() {
+        let config: TestConfig =
+            toml::from_str("username = \"bruker\"\npassword = \"hemmelig\"").unwrap();
+        let debug = format!("{:?}", config);
+        assert_eq!(config.username, "bruker");
+        assert!(debug.contains("bruker"));
+        assert!(!debug.contains("hemmelig"));
+        assert_eq!(config.password.to_string(), REDACTED);
+        assert_eq!(config.password.expose(), "hemmelig");
+    }
+}
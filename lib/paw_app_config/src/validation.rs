@@ -0,0 +1,342 @@
+use crate::error::ConfigError;
+use crate::secret::redacted;
+use paw_rust_base::env::RuntimeEnv;
+use std::fmt;
+use std::path::Path;
+
+/// Hvilke miljøer et krav gjelder for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequiredIn {
+    Always,
+    /// Alle miljøer unntatt `RuntimeEnv::Local`
+    Nais,
+    Only(Vec<RuntimeEnv>),
+}
+
+impl RequiredIn {
+    fn applies_to(&self, runtime_env: &RuntimeEnv) -> bool {
+        match self {
+            RequiredIn::Always => true,
+            RequiredIn::Nais => *runtime_env != RuntimeEnv::Local,
+            RequiredIn::Only(envs) => envs.contains(runtime_env),
+        }
+    }
+}
+
+/// Hva slags verdi en miljøvariabel skal inneholde, og hvordan den vises i rapporten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Text,
+    Secret,
+    FilePath,
+    Url,
+}
+
+impl ValueKind {
+    /// Gjetter type ut fra navnet på variabelen, brukes for `${VAR}` i config-filer.
+    fn from_name(name: &str) -> Self {
+        const SECRET_MARKERS: [&str; 4] = ["PASSWORD", "SECRET", "TOKEN", "JWK"];
+        if SECRET_MARKERS.iter().any(|marker| name.contains(marker)) {
+            ValueKind::Secret
+        } else if name.ends_with("_PATH") {
+            ValueKind::FilePath
+        } else {
+            ValueKind::Text
+        }
+    }
+
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if value.trim().is_empty() {
+            return Err("er tom".to_string());
+        }
+        match self {
+            ValueKind::FilePath if !Path::new(value).exists() => {
+                Err(format!("filen {} finnes ikke", value))
+            }
+            ValueKind::Url if !(value.starts_with("http://") || value.starts_with("https://")) => {
+                Err("er ikke en http(s)-url".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn display_value(&self, value: &str) -> String {
+        match self {
+            ValueKind::Secret => redacted(),
+            _ => value.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Missing,
+    Invalid(String),
+    NotRequired,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub source: String,
+    pub status: CheckStatus,
+    /// Verdien slik den kan vises i logg, hemmeligheter er allerede sladdet
+    pub display_value: Option<String>,
+}
+
+impl CheckResult {
+    fn is_error(&self) -> bool {
+        matches!(self.status, CheckStatus::Missing | CheckStatus::Invalid(_))
+    }
+}
+
+/// Samler alle krav til konfigurasjon før oppstart, slik at alle feil rapporteres samtidig
+/// i stedet for at applikasjonen stopper på den første.
+pub struct ConfigValidator {
+    runtime_env: RuntimeEnv,
+    results: Vec<CheckResult>,
+}
+
+impl ConfigValidator {
+    pub fn new(runtime_env: RuntimeEnv) -> Self {
+        ConfigValidator {
+            runtime_env,
+            results: Vec::new(),
+        }
+    }
+
+    pub fn require_env(mut self, name: &str, kind: ValueKind, required_in: RequiredIn) -> Self {
+        let result = if !required_in.applies_to(&self.runtime_env) {
+            CheckResult {
+                source: name.to_string(),
+                status: CheckStatus::NotRequired,
+                display_value: None,
+            }
+        } else {
+            match std::env::var(name) {
+                Err(_) => CheckResult {
+                    source: name.to_string(),
+                    status: CheckStatus::Missing,
+                    display_value: None,
+                },
+                Ok(value) => CheckResult {
+                    source: name.to_string(),
+                    status: kind
+                        .validate(&value)
+                        .map_or_else(CheckStatus::Invalid, |_| CheckStatus::Ok),
+                    display_value: Some(kind.display_value(&value)),
+                },
+            }
+        };
+        self.results.push(result);
+        self
+    }
+
+    /// Krever alle miljøvariabler som refereres som `${VAR}` i innholdet til en config-fil.
+    pub fn require_placeholders(mut self, content: &str) -> Self {
+        for name in placeholders(content) {
+            if !self.results.iter().any(|result| result.source == name) {
+                self = self.require_env(&name, ValueKind::from_name(&name), RequiredIn::Always);
+            }
+        }
+        self
+    }
+
+    /// Registrerer resultatet av å lese en config-fil.
+    pub fn check_config<T, E: fmt::Display>(mut self, source: &str, result: Result<T, E>) -> Self {
+        self.results.push(CheckResult {
+            source: source.to_string(),
+            status: match result {
+                Ok(_) => CheckStatus::Ok,
+                Err(e) => CheckStatus::Invalid(e.to_string()),
+            },
+            display_value: None,
+        });
+        self
+    }
+
+    pub fn report(self) -> ConfigReport {
+        ConfigReport {
+            runtime_env: self.runtime_env,
+            results: self.results,
+        }
+    }
+}
+
+fn placeholders(content: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("${") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find('}') else { break };
+        let placeholder = &rest[..end];
+        // `${VAR:-standard}` har en standardverdi og er ikke påkrevd
+        if !placeholder.contains(":-") && !names.iter().any(|name| name == placeholder) {
+            names.push(placeholder.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    names
+}
+
+#[derive(Debug)]
+pub struct ConfigReport {
+    runtime_env: RuntimeEnv,
+    results: Vec<CheckResult>,
+}
+
+impl ConfigReport {
+    pub fn results(&self) -> &[CheckResult] {
+        &self.results
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results.iter().filter(|result| result.is_error())
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn log(&self) {
+        if self.is_ok() {
+            tracing::info!("{}", self);
+        } else {
+            tracing::error!("{}", self);
+        }
+    }
+
+    /// Feiler med alle manglende og ugyldige verdier dersom rapporten inneholder feil.
+    pub fn into_result(self) -> Result<(), ConfigError> {
+        let errors = self
+            .errors()
+            .map(|result| match &result.status {
+                CheckStatus::Invalid(reason) => format!("{}: {}", result.source, reason),
+                _ => format!("{}: mangler", result.source),
+            })
+            .collect::<Vec<String>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Validation(errors.join(", ")))
+        }
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error_count = self.errors().count();
+        write!(
+            f,
+            "Konfigurasjonsrapport for {:?}: {} kilder, {} feil",
+            self.runtime_env,
+            self.results.len(),
+            error_count
+        )?;
+        for result in &self.results {
+            let status = match &result.status {
+                CheckStatus::Ok => "OK".to_string(),
+                CheckStatus::Missing => "MANGLER".to_string(),
+                CheckStatus::Invalid(reason) => format!("UGYLDIG ({})", reason),
+                CheckStatus::NotRequired => "IKKE PÅKREVD".to_string(),
+            };
+            write!(f, "\n  {} {}", status, result.source)?;
+            if let Some(value) = &result.display_value {
+                write!(f, " = {}", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finner_placeholders_uten_standardverdi() {
+        let content = r#"
+            host = "${DB_HOST}"
+            port = "${DB_PORT:-5432}"
+            password = "${DB_PASSWORD}"
+            host_igjen = "${DB_HOST}"
+        "#;
+        assert_eq!(placeholders(content), vec!["DB_HOST", "DB_PASSWORD"]);
+    }
+
+    #[test]
+    fn rapporterer_alle_feil_og_sladder_hemmeligheter() {
+        temp_env::with_vars(
+            [
+                ("PAW_TEST_VALIDATION_HOST", Some("localhost")),
+                ("PAW_TEST_VALIDATION_PASSWORD", Some("hemmelig")),
+                ("PAW_TEST_VALIDATION_URL", Some("localhost:8080")),
+                ("PAW_TEST_VALIDATION_MISSING", None),
+                ("PAW_TEST_VALIDATION_CA_PATH", Some("/finnes/ikke/ca.pem")),
+            ],
+            || {
+                let report = ConfigValidator::new(RuntimeEnv::DevGcp)
+                    .require_placeholders(
+                        r#"
+                        host = "${PAW_TEST_VALIDATION_HOST}"
+                        password = "${PAW_TEST_VALIDATION_PASSWORD}"
+                        ca_path = "${PAW_TEST_VALIDATION_CA_PATH}"
+                    "#,
+                    )
+                    .require_env("PAW_TEST_VALIDATION_URL", ValueKind::Url, RequiredIn::Nais)
+                    .require_env(
+                        "PAW_TEST_VALIDATION_MISSING",
+                        ValueKind::Text,
+                        RequiredIn::Always,
+                    )
+                    .check_config("test_config.toml", Err::<(), _>("ugyldig toml"))
+                    .report();
+
+                let text = report.to_string();
+                assert!(
+                    text.contains("OK PAW_TEST_VALIDATION_HOST = localhost"),
+                    "{}",
+                    text
+                );
+                assert!(
+                    text.contains("PAW_TEST_VALIDATION_PASSWORD = ********"),
+                    "{}",
+                    text
+                );
+                assert!(!text.contains("hemmelig"), "{}", text);
+                assert_eq!(report.errors().count(), 4);
+                let error = report.into_result().unwrap_err().to_string();
+                assert!(
+                    error.contains("PAW_TEST_VALIDATION_MISSING: mangler"),
+                    "{}",
+                    error
+                );
+                assert!(
+                    error.contains("test_config.toml: ugyldig toml"),
+                    "{}",
+                    error
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn krav_gjelder_bare_for_angitte_miljoer() {
+        temp_env::with_var_unset("PAW_TEST_VALIDATION_NAIS_ONLY", || {
+            let report = ConfigValidator::new(RuntimeEnv::Local)
+                .require_env(
+                    "PAW_TEST_VALIDATION_NAIS_ONLY",
+                    ValueKind::Text,
+                    RequiredIn::Nais,
+                )
+                .require_env(
+                    "PAW_TEST_VALIDATION_NAIS_ONLY",
+                    ValueKind::Text,
+                    RequiredIn::Only(vec![RuntimeEnv::ProdGcp]),
+                )
+                .report();
+            assert!(report.is_ok());
+            assert!(report.into_result().is_ok());
+        });
+    }
+}
@@ -0,0 +1,157 @@
+use crate::error::ConfigError;
+use crate::layered::LayeredConfig;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Typet håndtak til den delen av konfigurasjonen som kan endres uten omstart.
+#[derive(Debug, Clone)]
+pub struct ConfigWatch<D> {
+    receiver: watch::Receiver<D>,
+}
+
+impl<D: Clone> ConfigWatch<D> {
+    /// Håndtak med en fast verdi som aldri endres, f.eks. for tester.
+    pub fn fixed(value: D) -> Self {
+        let (sender, receiver) = watch::channel(value);
+        // Receiveren beholder siste verdi selv om senderen er droppet
+        drop(sender);
+        ConfigWatch { receiver }
+    }
+
+    pub fn current(&self) -> D {
+        self.receiver.borrow().clone()
+    }
+
+    /// Venter til konfigurasjonen er endret. Venter for alltid dersom den aldri kan endres.
+    pub async fn changed(&mut self) {
+        if self.receiver.changed().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// Leser `D` fra `config` og starter en task som sjekker alle lagene hvert `poll_interval`.
+/// Når innholdet er endret leses `D` på nytt og sendes ut på håndtaket. Ugyldig
+/// konfigurasjon logges, og forrige verdi beholdes.
+pub fn watch_dynamic_config<D>(
+    config: LayeredConfig,
+    poll_interval: Duration,
+    cancellation_token: CancellationToken,
+) -> Result<(ConfigWatch<D>, JoinHandle<anyhow::Result<()>>), ConfigError>
+where
+    D: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let mut current_table = config.merged_table()?;
+    let initial: D = current_table.clone().try_into()?;
+    let (sender, receiver) = watch::channel(initial);
+
+    let handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            let table = match config.merged_table() {
+                Ok(table) => table,
+                Err(e) => {
+                    tracing::warn!("Kunne ikke lese konfigurasjon, beholder forrige: {}", e);
+                    continue;
+                }
+            };
+            if table == current_table {
+                continue;
+            }
+            match table.clone().try_into::<D>() {
+                Ok(value) => {
+                    tracing::info!("Dynamisk konfigurasjon er endret");
+                    sender.send_replace(value);
+                }
+                Err(e) => {
+                    tracing::warn!("Ugyldig konfigurasjon, beholder forrige: {}", e);
+                }
+            }
+            current_table = table;
+        }
+        Ok(())
+    });
+
+    Ok((ConfigWatch { receiver }, handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Dynamic {
+        batch_size: u32,
+    }
+
+    fn config_file(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "paw_app_config_watch_{}_{}.toml",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn fast_verdi_endres_ikke() {
+        let mut watch = ConfigWatch::fixed(Dynamic { batch_size: 1 });
+        assert_eq!(watch.current().batch_size, 1);
+        let changed = tokio::time::timeout(Duration::from_millis(10), watch.changed()).await;
+        assert!(changed.is_err());
+    }
+
+    #[tokio::test]
+    async fn endring_i_fil_sendes_ut() {
+        let path = config_file("endring", "batch_size = 20");
+        let token = CancellationToken::new();
+        let (mut watch, handle) = watch_dynamic_config::<Dynamic>(
+            LayeredConfig::new("batch_size = 10").with_file(&path),
+            Duration::from_millis(5),
+            token.clone(),
+        )
+        .unwrap();
+        assert_eq!(watch.current().batch_size, 20);
+
+        std::fs::write(&path, "batch_size = 30").unwrap();
+        tokio::time::timeout(Duration::from_secs(5), watch.changed())
+            .await
+            .unwrap();
+        assert_eq!(watch.current().batch_size, 30);
+
+        token.cancel();
+        handle.await.unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn ugyldig_endring_beholder_forrige_verdi() {
+        let path = config_file("ugyldig", "batch_size = 20");
+        let token = CancellationToken::new();
+        let (mut watch, handle) = watch_dynamic_config::<Dynamic>(
+            LayeredConfig::new("batch_size = 10").with_file(&path),
+            Duration::from_millis(5),
+            token.clone(),
+        )
+        .unwrap();
+
+        std::fs::write(&path, "batch_size = \"mange\"").unwrap();
+        let changed = tokio::time::timeout(Duration::from_millis(50), watch.changed()).await;
+        assert!(changed.is_err());
+        assert_eq!(watch.current().batch_size, 20);
+
+        token.cancel();
+        handle.await.unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,231 @@
+//! Norsk virkedagskalender: lørdag, søndag og offentlige helligdager er ikke virkedager.
+
+use chrono::{
+    DateTime, Datelike, Days, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Europe::Oslo;
+
+/// Første påskedag etter den gregorianske kalenderen (anonym algoritme / Meeus).
+pub fn paaskedag(aar: i32) -> NaiveDate {
+    let a = aar % 19;
+    let b = aar / 100;
+    let c = aar % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let maaned = (h + l - 7 * m + 114) / 31;
+    let dag = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(aar, maaned as u32, dag as u32)
+        .expect("Påskedag skal alltid være en gyldig dato")
+}
+
+/// Nyttårsdag, 1. mai, 17. mai, juledagene og de bevegelige helligdagene rundt påske og pinse.
+pub fn er_helligdag(dato: NaiveDate) -> bool {
+    if matches!(
+        (dato.month(), dato.day()),
+        (1, 1) | (5, 1) | (5, 17) | (12, 25) | (12, 26)
+    ) {
+        return true;
+    }
+    let dager_fra_paaske = (dato - paaskedag(dato.year())).num_days();
+    matches!(
+        dager_fra_paaske,
+        // Skjærtorsdag, langfredag, 1. og 2. påskedag
+        -3 | -2 | 0 | 1
+        // Kristi himmelfartsdag, 1. og 2. pinsedag
+        | 39 | 49 | 50
+    )
+}
+
+pub fn er_virkedag(dato: NaiveDate) -> bool {
+    !matches!(dato.weekday(), Weekday::Sat | Weekday::Sun) && !er_helligdag(dato)
+}
+
+/// `dato` hvis den er en virkedag, ellers første virkedag etter.
+pub fn neste_virkedag(dato: NaiveDate) -> NaiveDate {
+    let mut dato = dato;
+    while !er_virkedag(dato) {
+        dato = dato + Days::new(1);
+    }
+    dato
+}
+
+/// Datoen `antall` virkedager etter `dato`. Helger og helligdager hoppes over.
+pub fn legg_til_virkedager(dato: NaiveDate, antall: u32) -> NaiveDate {
+    let mut dato = dato;
+    for _ in 0..antall {
+        dato = neste_virkedag(dato + Days::new(1));
+    }
+    dato
+}
+
+/// Datoen i Norge (Europe/Oslo) for `tidspunkt`. Kalenderen gjelder norske datoer, så
+/// tidspunkter må gjøres om før de sammenlignes med virkedager.
+pub fn norsk_dato(tidspunkt: DateTime<Utc>) -> NaiveDate {
+    tidspunkt.with_timezone(&Oslo).date_naive()
+}
+
+/// Tidspunktet i UTC for et lokalt tidspunkt i norsk tid (Europe/Oslo). Når klokken stilles
+/// tilbake finnes tidspunktet to ganger, og det første brukes. Tidspunkter som ikke finnes
+/// fordi klokken stilles frem, tolkes som normaltid (UTC+1).
+pub fn fra_norsk_tid(tidspunkt: NaiveDateTime) -> DateTime<Utc> {
+    match Oslo.from_local_datetime(&tidspunkt).earliest() {
+        Some(norsk_tidspunkt) => norsk_tidspunkt.to_utc(),
+        None => Utc.from_utc_datetime(&(tidspunkt - TimeDelta::hours(1))),
+    }
+}
+
+/// Antall virkedager etter `fra` til og med `til`. 0 hvis `til` ikke er etter `fra`.
+pub fn virkedager_mellom(fra: NaiveDate, til: NaiveDate) -> u32 {
+    fra.iter_days()
+        .skip(1)
+        .take_while(|dato| *dato <= til)
+        .filter(|dato| er_virkedag(*dato))
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dato(aar: i32, maaned: u32, dag: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(aar, maaned, dag).unwrap()
+    }
+
+    #[test]
+    fn paaskedag_for_kjente_aar() {
+        assert_eq!(paaskedag(2024), dato(2024, 3, 31));
+        assert_eq!(paaskedag(2025), dato(2025, 4, 20));
+        assert_eq!(paaskedag(2026), dato(2026, 4, 5));
+        assert_eq!(paaskedag(2027), dato(2027, 3, 28));
+    }
+
+    #[test]
+    fn helligdager_2026() {
+        let helligdager = [
+            dato(2026, 1, 1),
+            dato(2026, 4, 2),
+            dato(2026, 4, 3),
+            dato(2026, 4, 5),
+            dato(2026, 4, 6),
+            dato(2026, 5, 1),
+            dato(2026, 5, 14),
+            dato(2026, 5, 17),
+            dato(2026, 5, 24),
+            dato(2026, 5, 25),
+            dato(2026, 12, 25),
+            dato(2026, 12, 26),
+        ];
+        for helligdag in helligdager {
+            assert!(er_helligdag(helligdag), "{helligdag} skal være helligdag");
+        }
+        let antall = dato(2026, 1, 1)
+            .iter_days()
+            .take_while(|d| d.year() == 2026)
+            .filter(|d| er_helligdag(*d))
+            .count();
+        assert_eq!(antall, helligdager.len());
+    }
+
+    #[test]
+    fn helg_og_helligdag_er_ikke_virkedag() {
+        assert!(er_virkedag(dato(2026, 10, 19)));
+        assert!(!er_virkedag(dato(2026, 10, 17)));
+        assert!(!er_virkedag(dato(2026, 10, 18)));
+        assert!(!er_virkedag(dato(2026, 4, 6)));
+    }
+
+    #[test]
+    fn neste_virkedag_hopper_over_helg_og_paaske() {
+        assert_eq!(neste_virkedag(dato(2026, 10, 19)), dato(2026, 10, 19));
+        assert_eq!(neste_virkedag(dato(2026, 10, 17)), dato(2026, 10, 19));
+        assert_eq!(neste_virkedag(dato(2026, 4, 2)), dato(2026, 4, 7));
+    }
+
+    #[test]
+    fn legg_til_virkedager_over_helg_og_jul() {
+        // Fredag + 1 virkedag = mandag
+        assert_eq!(
+            legg_til_virkedager(dato(2026, 10, 16), 1),
+            dato(2026, 10, 19)
+        );
+        assert_eq!(
+            legg_til_virkedager(dato(2026, 10, 19), 3),
+            dato(2026, 10, 22)
+        );
+        // Onsdag 23.12 + 3 virkedager: 24.12, 28.12, 29.12
+        assert_eq!(
+            legg_til_virkedager(dato(2026, 12, 23), 3),
+            dato(2026, 12, 29)
+        );
+        assert_eq!(
+            legg_til_virkedager(dato(2026, 10, 17), 0),
+            dato(2026, 10, 17)
+        );
+    }
+
+    #[test]
+    fn norsk_dato_bruker_norsk_tid_ogsaa_rundt_midnatt() {
+        let tidspunkt = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().to_utc();
+        // Sommertid, UTC+2
+        assert_eq!(
+            norsk_dato(tidspunkt("2026-10-18T22:30:00Z")),
+            dato(2026, 10, 19)
+        );
+        assert_eq!(
+            norsk_dato(tidspunkt("2026-10-19T21:59:00Z")),
+            dato(2026, 10, 19)
+        );
+        // Normaltid, UTC+1
+        assert_eq!(
+            norsk_dato(tidspunkt("2026-12-23T23:30:00Z")),
+            dato(2026, 12, 24)
+        );
+        assert_eq!(
+            norsk_dato(tidspunkt("2026-12-23T22:59:00Z")),
+            dato(2026, 12, 23)
+        );
+    }
+
+    #[test]
+    fn fra_norsk_tid_gir_utc_ogsaa_rundt_sommertid() {
+        let lokalt = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        let utc = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().to_utc();
+        // Sommertid, UTC+2
+        assert_eq!(
+            fra_norsk_tid(lokalt("2026-10-19 00:30")),
+            utc("2026-10-18T22:30:00Z")
+        );
+        // Normaltid, UTC+1
+        assert_eq!(
+            fra_norsk_tid(lokalt("2026-12-24 00:30")),
+            utc("2026-12-23T23:30:00Z")
+        );
+        // Klokken stilles tilbake 25. oktober 2026, 02:30 finnes to ganger
+        assert_eq!(
+            fra_norsk_tid(lokalt("2026-10-25 02:30")),
+            utc("2026-10-25T00:30:00Z")
+        );
+        // Klokken stilles frem 29. mars 2026, 02:30 finnes ikke
+        assert_eq!(
+            fra_norsk_tid(lokalt("2026-03-29 02:30")),
+            utc("2026-03-29T01:30:00Z")
+        );
+    }
+
+    #[test]
+    fn virkedager_mellom_teller_ikke_helg_og_helligdager() {
+        assert_eq!(virkedager_mellom(dato(2026, 10, 19), dato(2026, 10, 19)), 0);
+        assert_eq!(virkedager_mellom(dato(2026, 10, 16), dato(2026, 10, 19)), 1);
+        assert_eq!(virkedager_mellom(dato(2026, 10, 19), dato(2026, 10, 26)), 5);
+        // Onsdag før påske til onsdag etter: skjærtorsdag, langfredag og 2. påskedag er fri
+        assert_eq!(virkedager_mellom(dato(2026, 4, 1), dato(2026, 4, 8)), 2);
+        assert_eq!(virkedager_mellom(dato(2026, 10, 26), dato(2026, 10, 19)), 0);
+    }
+}
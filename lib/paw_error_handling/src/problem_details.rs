@@ -45,6 +45,42 @@ impl ProblemDetails {
         }
     }
 
+    pub fn forbidden(instance: &str, detail: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            problem_type: "urn:paw:http:forbidden".to_string(),
+            title: "Forbidden".to_string(),
+            status: 403u16,
+            detail: Some(detail.to_string()),
+            instance: instance.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn not_found(instance: &str, detail: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            problem_type: "urn:paw:http:not-found".to_string(),
+            title: "Not Found".to_string(),
+            status: 404u16,
+            detail: Some(detail.to_string()),
+            instance: instance.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn conflict(instance: &str, detail: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            problem_type: "urn:paw:http:conflict".to_string(),
+            title: "Conflict".to_string(),
+            status: 409u16,
+            detail: Some(detail.to_string()),
+            instance: instance.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
     pub fn database_error(instance: &str, detail: &str) -> Self {
         Self {
             id: Uuid::new_v4(),
@@ -29,6 +29,7 @@ mod tests {
                 .unwrap(),
             name: None,
             roles: vec![],
+            groups: vec![],
         });
         assert!(AllowAll.is_authorized(&principal));
     }
@@ -0,0 +1,153 @@
+use std::fmt::Write as FmtWrite;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_subscriber::filter::{FilterFn, filter_fn};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, MakeWriter};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{Layer, fmt};
+
+/// Log target for auditlogg (CEF-linjer med identitetsnummer). Hendelser med dette target går bare
+/// til auditlogg-laget, og aldri til stdout eller OpenTelemetry.
+pub const AUDIT_LOG_TARGET: &str = "auditlog";
+
+fn er_audit(metadata: &Metadata<'_>) -> bool {
+    metadata.target() == AUDIT_LOG_TARGET
+}
+
+/// Filter for vanlige lag (stdout og OpenTelemetry) som slipper gjennom alt unntatt auditlogg
+pub(crate) fn uten_audit() -> FilterFn {
+    filter_fn(|metadata| !er_audit(metadata))
+}
+
+/// Lag som skriver meldingen i auditlogg-hendelser, én linje per hendelse, til `writer`
+pub(crate) fn audit_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    fmt::layer()
+        .event_format(AuditFormat)
+        .with_ansi(false)
+        .with_writer(writer)
+        .with_filter(filter_fn(er_audit))
+}
+
+/// Skriver bare meldingen, uten tidspunkt, nivå eller span-felter, siden CEF-linjen er komplett
+struct AuditFormat;
+
+impl<S, N> FormatEvent<S, N> for AuditFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        struct MeldingVisitor(String);
+
+        impl Visit for MeldingVisitor {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    let _ = write!(self.0, "{:?}", value);
+                }
+            }
+        }
+
+        let mut visitor = MeldingVisitor(String::new());
+        event.record(&mut visitor);
+        writeln!(writer, "{}", visitor.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel_format::OtelFormat;
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Buffer {
+        fn innhold(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn auditlogg_skrives_bare_til_audit_laget() {
+        for format in [OtelFormat::OtelJson, OtelFormat::Compact] {
+            let stdout = Buffer::default();
+            let audit = Buffer::default();
+            let subscriber = tracing_subscriber::registry()
+                .with(crate::otel_setup::stdout_layer(format, stdout.clone()))
+                .with(audit_layer(audit.clone()));
+
+            tracing::subscriber::with_default(subscriber, || {
+                let span = tracing::info_span!("oppslag", bruker = "Z999999");
+                let _guard = span.enter();
+                tracing::info!("Vanlig logglinje");
+                tracing::info!(target: AUDIT_LOG_TARGET, "CEF:0|paw|suid=Z999999 duid=12345678901");
+            });
+
+            let stdout = stdout.innhold();
+            assert!(stdout.contains("Vanlig logglinje"));
+            assert!(!stdout.contains("12345678901"));
+            assert!(!stdout.contains("CEF:0"));
+            assert_eq!(audit.innhold(), "CEF:0|paw|suid=Z999999 duid=12345678901\n");
+        }
+    }
+
+    #[test]
+    fn auditlogg_sendes_ikke_til_opentelemetry() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(crate::otel_setup::otel_layer(provider.tracer("test")))
+            .with(audit_layer(Buffer::default()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("oppslag");
+            let _guard = span.enter();
+            tracing::info!("Vanlig logglinje");
+            tracing::info!(target: AUDIT_LOG_TARGET, "CEF:0|paw|duid=12345678901");
+        });
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 1);
+        let hendelser: Vec<_> = spans[0]
+            .events
+            .iter()
+            .map(|hendelse| hendelse.name.to_string())
+            .collect();
+        assert_eq!(hendelser, vec!["Vanlig logglinje"]);
+    }
+}
@@ -5,4 +5,7 @@ use serde::Deserialize;
 pub struct OtelTracingConfig {
     pub format: OtelFormat,
     pub directives: Vec<String>,
+    /// Fil for auditlogg (log target `AUDIT_LOG_TARGET`). Uten fil forkastes auditlogg.
+    #[serde(default)]
+    pub audit_log_path: Option<String>,
 }
@@ -1,3 +1,4 @@
+pub mod audit_log;
 pub mod config;
 pub mod error;
 pub(crate) mod header_extractor;
@@ -5,3 +6,4 @@ pub(crate) mod otel_format;
 pub mod otel_middleware;
 pub mod otel_setup;
 pub(crate) mod otlp_exporter;
+pub mod trace_id;
@@ -1,17 +1,23 @@
+use crate::audit_log::{audit_layer, uten_audit};
 use crate::config::OtelTracingConfig;
+use crate::otel_format::OtelFormat;
 use crate::otlp_exporter::nais_otlp_exporter;
 use anyhow::Result;
-use opentelemetry::trace::TracerProvider;
+use opentelemetry::trace::{Tracer, TracerProvider};
 use opentelemetry::{global, KeyValue};
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::Resource;
 use paw_rust_base::env::{nais_namespace, nais_otel_service_name};
-use tracing::info;
+use std::fs::OpenOptions;
+use std::sync::Mutex;
+use tracing::{info, Subscriber};
 use tracing_opentelemetry::OpenTelemetryLayer;
 
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_subscriber::{fmt, EnvFilter, Layer};
 
 pub fn setup_otel(config: OtelTracingConfig) -> Result<()> {
     let exporter = nais_otlp_exporter()?;
@@ -46,12 +52,18 @@ pub fn setup_otel(config: OtelTracingConfig) -> Result<()> {
             directive.parse().map(|d| filter.add_directive(d))
         })?;
 
-    let fmt_layer = fmt::layer().event_format(config.format).with_ansi(false);
+    let audit_layer = match &config.audit_log_path {
+        Some(path) => Some(audit_layer(Mutex::new(
+            OpenOptions::new().create(true).append(true).open(path)?,
+        ))),
+        None => None,
+    };
 
     tracing_subscriber::registry()
         .with(filter)
-        .with(OpenTelemetryLayer::new(tracer))
-        .with(fmt_layer)
+        .with(otel_layer(tracer))
+        .with(stdout_layer(config.format, std::io::stdout))
+        .with(audit_layer)
         .init();
     info!(
         "Initialized NAIS OpenTelemetry with service name: {}, namespace: {}, exporter_active={}",
@@ -59,3 +71,26 @@ pub fn setup_otel(config: OtelTracingConfig) -> Result<()> {
     );
     Ok(())
 }
+
+/// Sender spans og hendelser til OpenTelemetry, unntatt auditlogg
+pub(crate) fn otel_layer<S, T>(tracer: T) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    T: Tracer + Send + Sync + 'static,
+    T::Span: Send + Sync,
+{
+    OpenTelemetryLayer::new(tracer).with_filter(uten_audit())
+}
+
+/// Skriver logglinjer i `format` til `writer` (stdout), unntatt auditlogg
+pub(crate) fn stdout_layer<S, W>(format: OtelFormat, writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    fmt::layer()
+        .event_format(format)
+        .with_ansi(false)
+        .with_writer(writer)
+        .with_filter(uten_audit())
+}
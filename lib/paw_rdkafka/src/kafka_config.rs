@@ -1,5 +1,6 @@
 use crate::error::KafkaError;
 use anyhow::Result;
+use paw_app_config::secret::Secret;
 use rdkafka::ClientConfig;
 use rdkafka::config::RDKafkaLogLevel;
 use serde::Deserialize;
@@ -68,9 +69,9 @@ pub fn create_kafka_client_config(kafka_config: KafkaConfig) -> Result<ClientCon
             .ok_or_else(|| "Missing ca path".to_string())
             .map_err(KafkaError::Config)?;
         config
-            .set("ssl.key.location", private_key_path.into_inner())
-            .set("ssl.certificate.location", certificate_path.into_inner())
-            .set("ssl.ca.location", ca_path.into_inner());
+            .set("ssl.key.location", private_key_path.expose())
+            .set("ssl.certificate.location", certificate_path.expose())
+            .set("ssl.ca.location", ca_path.expose());
     }
 
     Ok(config)
@@ -83,9 +84,9 @@ pub struct KafkaConfig {
     pub group_id_prefix: String,
     pub auto_commit: Option<bool>,
     pub security_protocol: Option<String>,
-    pub private_key_path: Option<String>,
-    pub certificate_path: Option<String>,
-    pub ca_path: Option<String>,
+    pub private_key_path: Option<Secret<String>>,
+    pub certificate_path: Option<Secret<String>>,
+    pub ca_path: Option<Secret<String>>,
     pub auto_offset_reset: Option<String>,
     pub session_timeout_ms: Option<i64>,
     pub hwm_version: i16,
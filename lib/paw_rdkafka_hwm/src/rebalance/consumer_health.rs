@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use health_and_monitoring::CheckType;
+use health_and_monitoring::dependency_health::{DependencyCheck, DependencyHealth};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_MAX_REBALANCE_DURATION: Duration = Duration::from_secs(120);
+
+/// Tilstand for consumeren som oppdateres av `HwmRebalanceHandler` og leses av helsesjekkene.
+#[derive(Debug, Default)]
+pub struct KafkaConsumerHealth {
+    has_been_assigned: AtomicBool,
+    assigned_partitions: AtomicUsize,
+    rebalance_started: Mutex<Option<Instant>>,
+}
+
+impl KafkaConsumerHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rebalance_started(&self) {
+        *self.rebalance_started.lock().unwrap() = Some(Instant::now());
+    }
+
+    pub fn rebalance_finished(&self) {
+        *self.rebalance_started.lock().unwrap() = None;
+    }
+
+    pub fn set_assigned_partitions(&self, partitions: usize) {
+        self.assigned_partitions.store(partitions, Ordering::Relaxed);
+        self.has_been_assigned.store(true, Ordering::Relaxed);
+    }
+
+    pub fn has_been_assigned(&self) -> bool {
+        self.has_been_assigned.load(Ordering::Relaxed)
+    }
+
+    pub fn assigned_partitions(&self) -> usize {
+        self.assigned_partitions.load(Ordering::Relaxed)
+    }
+
+    pub fn rebalance_duration(&self) -> Option<Duration> {
+        self.rebalance_started
+            .lock()
+            .unwrap()
+            .map(|started| started.elapsed())
+    }
+}
+
+/// Feiler til consumeren har fått tildelt partisjoner for første gang.
+pub struct KafkaAssignmentCheck {
+    consumer_health: Arc<KafkaConsumerHealth>,
+}
+
+impl KafkaAssignmentCheck {
+    pub fn new(consumer_health: Arc<KafkaConsumerHealth>) -> Self {
+        Self { consumer_health }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for KafkaAssignmentCheck {
+    fn name(&self) -> String {
+        "kafka-assignment".to_string()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        if self.consumer_health.has_been_assigned() {
+            Ok(())
+        } else {
+            Err("Consumer har ikke fått tildelt partisjoner".to_string())
+        }
+    }
+}
+
+/// Feiler dersom en rebalansering har pågått lenger enn `max_rebalance_duration`.
+pub struct KafkaRebalanceCheck {
+    consumer_health: Arc<KafkaConsumerHealth>,
+    max_rebalance_duration: Duration,
+}
+
+impl KafkaRebalanceCheck {
+    pub fn new(
+        consumer_health: Arc<KafkaConsumerHealth>,
+        max_rebalance_duration: Duration,
+    ) -> Self {
+        Self {
+            consumer_health,
+            max_rebalance_duration,
+        }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for KafkaRebalanceCheck {
+    fn name(&self) -> String {
+        "kafka-rebalance".to_string()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        match self.consumer_health.rebalance_duration() {
+            Some(duration) if duration > self.max_rebalance_duration => Err(format!(
+                "Rebalansering har pågått i {} sekunder",
+                duration.as_secs()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Legger til sjekk for tildeling av partisjoner (readiness) og langvarig rebalansering
+/// (liveness) for consumeren som oppdaterer `consumer_health`.
+pub fn add_kafka_consumer_checks(
+    dependency_health: &mut DependencyHealth,
+    consumer_health: Arc<KafkaConsumerHealth>,
+) {
+    dependency_health.add_check(
+        KafkaAssignmentCheck::new(consumer_health.clone()),
+        CheckType::IsReady,
+    );
+    dependency_health.add_check(
+        KafkaRebalanceCheck::new(consumer_health, DEFAULT_MAX_REBALANCE_DURATION),
+        CheckType::IsAlive,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn assignment_check_feiler_foer_tildeling() {
+        let consumer_health = Arc::new(KafkaConsumerHealth::new());
+        let check = KafkaAssignmentCheck::new(consumer_health.clone());
+        assert!(check.check().await.is_err());
+        consumer_health.set_assigned_partitions(0);
+        assert!(check.check().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn rebalance_check_feiler_ved_langvarig_rebalansering() {
+        let consumer_health = Arc::new(KafkaConsumerHealth::new());
+        let check = KafkaRebalanceCheck::new(consumer_health.clone(), Duration::ZERO);
+        assert!(check.check().await.is_ok());
+        consumer_health.rebalance_started();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(check.check().await.is_err());
+        consumer_health.rebalance_finished();
+        assert!(check.check().await.is_ok());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::rebalance::consumer_health::KafkaConsumerHealth;
 use crate::rebalance::get_hwms::get_hwms;
 use health_and_monitoring::simple_app_state::AppState;
 use rdkafka::ClientContext;
@@ -12,6 +13,7 @@ pub struct HwmRebalanceHandler {
     pub pg_pool: PgPool,
     pub app_state: Arc<AppState>,
     pub version: i16,
+    pub consumer_health: Arc<KafkaConsumerHealth>,
 }
 
 impl ConsumerContext for HwmRebalanceHandler {
@@ -20,6 +22,19 @@ impl ConsumerContext for HwmRebalanceHandler {
         base_consumer: &BaseConsumer<Self>,
         err: RDKafkaRespErr,
         tpl: &mut TopicPartitionList,
+    ) {
+        self.consumer_health.rebalance_started();
+        self.handle_rebalance(base_consumer, err, tpl);
+        self.consumer_health.rebalance_finished();
+    }
+}
+
+impl HwmRebalanceHandler {
+    fn handle_rebalance(
+        &self,
+        base_consumer: &BaseConsumer<Self>,
+        err: RDKafkaRespErr,
+        tpl: &mut TopicPartitionList,
     ) {
         match err {
             RDKafkaRespErr::RD_KAFKA_RESP_ERR__ASSIGN_PARTITIONS => {
@@ -45,6 +60,7 @@ impl ConsumerContext for HwmRebalanceHandler {
                 match base_consumer.assign(tpl) {
                     Ok(_) => {
                         tracing::info!(partitions = ?tpl_as_string(tpl), "Consumer assigned with HWM offsets");
+                        self.consumer_health.set_assigned_partitions(tpl.count());
                     }
                     Err(e) => {
                         tracing::error!(error = %e, "Failed to assign partitions");
@@ -57,7 +73,10 @@ impl ConsumerContext for HwmRebalanceHandler {
                 tracing::info!(partitions = ?tpl_as_string(tpl), "Partitions revoked");
 
                 match base_consumer.unassign() {
-                    Ok(_) => tracing::info!("Unassigned partitions from consumer"),
+                    Ok(_) => {
+                        tracing::info!("Unassigned partitions from consumer");
+                        self.consumer_health.set_assigned_partitions(0);
+                    }
                     Err(e) => {
                         tracing::error!(error = %e, "Failed to unassign partitions");
                         self.app_state.set_is_alive(false);
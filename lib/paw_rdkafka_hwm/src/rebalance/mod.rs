@@ -1,2 +1,3 @@
+pub mod consumer_health;
 pub mod hwm_rebalance_handler;
 mod get_hwms;
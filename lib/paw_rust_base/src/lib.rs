@@ -2,3 +2,4 @@ pub mod env;
 pub mod error;
 pub mod panic_logger;
 pub mod git;
+pub mod task_supervisor;
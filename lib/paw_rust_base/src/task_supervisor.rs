@@ -0,0 +1,302 @@
+use crate::error::ServerError;
+use anyhow::Result;
+use std::time::Duration;
+use tokio::signal::unix::{SignalKind, signal};
+use tokio::task::{AbortHandle, JoinError, JoinHandle, JoinSet};
+use tokio_util::sync::CancellationToken;
+
+pub const DEFAULT_SHUTDOWN_DEADLINE: Duration = Duration::from_secs(20);
+pub const DEFAULT_DRAIN_PERIOD: Duration = Duration::from_secs(5);
+
+type ShutdownHook = Box<dyn FnOnce() + Send + 'static>;
+
+/// Felles håndtering av applikasjonens tasks og nedstenging.
+///
+/// Når en task avslutter, eller applikasjonen mottar SIGTERM/SIGINT, kjøres først
+/// shutdown-hooks (f.eks. sette readiness til false). Etter `drain_period`, slik at
+/// Kubernetes rekker å se readiness og slutte å sende trafikk, kanselleres
+/// `cancellation_token` slik at tasks kan fullføre pågående arbeid. Tasks som ikke er
+/// ferdige innen `shutdown_deadline` avbrytes.
+pub struct TaskSupervisor {
+    cancellation_token: CancellationToken,
+    drain_period: Duration,
+    shutdown_deadline: Duration,
+    shutdown_hooks: Vec<ShutdownHook>,
+    tasks: JoinSet<(String, Result<Result<()>, JoinError>)>,
+    abort_handles: Vec<(String, AbortHandle)>,
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self::new(DEFAULT_SHUTDOWN_DEADLINE)
+    }
+}
+
+impl TaskSupervisor {
+    pub fn new(shutdown_deadline: Duration) -> Self {
+        TaskSupervisor {
+            cancellation_token: CancellationToken::new(),
+            drain_period: DEFAULT_DRAIN_PERIOD,
+            shutdown_deadline,
+            shutdown_hooks: Vec::new(),
+            tasks: JoinSet::new(),
+            abort_handles: Vec::new(),
+        }
+    }
+
+    /// Tid mellom shutdown-hooks og kansellering av tasks.
+    pub fn with_drain_period(mut self, drain_period: Duration) -> Self {
+        self.drain_period = drain_period;
+        self
+    }
+
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
+    pub fn add_task(&mut self, name: &str, handle: JoinHandle<Result<()>>) {
+        self.abort_handles
+            .push((name.to_string(), handle.abort_handle()));
+        let name = name.to_string();
+        self.tasks.spawn(async move { (name, handle.await) });
+    }
+
+    /// Kjøres i registreringsrekkefølge før tasks kanselleres.
+    pub fn on_shutdown(&mut self, hook: impl FnOnce() + Send + 'static) {
+        self.shutdown_hooks.push(Box::new(hook));
+    }
+
+    /// Venter på shutdown-signal eller at en task avslutter, og stenger deretter ned.
+    /// Returnerer feil dersom en task avsluttet med feil, eller signalet ikke kunne settes opp.
+    pub async fn run(self) -> Result<()> {
+        self.run_until(shutdown_signal()).await
+    }
+
+    async fn run_until(mut self, signal: impl Future<Output = Result<String>>) -> Result<()> {
+        let mut first_error = None;
+        tokio::select! {
+            signal = signal => match signal {
+                Ok(signal) => tracing::info!("Mottok shutdown-signal: {}", signal),
+                Err(e) => {
+                    tracing::error!("Kunne ikke vente på shutdown-signal, stenger ned: {}", e);
+                    first_error = Some(e);
+                }
+            },
+            Some(Ok((name, result))) = self.tasks.join_next(), if !self.tasks.is_empty() => {
+                tracing::warn!("{} avsluttet, starter nedstenging", name);
+                first_error = handle_task_result(&name, result);
+            }
+        }
+
+        for hook in self.shutdown_hooks.drain(..) {
+            hook();
+        }
+        if !self.drain_period.is_zero() {
+            tracing::info!(
+                "Venter {} sekunder før tasks stoppes",
+                self.drain_period.as_secs()
+            );
+            tokio::time::sleep(self.drain_period).await;
+        }
+        self.cancellation_token.cancel();
+
+        let drain = async {
+            let mut errors = Vec::new();
+            while let Some(joined) = self.tasks.join_next().await {
+                if let Ok((name, result)) = joined {
+                    errors.extend(handle_task_result(&name, result));
+                }
+            }
+            errors
+        };
+        match tokio::time::timeout(self.shutdown_deadline, drain).await {
+            Ok(errors) => {
+                first_error = first_error.or(errors.into_iter().next());
+            }
+            Err(_) => {
+                for (name, abort_handle) in &self.abort_handles {
+                    if !abort_handle.is_finished() {
+                        tracing::error!(
+                            "{} ble ikke ferdig innen {} sekunder, avbryter",
+                            name,
+                            self.shutdown_deadline.as_secs()
+                        );
+                        abort_handle.abort();
+                    }
+                }
+            }
+        }
+        tracing::info!("Alle tasks er stoppet");
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+pub async fn shutdown_signal() -> Result<String> {
+    let mut term_signal = signal(SignalKind::terminate())?;
+    let mut interrupt_signal = signal(SignalKind::interrupt())?;
+    tokio::select! {
+        _ = term_signal.recv() => Ok("SIGTERM".to_string()),
+        _ = interrupt_signal.recv() => Ok("SIGINT".to_string())
+    }
+}
+
+fn handle_task_result(name: &str, result: Result<Result<()>, JoinError>) -> Option<anyhow::Error> {
+    match result {
+        Ok(Ok(())) => {
+            tracing::info!("{} avsluttet normalt", name);
+            None
+        }
+        Ok(Err(e)) => {
+            tracing::error!("{} avsluttet med feil: {}", name, e);
+            Some(
+                ServerError::InternalProcessTerminated {
+                    process: name.to_string(),
+                    message: e.to_string(),
+                }
+                .into(),
+            )
+        }
+        Err(e) if e.is_cancelled() => {
+            tracing::warn!("{} ble avbrutt", name);
+            None
+        }
+        Err(e) => {
+            tracing::error!("Feil i spawned task for {}: {}", name, e);
+            Some(
+                ServerError::InternalProcessTerminated {
+                    process: name.to_string(),
+                    message: e.to_string(),
+                }
+                .into(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tokio::time::Instant;
+
+    fn cooperative_task(token: CancellationToken, done: Arc<AtomicBool>) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move {
+            token.cancelled().await;
+            done.store(true, Ordering::SeqCst);
+            Ok(())
+        })
+    }
+
+    #[tokio::test]
+    async fn signal_kjoerer_hooks_og_kansellerer_tasks() {
+        let mut supervisor = TaskSupervisor::default().with_drain_period(Duration::ZERO);
+        let done = Arc::new(AtomicBool::new(false));
+        let hook_called = Arc::new(AtomicBool::new(false));
+        supervisor.add_task(
+            "Cooperative",
+            cooperative_task(supervisor.cancellation_token(), done.clone()),
+        );
+        let hook_flag = hook_called.clone();
+        supervisor.on_shutdown(move || hook_flag.store(true, Ordering::SeqCst));
+
+        let result = supervisor
+            .run_until(async { Ok("SIGTERM".to_string()) })
+            .await;
+
+        assert!(result.is_ok());
+        assert!(hook_called.load(Ordering::SeqCst));
+        assert!(done.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn feilende_task_stopper_andre_tasks_og_gir_feil() {
+        let mut supervisor = TaskSupervisor::default().with_drain_period(Duration::ZERO);
+        let done = Arc::new(AtomicBool::new(false));
+        supervisor.add_task(
+            "Cooperative",
+            cooperative_task(supervisor.cancellation_token(), done.clone()),
+        );
+        supervisor.add_task(
+            "Feilende",
+            tokio::spawn(async { Err(anyhow::anyhow!("boom")) }),
+        );
+
+        let result = supervisor.run_until(std::future::pending()).await;
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("Feilende"), "{}", error);
+        assert!(done.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn task_som_ikke_stopper_avbrytes_etter_frist() {
+        let mut supervisor =
+            TaskSupervisor::new(Duration::from_millis(10)).with_drain_period(Duration::ZERO);
+        let handle = tokio::spawn(async {
+            std::future::pending::<()>().await;
+            Ok(())
+        });
+        let abort_handle = handle.abort_handle();
+        supervisor.add_task("Haengende", handle);
+
+        let result = supervisor
+            .run_until(async { Ok("SIGTERM".to_string()) })
+            .await;
+
+        assert!(result.is_ok());
+        tokio::task::yield_now().await;
+        assert!(abort_handle.is_finished());
+    }
+
+    #[tokio::test]
+    async fn tasks_kanselleres_foerst_etter_drain_period() {
+        let drain_period = Duration::from_millis(100);
+        let mut supervisor = TaskSupervisor::default().with_drain_period(drain_period);
+        let hook_called_at = Arc::new(Mutex::new(None));
+        let cancelled_at = Arc::new(Mutex::new(None));
+        let token = supervisor.cancellation_token();
+        let task_cancelled_at = cancelled_at.clone();
+        supervisor.add_task(
+            "Cooperative",
+            tokio::spawn(async move {
+                token.cancelled().await;
+                *task_cancelled_at.lock().unwrap() = Some(Instant::now());
+                Ok(())
+            }),
+        );
+        let hook_flag = hook_called_at.clone();
+        supervisor.on_shutdown(move || *hook_flag.lock().unwrap() = Some(Instant::now()));
+
+        let result = supervisor
+            .run_until(async { Ok("SIGTERM".to_string()) })
+            .await;
+
+        assert!(result.is_ok());
+        let hook_called_at = hook_called_at.lock().unwrap().unwrap();
+        let cancelled_at = cancelled_at.lock().unwrap().unwrap();
+        assert!(cancelled_at - hook_called_at >= drain_period);
+    }
+
+    #[tokio::test]
+    async fn feil_fra_signal_kansellerer_tasks_og_gir_feil() {
+        let mut supervisor = TaskSupervisor::default().with_drain_period(Duration::ZERO);
+        let done = Arc::new(AtomicBool::new(false));
+        supervisor.add_task(
+            "Cooperative",
+            cooperative_task(supervisor.cancellation_token(), done.clone()),
+        );
+
+        let result = supervisor
+            .run_until(async { Err(anyhow::anyhow!("signal feilet")) })
+            .await;
+
+        assert_eq!(result.unwrap_err().to_string(), "signal feilet");
+        assert!(done.load(Ordering::SeqCst));
+    }
+}
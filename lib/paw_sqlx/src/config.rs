@@ -1,14 +1,15 @@
+use paw_app_config::secret::Secret;
 use serde::Deserialize;
 use serde_env_field::env_field_wrap;
 use std::str::FromStr;
 
 #[env_field_wrap]
-#[derive(Deserialize)]
+#[derive(Debug, Deserialize)]
 pub struct DatabaseConfig {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub password: String,
+    pub password: Secret<String>,
     pub database: String,
     pub statement_log_level: Option<String>
 }
@@ -17,7 +18,11 @@ impl DatabaseConfig {
     pub fn full_url(&self) -> String {
         format!(
             "postgresql://{}:{}@{}:{}/{}",
-            self.username, self.password, self.host, self.port, self.database
+            self.username,
+            self.password.expose(),
+            self.host,
+            self.port,
+            self.database
         )
     }
 
@@ -29,18 +34,6 @@ impl DatabaseConfig {
     }
 }
 
-impl std::fmt::Debug for DatabaseConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("DatabaseConfig")
-            .field("host", &self.host)
-            .field("port", &self.port)
-            .field("username", &self.username)
-            .field("password", &"********")
-            .field("database", &self.database)
-            .finish()
-    }
-}
-
 impl std::fmt::Display for DatabaseConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
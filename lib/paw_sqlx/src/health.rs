@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use health_and_monitoring::dependency_health::DependencyCheck;
+use sqlx::PgPool;
+
+pub struct PgPoolCheck {
+    pg_pool: PgPool,
+}
+
+impl PgPoolCheck {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for PgPoolCheck {
+    fn name(&self) -> String {
+        "postgres".to_string()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pg_pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
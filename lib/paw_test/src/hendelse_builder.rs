@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use interne_hendelser::{
-    Avvist, InterneHendelser, Startet,
+    AutomatiskIdMergeIkkeMulig, Avvist, AvvistStoppAvPeriode, InterneHendelser, PeriodeRad,
+    Startet,
     vo::{Bruker, BrukerType, Metadata, Opplysning},
 };
 use std::collections::HashSet;
@@ -134,3 +135,108 @@ impl AvvistBuilder {
         }
     }
 }
+
+impl AsJson for AvvistStoppAvPeriode {
+    fn as_json(&self) -> String {
+        serde_json::to_string(&InterneHendelser::AvvistStoppAvPeriode(self.clone()))
+            .expect("AvvistStoppAvPeriode skal kunne serialiseres")
+    }
+}
+
+pub struct AvvistStoppAvPeriodeBuilder {
+    pub hendelse_id: Uuid,
+    pub arbeidssoeker_id: i64,
+    pub identitetsnummer: String,
+    pub tidspunkt: DateTime<Utc>,
+    pub bruker_type: BrukerType,
+    pub utfoert_av_id: String,
+    pub opplysninger: HashSet<Opplysning>,
+}
+
+impl Default for AvvistStoppAvPeriodeBuilder {
+    fn default() -> Self {
+        Self {
+            hendelse_id: Uuid::new_v4(),
+            arbeidssoeker_id: 1,
+            identitetsnummer: default_ident(),
+            tidspunkt: default_tidspunkt(),
+            bruker_type: BrukerType::System,
+            utfoert_av_id: "Testsystem".to_string(),
+            opplysninger: HashSet::new(),
+        }
+    }
+}
+
+impl AvvistStoppAvPeriodeBuilder {
+    pub fn build(&self) -> AvvistStoppAvPeriode {
+        AvvistStoppAvPeriode {
+            hendelse_id: self.hendelse_id,
+            id: self.arbeidssoeker_id,
+            identitetsnummer: self.identitetsnummer.clone(),
+            metadata: Metadata {
+                tidspunkt: self.tidspunkt,
+                utfoert_av: Bruker {
+                    bruker_type: self.bruker_type.clone(),
+                    id: self.utfoert_av_id.clone(),
+                    sikkerhetsnivaa: None,
+                },
+                kilde: "Testkilde".to_string(),
+                aarsak: "Test".to_string(),
+                tidspunkt_fra_kilde: None,
+            },
+            opplysninger: self.opplysninger.clone(),
+        }
+    }
+}
+
+impl AsJson for AutomatiskIdMergeIkkeMulig {
+    fn as_json(&self) -> String {
+        serde_json::to_string(&InterneHendelser::AutomatiskIdMergeIkkeMulig(self.clone()))
+            .expect("AutomatiskIdMergeIkkeMulig skal kunne serialiseres")
+    }
+}
+
+pub struct AutomatiskIdMergeIkkeMuligBuilder {
+    pub hendelse_id: Uuid,
+    pub arbeidssoeker_id: i64,
+    pub identitetsnummer: String,
+    pub tidspunkt: DateTime<Utc>,
+    pub perioder: HashSet<PeriodeRad>,
+}
+
+impl Default for AutomatiskIdMergeIkkeMuligBuilder {
+    fn default() -> Self {
+        Self {
+            hendelse_id: Uuid::new_v4(),
+            arbeidssoeker_id: 1,
+            identitetsnummer: default_ident(),
+            tidspunkt: default_tidspunkt(),
+            perioder: HashSet::new(),
+        }
+    }
+}
+
+impl AutomatiskIdMergeIkkeMuligBuilder {
+    pub fn build(&self) -> AutomatiskIdMergeIkkeMulig {
+        AutomatiskIdMergeIkkeMulig {
+            identitetsnummer: self.identitetsnummer.clone(),
+            id: self.arbeidssoeker_id,
+            hendelse_id: self.hendelse_id,
+            metadata: Metadata {
+                tidspunkt: self.tidspunkt,
+                utfoert_av: Bruker {
+                    bruker_type: BrukerType::System,
+                    id: "Testsystem".to_string(),
+                    sikkerhetsnivaa: None,
+                },
+                kilde: "Testkilde".to_string(),
+                aarsak: "Test".to_string(),
+                tidspunkt_fra_kilde: None,
+            },
+            gjeldene_identitetsnummer: None,
+            pdl_identitetsnummer: HashSet::new(),
+            lokale_alias: HashSet::new(),
+            perioder: self.perioder.clone(),
+        }
+    }
+}
@@ -3,7 +3,11 @@ use anyhow::Result;
 use graphql_client::{GraphQLQuery, QueryBody};
 use pdl_graphql::pdl::hent_person_bolk::HentPersonBolkHentPersonBolk;
 use pdl_graphql::pdl::hent_person_navn::HentPersonNavnHentPerson;
-use pdl_graphql::pdl::{hent_person_bolk, hent_person_navn, HentPersonBolk, HentPersonNavn};
+use pdl_graphql::pdl::hent_person_navn_bolk::HentPersonNavnBolkHentPersonBolk;
+use pdl_graphql::pdl::{
+    hent_person_bolk, hent_person_navn, hent_person_navn_bolk, HentPersonBolk, HentPersonNavn,
+    HentPersonNavnBolk,
+};
 use std::sync::Arc;
 use texas_client::token_client::M2MTokenClient;
 use tracing::instrument;
@@ -98,6 +102,30 @@ impl PDLClient {
         }
     }
 
+    /// Navn og adressebeskyttelse for opptil 1000 identer. Hver ident har egen `code`, og
+    /// `person` er `None` hvis personen ikke finnes.
+    #[instrument(skip(self, identitetsnummer))]
+    pub async fn hent_person_navn_bolk(
+        &self,
+        identitetsnummer: Vec<Identitetsnummer>,
+    ) -> Result<Vec<HentPersonNavnBolkHentPersonBolk>> {
+        let variables = hent_person_navn_bolk::Variables {
+            identer: identitetsnummer.into_iter().map(|id| id.into()).collect(),
+            historisk: Some(false),
+        };
+        let request_body = HentPersonNavnBolk::build_query(variables);
+        let response: graphql_client::Response<hent_person_navn_bolk::ResponseData> =
+            self.hent_data(request_body).await?;
+        if let Some(errors) = response.errors {
+            let messages: Vec<String> = errors.iter().map(|e| e.message.clone()).collect();
+            return Err(PDLQueryError::UnknownError(messages.join(", ")).into());
+        }
+        let data = response
+            .data
+            .ok_or_else(|| PDLQueryError::UnknownError("No data in PDL response".to_string()))?;
+        Ok(data.hent_person_bolk)
+    }
+
     pub async fn hent_data<
         Variables: serde::Serialize,
         ResponseData: serde::de::DeserializeOwned,
@@ -222,6 +250,29 @@ mod tests {
         assert_eq!(navn.etternavn, "Nordmann");
     }
 
+    #[tokio::test]
+    async fn test_hent_person_navn_bolk() {
+        let context = init().await;
+
+        let identitetsnummer = Identitetsnummer::new("01017012345".to_string())
+            .expect("Kunne ikke opprette Identitetsnummer");
+        let response = context
+            .client
+            .hent_person_navn_bolk(vec![identitetsnummer])
+            .await
+            .expect("Kunne ikke hente response");
+
+        assert_eq!(response.len(), 1);
+        let person_bolk = response.first().expect("Ingen person i response");
+        assert_eq!(person_bolk.ident, "01017012345");
+        assert_eq!(person_bolk.code, "ok");
+        let person = person_bolk.person.as_ref().expect("Ingen person funnet");
+        let navn = person.navn.first().expect("Ingen navn funnet i response");
+        assert_eq!(navn.fornavn, "Ola");
+        assert_eq!(navn.etternavn, "Nordmann");
+        assert!(person.adressebeskyttelse.is_empty());
+    }
+
     #[tokio::test]
     async fn test_hent_person_navn() {
         let context = init().await;
@@ -0,0 +1,92 @@
+use crate::token_client::M2MTokenClient;
+use async_trait::async_trait;
+use health_and_monitoring::dependency_health::DependencyCheck;
+use std::sync::Arc;
+
+/// Sjekker at token-endepunktet (texas) svarer ved å hente et M2M-token for `target`.
+pub struct TokenEndpointCheck<C: M2MTokenClient> {
+    token_client: Arc<C>,
+    target: String,
+}
+
+impl<C: M2MTokenClient> TokenEndpointCheck<C> {
+    pub fn new(token_client: Arc<C>, target: String) -> Self {
+        Self {
+            token_client,
+            target,
+        }
+    }
+}
+
+#[async_trait]
+impl<C: M2MTokenClient + Send + Sync> DependencyCheck for TokenEndpointCheck<C> {
+    fn name(&self) -> String {
+        "token-endpoint".to_string()
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.token_client
+            .get_token(self.target.clone())
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TokenClientConfig;
+    use crate::token_client::{create_token_client, ReqwestTokenClient};
+    use mockito::Server;
+    use reqwest::Client;
+    use serde_json::json;
+
+    fn create_check(base_url: String) -> TokenEndpointCheck<ReqwestTokenClient> {
+        let config = TokenClientConfig {
+            token_endpoint: format!("{}/api/v1/token", base_url).into(),
+            token_exchange_endpoint: None,
+        };
+        let client = Arc::new(create_token_client(config, Client::new()));
+        TokenEndpointCheck::new(client, "api://test-scope/.default".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_token_endpoint_oppe() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "access_token": "et-gyldig-token",
+                    "expires_in": 3600,
+                    "token_type": "Bearer"
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let result = create_check(server.url()).check().await;
+
+        assert!(result.is_ok(), "{:?}", result);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_token_endpoint_nede() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/token")
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let result = create_check(server.url()).check().await;
+
+        assert!(result.is_err());
+        mock.assert_async().await;
+    }
+}
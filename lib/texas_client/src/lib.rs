@@ -1,5 +1,6 @@
 pub mod config;
 pub mod error;
+pub mod health;
 pub mod request;
 pub mod response;
 pub mod token_client;
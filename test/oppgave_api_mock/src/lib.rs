@@ -0,0 +1,393 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{Datelike, NaiveDate, Timelike, Utc};
+use serde_json::{Value, json};
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const OPPGAVER_PATH: &str = "/api/v1/oppgaver";
+const OPPGAVE_PATH: &str = "/api/v1/oppgaver/{id}";
+const TILDELT_ENHETSNR: &str = "4863";
+const FOERSTE_OPPGAVE_ID: i64 = 700_001;
+
+/// Feil som gis på neste kall til `POST /api/v1/oppgaver` i stedet for vanlig svar.
+#[derive(Debug, Clone)]
+pub enum InjisertFeil {
+    /// Svarer med gitt status (typisk 5xx) uten å opprette oppgaven
+    Serverfeil(StatusCode),
+    /// Venter gitt tid og svarer 504 uten å opprette oppgaven.
+    /// Lengre ventetid enn klientens timeout gir timeout hos klienten.
+    Timeout(Duration),
+    /// Oppretter oppgaven, men svarer 409 som om et tidligere forsøk allerede hadde opprettet den
+    Konflikt,
+    /// Svarer 409 uten å opprette oppgaven, slik at søk på `uuid` ikke finner den
+    KonfliktUtenOppgave,
+}
+
+/// En oppgave slik den ligger i mocken.
+#[derive(Debug, Clone)]
+pub struct MockOppgave {
+    pub id: i64,
+    pub versjon: i32,
+    pub status: String,
+    pub tildelt_enhetsnr: String,
+    pub tilordnet_ressurs: Option<String>,
+    /// Forespørselen oppgaven ble opprettet med
+    pub request: Value,
+}
+
+/// Tilstandsfull Oppgave API som kjører i samme prosess som testen.
+///
+/// Forespørsler valideres som i Oppgave API, samme `uuid` gir 409, og alle endringer gir en
+/// `OppgaveHendelseMelding`-payload som kan sendes videre til konsumenten av oppgavehendelser.
+#[derive(Clone, Default)]
+pub struct OppgaveApiMock {
+    tilstand: Arc<Mutex<Tilstand>>,
+}
+
+#[derive(Default)]
+struct Tilstand {
+    oppgaver: Vec<MockOppgave>,
+    injiserte_feil: VecDeque<InjisertFeil>,
+    antall_opprett_kall: usize,
+    hendelser: Vec<String>,
+}
+
+impl OppgaveApiMock {
+    /// Starter mocken på en ledig port og returnerer base-url.
+    pub async fn start(&self) -> Result<String, Box<dyn Error>> {
+        let router = Router::new()
+            .route(OPPGAVER_PATH, post(opprett).get(sok))
+            .route(OPPGAVE_PATH, get(hent))
+            .with_state(self.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        Ok(base_url)
+    }
+
+    /// Legger en feil i kø. Hvert kall til `POST` som ellers ville opprettet en oppgave bruker den
+    /// eldste feilen i køen. Kall som gir 400 eller 409 bruker ingen feil.
+    pub fn injiser_feil(&self, feil: InjisertFeil) {
+        self.tilstand().injiserte_feil.push_back(feil);
+    }
+
+    pub fn antall_oppgaver(&self) -> usize {
+        self.tilstand().oppgaver.len()
+    }
+
+    pub fn antall_opprett_kall(&self) -> usize {
+        self.tilstand().antall_opprett_kall
+    }
+
+    pub fn oppgave_med_uuid(&self, uuid: &str) -> Option<MockOppgave> {
+        self.tilstand()
+            .oppgaver
+            .iter()
+            .find(|oppgave| oppgave.request["uuid"] == uuid)
+            .cloned()
+    }
+
+    /// Nyeste oppgave for personen
+    pub fn oppgave_for_personident(&self, personident: &str) -> Option<MockOppgave> {
+        self.tilstand()
+            .oppgaver
+            .iter()
+            .rev()
+            .find(|oppgave| oppgave.request["personident"] == personident)
+            .cloned()
+    }
+
+    /// Alle hendelser mocken har laget, i rekkefølge. Opprettelse gir `OPPGAVE_OPPRETTET`.
+    pub fn hendelser(&self) -> Vec<String> {
+        self.tilstand().hendelser.clone()
+    }
+
+    /// Tildeler oppgaven til en veileder og returnerer `OPPGAVE_ENDRET`-hendelsen.
+    pub fn tildel(&self, oppgave_id: i64, nav_ident: &str) -> String {
+        self.endre(oppgave_id, "OPPGAVE_ENDRET", nav_ident, |oppgave| {
+            oppgave.tilordnet_ressurs = Some(nav_ident.to_string());
+        })
+    }
+
+    /// Ferdigstiller oppgaven og returnerer `OPPGAVE_FERDIGSTILT`-hendelsen.
+    pub fn ferdigstill(&self, oppgave_id: i64, nav_ident: &str) -> String {
+        self.endre(oppgave_id, "OPPGAVE_FERDIGSTILT", nav_ident, |oppgave| {
+            oppgave.status = "FERDIGSTILT".to_string();
+        })
+    }
+
+    /// Feilregistrerer oppgaven og returnerer `OPPGAVE_FEILREGISTRERT`-hendelsen.
+    pub fn feilregistrer(&self, oppgave_id: i64, nav_ident: &str) -> String {
+        self.endre(oppgave_id, "OPPGAVE_FEILREGISTRERT", nav_ident, |oppgave| {
+            oppgave.status = "FEILREGISTRERT".to_string();
+        })
+    }
+
+    fn endre(
+        &self,
+        oppgave_id: i64,
+        hendelsestype: &str,
+        nav_ident: &str,
+        endring: impl FnOnce(&mut MockOppgave),
+    ) -> String {
+        let mut tilstand = self.tilstand();
+        let oppgave = tilstand
+            .oppgaver
+            .iter_mut()
+            .find(|oppgave| oppgave.id == oppgave_id)
+            .unwrap_or_else(|| panic!("Fant ikke oppgave {} i Oppgave API-mock", oppgave_id));
+        endring(oppgave);
+        oppgave.versjon += 1;
+        let hendelse = oppgave_hendelse(hendelsestype, oppgave, Some(nav_ident));
+        tilstand.hendelser.push(hendelse.clone());
+        hendelse
+    }
+
+    fn tilstand(&self) -> std::sync::MutexGuard<'_, Tilstand> {
+        self.tilstand
+            .lock()
+            .expect("Oppgave API-mock: mutex forgiftet")
+    }
+}
+
+enum OpprettSvar {
+    Svar(StatusCode, Value),
+    Vent(Duration),
+}
+
+async fn opprett(
+    State(mock): State<OppgaveApiMock>,
+    Json(request): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let svar = {
+        let mut tilstand = mock.tilstand();
+        tilstand.antall_opprett_kall += 1;
+        if let Err(feilmelding) = valider_opprett_oppgave_request(&request) {
+            OpprettSvar::Svar(
+                StatusCode::BAD_REQUEST,
+                json!({ "feilmelding": feilmelding }),
+            )
+        } else if request["uuid"].is_string()
+            && tilstand
+                .oppgaver
+                .iter()
+                .any(|o| o.request["uuid"] == request["uuid"])
+        {
+            OpprettSvar::Svar(
+                StatusCode::CONFLICT,
+                json!({ "feilmelding": "Oppgave med uuid finnes allerede" }),
+            )
+        } else {
+            match tilstand.injiserte_feil.pop_front() {
+                Some(InjisertFeil::Serverfeil(status)) => {
+                    OpprettSvar::Svar(status, json!({ "feilmelding": "Injisert feil" }))
+                }
+                Some(InjisertFeil::Timeout(ventetid)) => OpprettSvar::Vent(ventetid),
+                Some(InjisertFeil::Konflikt) => {
+                    lagre_ny_oppgave(&mut tilstand, request);
+                    OpprettSvar::Svar(
+                        StatusCode::CONFLICT,
+                        json!({ "feilmelding": "Oppgave med uuid finnes allerede" }),
+                    )
+                }
+                Some(InjisertFeil::KonfliktUtenOppgave) => OpprettSvar::Svar(
+                    StatusCode::CONFLICT,
+                    json!({ "feilmelding": "Oppgave med uuid finnes allerede" }),
+                ),
+                None => {
+                    let oppgave = lagre_ny_oppgave(&mut tilstand, request);
+                    OpprettSvar::Svar(StatusCode::CREATED, oppgave_dto(&oppgave))
+                }
+            }
+        }
+    };
+    match svar {
+        OpprettSvar::Svar(status, body) => (status, Json(body)),
+        OpprettSvar::Vent(ventetid) => {
+            tokio::time::sleep(ventetid).await;
+            (
+                StatusCode::GATEWAY_TIMEOUT,
+                Json(json!({ "feilmelding": "Injisert timeout" })),
+            )
+        }
+    }
+}
+
+fn lagre_ny_oppgave(tilstand: &mut Tilstand, request: Value) -> MockOppgave {
+    let oppgave = MockOppgave {
+        id: FOERSTE_OPPGAVE_ID + tilstand.oppgaver.len() as i64,
+        versjon: 1,
+        status: "OPPRETTET".to_string(),
+        tildelt_enhetsnr: request["tildeltEnhetsnr"]
+            .as_str()
+            .unwrap_or(TILDELT_ENHETSNR)
+            .to_string(),
+        tilordnet_ressurs: None,
+        request,
+    };
+    tilstand
+        .hendelser
+        .push(oppgave_hendelse("OPPGAVE_OPPRETTET", &oppgave, None));
+    tilstand.oppgaver.push(oppgave.clone());
+    oppgave
+}
+
+async fn sok(
+    State(mock): State<OppgaveApiMock>,
+    Query(parametre): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    let tilstand = mock.tilstand();
+    let oppgaver: Vec<Value> = tilstand
+        .oppgaver
+        .iter()
+        .filter(|oppgave| {
+            parametre
+                .get("uuid")
+                .is_none_or(|uuid| oppgave.request["uuid"] == uuid.as_str())
+        })
+        .map(oppgave_dto)
+        .collect();
+    Json(json!({ "antallTreffTotalt": oppgaver.len(), "oppgaver": oppgaver }))
+}
+
+async fn hent(
+    State(mock): State<OppgaveApiMock>,
+    Path(id): Path<i64>,
+) -> Result<Json<Value>, StatusCode> {
+    let tilstand = mock.tilstand();
+    tilstand
+        .oppgaver
+        .iter()
+        .find(|oppgave| oppgave.id == id)
+        .map(|oppgave| Json(oppgave_dto(oppgave)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Samme krav som Oppgave API stiller til `OpprettOppgaveRequest`.
+pub fn valider_opprett_oppgave_request(request: &Value) -> Result<(), String> {
+    let aktiv_dato = dato(request, "aktivDato")?.ok_or("aktivDato mangler")?;
+    for felt in ["tema", "oppgavetype"] {
+        if request[felt].as_str().is_none_or(str::is_empty) {
+            return Err(format!("{} mangler", felt));
+        }
+    }
+    if !matches!(request["prioritet"].as_str(), Some("HOY" | "NORM" | "LAV")) {
+        return Err(format!("Ugyldig prioritet: {}", request["prioritet"]));
+    }
+    let antall_identer = ["personident", "orgnr", "samhandlernr"]
+        .iter()
+        .filter(|felt| !request[**felt].is_null())
+        .count();
+    if antall_identer > 1 {
+        return Err("Kun én av personident, orgnr og samhandlernr kan være satt".to_string());
+    }
+    if let Some(personident) = request.get("personident").filter(|v| !v.is_null()) {
+        let gyldig = personident.as_str().is_some_and(|ident| {
+            matches!(ident.len(), 11 | 13) && ident.chars().all(|c| c.is_ascii_digit())
+        });
+        if !gyldig {
+            return Err(format!("Ugyldig personident: {}", personident));
+        }
+    }
+    if let Some(frist) = dato(request, "fristFerdigstillelse")?
+        && frist < aktiv_dato
+    {
+        return Err("fristFerdigstillelse kan ikke være før aktivDato".to_string());
+    }
+    if let Some(uuid) = request.get("uuid").filter(|v| !v.is_null()) {
+        uuid.as_str()
+            .and_then(|uuid| uuid::Uuid::parse_str(uuid).ok())
+            .ok_or_else(|| format!("Ugyldig uuid: {}", uuid))?;
+    }
+    Ok(())
+}
+
+fn dato(request: &Value, felt: &str) -> Result<Option<NaiveDate>, String> {
+    match &request[felt] {
+        Value::Null => Ok(None),
+        Value::String(dato) => NaiveDate::parse_from_str(dato, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| format!("Ugyldig {}: {}", felt, dato)),
+        annet => Err(format!("Ugyldig {}: {}", felt, annet)),
+    }
+}
+
+fn oppgave_dto(oppgave: &MockOppgave) -> Value {
+    let request = &oppgave.request;
+    json!({
+        "id": oppgave.id,
+        "personident": request["personident"],
+        "tildeltEnhetsnr": oppgave.tildelt_enhetsnr,
+        "tilordnetRessurs": oppgave.tilordnet_ressurs,
+        "beskrivelse": request["beskrivelse"],
+        "tema": request["tema"],
+        "behandlingstema": request["behandlingstema"],
+        "oppgavetype": request["oppgavetype"],
+        "behandlingstype": request["behandlingstype"],
+        "versjon": oppgave.versjon,
+        "prioritet": request["prioritet"],
+        "status": oppgave.status,
+        "fristFerdigstillelse": request["fristFerdigstillelse"],
+        "aktivDato": request["aktivDato"],
+    })
+}
+
+/// `OppgaveHendelseMelding` slik Oppgave API publiserer den, med datoer som Jackson-arrays.
+fn oppgave_hendelse(hendelsestype: &str, oppgave: &MockOppgave, nav_ident: Option<&str>) -> String {
+    let request = &oppgave.request;
+    let naa = Utc::now().naive_utc();
+    let jackson_dato = |felt: &str| {
+        dato(request, felt)
+            .ok()
+            .flatten()
+            .map(|dato| json!([dato.year(), dato.month(), dato.day()]))
+    };
+    let prioritet = match request["prioritet"].as_str() {
+        Some("NORM") => "NORMAL",
+        Some(prioritet) => prioritet,
+        None => "NORMAL",
+    };
+    json!({
+        "hendelse": {
+            "hendelsestype": hendelsestype,
+            "tidspunkt": [
+                naa.year(), naa.month(), naa.day(),
+                naa.hour(), naa.minute(), naa.second(), naa.nanosecond()
+            ]
+        },
+        "utfortAv": nav_ident.map(|nav_ident| json!({
+            "navIdent": nav_ident,
+            "enhetsnr": oppgave.tildelt_enhetsnr,
+        })),
+        "oppgave": {
+            "oppgaveId": oppgave.id,
+            "versjon": oppgave.versjon,
+            "tilordning": {
+                "enhetsnr": oppgave.tildelt_enhetsnr,
+                "enhetsmappeId": null,
+                "navIdent": oppgave.tilordnet_ressurs,
+            },
+            "kategorisering": {
+                "tema": request["tema"],
+                "oppgavetype": request["oppgavetype"],
+                "behandlingstema": request["behandlingstema"],
+                "behandlingstype": request["behandlingstype"],
+                "prioritet": prioritet,
+            },
+            "behandlingsperiode": {
+                "aktiv": jackson_dato("aktivDato"),
+                "frist": jackson_dato("fristFerdigstillelse"),
+            },
+            "bruker": request["personident"].as_str().map(|ident| json!({
+                "ident": ident,
+                "identType": "FOLKEREGISTERIDENT",
+            })),
+        }
+    })
+    .to_string()
+}
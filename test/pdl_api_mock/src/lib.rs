@@ -94,6 +94,7 @@ pub async fn init_pdl_mock(
         }
         for person in &response.hent_person_navn {
             mocks.push(person_navn_mock(mockito_server, match_ident, person).await);
+            mocks.push(person_navn_bolk_mock(mockito_server, match_ident, person).await);
         }
     }
 
@@ -229,7 +230,8 @@ async fn person_navn_mock(
                                 "mellomnavn": person.mellomnavn,
                                 "etternavn": person.etternavn,
                             }
-                        ]
+                        ],
+                        "adressebeskyttelse": []
                     }
                 }
             })
@@ -238,3 +240,46 @@ async fn person_navn_mock(
         .create_async()
         .await
 }
+
+async fn person_navn_bolk_mock(
+    mockito_server: &mut ServerGuard,
+    match_ident: &String,
+    person: &PdlMockPersonNavn,
+) -> Mock {
+    mockito_server
+        .mock("POST", "/pdl")
+        .match_body(Matcher::PartialJson(json!({
+            "operationName": "HentPersonNavnBolk",
+            "variables": {
+                "identer": [match_ident],
+                "historisk": false
+            }
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/graphql-response+json")
+        .with_body(
+            json!({
+                "data": {
+                    "hentPersonBolk": [
+                        {
+                            "ident": match_ident,
+                            "person": {
+                                "navn": [
+                                    {
+                                        "fornavn": person.fornavn,
+                                        "mellomnavn": person.mellomnavn,
+                                        "etternavn": person.etternavn,
+                                    }
+                                ],
+                                "adressebeskyttelse": []
+                            },
+                            "code": "ok"
+                        }
+                    ]
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await
+}
@@ -0,0 +1,66 @@
+use chrono::Utc;
+use interne_hendelser::identiteter::identitet::Identitet;
+use interne_hendelser::identiteter::identitet_hendelse::{
+    IdentitetHendelse, IdentiteterEndretHendelse, IdentiteterMergetHendelse,
+    IdentiteterSlettetHendelse, IdentiteterSplittetHendelse,
+};
+use interne_hendelser::identiteter::identitet_type::IdentitetType;
+use uuid::Uuid;
+
+pub fn create_dummy_identitet(
+    identitet: &str,
+    identitet_type: IdentitetType,
+    gjeldende: bool,
+) -> Identitet {
+    Identitet {
+        identitet: identitet.to_string(),
+        identitet_type,
+        gjeldende,
+    }
+}
+
+pub fn create_dummy_identiteter_endret(
+    identiteter: Vec<Identitet>,
+    tidligere_identiteter: Vec<Identitet>,
+) -> IdentitetHendelse {
+    IdentitetHendelse::IdentiteterEndret(IdentiteterEndretHendelse {
+        hendelse_id: Uuid::new_v4(),
+        hendelse_tidspunkt: Utc::now(),
+        identiteter,
+        tidligere_identiteter,
+    })
+}
+
+pub fn create_dummy_identiteter_merget(
+    identiteter: Vec<Identitet>,
+    tidligere_identiteter: Vec<Identitet>,
+) -> IdentitetHendelse {
+    IdentitetHendelse::IdentiteterMerget(IdentiteterMergetHendelse {
+        hendelse_id: Uuid::new_v4(),
+        hendelse_tidspunkt: Utc::now(),
+        identiteter,
+        tidligere_identiteter,
+    })
+}
+
+pub fn create_dummy_identiteter_splittet(
+    identiteter: Vec<Identitet>,
+    tidligere_identiteter: Vec<Identitet>,
+) -> IdentitetHendelse {
+    IdentitetHendelse::IdentiteterSplittet(IdentiteterSplittetHendelse {
+        hendelse_id: Uuid::new_v4(),
+        hendelse_tidspunkt: Utc::now(),
+        identiteter,
+        tidligere_identiteter,
+    })
+}
+
+pub fn create_dummy_identiteter_slettet(
+    tidligere_identiteter: Vec<Identitet>,
+) -> IdentitetHendelse {
+    IdentitetHendelse::IdentiteterSlettet(IdentiteterSlettetHendelse {
+        hendelse_id: Uuid::new_v4(),
+        hendelse_tidspunkt: Utc::now(),
+        tidligere_identiteter,
+    })
+}
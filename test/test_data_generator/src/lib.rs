@@ -1,4 +1,5 @@
 pub mod dab_oppfolgingsperiode;
 pub mod eksterne_hendelser;
+pub mod identiteter;
 pub mod avro;
 pub mod json;